@@ -1,6 +1,8 @@
-#[cfg(not(feature = "tokio"))]
+#[cfg(all(not(feature = "async-io"), not(feature = "tokio")))]
+use super::threaded::{Executor as AsyncExecutor, Task as AsyncTask};
+#[cfg(all(feature = "async-io", not(feature = "tokio")))]
 use async_executor::Executor as AsyncExecutor;
-#[cfg(not(feature = "tokio"))]
+#[cfg(all(feature = "async-io", not(feature = "tokio")))]
 use async_task::Task as AsyncTask;
 #[cfg(not(feature = "tokio"))]
 use std::sync::Arc;
@@ -20,7 +22,7 @@ use tokio::task::JoinHandle;
 /// See [`crate::Connection::executor`] for an example of integration with external runtimes.
 ///
 /// **Note:** You can (and should) completely ignore this type when building with `tokio` feature
-/// enabled.
+/// enabled. The same goes for the `blocking-only` build, where each task runs on its own thread.
 #[cfg(not(feature = "tokio"))]
 #[derive(Debug, Clone)]
 pub struct Executor<'a> {
@@ -167,11 +169,16 @@ where
     where
         F: FnOnce() -> T + Send + 'static,
     {
-        #[cfg(not(feature = "tokio"))]
+        #[cfg(all(feature = "async-io", not(feature = "tokio")))]
         {
             Self(Some(blocking::unblock(f)))
         }
 
+        #[cfg(all(not(feature = "async-io"), not(feature = "tokio")))]
+        {
+            Self(Some(super::threaded::unblock(f)))
+        }
+
         #[cfg(feature = "tokio")]
         {
             #[cfg(tokio_unstable)]
@@ -13,6 +13,9 @@ use std::{
 
 use futures_core::Stream;
 
+#[cfg(all(not(feature = "async-io"), not(feature = "tokio")))]
+use super::threaded as async_fs;
+
 #[cfg(not(feature = "tokio"))]
 #[derive(Debug)]
 pub struct FileLines(futures_util::io::Lines<futures_util::io::BufReader<async_fs::File>>);
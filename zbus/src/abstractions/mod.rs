@@ -4,10 +4,18 @@
 /// enabled.
 mod executor;
 pub use executor::*;
+#[cfg(all(not(feature = "async-io"), not(feature = "tokio")))]
+pub(crate) mod threaded;
+#[cfg(all(feature = "async-io", not(feature = "tokio")))]
+pub(crate) use async_io::Async;
+#[cfg(all(not(feature = "async-io"), not(feature = "tokio")))]
+pub(crate) use threaded::Async;
 mod async_drop;
 pub(crate) mod async_lock;
 pub use async_drop::*;
 pub(crate) mod file;
+mod timeout;
+pub(crate) use timeout::timeout;
 
 // Not macOS-specific itself but only used on macOS.
 #[cfg(target_os = "macos")]
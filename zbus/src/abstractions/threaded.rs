@@ -0,0 +1,522 @@
+//! A minimal thread-based runtime, used when neither `async-io` nor `tokio` feature is enabled.
+//!
+//! Every task runs on its own thread and all I/O is blocking, so there is no need for a reactor or
+//! an executor. This is only meant for the `blocking-only` build, where it allows zbus to be built
+//! without pulling in any async runtime dependencies.
+//!
+//! Providing only a specific API that we need internally.
+
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    future::{poll_fn, Future},
+    io::{self, Read, Write},
+    marker::PhantomData,
+    net::{SocketAddr, TcpStream},
+    path::Path,
+    pin::{pin, Pin},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Condvar, Mutex, OnceLock,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+use futures_util::io::{AsyncRead, AsyncWrite};
+
+/// Run the `future` to completion on the current thread.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// An executor that runs each task on a dedicated thread.
+#[derive(Debug, Default)]
+pub(crate) struct Executor<'a> {
+    tasks: Arc<AtomicUsize>,
+    phantom: PhantomData<&'a ()>,
+}
+
+impl<'a> Executor<'a> {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn spawn<T: Send + 'static>(
+        &self,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> Task<T> {
+        struct TaskCount(Arc<AtomicUsize>);
+
+        impl Drop for TaskCount {
+            fn drop(&mut self) {
+                self.0.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        self.tasks.fetch_add(1, Ordering::SeqCst);
+        let count = TaskCount(self.tasks.clone());
+
+        Task::spawn(async move {
+            let _count = count;
+
+            future.await
+        })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.tasks.load(Ordering::SeqCst) == 0
+    }
+
+    /// Tasks run on their own threads so there is never anything to tick.
+    pub(crate) async fn tick(&self) {
+        std::future::pending().await
+    }
+
+    pub(crate) async fn run<T>(&self, future: impl Future<Output = T>) -> T {
+        future.await
+    }
+}
+
+/// Launch the given blocking function in a task.
+pub(crate) fn unblock<T, F>(f: F) -> Task<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    Task::spawn(async move { f() })
+}
+
+/// Sleep for the given `duration`.
+///
+/// All sleeps are driven by a single timer thread, shared by the whole process. Dropping the
+/// returned future cancels the sleep.
+pub(crate) fn sleep(duration: Duration) -> Sleep {
+    Sleep {
+        deadline: Instant::now() + duration,
+        id: None,
+    }
+}
+
+/// The future returned by [`sleep`].
+#[derive(Debug)]
+pub(crate) struct Sleep {
+    deadline: Instant,
+    /// The ID of the timer, once registered with the timer thread.
+    id: Option<u64>,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if Instant::now() >= self.deadline {
+            if let Some(id) = self.id.take() {
+                Timers::get().remove(self.deadline, id);
+            }
+
+            return Poll::Ready(());
+        }
+
+        let id = Timers::get().insert(self.deadline, self.id, cx.waker());
+        self.id = Some(id);
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            Timers::get().remove(self.deadline, id);
+        }
+    }
+}
+
+/// The timers of all pending sleeps, and the thread waking them up when they expire.
+struct Timers {
+    queue: Mutex<TimerQueue>,
+    // Notified when a timer with an earlier deadline than all others is inserted.
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct TimerQueue {
+    // Ordered by deadline first so the next one to expire is always the first one.
+    wakers: BTreeMap<(Instant, u64), Waker>,
+    next_id: u64,
+}
+
+impl Timers {
+    fn get() -> &'static Self {
+        static TIMERS: OnceLock<Timers> = OnceLock::new();
+
+        TIMERS.get_or_init(|| {
+            thread::Builder::new()
+                .name("zbus timer".into())
+                .spawn(|| Timers::get().run())
+                .expect("failed to spawn the zbus timer thread");
+
+            Self {
+                queue: Mutex::new(TimerQueue::default()),
+                changed: Condvar::new(),
+            }
+        })
+    }
+
+    /// Register the `waker` of the timer `id` (or a new timer if `None`), returning its ID.
+    fn insert(&self, deadline: Instant, id: Option<u64>, waker: &Waker) -> u64 {
+        let mut queue = self.queue.lock().expect("lock poisoned");
+        let id = id.unwrap_or_else(|| {
+            queue.next_id += 1;
+
+            queue.next_id
+        });
+        let is_first = queue
+            .wakers
+            .keys()
+            .next()
+            .map_or(true, |first| (deadline, id) <= *first);
+        match queue.wakers.get_mut(&(deadline, id)) {
+            Some(w) => w.clone_from(waker),
+            None => {
+                queue.wakers.insert((deadline, id), waker.clone());
+            }
+        }
+        drop(queue);
+
+        if is_first {
+            self.changed.notify_one();
+        }
+
+        id
+    }
+
+    fn remove(&self, deadline: Instant, id: u64) {
+        self.queue
+            .lock()
+            .expect("lock poisoned")
+            .wakers
+            .remove(&(deadline, id));
+    }
+
+    fn run(&self) {
+        let mut queue = self.queue.lock().expect("lock poisoned");
+        loop {
+            let now = Instant::now();
+            let mut expired = vec![];
+            while let Some(entry) = queue.wakers.first_entry() {
+                if entry.key().0 > now {
+                    break;
+                }
+                expired.push(entry.remove());
+            }
+
+            if !expired.is_empty() {
+                // Don't hold the lock while waking the tasks up.
+                drop(queue);
+                expired.into_iter().for_each(Waker::wake);
+                queue = self.queue.lock().expect("lock poisoned");
+
+                continue;
+            }
+
+            queue = match queue.wakers.keys().next() {
+                Some(&(deadline, _)) => {
+                    self.changed
+                        .wait_timeout(queue, deadline - now)
+                        .expect("lock poisoned")
+                        .0
+                }
+                None => self.changed.wait(queue).expect("lock poisoned"),
+            };
+        }
+    }
+}
+
+/// A handle to a task running on its own thread.
+///
+/// Same as `async_task::Task`, the task is cancelled when this handle is dropped, unless it was
+/// detached.
+pub(crate) struct Task<T> {
+    shared: Arc<TaskShared<T>>,
+    thread: Thread,
+    detached: bool,
+}
+
+struct TaskShared<T> {
+    output: Mutex<Option<T>>,
+    waker: Mutex<Option<Waker>>,
+    finished: AtomicBool,
+    cancelled: AtomicBool,
+}
+
+impl<T: Send + 'static> Task<T> {
+    fn spawn(future: impl Future<Output = T> + Send + 'static) -> Self {
+        let shared = Arc::new(TaskShared {
+            output: Mutex::new(None),
+            waker: Mutex::new(None),
+            finished: AtomicBool::new(false),
+            cancelled: AtomicBool::new(false),
+        });
+        let task_shared = shared.clone();
+        let thread = thread::Builder::new()
+            .name("zbus task".into())
+            .spawn(move || {
+                let mut future = pin!(future);
+                let output = block_on(poll_fn(|cx| {
+                    if task_shared.cancelled.load(Ordering::SeqCst) {
+                        return Poll::Ready(None);
+                    }
+
+                    future.as_mut().poll(cx).map(Some)
+                }));
+
+                *task_shared.output.lock().expect("lock poisoned") = output;
+                task_shared.finished.store(true, Ordering::SeqCst);
+                if let Some(waker) = task_shared.waker.lock().expect("lock poisoned").take() {
+                    waker.wake();
+                }
+            })
+            .expect("failed to spawn a thread for a zbus task")
+            .thread()
+            .clone();
+
+        Self {
+            shared,
+            thread,
+            detached: false,
+        }
+    }
+}
+
+impl<T> Task<T> {
+    pub(crate) fn detach(mut self) {
+        self.detached = true;
+    }
+}
+
+impl<T> Drop for Task<T> {
+    fn drop(&mut self) {
+        if !self.detached {
+            self.shared.cancelled.store(true, Ordering::SeqCst);
+            self.thread.unpark();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Task<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Task")
+            .field("thread", &self.thread)
+            .field("finished", &self.shared.finished)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Future for Task<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let shared = &self.shared;
+        if !shared.finished.load(Ordering::SeqCst) {
+            *shared.waker.lock().expect("lock poisoned") = Some(cx.waker().clone());
+
+            // Re-check in case the task finished before we registered the waker.
+            if !shared.finished.load(Ordering::SeqCst) {
+                return Poll::Pending;
+            }
+        }
+
+        let output = shared.output.lock().expect("lock poisoned").take();
+
+        // A task is only ever cancelled when its handle is dropped, so there is always an output.
+        Poll::Ready(output.expect("task polled after completion"))
+    }
+}
+
+/// A wrapper for blocking I/O types, mirroring the API of `async_io::Async` that we need.
+///
+/// I/O operations block the calling thread until they're complete.
+#[derive(Debug)]
+pub(crate) struct Async<T>(T);
+
+impl<T: Blocking> Async<T> {
+    pub(crate) fn new(io: T) -> io::Result<Self> {
+        io.set_nonblocking(false)?;
+
+        Ok(Self(io))
+    }
+}
+
+impl Async<TcpStream> {
+    pub(crate) async fn connect(addr: SocketAddr) -> io::Result<Self> {
+        Self::new(TcpStream::connect(addr)?)
+    }
+}
+
+impl<T> Async<T> {
+    pub(crate) fn get_ref(&self) -> &T {
+        &self.0
+    }
+
+    pub(crate) async fn write_with<R>(&self, op: impl FnMut(&T) -> io::Result<R>) -> io::Result<R> {
+        let mut op = op;
+
+        op(&self.0)
+    }
+
+    /// The underlying I/O type is blocking so it's always considered readable.
+    #[cfg(unix)]
+    pub(crate) fn poll_readable(&self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    /// The underlying I/O type is blocking so it's always considered writable.
+    #[cfg(unix)]
+    pub(crate) fn poll_writable(&self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(unix)]
+impl<T: AsRawFd> AsRawFd for Async<T> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl<T> AsyncRead for &Async<T>
+where
+    for<'a> &'a T: Read,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready((&self.0).read(buf))
+    }
+}
+
+impl<T> AsyncWrite for &Async<T>
+where
+    for<'a> &'a T: Write,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready((&self.0).write(buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready((&self.0).flush())
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Blocking socket types.
+pub(crate) trait Blocking {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()>;
+
+    /// Shut down both the reading and writing halves.
+    fn shutdown(&self) -> io::Result<()>;
+}
+
+impl Blocking for TcpStream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        TcpStream::set_nonblocking(self, nonblocking)
+    }
+
+    fn shutdown(&self) -> io::Result<()> {
+        TcpStream::shutdown(self, std::net::Shutdown::Both)
+    }
+}
+
+#[cfg(unix)]
+impl Blocking for std::os::unix::net::UnixStream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        std::os::unix::net::UnixStream::set_nonblocking(self, nonblocking)
+    }
+
+    fn shutdown(&self) -> io::Result<()> {
+        std::os::unix::net::UnixStream::shutdown(self, std::net::Shutdown::Both)
+    }
+}
+
+#[cfg(windows)]
+impl Blocking for uds_windows::UnixStream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        uds_windows::UnixStream::set_nonblocking(self, nonblocking)
+    }
+
+    fn shutdown(&self) -> io::Result<()> {
+        uds_windows::UnixStream::shutdown(self, std::net::Shutdown::Both)
+    }
+}
+
+#[cfg(feature = "vsock")]
+impl Blocking for vsock::VsockStream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        vsock::VsockStream::set_nonblocking(self, nonblocking)
+    }
+
+    fn shutdown(&self) -> io::Result<()> {
+        vsock::VsockStream::shutdown(self, std::net::Shutdown::Both)
+    }
+}
+
+/// A file, mirroring the API of `async_fs::File` that we need.
+#[derive(Debug)]
+pub(crate) struct File(std::fs::File);
+
+impl File {
+    pub(crate) async fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        std::fs::File::open(path).map(Self)
+    }
+}
+
+impl AsyncRead for File {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(self.get_mut().0.read(buf))
+    }
+}
+
+// Not unix-specific itself but only used on unix.
+#[cfg(unix)]
+pub(crate) async fn metadata(path: impl AsRef<Path>) -> io::Result<std::fs::Metadata> {
+    std::fs::metadata(path)
+}
@@ -0,0 +1,44 @@
+use std::{future::Future, io, time::Duration};
+
+use crate::{Error, Result};
+
+/// Await `future`, failing with a [`io::ErrorKind::TimedOut`] error if it doesn't resolve within
+/// `duration`.
+#[cfg(feature = "tokio")]
+pub(crate) async fn timeout<F, T>(future: F, duration: Duration) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    tokio::time::timeout(duration, future)
+        .await
+        .map_err(|_| timed_out(duration))?
+}
+
+/// Await `future`, failing with a [`io::ErrorKind::TimedOut`] error if it doesn't resolve within
+/// `duration`.
+#[cfg(not(feature = "tokio"))]
+pub(crate) async fn timeout<F, T>(future: F, duration: Duration) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    use futures_util::future::{select, Either};
+    use std::pin::pin;
+
+    #[cfg(feature = "async-io")]
+    let timer = async_io::Timer::after(duration);
+    #[cfg(not(feature = "async-io"))]
+    let timer = crate::abstractions::threaded::sleep(duration);
+
+    match select(pin!(future), pin!(timer)).await {
+        Either::Left((result, _)) => result,
+        Either::Right(_) => Err(timed_out(duration)),
+    }
+}
+
+fn timed_out(duration: Duration) -> Error {
+    io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!("timed out after {duration:?}"),
+    )
+    .into()
+}
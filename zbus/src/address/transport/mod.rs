@@ -2,11 +2,11 @@
 //!
 //! This module provides the trasport information for D-Bus addresses.
 
+#[cfg(not(feature = "tokio"))]
+use crate::abstractions::Async;
 #[cfg(windows)]
 use crate::win32::autolaunch_bus_address;
 use crate::{Error, Result};
-#[cfg(not(feature = "tokio"))]
-use async_io::Async;
 use std::collections::HashMap;
 #[cfg(not(feature = "tokio"))]
 use std::net::TcpStream;
@@ -1,7 +1,7 @@
 use super::encode_percents;
-use crate::{Error, Result};
 #[cfg(not(feature = "tokio"))]
-use async_io::Async;
+use crate::abstractions::Async;
+use crate::{Error, Result};
 #[cfg(not(feature = "tokio"))]
 use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
 use std::{
@@ -194,6 +194,15 @@ impl<'a> Builder<'a> {
         self.0.serve_at(path, iface).map(Self)
     }
 
+    /// Serve the [`org.freedesktop.LogControl1`][lc] interface at its expected path.
+    ///
+    /// See [`zbus::connection::Builder::serve_log_control`] for details.
+    ///
+    /// [lc]: https://www.freedesktop.org/software/systemd/man/latest/org.freedesktop.LogControl1.html
+    pub fn serve_log_control(self, log_control: crate::fdo::LogControl) -> Result<Self> {
+        self.0.serve_log_control(log_control).map(Self)
+    }
+
     /// Register a well-known name for this connection on the bus.
     ///
     /// This is similar to [`zbus::blocking::Connection::request_name`], except the name is
@@ -9,6 +9,7 @@ use zvariant::ObjectPath;
 
 use crate::{
     blocking::ObjectServer,
+    connection::{FilterAction, FilterId},
     fdo::{ConnectionCredentials, RequestNameFlags, RequestNameReply},
     message::Message,
     utils::block_on,
@@ -40,6 +41,33 @@ impl Connection {
         block_on(crate::Connection::system()).map(Self::from)
     }
 
+    /// Get a shared `Connection` to the session/user message bus.
+    ///
+    /// See [`crate::Connection::shared_session`] for details.
+    pub fn shared_session() -> Result<Self> {
+        block_on(crate::Connection::shared_session()).map(Self::from)
+    }
+
+    /// Get a shared `Connection` to the system-wide message bus.
+    ///
+    /// See [`crate::Connection::shared_session`] for details.
+    pub fn shared_system() -> Result<Self> {
+        block_on(crate::Connection::shared_system()).map(Self::from)
+    }
+
+    /// Get a shared `Connection` to the bus at the given [D-Bus bus address].
+    ///
+    /// See [`crate::Connection::shared`] for details.
+    ///
+    /// [D-Bus bus address]: https://dbus.freedesktop.org/doc/dbus-specification.html#addresses
+    pub fn shared<A>(address: A) -> Result<Self>
+    where
+        A: TryInto<crate::Address>,
+        A::Error: Into<Error>,
+    {
+        block_on(crate::Connection::shared(address)).map(Self::from)
+    }
+
     /// The capacity of the main (unfiltered) queue.
     pub fn max_queued(&self) -> usize {
         self.inner.max_queued()
@@ -232,6 +260,23 @@ impl Connection {
         self.inner
     }
 
+    /// Add a filter for incoming messages.
+    ///
+    /// See [`crate::Connection::add_filter`] for details.
+    pub fn add_filter<F>(&self, filter: F) -> FilterId
+    where
+        F: Fn(&Message) -> FilterAction + Send + Sync + 'static,
+    {
+        self.inner.add_filter(filter)
+    }
+
+    /// Remove a filter added with [`Connection::add_filter`].
+    ///
+    /// Returns `false` if the filter was already removed.
+    pub fn remove_filter(&self, id: FilterId) -> bool {
+        self.inner.remove_filter(id)
+    }
+
     /// Returns a listener, notified on various connection activity.
     ///
     /// This function is meant for the caller to implement idle or timeout on inactivity.
@@ -37,5 +37,8 @@ assert_impl_all!(MonitoringProxy<'_>: Send, Sync, Unpin);
 gen_stats_proxy!(false, true);
 assert_impl_all!(StatsProxy<'_>: Send, Sync, Unpin);
 
+gen_log_control_proxy!(false, true);
+assert_impl_all!(LogControlProxy<'_>: Send, Sync, Unpin);
+
 gen_dbus_proxy!(false, true);
 assert_impl_all!(DBusProxy<'_>: Send, Sync, Unpin);
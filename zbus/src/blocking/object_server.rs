@@ -4,7 +4,10 @@ use static_assertions::assert_impl_all;
 use zvariant::ObjectPath;
 
 use crate::{
-    object_server::{Interface, InterfaceDeref, InterfaceDerefMut, SignalContext},
+    object_server::{
+        Interface, InterfaceDeref, InterfaceDerefMut, RegisteredInterface, RegisteredObject,
+        SignalContext,
+    },
     utils::block_on,
     Error, Result,
 };
@@ -222,6 +225,26 @@ impl ObjectServer {
         })
     }
 
+    /// The objects registered on the server, sorted by path.
+    ///
+    /// See [`crate::ObjectServer::objects`] for details.
+    pub fn objects(&self) -> Vec<RegisteredObject> {
+        block_on(self.azync.objects())
+    }
+
+    /// The interfaces registered at the given path, sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// If there is no object at the given path, `Error::InterfaceNotFound` error is returned.
+    pub fn interfaces<'p, P>(&self, path: P) -> Result<Vec<RegisteredInterface>>
+    where
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        block_on(self.azync.interfaces(path))
+    }
+
     /// Get a reference to the underlying async ObjectServer.
     pub fn inner(&self) -> &crate::ObjectServer {
         &self.azync
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use static_assertions::assert_impl_all;
 use zbus_names::{BusName, InterfaceName};
 use zvariant::ObjectPath;
@@ -52,6 +54,25 @@ impl<'a, T> Builder<'a, T> {
         Self(self.0.uncached_properties(properties))
     }
 
+    /// Set the maximum time to wait for the reply to a method call.
+    ///
+    /// See [`crate::proxy::Builder::method_timeout`] for details.
+    #[must_use]
+    pub fn method_timeout(self, timeout: Duration) -> Self {
+        Self(self.0.method_timeout(timeout))
+    }
+
+    /// Verify calls against the introspected remote interface.
+    ///
+    /// See [`crate::proxy::Builder::verify_signatures`] for details.
+    ///
+    /// This method is only available when the `verify-signatures` feature is enabled.
+    #[cfg(feature = "verify-signatures")]
+    #[must_use]
+    pub fn verify_signatures(self, mode: crate::proxy::VerifySignatures) -> Self {
+        Self(self.0.verify_signatures(mode))
+    }
+
     /// Build a proxy from the builder.
     ///
     /// # Panics
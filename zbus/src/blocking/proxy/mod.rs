@@ -3,21 +3,23 @@
 use enumflags2::BitFlags;
 use futures_util::StreamExt;
 use static_assertions::assert_impl_all;
-use std::{fmt, ops::Deref};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    time::Duration,
+};
 use zbus_names::{BusName, InterfaceName, MemberName, UniqueName};
 use zvariant::{ObjectPath, OwnedValue, Value};
 
-use crate::{
-    blocking::Connection,
-    message::Message,
-    proxy::{MethodFlags, ProxyDefault},
-    utils::block_on,
-    Error, Result,
-};
+use crate::{blocking::Connection, message::Message, utils::block_on, Error, Result};
 
 use crate::fdo;
 
 mod builder;
+#[cfg(feature = "verify-signatures")]
+pub use crate::proxy::VerifySignatures;
+pub use crate::proxy::{CacheProperties, MethodFlags, ProxyDefault};
 pub use builder::Builder;
 
 /// A blocking wrapper of [`crate::Proxy`].
@@ -79,6 +81,22 @@ impl fmt::Debug for Proxy<'_> {
 
 assert_impl_all!(Proxy<'_>: Send, Sync, Unpin);
 
+/// Same as for [`crate::Proxy`], proxies using the same connection and targeting the same
+/// destination, path and interface are equal.
+impl PartialEq for Proxy<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner() == other.inner()
+    }
+}
+
+impl Eq for Proxy<'_> {}
+
+impl Hash for Proxy<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner().hash(state);
+    }
+}
+
 impl<'a> Proxy<'a> {
     /// Create a new `Proxy` for the given destination/path/interface.
     pub fn new<D, P, I>(
@@ -242,6 +260,19 @@ impl<'a> Proxy<'a> {
         block_on(self.inner().call(method_name, body))
     }
 
+    /// Call a method and return the reply body, along with the round-trip time of the call.
+    ///
+    /// See [`crate::Proxy::call_timed`] for details.
+    pub fn call_timed<'m, M, B, R>(&self, method_name: M, body: &B) -> Result<(R, Duration)>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        R: for<'d> zvariant::DynamicDeserialize<'d>,
+    {
+        block_on(self.inner().call_timed(method_name, body))
+    }
+
     /// Call a method and return the reply body, optionally supplying a set of
     /// method flags to control the way the method call message is sent and handled.
     ///
@@ -266,6 +297,13 @@ impl<'a> Proxy<'a> {
         block_on(self.inner().call_with_flags(method_name, flags, body))
     }
 
+    /// The maximum time to wait for the reply to a method call, if any.
+    ///
+    /// See [`crate::proxy::Builder::method_timeout`] for details.
+    pub fn method_timeout(&self) -> Option<Duration> {
+        self.inner().method_timeout()
+    }
+
     /// Call a method without expecting a reply
     ///
     /// This sets the `NoReplyExpected` flag on the calling message and does not wait for a reply.
@@ -278,6 +316,34 @@ impl<'a> Proxy<'a> {
         block_on(self.inner().call_noreply(method_name, body))
     }
 
+    /// Call a method that returns an object path and wait for a completion signal on that path.
+    ///
+    /// See [`crate::Proxy::call_with_response_signal`] for details.
+    pub fn call_with_response_signal<'m, 'i, 's, M, I, S, B, R>(
+        &self,
+        method_name: M,
+        body: &B,
+        signal_interface: I,
+        signal_name: S,
+    ) -> Result<R>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        I: TryInto<InterfaceName<'i>>,
+        I::Error: Into<Error>,
+        S: TryInto<MemberName<'s>>,
+        S::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        R: for<'d> zvariant::DynamicDeserialize<'d>,
+    {
+        block_on(self.inner().call_with_response_signal(
+            method_name,
+            body,
+            signal_interface,
+            signal_name,
+        ))
+    }
+
     /// Create a stream for signal named `signal_name`.
     ///
     /// # Errors
@@ -346,6 +412,20 @@ impl<'a> Proxy<'a> {
         PropertyIterator(block_on(self.inner().receive_property_changed(name)))
     }
 
+    /// Get an iterator that watches the value of the property `name`.
+    ///
+    /// See [`crate::Proxy::watch_property`] for details.
+    pub fn watch_property<'name: 'a, T>(
+        &self,
+        name: &'name str,
+    ) -> Result<PropertyWatchIterator<'a, T>>
+    where
+        T: TryFrom<OwnedValue> + PartialEq + Clone + Unpin + Send + Sync + 'a,
+        T::Error: Into<Error>,
+    {
+        block_on(self.inner().watch_property(name)).map(PropertyWatchIterator)
+    }
+
     /// Get an iterator to receive property changed events.
     ///
     /// Note that zbus doesn't queue the updates. If the listener is slower than the receiver, it
@@ -443,6 +523,27 @@ where
     }
 }
 
+/// An [`std::iter::Iterator`] implementation that yields the values of a property.
+///
+/// Use [`Proxy::watch_property`] to create an instance of this type.
+#[derive(Debug)]
+pub struct PropertyWatchIterator<'a, T>(crate::proxy::PropertyWatchStream<'a, T>);
+
+impl<T> PropertyWatchIterator<'_, T> {
+    /// The name of the property being watched.
+    pub fn name(&self) -> &str {
+        self.0.name()
+    }
+}
+
+impl<T> std::iter::Iterator for PropertyWatchIterator<'_, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        block_on(self.0.next())
+    }
+}
+
 /// A property changed event.
 ///
 /// The property changed event generated by [`PropertyIterator`].
@@ -559,4 +660,36 @@ mod tests {
         // one we subscribed to.
         assert!(signal.args().unwrap().name() == well_known);
     }
+
+    #[test]
+    #[timeout(15000)]
+    fn method_timeout() {
+        // A connection without any served interfaces never replies to method calls.
+        let _service = blocking::connection::Builder::session()
+            .unwrap()
+            .name("org.freedesktop.zbus.BlockingProxyTimeoutTest")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let conn = Connection::session().unwrap();
+        let timeout = Duration::from_millis(100);
+        let proxy: Proxy<'_> = Builder::new(&conn)
+            .destination("org.freedesktop.zbus.BlockingProxyTimeoutTest")
+            .unwrap()
+            .path("/org/freedesktop/zbus/Test")
+            .unwrap()
+            .interface("org.freedesktop.zbus.Test")
+            .unwrap()
+            .cache_properties(CacheProperties::No)
+            .method_timeout(timeout)
+            .build()
+            .unwrap();
+        assert_eq!(proxy.method_timeout(), Some(timeout));
+
+        match proxy.call::<_, _, ()>("Test", &()).unwrap_err() {
+            Error::InputOutput(e) => assert_eq!(e.kind(), std::io::ErrorKind::TimedOut),
+            e => panic!("unexpected error: {e}"),
+        }
+    }
 }
@@ -1,5 +1,5 @@
 #[cfg(not(feature = "tokio"))]
-use async_io::Async;
+use crate::abstractions::Async;
 use event_listener::Event;
 use static_assertions::assert_impl_all;
 #[cfg(not(feature = "tokio"))]
@@ -35,6 +35,9 @@ use super::{
     socket::{BoxedSplit, ReadHalf, Split, WriteHalf},
 };
 
+#[cfg(unix)]
+use super::FdPolicy;
+
 const DEFAULT_MAX_QUEUED: usize = 64;
 
 #[derive(Debug)]
@@ -72,6 +75,8 @@ pub struct Builder<'a> {
     unique_name: Option<crate::names::UniqueName<'a>>,
     cookie_context: Option<super::handshake::CookieContext<'a>>,
     cookie_id: Option<usize>,
+    #[cfg(unix)]
+    fd_policy: FdPolicy,
 }
 
 assert_impl_all!(Builder<'_>: Send, Sync, Unpin);
@@ -293,6 +298,19 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Set what to do with the file descriptors received in messages.
+    ///
+    /// By default, [`FdPolicy::Close`] is used and the file descriptors are closed when the
+    /// messages carrying them are dropped.
+    ///
+    /// This method is only available on Unix platforms.
+    #[cfg(unix)]
+    pub fn fd_policy(mut self, policy: FdPolicy) -> Self {
+        self.fd_policy = policy;
+
+        self
+    }
+
     /// Enable or disable the internal executor thread.
     ///
     /// The thread is enabled by default.
@@ -325,6 +343,16 @@ impl<'a> Builder<'a> {
         Ok(self)
     }
 
+    /// Serve the [`org.freedesktop.LogControl1`][lc] interface at its expected path.
+    ///
+    /// This allows changing the log level and target of the service at runtime. See
+    /// [`fdo::LogControl`](crate::fdo::LogControl) for details.
+    ///
+    /// [lc]: https://www.freedesktop.org/software/systemd/man/latest/org.freedesktop.LogControl1.html
+    pub fn serve_log_control(self, log_control: crate::fdo::LogControl) -> Result<Self> {
+        self.serve_at("/org/freedesktop/LogControl1", log_control)
+    }
+
     /// Register a well-known name for this connection on the bus.
     ///
     /// This is similar to [`zbus::Connection::request_name`], except the name is requested as part
@@ -374,12 +402,12 @@ impl<'a> Builder<'a> {
     /// result in [`Error::Unsupported`] error.
     pub async fn build(self) -> Result<Connection> {
         let executor = Executor::new();
-        #[cfg(not(feature = "tokio"))]
+        #[cfg(all(feature = "async-io", not(feature = "tokio")))]
         let internal_executor = self.internal_executor;
         // Box the future as it's large and can cause stack overflow.
         let conn = Box::pin(executor.run(self.build_(executor.clone()))).await?;
 
-        #[cfg(not(feature = "tokio"))]
+        #[cfg(all(feature = "async-io", not(feature = "tokio")))]
         start_internal_executor(&executor, internal_executor)?;
 
         Ok(conn)
@@ -487,6 +515,8 @@ impl<'a> Builder<'a> {
             already_received_bytes,
             #[cfg(unix)]
             already_received_fds,
+            #[cfg(unix)]
+            self.fd_policy,
         );
 
         for name in self.names {
@@ -511,6 +541,8 @@ impl<'a> Builder<'a> {
             unique_name: None,
             cookie_id: None,
             cookie_context: None,
+            #[cfg(unix)]
+            fd_policy: FdPolicy::default(),
         }
     }
 
@@ -561,7 +593,7 @@ impl<'a> Builder<'a> {
 ///
 /// Returns a dummy task that keep the executor ticking thread from exiting due to absence of any
 /// tasks until socket reader task kicks in.
-#[cfg(not(feature = "tokio"))]
+#[cfg(all(feature = "async-io", not(feature = "tokio")))]
 fn start_internal_executor(executor: &Executor<'static>, internal_executor: bool) -> Result<()> {
     if internal_executor {
         let executor = executor.clone();
@@ -163,8 +163,8 @@ mod tests {
             p1.set_nonblocking(true).unwrap();
 
             (
-                async_io::Async::new(p0).unwrap(),
-                async_io::Async::new(p1).unwrap(),
+                crate::abstractions::Async::new(p0).unwrap(),
+                crate::abstractions::Async::new(p1).unwrap(),
             )
         };
 
@@ -10,8 +10,12 @@ use std::{
     num::NonZeroU32,
     ops::Deref,
     pin::Pin,
-    sync::{Arc, OnceLock, Weak},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock, Weak,
+    },
     task::{Context, Poll},
+    time::Instant,
 };
 use tracing::{debug, info_span, instrument, trace, trace_span, warn, Instrument};
 use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, OwnedUniqueName, WellKnownName};
@@ -37,7 +41,10 @@ pub mod socket;
 pub use socket::Socket;
 
 mod socket_reader;
-use socket_reader::SocketReader;
+use socket_reader::{ReaderState, SocketReader};
+
+mod shared;
+pub use shared::set_sharing_enabled;
 
 pub(crate) mod handshake;
 use handshake::Authenticated;
@@ -58,6 +65,8 @@ pub(crate) struct ConnectionInner {
 
     activity_event: Arc<Event>,
     socket_write: Mutex<Box<dyn socket::WriteHalf>>,
+    // Set once the connection is closed, or the socket can't be read from anymore.
+    closed: Arc<AtomicBool>,
 
     // Our executor
     executor: Executor<'static>,
@@ -74,12 +83,95 @@ pub(crate) struct ConnectionInner {
 
     object_server: OnceLock<blocking::ObjectServer>,
     object_server_dispatch_task: OnceLock<Task<()>>,
+
+    filters: Arc<std::sync::RwLock<Filters>>,
 }
 
 type Subscriptions = HashMap<OwnedMatchRule, (u64, InactiveReceiver<Result<Message>>)>;
 
 pub(crate) type MsgBroadcaster = Broadcaster<Result<Message>>;
 
+/// What to do with an incoming message, as decided by a [filter](Connection::add_filter).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FilterAction {
+    /// Let the message through, to the next filter and then to the streams and the object server.
+    Pass,
+    /// The filter took care of the message, so it's not dispatched any further.
+    Consume,
+    /// Reject the message, so it's not dispatched any further.
+    ///
+    /// Unlike [`FilterAction::Consume`], the caller of a rejected method call expecting a reply is
+    /// sent an `org.freedesktop.DBus.Error.AccessDenied` error.
+    Drop,
+}
+
+/// Identifier of a filter, as returned by [`Connection::add_filter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FilterId(u64);
+
+pub(crate) type Filter = Arc<dyn Fn(&Message) -> FilterAction + Send + Sync>;
+
+/// The filters of a connection, in the order they were added.
+#[derive(Default)]
+pub(crate) struct Filters {
+    next_id: u64,
+    filters: Vec<(FilterId, Filter)>,
+}
+
+impl Filters {
+    /// Run the message through the filters, until one doesn't let it pass.
+    pub(crate) fn apply(filters: &std::sync::RwLock<Filters>, msg: &Message) -> FilterAction {
+        // Don't hold the lock while running the filters, so they can add or remove filters.
+        let filters: Vec<_> = {
+            let filters = filters.read().expect("lock poisoned");
+            if filters.filters.is_empty() {
+                return FilterAction::Pass;
+            }
+
+            filters.filters.iter().map(|(_, f)| f.clone()).collect()
+        };
+
+        filters
+            .iter()
+            .map(|f| f(msg))
+            .find(|action| *action != FilterAction::Pass)
+            .unwrap_or(FilterAction::Pass)
+    }
+}
+
+impl std::fmt::Debug for Filters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.filters.iter().map(|(id, _)| id))
+            .finish()
+    }
+}
+
+/// What a connection does with the file descriptors it receives in messages.
+///
+/// See [`Builder::fd_policy`].
+///
+/// This type is only available on Unix platforms.
+#[cfg(unix)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FdPolicy {
+    /// The messages own the file descriptors and close them when dropped.
+    ///
+    /// To keep a file descriptor around, either deserialize it to [`zvariant::OwnedFd`] (which
+    /// duplicates it) or take it out of the message with [`Message::take_fds`].
+    #[default]
+    Close,
+    /// The file descriptors are handed to the application, which is responsible for closing
+    /// them.
+    ///
+    /// The messages only borrow the file descriptors, which are therefore kept open after the
+    /// messages are dropped. This avoids duplicating file descriptors that are meant to be kept
+    /// around, but they're leaked unless closed explicitly.
+    Keep,
+}
+
 /// A D-Bus connection.
 ///
 /// A connection to a D-Bus bus, or a direct peer.
@@ -204,6 +296,14 @@ assert_impl_all!(Connection: Send, Sync, Unpin);
 pub(crate) struct PendingMethodCall {
     stream: Option<MessageStream>,
     serial: NonZeroU32,
+    send_timestamp: Option<Instant>,
+}
+
+impl PendingMethodCall {
+    /// When the method call was sent.
+    pub(crate) fn send_timestamp(&self) -> Option<Instant> {
+        self.send_timestamp
+    }
 }
 
 impl Future for PendingMethodCall {
@@ -279,6 +379,7 @@ impl Connection {
 
         self.inner.activity_event.notify(usize::MAX);
         let mut write = self.inner.socket_write.lock().await;
+        msg.set_send_timestamp(Instant::now());
 
         write.send_message(msg).await
     }
@@ -378,7 +479,11 @@ impl Connection {
         if flags.contains(Flags::NoReplyExpected) {
             Ok(None)
         } else {
-            Ok(Some(PendingMethodCall { stream, serial }))
+            Ok(Some(PendingMethodCall {
+                stream,
+                serial,
+                send_timestamp: msg.send_timestamp(),
+            }))
         }
     }
 
@@ -575,9 +680,9 @@ impl Connection {
     ///
     /// * Same as that of [`Connection::request_name`].
     /// * If you wish to track changes to name ownership after this call, make sure that the
-    /// [`fdo::NameAcquired`] and/or [`fdo::NameLostStream`] instance(s) are created **before**
-    /// calling this method. Otherwise, you may loose the signal if it's emitted after this call but
-    /// just before the stream instance get created.
+    ///   [`fdo::NameAcquired`] and/or [`fdo::NameLostStream`] instance(s) are created **before**
+    ///   calling this method. Otherwise, you may loose the signal if it's emitted after this call but
+    ///   just before the stream instance get created.
     pub async fn request_name_with_flags<'w, W>(
         &self,
         well_known_name: W,
@@ -736,8 +841,7 @@ impl Connection {
     {
         let well_known_name: WellKnownName<'w> = well_known_name.try_into().map_err(Into::into)?;
         let mut names = self.inner.registered_names.lock().await;
-        // FIXME: Should be possible to avoid cloning/allocation here
-        if names.remove(&well_known_name.to_owned()).is_none() {
+        if names.remove(well_known_name.as_str()).is_none() {
             return Ok(false);
         };
 
@@ -827,7 +931,7 @@ impl Connection {
     /// ```
     /// # // Disable on windows because somehow it triggers a stack overflow there:
     /// # // https://gitlab.freedesktop.org/zeenix/zbus/-/jobs/34023494
-    /// # #[cfg(not(target_os = "unix"))]
+    /// # #[cfg(not(windows))]
     /// # {
     /// use zbus::connection::Builder;
     /// use tokio::task::spawn;
@@ -1133,6 +1237,7 @@ impl Connection {
             inner: Arc::new(ConnectionInner {
                 activity_event: Arc::new(Event::new()),
                 socket_write: Mutex::new(auth.socket_write),
+                closed: Arc::new(AtomicBool::new(false)),
                 server_guid: auth.server_guid,
                 #[cfg(unix)]
                 cap_unix_fd,
@@ -1148,6 +1253,7 @@ impl Connection {
                 msg_receiver,
                 method_return_receiver,
                 registered_names: Mutex::new(HashMap::new()),
+                filters: Default::default(),
             }),
         };
 
@@ -1168,6 +1274,76 @@ impl Connection {
         Builder::system()?.build().await
     }
 
+    /// Get a shared `Connection` to the session/user message bus.
+    ///
+    /// Unlike [`Connection::session`], this hands out clones of the same connection to all
+    /// callers in the process, for as long as at least one of them keeps it alive. This is meant
+    /// for libraries that need a bus connection but don't want to open their own socket each.
+    ///
+    /// Keep in mind that anything done on the connection is visible to all its users, e.g
+    /// [`Connection::close`] closes it for everyone. A new connection is made by the next call once
+    /// the shared one is closed or broken. If you need a private connection, use
+    /// [`Connection::session`] or [`Builder::session`] instead. Applications can also disable
+    /// sharing entirely with [`set_sharing_enabled`].
+    pub async fn shared_session() -> Result<Self> {
+        shared::connection(crate::Address::session()?).await
+    }
+
+    /// Get a shared `Connection` to the system-wide message bus.
+    ///
+    /// See [`Connection::shared_session`] for details.
+    pub async fn shared_system() -> Result<Self> {
+        shared::connection(crate::Address::system()?).await
+    }
+
+    /// Get a shared `Connection` to the bus at the given [D-Bus bus address].
+    ///
+    /// Connections are shared per address. See [`Connection::shared_session`] for details.
+    ///
+    /// [D-Bus bus address]: https://dbus.freedesktop.org/doc/dbus-specification.html#addresses
+    pub async fn shared<A>(address: A) -> Result<Self>
+    where
+        A: TryInto<crate::Address>,
+        A::Error: Into<Error>,
+    {
+        shared::connection(address.try_into().map_err(Into::into)?).await
+    }
+
+    /// Add a filter for incoming messages.
+    ///
+    /// Filters are run on every message received, in the order they were added, before the message
+    /// is dispatched to the message streams and the object server. Each filter decides whether the
+    /// message is let through or not, through the returned [`FilterAction`]. Once a filter doesn't
+    /// let a message through, the subsequent filters are not run for it.
+    ///
+    /// This mirrors the filter functions of libdbus and is mostly useful for porting applications
+    /// relying on them, or for implementing connection-wide security policies.
+    ///
+    /// Since filters are run by the task reading the socket, they must be cheap. Any heavy
+    /// processing, I/O or blocking in a filter delays the reception of all messages.
+    pub fn add_filter<F>(&self, filter: F) -> FilterId
+    where
+        F: Fn(&Message) -> FilterAction + Send + Sync + 'static,
+    {
+        let mut filters = self.inner.filters.write().expect("lock poisoned");
+        let id = FilterId(filters.next_id);
+        filters.next_id += 1;
+        filters.filters.push((id, Arc::new(filter)));
+
+        id
+    }
+
+    /// Remove a filter added with [`Connection::add_filter`].
+    ///
+    /// Returns `false` if the filter was already removed.
+    pub fn remove_filter(&self, id: FilterId) -> bool {
+        let mut filters = self.inner.filters.write().expect("lock poisoned");
+        let len = filters.filters.len();
+        filters.filters.retain(|(i, _)| *i != id);
+
+        filters.filters.len() != len
+    }
+
     /// Returns a listener, notified on various connection activity.
     ///
     /// This function is meant for the caller to implement idle or timeout on inactivity.
@@ -1196,6 +1372,7 @@ impl Connection {
     ///
     /// After this call, all reading and writing operations will fail.
     pub async fn close(self) -> Result<()> {
+        self.inner.closed.store(true, Ordering::Release);
         self.inner.activity_event.notify(usize::MAX);
         self.inner
             .socket_write
@@ -1206,11 +1383,17 @@ impl Connection {
             .map_err(Into::into)
     }
 
+    /// Whether the connection was closed, or can't receive messages anymore.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.inner.closed.load(Ordering::Acquire)
+    }
+
     pub(crate) fn init_socket_reader(
         &self,
         socket_read: Box<dyn socket::ReadHalf>,
         already_read: Vec<u8>,
         #[cfg(unix)] already_received_fds: Vec<std::os::fd::OwnedFd>,
+        #[cfg(unix)] fd_policy: FdPolicy,
     ) {
         let inner = &self.inner;
         inner
@@ -1218,11 +1401,18 @@ impl Connection {
             .set(
                 SocketReader::new(
                     socket_read,
-                    inner.msg_senders.clone(),
                     already_read,
                     #[cfg(unix)]
                     already_received_fds,
-                    inner.activity_event.clone(),
+                    ReaderState {
+                        senders: inner.msg_senders.clone(),
+                        filters: inner.filters.clone(),
+                        #[cfg(unix)]
+                        fd_policy,
+                        activity_event: inner.activity_event.clone(),
+                        closed: inner.closed.clone(),
+                        conn: self.into(),
+                    },
                 )
                 .spawn(&inner.executor),
             )
@@ -1310,6 +1500,23 @@ mod tests {
         crate::utils::block_on(test_disconnect_on_drop());
     }
 
+    #[test]
+    #[timeout(15000)]
+    fn shared_reconnect_after_close() {
+        crate::utils::block_on(async {
+            let conn = Connection::shared_session().await.unwrap();
+            let conn2 = Connection::shared_session().await.unwrap();
+            assert_eq!(conn.unique_name(), conn2.unique_name());
+
+            // Once closed, the shared connection is replaced by a new one.
+            conn2.close().await.unwrap();
+            let conn3 = Connection::shared_session().await.unwrap();
+            assert_ne!(conn.unique_name(), conn3.unique_name());
+            let conn4 = Connection::shared_session().await.unwrap();
+            assert_eq!(conn3.unique_name(), conn4.unique_name());
+        });
+    }
+
     async fn test_disconnect_on_drop() {
         #[derive(Default)]
         struct MyInterface {}
@@ -1345,6 +1552,359 @@ mod tests {
         let name_has_owner = dbus.name_has_owner(name.try_into().unwrap()).await.unwrap();
         assert!(!name_has_owner);
     }
+
+    #[test]
+    #[timeout(15000)]
+    fn filters() {
+        crate::utils::block_on(test_filters());
+    }
+
+    async fn test_filters() {
+        struct Service;
+
+        #[crate::interface(name = "org.zbus.Test.Filtered")]
+        impl Service {
+            fn public(&self) -> u32 {
+                42
+            }
+
+            fn secret(&self) -> u32 {
+                7
+            }
+        }
+
+        let service = Builder::session()
+            .unwrap()
+            .serve_at("/org/zbus/Test/Filtered", Service)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        let id = service.add_filter(|msg| {
+            let header = msg.header();
+            match header.member().map(|m| m.as_str()) {
+                Some("Secret") => FilterAction::Drop,
+                _ => FilterAction::Pass,
+            }
+        });
+
+        let client = &Connection::session().await.unwrap();
+        let dest = &service.unique_name().unwrap().to_string();
+        let call = move |method| {
+            client.call_method(
+                Some(dest.as_str()),
+                "/org/zbus/Test/Filtered",
+                Some("org.zbus.Test.Filtered"),
+                method,
+                &(),
+            )
+        };
+        let reply = call("Public").await.unwrap();
+        assert_eq!(reply.body().deserialize::<u32>().unwrap(), 42);
+        match call("Secret").await.unwrap_err() {
+            Error::MethodError(name, _, _) => {
+                assert_eq!(name.as_str(), "org.freedesktop.DBus.Error.AccessDenied")
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+
+        assert!(service.remove_filter(id));
+        assert!(!service.remove_filter(id));
+        let reply = call("Secret").await.unwrap();
+        assert_eq!(reply.body().deserialize::<u32>().unwrap(), 7);
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn interface_init() {
+        crate::utils::block_on(test_interface_init());
+    }
+
+    async fn test_interface_init() {
+        struct Service {
+            path: Option<String>,
+        }
+
+        #[crate::interface(name = "org.zbus.Test.Init")]
+        impl Service {
+            #[zbus(init)]
+            async fn init(
+                &mut self,
+                _conn: &Connection,
+                ctxt: crate::object_server::SignalContext<'_>,
+            ) {
+                self.path = Some(ctxt.path().to_string());
+            }
+
+            fn path(&self) -> String {
+                self.path.clone().unwrap_or_default()
+            }
+        }
+
+        let service = Builder::session()
+            .unwrap()
+            .serve_at("/org/zbus/Test/Init", Service { path: None })
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        service
+            .object_server()
+            .at("/org/zbus/Test/Init2", Service { path: None })
+            .await
+            .unwrap();
+
+        let client = Connection::session().await.unwrap();
+        for path in ["/org/zbus/Test/Init", "/org/zbus/Test/Init2"] {
+            let reply = client
+                .call_method(
+                    Some(service.unique_name().unwrap()),
+                    path,
+                    Some("org.zbus.Test.Init"),
+                    "Path",
+                    &(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(reply.body().deserialize::<String>().unwrap(), path);
+        }
+
+        // `init` isn't exposed on the bus.
+        let reply = client
+            .call_method(
+                Some(service.unique_name().unwrap()),
+                "/org/zbus/Test/Init",
+                Some("org.freedesktop.DBus.Introspectable"),
+                "Introspect",
+                &(),
+            )
+            .await
+            .unwrap();
+        let xml = reply.body().deserialize::<String>().unwrap();
+        assert!(xml.contains(r#"<method name="Path">"#));
+        assert!(!xml.contains(r#"<method name="Init">"#));
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn object_server_registry() {
+        crate::utils::block_on(test_object_server_registry());
+    }
+
+    async fn test_object_server_registry() {
+        struct Service;
+
+        #[crate::interface(name = "org.zbus.Test.Registry")]
+        impl Service {
+            fn ping(&self) {}
+        }
+
+        let before = std::time::SystemTime::now();
+        let conn = Builder::session()
+            .unwrap()
+            .serve_at("/org/zbus/Test/Registry/A", Service)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        let server = conn.object_server();
+        server
+            .at("/org/zbus/Test/Registry/B", Service)
+            .await
+            .unwrap();
+        server
+            .at("/org/zbus/Test/Registry", crate::fdo::ObjectManager)
+            .await
+            .unwrap();
+
+        let objects = server.objects().await;
+        let paths: Vec<_> = objects.iter().map(|o| o.path().as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "/org/zbus/Test/Registry",
+                "/org/zbus/Test/Registry/A",
+                "/org/zbus/Test/Registry/B",
+            ]
+        );
+        assert!(objects[0].has_interface("org.freedesktop.DBus.ObjectManager"));
+        assert!(!objects[0].has_interface("org.zbus.Test.Registry"));
+        for object in &objects[1..] {
+            let iface = object
+                .interfaces()
+                .iter()
+                .find(|i| i.name() == "org.zbus.Test.Registry")
+                .unwrap();
+            assert!(iface.registered_at() >= before);
+        }
+
+        let interfaces = server
+            .interfaces("/org/zbus/Test/Registry/A")
+            .await
+            .unwrap();
+        let names: Vec<_> = interfaces.iter().map(|i| i.name().as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "org.freedesktop.DBus.Introspectable",
+                "org.freedesktop.DBus.Peer",
+                "org.freedesktop.DBus.Properties",
+                "org.zbus.Test.Registry",
+            ]
+        );
+
+        // Objects are gone from the registry once their last interface is removed.
+        assert!(server
+            .remove::<Service, _>("/org/zbus/Test/Registry/B")
+            .await
+            .unwrap());
+        assert_eq!(server.objects().await.len(), 2);
+        assert!(matches!(
+            server.interfaces("/org/zbus/Test/Registry/B").await,
+            Err(Error::InterfaceNotFound)
+        ));
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn interface_compose() {
+        crate::utils::block_on(test_interface_compose());
+    }
+
+    async fn test_interface_compose() {
+        #[derive(Default)]
+        struct Common {
+            verbose: bool,
+        }
+
+        #[crate::interface(name = "org.zbus.Test.Common")]
+        impl Common {
+            fn version(&self) -> &str {
+                "1.0"
+            }
+
+            fn reset(&mut self) {
+                self.verbose = false;
+            }
+
+            #[zbus(property)]
+            fn verbose(&self) -> bool {
+                self.verbose
+            }
+
+            #[zbus(property)]
+            fn set_verbose(&mut self, verbose: bool) {
+                self.verbose = verbose;
+            }
+        }
+
+        #[derive(Default)]
+        struct Player {
+            common: Common,
+        }
+
+        impl AsRef<Common> for Player {
+            fn as_ref(&self) -> &Common {
+                &self.common
+            }
+        }
+
+        impl AsMut<Common> for Player {
+            fn as_mut(&mut self) -> &mut Common {
+                &mut self.common
+            }
+        }
+
+        #[crate::interface(name = "org.zbus.Test.Player", compose("Common"))]
+        impl Player {
+            fn play(&self) -> bool {
+                true
+            }
+        }
+
+        let service = Builder::session()
+            .unwrap()
+            .serve_at("/org/zbus/Test/Player", Player::default())
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        let client = Connection::session().await.unwrap();
+        let proxy = crate::Proxy::new(
+            &client,
+            service.unique_name().unwrap(),
+            "/org/zbus/Test/Player",
+            "org.zbus.Test.Player",
+        )
+        .await
+        .unwrap();
+
+        assert!(proxy.call::<_, _, bool>("Play", &()).await.unwrap());
+        assert_eq!(
+            proxy.call::<_, _, String>("Version", &()).await.unwrap(),
+            "1.0"
+        );
+        proxy.set_property("Verbose", true).await.unwrap();
+        assert!(proxy.get_property::<bool>("Verbose").await.unwrap());
+        proxy.call::<_, _, ()>("Reset", &()).await.unwrap();
+        let props = crate::fdo::PropertiesProxy::builder(&client)
+            .destination(service.unique_name().unwrap())
+            .unwrap()
+            .path("/org/zbus/Test/Player")
+            .unwrap()
+            .build()
+            .await
+            .unwrap()
+            .get_all(zvariant::Optional::from(Some(
+                InterfaceName::try_from("org.zbus.Test.Player").unwrap(),
+            )))
+            .await
+            .unwrap();
+        assert!(!bool::try_from(&props["Verbose"]).unwrap());
+
+        let xml = crate::fdo::IntrospectableProxy::builder(&client)
+            .destination(service.unique_name().unwrap())
+            .unwrap()
+            .path("/org/zbus/Test/Player")
+            .unwrap()
+            .build()
+            .await
+            .unwrap()
+            .introspect()
+            .await
+            .unwrap();
+        let node = zbus_xml::Node::from_reader(xml.as_bytes()).unwrap();
+        let iface = node
+            .interfaces()
+            .iter()
+            .find(|i| i.name() == "org.zbus.Test.Player")
+            .unwrap();
+        let methods: Vec<_> = iface
+            .methods()
+            .iter()
+            .map(|m| m.name().to_string())
+            .collect();
+        assert!(methods.contains(&"Reset".to_string()));
+        assert!(iface.properties().iter().any(|p| p.name() == "Verbose"));
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn shared_session() {
+        crate::utils::block_on(test_shared_session());
+    }
+
+    async fn test_shared_session() {
+        let conn1 = Connection::shared_session().await.unwrap();
+        let conn2 = Connection::shared_session().await.unwrap();
+        assert_eq!(conn1.unique_name(), conn2.unique_name());
+
+        // Once all users are gone, a new connection is created.
+        let unique_name = conn1.unique_name().unwrap().to_owned();
+        drop((conn1, conn2));
+        let conn = Connection::shared_session().await.unwrap();
+        assert_ne!(conn.unique_name().unwrap(), &unique_name);
+    }
 }
 
 #[cfg(feature = "p2p")]
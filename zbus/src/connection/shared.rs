@@ -0,0 +1,67 @@
+//! Process-wide registry of shared connections.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
+};
+
+use crate::{async_lock::Mutex, Address, Result};
+
+use super::{Builder, Connection, WeakConnection};
+
+static SHARING_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Enable or disable connection sharing for the whole process.
+///
+/// When disabled, [`Connection::shared_session`], [`Connection::shared_system`] and
+/// [`Connection::shared`] create a new connection on each call, just like their non-shared
+/// counterparts. This allows applications to opt-out of sharing, even for connections created by
+/// the libraries they use.
+///
+/// Connections that are already shared are not affected. Sharing is enabled by default.
+pub fn set_sharing_enabled(enabled: bool) {
+    SHARING_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Get a connection to `address` from the registry, creating it if needed.
+pub(crate) async fn connection(address: Address) -> Result<Connection> {
+    if !SHARING_ENABLED.load(Ordering::Relaxed) {
+        return Builder::address(address)?.build().await;
+    }
+
+    // Each address gets its own entry, locked while connecting so that concurrent callers for the
+    // same address share the same connection, without holding up the callers for other addresses.
+    static REGISTRY: OnceLock<std::sync::Mutex<HashMap<String, Entry>>> = OnceLock::new();
+    let entry = {
+        let mut registry = REGISTRY
+            .get_or_init(Default::default)
+            .lock()
+            .expect("lock poisoned");
+        // Forget about the connections that were dropped in the meantime.
+        registry.retain(|_, entry| {
+            Arc::strong_count(entry) > 1
+                || entry.try_lock().map_or(true, |weak| {
+                    weak.as_ref().is_some_and(|w| w.inner.strong_count() > 0)
+                })
+        });
+
+        registry.entry(address.to_string()).or_default().clone()
+    };
+
+    let mut weak = entry.lock().await;
+    if let Some(conn) = weak.as_ref().and_then(WeakConnection::upgrade) {
+        if !conn.is_closed() {
+            return Ok(conn);
+        }
+    }
+
+    let conn = Builder::address(address)?.build().await?;
+    *weak = Some((&conn).into());
+
+    Ok(conn)
+}
+
+type Entry = Arc<Mutex<Option<WeakConnection>>>;
@@ -11,7 +11,7 @@ mod unix;
 mod vsock;
 
 #[cfg(not(feature = "tokio"))]
-use async_io::Async;
+use crate::abstractions::Async;
 #[cfg(not(feature = "tokio"))]
 use std::sync::Arc;
 use std::{io, mem};
@@ -394,7 +394,7 @@ impl WriteHalf for Box<dyn WriteHalf> {
     }
 }
 
-#[cfg(not(feature = "tokio"))]
+#[cfg(all(feature = "async-io", not(feature = "tokio")))]
 impl<T> Socket for Async<T>
 where
     T: std::fmt::Debug + Send + Sync,
@@ -412,3 +412,77 @@ where
         }
     }
 }
+
+#[cfg(all(not(feature = "async-io"), not(feature = "tokio")))]
+impl<T> Socket for Async<T>
+where
+    T: crate::abstractions::threaded::Blocking + std::fmt::Debug + Send + Sync + 'static,
+    Arc<Async<T>>: ReadHalf + WriteHalf,
+{
+    type ReadHalf = Arc<Async<T>>;
+    type WriteHalf = ShutdownOnDrop<T>;
+
+    fn split(self) -> Split<Self::ReadHalf, Self::WriteHalf> {
+        let arc = Arc::new(self);
+
+        Split {
+            read: arc.clone(),
+            write: ShutdownOnDrop(arc),
+        }
+    }
+}
+
+/// The write half of a blocking socket.
+///
+/// The read half is typically blocked on a read from its own thread, so the socket is shut down when
+/// the write half is dropped to get the reading side to return.
+#[cfg(all(not(feature = "async-io"), not(feature = "tokio")))]
+#[derive(Debug)]
+pub(crate) struct ShutdownOnDrop<T: crate::abstractions::threaded::Blocking>(Arc<Async<T>>);
+
+#[cfg(all(not(feature = "async-io"), not(feature = "tokio")))]
+#[async_trait::async_trait]
+impl<T> WriteHalf for ShutdownOnDrop<T>
+where
+    T: crate::abstractions::threaded::Blocking + std::fmt::Debug + Send + Sync + 'static,
+    Arc<Async<T>>: WriteHalf,
+{
+    async fn sendmsg(
+        &mut self,
+        buffer: &[u8],
+        #[cfg(unix)] fds: &[BorrowedFd<'_>],
+    ) -> io::Result<usize> {
+        self.0
+            .sendmsg(
+                buffer,
+                #[cfg(unix)]
+                fds,
+            )
+            .await
+    }
+
+    #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+    async fn send_zero_byte(&mut self) -> io::Result<Option<usize>> {
+        self.0.send_zero_byte().await
+    }
+
+    async fn close(&mut self) -> io::Result<()> {
+        self.0.close().await
+    }
+
+    fn can_pass_unix_fd(&self) -> bool {
+        self.0.can_pass_unix_fd()
+    }
+
+    async fn peer_credentials(&mut self) -> io::Result<ConnectionCredentials> {
+        self.0.peer_credentials().await
+    }
+}
+
+#[cfg(all(not(feature = "async-io"), not(feature = "tokio")))]
+impl<T: crate::abstractions::threaded::Blocking> Drop for ShutdownOnDrop<T> {
+    fn drop(&mut self) {
+        // Nothing to do if the socket is already closed.
+        let _ = self.0.get_ref().shutdown();
+    }
+}
@@ -1,5 +1,5 @@
 #[cfg(not(feature = "tokio"))]
-use async_io::Async;
+use crate::abstractions::Async;
 use std::io;
 #[cfg(unix)]
 use std::os::fd::BorrowedFd;
@@ -1,5 +1,5 @@
 #[cfg(not(feature = "tokio"))]
-use async_io::Async;
+use crate::abstractions::Async;
 #[cfg(unix)]
 use std::os::unix::io::{AsRawFd, BorrowedFd, FromRawFd, RawFd};
 #[cfg(all(unix, not(feature = "tokio")))]
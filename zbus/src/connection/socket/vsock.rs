@@ -3,7 +3,7 @@ use super::{Socket, Split};
 
 #[cfg(all(feature = "vsock", not(feature = "tokio")))]
 #[async_trait::async_trait]
-impl super::ReadHalf for std::sync::Arc<async_io::Async<vsock::VsockStream>> {
+impl super::ReadHalf for std::sync::Arc<crate::abstractions::Async<vsock::VsockStream>> {
     async fn recvmsg(&mut self, buf: &mut [u8]) -> super::RecvmsgResult {
         match futures_util::AsyncReadExt::read(&mut self.as_ref(), buf).await {
             Err(e) => Err(e),
@@ -20,7 +20,7 @@ impl super::ReadHalf for std::sync::Arc<async_io::Async<vsock::VsockStream>> {
 
 #[cfg(all(feature = "vsock", not(feature = "tokio")))]
 #[async_trait::async_trait]
-impl super::WriteHalf for std::sync::Arc<async_io::Async<vsock::VsockStream>> {
+impl super::WriteHalf for std::sync::Arc<crate::abstractions::Async<vsock::VsockStream>> {
     async fn sendmsg(
         &mut self,
         buf: &[u8],
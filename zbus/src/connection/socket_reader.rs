@@ -1,41 +1,63 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
 
 use event_listener::Event;
 use tracing::{debug, instrument, trace};
 
 use crate::{
-    async_lock::Mutex, connection::MsgBroadcaster, Executor, Message, OwnedMatchRule, Task,
+    async_lock::Mutex,
+    connection::MsgBroadcaster,
+    fdo,
+    message::{Flags, Type},
+    Executor, Message, OwnedMatchRule, Task,
 };
 
-use super::socket::ReadHalf;
+#[cfg(unix)]
+use super::FdPolicy;
+use super::{socket::ReadHalf, FilterAction, Filters, WeakConnection};
 
 #[derive(Debug)]
 pub(crate) struct SocketReader {
     socket: Box<dyn ReadHalf>,
-    senders: Arc<Mutex<HashMap<Option<OwnedMatchRule>, MsgBroadcaster>>>,
     already_received_bytes: Vec<u8>,
     #[cfg(unix)]
     already_received_fds: Vec<std::os::fd::OwnedFd>,
     prev_seq: u64,
-    activity_event: Arc<Event>,
+    state: ReaderState,
+}
+
+/// The connection state the socket reader handles the received messages with.
+#[derive(Debug)]
+pub(crate) struct ReaderState {
+    pub senders: Arc<Mutex<HashMap<Option<OwnedMatchRule>, MsgBroadcaster>>>,
+    pub filters: Arc<std::sync::RwLock<Filters>>,
+    #[cfg(unix)]
+    pub fd_policy: FdPolicy,
+    pub activity_event: Arc<Event>,
+    pub closed: Arc<AtomicBool>,
+    pub conn: WeakConnection,
 }
 
 impl SocketReader {
     pub fn new(
         socket: Box<dyn ReadHalf>,
-        senders: Arc<Mutex<HashMap<Option<OwnedMatchRule>, MsgBroadcaster>>>,
         already_received_bytes: Vec<u8>,
         #[cfg(unix)] already_received_fds: Vec<std::os::fd::OwnedFd>,
-        activity_event: Arc<Event>,
+        state: ReaderState,
     ) -> Self {
         Self {
             socket,
-            senders,
             already_received_bytes,
             #[cfg(unix)]
             already_received_fds,
             prev_seq: 0,
-            activity_event,
+            state,
         }
     }
 
@@ -54,7 +76,26 @@ impl SocketReader {
                 Err(e) => trace!("Error reading from the socket: {:?}", e),
             };
 
-            let mut senders = self.senders.lock().await;
+            if let Ok(msg) = &msg {
+                match Filters::apply(&self.state.filters, msg) {
+                    FilterAction::Pass => (),
+                    FilterAction::Consume => {
+                        trace!("Message consumed by a filter");
+
+                        continue;
+                    }
+                    FilterAction::Drop => {
+                        trace!("Message rejected by a filter");
+                        self.reject(msg).await;
+
+                        continue;
+                    }
+                }
+            }
+
+            let is_err = msg.is_err();
+            let mut senders = self.state.senders.lock().await;
+            let mut matching = vec![];
             for (rule, sender) in &*senders {
                 if let Ok(msg) = &msg {
                     if let Some(rule) = rule.as_ref() {
@@ -70,7 +111,17 @@ impl SocketReader {
                     }
                 }
 
-                if let Err(e) = sender.broadcast_direct(msg.clone()).await {
+                matching.push((rule, sender));
+            }
+
+            // The last stream gets the message itself, so that we don't keep any reference to it
+            // after it's been received. Otherwise, `Message::take_fds` couldn't be used on it.
+            let mut msg = Some(msg);
+            let last = matching.len().saturating_sub(1);
+            for (i, (rule, sender)) in matching.into_iter().enumerate() {
+                let msg = if i == last { msg.take() } else { msg.clone() };
+                let msg = msg.expect("message already broadcasted");
+                if let Err(e) = sender.broadcast_direct(msg).await {
                     // An error would be due to either of these:
                     //
                     // 1. the channel is closed.
@@ -84,9 +135,10 @@ impl SocketReader {
                     );
                 }
             }
-            trace!("Broadcasted to all streams: {:?}", msg);
+            trace!("Broadcasted to all streams");
 
-            if msg.is_err() {
+            if is_err {
+                self.state.closed.store(true, Ordering::Release);
                 senders.clear();
                 trace!("Socket reading task stopped");
 
@@ -95,9 +147,27 @@ impl SocketReader {
         }
     }
 
+    // Reply to a method call rejected by a filter, if the caller expects a reply.
+    async fn reject(&self, msg: &Message) {
+        let header = msg.header();
+        if msg.message_type() != Type::MethodCall
+            || header.primary().flags().contains(Flags::NoReplyExpected)
+        {
+            return;
+        }
+        let Some(conn) = self.state.conn.upgrade() else {
+            return;
+        };
+
+        let err = fdo::Error::AccessDenied("Message rejected by a filter".to_string());
+        if let Err(e) = conn.reply_dbus_error(&header, err).await {
+            debug!("Failed to reply to rejected method call: {:?}", e);
+        }
+    }
+
     #[instrument]
     async fn read_socket(&mut self) -> crate::Result<Message> {
-        self.activity_event.notify(usize::MAX);
+        self.state.activity_event.notify(usize::MAX);
         let seq = self.prev_seq + 1;
         let msg = self
             .socket
@@ -108,6 +178,12 @@ impl SocketReader {
                 &mut self.already_received_fds,
             )
             .await?;
+        msg.set_recv_timestamp(Instant::now());
+        #[cfg(unix)]
+        let msg = match self.state.fd_policy {
+            FdPolicy::Close => msg,
+            FdPolicy::Keep => msg.keep_fds()?,
+        };
         self.prev_seq = seq;
 
         Ok(msg)
@@ -7,7 +7,7 @@ use enumflags2::{bitflags, BitFlags};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use static_assertions::assert_impl_all;
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt, str::FromStr};
 use zbus_names::{
     BusName, InterfaceName, OwnedBusName, OwnedInterfaceName, OwnedUniqueName, UniqueName,
     WellKnownName,
@@ -460,6 +460,344 @@ macro_rules! gen_stats_proxy {
 gen_stats_proxy!(true, false);
 assert_impl_all!(StatsProxy<'_>: Send, Sync, Unpin);
 
+#[rustfmt::skip]
+macro_rules! gen_log_control_proxy {
+    ($gen_async:literal, $gen_blocking:literal) => {
+        /// Proxy for the `org.freedesktop.LogControl1` interface.
+        ///
+        /// See [`LogControl`](crate::fdo::LogControl) for the service-side implementation.
+        #[proxy(
+            interface = "org.freedesktop.LogControl1",
+            default_path = "/org/freedesktop/LogControl1",
+            gen_async = $gen_async,
+            gen_blocking = $gen_blocking,
+        )]
+        trait LogControl {
+            /// The maximum log level of the service, as one of the syslog priority names (e.g
+            /// `"info"` or `"debug"`).
+            #[zbus(property)]
+            fn log_level(&self) -> Result<String>;
+
+            /// Set the maximum log level of the service.
+            #[zbus(property)]
+            fn set_log_level(&self, level: &str) -> Result<()>;
+
+            /// Where the service sends its logs (e.g `"journal"` or `"console"`).
+            #[zbus(property)]
+            fn log_target(&self) -> Result<String>;
+
+            /// Set where the service sends its logs.
+            #[zbus(property)]
+            fn set_log_target(&self, target: &str) -> Result<()>;
+
+            /// The syslog identifier used by the service when logging.
+            #[zbus(property)]
+            fn syslog_identifier(&self) -> Result<String>;
+        }
+    };
+}
+
+gen_log_control_proxy!(true, false);
+assert_impl_all!(LogControlProxy<'_>: Send, Sync, Unpin);
+
+/// A log level of the [`LogControl`] interface.
+///
+/// The levels are the syslog priorities, from the most to the least severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    /// The system is unusable.
+    Emerg,
+    /// Action must be taken immediately.
+    Alert,
+    /// Critical conditions.
+    Crit,
+    /// Error conditions.
+    Err,
+    /// Warning conditions.
+    Warning,
+    /// Normal but significant conditions.
+    Notice,
+    /// Informational messages.
+    Info,
+    /// Debug messages.
+    Debug,
+}
+
+impl LogLevel {
+    /// The name of the level, as used on the bus.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Emerg => "emerg",
+            LogLevel::Alert => "alert",
+            LogLevel::Crit => "crit",
+            LogLevel::Err => "err",
+            LogLevel::Warning => "warning",
+            LogLevel::Notice => "notice",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "emerg" => Ok(LogLevel::Emerg),
+            "alert" => Ok(LogLevel::Alert),
+            "crit" => Ok(LogLevel::Crit),
+            "err" => Ok(LogLevel::Err),
+            "warning" => Ok(LogLevel::Warning),
+            "notice" => Ok(LogLevel::Notice),
+            "info" => Ok(LogLevel::Info),
+            "debug" => Ok(LogLevel::Debug),
+            _ => Err(Error::InvalidArgs(format!("Invalid log level `{s}`"))),
+        }
+    }
+}
+
+impl From<tracing::Level> for LogLevel {
+    fn from(level: tracing::Level) -> Self {
+        match level {
+            tracing::Level::ERROR => LogLevel::Err,
+            tracing::Level::WARN => LogLevel::Warning,
+            tracing::Level::INFO => LogLevel::Info,
+            // `DEBUG` and `TRACE`.
+            _ => LogLevel::Debug,
+        }
+    }
+}
+
+/// The `tracing` filter enabling all events at or above the level.
+///
+/// `tracing` has no counterpart for the levels more severe than [`LogLevel::Err`], so these all
+/// map to [`LevelFilter::ERROR`](tracing::level_filters::LevelFilter::ERROR).
+impl From<LogLevel> for tracing::level_filters::LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Emerg | LogLevel::Alert | LogLevel::Crit | LogLevel::Err => Self::ERROR,
+            LogLevel::Warning => Self::WARN,
+            LogLevel::Notice | LogLevel::Info => Self::INFO,
+            LogLevel::Debug => Self::DEBUG,
+        }
+    }
+}
+
+/// A log target of the [`LogControl`] interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogTarget {
+    /// The standard error of the service.
+    Console,
+    /// The kernel log buffer.
+    Kmsg,
+    /// The systemd journal.
+    Journal,
+    /// The syslog socket.
+    Syslog,
+    /// Logs are discarded.
+    Null,
+    /// The service picks the target automatically.
+    Auto,
+}
+
+impl LogTarget {
+    /// The name of the target, as used on the bus.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogTarget::Console => "console",
+            LogTarget::Kmsg => "kmsg",
+            LogTarget::Journal => "journal",
+            LogTarget::Syslog => "syslog",
+            LogTarget::Null => "null",
+            LogTarget::Auto => "auto",
+        }
+    }
+}
+
+impl fmt::Display for LogTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for LogTarget {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "console" => Ok(LogTarget::Console),
+            "kmsg" => Ok(LogTarget::Kmsg),
+            "journal" => Ok(LogTarget::Journal),
+            "syslog" => Ok(LogTarget::Syslog),
+            "null" => Ok(LogTarget::Null),
+            "auto" => Ok(LogTarget::Auto),
+            _ => Err(Error::InvalidArgs(format!("Invalid log target `{s}`"))),
+        }
+    }
+}
+
+type LogHook<T> = Box<dyn Fn(T) -> Result<()> + Send + Sync>;
+
+/// Service-side implementation of the [`org.freedesktop.LogControl1`][lc] interface.
+///
+/// This interface allows changing the log level and target of a service at runtime, e.g through
+/// `systemctl service-log-level` and `systemctl service-log-target`. It's expected at the
+/// `/org/freedesktop/LogControl1` path, which [`connection::Builder::serve_log_control`] takes
+/// care of.
+///
+/// Since there is no global way to reconfigure logging at runtime, the changes are applied through
+/// the hooks you provide. A hook can refuse a change by returning an error, which is then returned
+/// to the caller. Without a hook, changes are only recorded.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::error::Error;
+/// use tracing_subscriber::{filter::LevelFilter, prelude::*, reload};
+/// use zbus::{
+///     connection,
+///     fdo::{LogControl, LogLevel, LogTarget},
+/// };
+///
+/// let (filter, handle) = reload::Layer::new(LevelFilter::INFO);
+/// tracing_subscriber::registry()
+///     .with(filter)
+///     .with(tracing_subscriber::fmt::layer())
+///     .init();
+///
+/// let log_control = LogControl::new(LogLevel::Info, LogTarget::Console, "my-service")
+///     .on_level_change(move |level| {
+///         handle
+///             .modify(|filter| *filter = level.into())
+///             .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+///     });
+///
+/// # zbus::block_on(async {
+/// let _conn = connection::Builder::session()?
+///     .name("org.myservice.MyService")?
+///     .serve_log_control(log_control)?
+///     .build()
+///     .await?;
+/// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+/// # })?;
+/// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+/// ```
+///
+/// [lc]: https://www.freedesktop.org/software/systemd/man/latest/org.freedesktop.LogControl1.html
+/// [`connection::Builder::serve_log_control`]: crate::connection::Builder::serve_log_control
+pub struct LogControl {
+    level: LogLevel,
+    target: LogTarget,
+    syslog_identifier: String,
+    level_hook: Option<LogHook<LogLevel>>,
+    target_hook: Option<LogHook<LogTarget>>,
+}
+
+assert_impl_all!(LogControl: Send, Sync, Unpin);
+
+impl LogControl {
+    /// Create a new `LogControl`, with the current log level and target of the service.
+    pub fn new<S>(level: LogLevel, target: LogTarget, syslog_identifier: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            level,
+            target,
+            syslog_identifier: syslog_identifier.into(),
+            level_hook: None,
+            target_hook: None,
+        }
+    }
+
+    /// Set the hook applying log level changes.
+    pub fn on_level_change<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(LogLevel) -> Result<()> + Send + Sync + 'static,
+    {
+        self.level_hook = Some(Box::new(hook));
+
+        self
+    }
+
+    /// Set the hook applying log target changes.
+    pub fn on_target_change<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(LogTarget) -> Result<()> + Send + Sync + 'static,
+    {
+        self.target_hook = Some(Box::new(hook));
+
+        self
+    }
+
+    /// The current log level.
+    pub fn level(&self) -> LogLevel {
+        self.level
+    }
+
+    /// The current log target.
+    pub fn target(&self) -> LogTarget {
+        self.target
+    }
+}
+
+impl fmt::Debug for LogControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogControl")
+            .field("level", &self.level)
+            .field("target", &self.target)
+            .field("syslog_identifier", &self.syslog_identifier)
+            .finish_non_exhaustive()
+    }
+}
+
+#[interface(name = "org.freedesktop.LogControl1")]
+impl LogControl {
+    #[zbus(property)]
+    fn log_level(&self) -> String {
+        self.level.to_string()
+    }
+
+    #[zbus(property)]
+    fn set_log_level(&mut self, level: &str) -> Result<()> {
+        let level = level.parse()?;
+        if let Some(hook) = &self.level_hook {
+            hook(level)?;
+        }
+        self.level = level;
+
+        Ok(())
+    }
+
+    #[zbus(property)]
+    fn log_target(&self) -> String {
+        self.target.to_string()
+    }
+
+    #[zbus(property)]
+    fn set_log_target(&mut self, target: &str) -> Result<()> {
+        let target = target.parse()?;
+        if let Some(hook) = &self.target_hook {
+            hook(target)?;
+        }
+        self.target = target;
+
+        Ok(())
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn syslog_identifier(&self) -> String {
+        self.syslog_identifier.clone()
+    }
+}
+
 /// The flags used by the bus [`request_name`] method.
 ///
 /// [`request_name`]: struct.DBusProxy.html#method.request_name
@@ -1096,6 +1434,70 @@ mod tests {
             });
     }
 
+    #[test]
+    #[timeout(15000)]
+    fn log_control() {
+        crate::utils::block_on(test_log_control());
+    }
+
+    async fn test_log_control() {
+        use fdo::{LogControl, LogControlProxy, LogLevel, LogTarget};
+        use std::sync::{Arc, Mutex};
+
+        let levels = Arc::new(Mutex::new(vec![]));
+        let log_control = LogControl::new(LogLevel::Info, LogTarget::Console, "zbus-test")
+            .on_level_change({
+                let levels = levels.clone();
+                move |level| {
+                    levels.lock().unwrap().push(level);
+
+                    Ok(())
+                }
+            })
+            .on_target_change(|target| match target {
+                LogTarget::Console | LogTarget::Null => Ok(()),
+                _ => Err(fdo::Error::NotSupported(format!("{target} not supported"))),
+            });
+        let service = crate::connection::Builder::session()
+            .unwrap()
+            .serve_log_control(log_control)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let client = crate::Connection::session().await.unwrap();
+        let proxy = LogControlProxy::builder(&client)
+            .destination(service.unique_name().unwrap())
+            .unwrap()
+            .cache_properties(crate::proxy::CacheProperties::No)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(proxy.log_level().await.unwrap(), "info");
+        assert_eq!(proxy.log_target().await.unwrap(), "console");
+        assert_eq!(proxy.syslog_identifier().await.unwrap(), "zbus-test");
+
+        proxy.set_log_level("debug").await.unwrap();
+        assert_eq!(proxy.log_level().await.unwrap(), "debug");
+        let err = proxy.set_log_level("verbose").await.unwrap_err();
+        assert!(matches!(err, fdo::Error::InvalidArgs(_)));
+        assert_eq!(*levels.lock().unwrap(), [LogLevel::Debug]);
+
+        proxy.set_log_target("null").await.unwrap();
+        let err = proxy.set_log_target("journal").await.unwrap_err();
+        assert!(matches!(err, fdo::Error::NotSupported(_)));
+        assert_eq!(proxy.log_target().await.unwrap(), "null");
+
+        let iface = service
+            .object_server()
+            .interface::<_, LogControl>("/org/freedesktop/LogControl1")
+            .await
+            .unwrap();
+        assert_eq!(iface.get().await.level(), LogLevel::Debug);
+        assert_eq!(iface.get().await.target(), LogTarget::Null);
+    }
+
     #[test]
     #[timeout(15000)]
     fn no_object_manager_signals_before_hello() {
@@ -28,13 +28,19 @@ mod doctests {
     doc_comment::doctest!("../../book/src/faq.md");
 }
 
-#[cfg(all(not(feature = "async-io"), not(feature = "tokio")))]
+#[cfg(all(
+    not(feature = "async-io"),
+    not(feature = "tokio"),
+    not(feature = "blocking-only")
+))]
 mod error_message {
     #[cfg(windows)]
-    compile_error!("Either \"async-io\" (default) or \"tokio\" must be enabled. On Windows \"async-io\" is (currently) required for UNIX socket support");
+    compile_error!("Either \"async-io\" (default), \"tokio\" or \"blocking-only\" must be enabled. On Windows \"async-io\" is (currently) required for UNIX socket support");
 
     #[cfg(not(windows))]
-    compile_error!("Either \"async-io\" (default) or \"tokio\" must be enabled.");
+    compile_error!(
+        "Either \"async-io\" (default), \"tokio\" or \"blocking-only\" must be enabled."
+    );
 }
 
 #[cfg(windows)]
@@ -106,6 +112,9 @@ pub use match_rule::PathSpec as MatchRulePathSpec;
 pub mod proxy;
 pub use proxy::Proxy;
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub mod memfd;
+
 #[deprecated(since = "4.0.0", note = "Use `proxy::Builder` instead")]
 #[doc(hidden)]
 pub use proxy::Builder as ProxyBuilder;
@@ -913,7 +922,7 @@ mod tests {
             "org.zbus.Issue260",
         )
         .await?
-        .call("Whatever", &())
+        .call::<_, _, ()>("Whatever", &())
         .await?;
         Ok(())
     }
@@ -1029,7 +1038,7 @@ mod tests {
             let path = change.get().await.unwrap();
             let received: u64 = path
                 .split('/')
-                .last()
+                .next_back()
                 .unwrap()
                 .parse()
                 .expect("invalid path");
@@ -1199,14 +1208,14 @@ mod tests {
                 let msg_data = msg.data();
                 let mut fds = vec![];
                 for _ in 0..2 {
-                    bytes.extend_from_slice(&*msg_data);
+                    bytes.extend_from_slice(msg_data);
                     fds.push(fd.as_fd());
                 }
 
                 #[cfg(feature = "tokio")]
                 let mut split = crate::conn::Socket::split(p1);
                 #[cfg(not(feature = "tokio"))]
-                let mut split = crate::conn::Socket::split(async_io::Async::new(p1)?);
+                let mut split = crate::conn::Socket::split(crate::abstractions::Async::new(p1)?);
                 split.write_mut().sendmsg(&bytes, &fds).await?;
 
                 server_listener.await;
@@ -1220,4 +1229,67 @@ mod tests {
         })
         .unwrap();
     }
+
+    #[cfg(all(unix, feature = "p2p"))]
+    #[test]
+    #[timeout(15000)]
+    fn fd_policy() {
+        use crate::{
+            connection::{Builder, FdPolicy},
+            MessageStream,
+        };
+        use futures_util::{stream::TryStreamExt, try_join};
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        use std::{
+            io::{Read, Write},
+            os::fd::{AsRawFd, FromRawFd},
+        };
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+        use zvariant::Fd;
+
+        block_on(async move {
+            let guid = crate::Guid::generate();
+            let (p0, p1) = UnixStream::pair().unwrap();
+            let server = Builder::unix_stream(p0).server(guid)?.p2p().build();
+            let client = Builder::unix_stream(p1)
+                .p2p()
+                .fd_policy(FdPolicy::Keep)
+                .build();
+            let (client, server) = try_join!(client, server)?;
+            let mut stream = MessageStream::from(&client);
+
+            let (mut writer, reader) = std::os::unix::net::UnixStream::pair().unwrap();
+            for _ in 0..2 {
+                server
+                    .emit_signal(None::<()>, "/", "org.zbus.Fds", "Fd", &(Fd::from(&reader),))
+                    .await?;
+            }
+            drop(reader);
+
+            // The received file descriptor is still open after the message is dropped.
+            let msg = stream.try_next().await?.unwrap();
+            assert!(msg.contains_fds());
+            let raw_fd = msg.body().deserialize::<Fd<'_>>()?.as_raw_fd();
+            drop(msg);
+            // SAFETY: Nothing else owns the file descriptor with `FdPolicy::Keep`.
+            let mut received = unsafe { std::os::unix::net::UnixStream::from_raw_fd(raw_fd) };
+            writer.write_all(b"kept")?;
+            let mut buf = [0; 4];
+            received.read_exact(&mut buf)?;
+            assert_eq!(&buf, b"kept");
+
+            // The file descriptors can be taken out of a message.
+            let mut msg = stream.try_next().await?.unwrap();
+            let fds = msg.take_fds().unwrap();
+            assert_eq!(fds.len(), 1);
+            assert!(!msg.contains_fds());
+            // SAFETY: Same as above.
+            drop(unsafe { std::os::fd::OwnedFd::from_raw_fd(fds[0].as_raw_fd()) });
+
+            Result::<()>::Ok(())
+        })
+        .unwrap();
+    }
 }
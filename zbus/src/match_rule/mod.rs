@@ -268,7 +268,7 @@ impl<'m> MatchRule<'m> {
             };
             match path_spec {
                 PathSpec::Path(path) if path != msg_path => return Ok(false),
-                PathSpec::PathNamespace(path_ns) if !msg_path.starts_with(path_ns.as_str()) => {
+                PathSpec::PathNamespace(path_ns) if !msg_path.starts_with_path(path_ns) => {
                     return Ok(false);
                 }
                 PathSpec::Path(_) | PathSpec::PathNamespace(_) => (),
@@ -278,10 +278,8 @@ impl<'m> MatchRule<'m> {
         // The arg0 namespace.
         if let Some(arg0_ns) = self.arg0ns() {
             if let Ok(arg0) = msg.body().deserialize_unchecked::<BusName<'_>>() {
-                match arg0.strip_prefix(arg0_ns.as_str()) {
-                    None => return Ok(false),
-                    Some(s) if !s.is_empty() && !s.starts_with('.') => return Ok(false),
-                    _ => (),
+                if !arg0.is_in_namespace(arg0_ns) {
+                    return Ok(false);
                 }
             } else {
                 return Ok(false);
@@ -576,3 +574,40 @@ impl PartialEq<MatchRule<'_>> for OwnedMatchRule {
         self.0 == *other
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Message;
+
+    #[test]
+    fn namespaces() {
+        let rule = MatchRule::builder()
+            .path_namespace("/org/zbus")
+            .unwrap()
+            .arg0ns("org.zbus")
+            .unwrap()
+            .build();
+        let signal = |path, arg0| {
+            Message::signal(path, "org.zbus.Test", "Changed")
+                .unwrap()
+                .build(&(arg0,))
+                .unwrap()
+        };
+
+        assert!(rule.matches(&signal("/org/zbus", "org.zbus")).unwrap());
+        assert!(rule
+            .matches(&signal("/org/zbus/Object", "org.zbus.Service"))
+            .unwrap());
+        // Namespaces are matched element-wise.
+        assert!(!rule
+            .matches(&signal("/org/zbusy", "org.zbus.Service"))
+            .unwrap());
+        assert!(!rule
+            .matches(&signal("/org/zbus/Object", "org.zbusy.Service"))
+            .unwrap());
+
+        let rule = MatchRule::builder().path_namespace("/").unwrap().build();
+        assert!(rule.matches(&signal("/org/zbus", "org.zbus")).unwrap());
+    }
+}
@@ -0,0 +1,334 @@
+//! Transfer of large payloads through sealed memory file descriptors.
+//!
+//! D-Bus is not meant for bulk data: every byte of a message is copied in and out of the socket (and
+//! through the bus broker), and brokers limit the size of messages. The common pattern for moving
+//! large amounts of data (e.g images or file contents) is instead to write them to a [memfd],
+//! [seal] it against any further modification and send its file descriptor. The receiving side can
+//! then map it into memory without copying, and without having to trust the sender not to change
+//! it under its feet.
+//!
+//! [`Payload`] implements that pattern and can be used as an argument or return type of methods in
+//! both [`interface`] and [`proxy`] declarations, where it's encoded as a file descriptor (`h`).
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use std::error::Error;
+//! use zbus::{interface, memfd::Payload, proxy, Connection};
+//!
+//! struct Thumbnailer;
+//!
+//! #[interface(name = "org.zbus.Thumbnailer")]
+//! impl Thumbnailer {
+//!     fn thumbnail(&self, image: Payload) -> zbus::fdo::Result<Payload> {
+//!         let thumbnail = make_thumbnail(&image);
+//!
+//!         Payload::new(&thumbnail).map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+//!     }
+//! }
+//! # fn make_thumbnail(image: &[u8]) -> Vec<u8> { image.to_vec() }
+//!
+//! #[proxy(
+//!     interface = "org.zbus.Thumbnailer",
+//!     default_service = "org.zbus.Thumbnailer",
+//!     default_path = "/org/zbus/Thumbnailer"
+//! )]
+//! trait Thumbnailer {
+//!     fn thumbnail(&self, image: &Payload) -> zbus::Result<Payload>;
+//! }
+//!
+//! # zbus::block_on(async {
+//! let connection = Connection::session().await?;
+//! let proxy = ThumbnailerProxy::new(&connection).await?;
+//! let image = Payload::new(&std::fs::read("image.png")?)?;
+//! let thumbnail = proxy.thumbnail(&image).await?;
+//! std::fs::write("thumbnail.png", &*thumbnail)?;
+//! # Ok::<_, Box<dyn Error + Send + Sync>>(())
+//! # })?;
+//! # Ok::<_, Box<dyn Error + Send + Sync>>(())
+//! ```
+//!
+//! [memfd]: https://man7.org/linux/man-pages/man2/memfd_create.2.html
+//! [seal]: https://man7.org/linux/man-pages/man2/fcntl.2.html#File_Sealing
+//! [`interface`]: macro@crate::interface
+//! [`proxy`]: macro@crate::proxy
+
+use std::{
+    ffi::{c_void, CStr},
+    fmt,
+    fs::File,
+    io::{self, Write},
+    num::NonZeroUsize,
+    ops::Deref,
+    os::fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd},
+    ptr::NonNull,
+};
+
+use nix::{
+    fcntl::{fcntl, FcntlArg, SealFlag},
+    sys::{
+        memfd::{memfd_create, MemFdCreateFlag},
+        mman::{mmap, munmap, MapFlags, ProtFlags},
+    },
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use static_assertions::assert_impl_all;
+use zvariant::{Fd, Signature, Type};
+
+use crate::{Error, Result};
+
+/// The seals that guarantee the contents of a memfd can't change anymore.
+const REQUIRED_SEALS: SealFlag = SealFlag::F_SEAL_SHRINK
+    .union(SealFlag::F_SEAL_GROW)
+    .union(SealFlag::F_SEAL_WRITE);
+
+/// A read-only byte payload, stored in a sealed memfd.
+///
+/// The payload derefs to its bytes, which are mapped into memory rather than copied. See the
+/// [module documentation](self) for details.
+pub struct Payload {
+    fd: OwnedFd,
+    map: Option<Mapping>,
+}
+
+assert_impl_all!(Payload: Send, Sync, Unpin);
+
+impl Payload {
+    /// Create a payload holding a copy of `data`.
+    pub fn new(data: &[u8]) -> Result<Self> {
+        let name = CStr::from_bytes_with_nul(b"zbus-payload\0").expect("invalid memfd name");
+        let fd = memfd_create(
+            name,
+            MemFdCreateFlag::MFD_CLOEXEC | MemFdCreateFlag::MFD_ALLOW_SEALING,
+        )?;
+        let mut file = File::from(fd);
+        file.write_all(data)?;
+        let fd = OwnedFd::from(file);
+        fcntl(
+            fd.as_raw_fd(),
+            FcntlArg::F_ADD_SEALS(REQUIRED_SEALS | SealFlag::F_SEAL_SEAL),
+        )?;
+
+        Self::map(fd, data.len())
+    }
+
+    /// Create a payload from the file descriptor of a sealed memfd.
+    ///
+    /// # Errors
+    ///
+    /// If the file descriptor is not a memfd sealed against writing, growing and shrinking, an
+    /// [`Error::InputOutput`] error is returned. Without these seals, the sender could modify the
+    /// payload while it's being read.
+    pub fn from_fd(fd: OwnedFd) -> Result<Self> {
+        let seals = SealFlag::from_bits_truncate(fcntl(fd.as_raw_fd(), FcntlArg::F_GET_SEALS)?);
+        if !seals.contains(REQUIRED_SEALS) {
+            return Err(
+                io::Error::new(io::ErrorKind::InvalidInput, "payload memfd is not sealed").into(),
+            );
+        }
+        let file = File::from(fd);
+        let len = usize::try_from(file.metadata()?.len()).map_err(|_| {
+            Error::from(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "payload too large to be mapped",
+            ))
+        })?;
+
+        Self::map(file.into(), len)
+    }
+
+    /// The payload bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        match &self.map {
+            // SAFETY: The mapping is valid for `len` bytes for as long as `self` lives and the seals
+            // guarantee the memory can't be changed, even by the creator of the memfd.
+            Some(map) => unsafe { std::slice::from_raw_parts(map.ptr.as_ptr().cast(), map.len) },
+            None => &[],
+        }
+    }
+
+    /// The number of bytes in the payload.
+    pub fn len(&self) -> usize {
+        self.map.as_ref().map(|map| map.len).unwrap_or(0)
+    }
+
+    /// Whether the payload is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn map(fd: OwnedFd, len: usize) -> Result<Self> {
+        // Empty files can't be mapped.
+        let map = match NonZeroUsize::new(len) {
+            Some(len) => {
+                // SAFETY: We map the whole file read-only and the file can't be shrunk, so the
+                // mapping stays valid until it's unmapped.
+                let ptr = unsafe {
+                    mmap(
+                        None,
+                        len,
+                        ProtFlags::PROT_READ,
+                        MapFlags::MAP_SHARED,
+                        &fd,
+                        0,
+                    )?
+                };
+                Some(Mapping {
+                    ptr,
+                    len: len.get(),
+                })
+            }
+            None => None,
+        };
+
+        Ok(Self { fd, map })
+    }
+}
+
+impl Deref for Payload {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl AsRef<[u8]> for Payload {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl AsFd for Payload {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
+
+impl fmt::Debug for Payload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Payload")
+            .field("fd", &self.fd)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl Type for Payload {
+    fn signature() -> Signature<'static> {
+        Fd::signature()
+    }
+}
+
+impl Serialize for Payload {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Fd::from(&self.fd).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Payload {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fd = zvariant::OwnedFd::deserialize(deserializer)?;
+
+        Self::from_fd(fd.into()).map_err(de::Error::custom)
+    }
+}
+
+/// A read-only memory mapping, unmapped on drop.
+struct Mapping {
+    ptr: NonNull<c_void>,
+    len: usize,
+}
+
+// SAFETY: The mapped memory is read-only and can't be modified through the memfd either.
+unsafe impl Send for Mapping {}
+unsafe impl Sync for Mapping {}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        // SAFETY: The mapping was created by `mmap` with this length and is not used anymore.
+        if let Err(e) = unsafe { munmap(self.ptr, self.len) } {
+            tracing::warn!("Failed to unmap payload: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{connection::Builder, interface, Connection};
+    use ntest::timeout;
+    use test_log::test;
+
+    #[test]
+    fn seals() {
+        let payload = Payload::new(b"hello").unwrap();
+        assert_eq!(&*payload, b"hello");
+        assert_eq!(payload.len(), 5);
+
+        // The contents can't be altered anymore.
+        let mut file = File::from(payload.fd.try_clone().unwrap());
+        file.write_all(b"world").unwrap_err();
+        file.set_len(2).unwrap_err();
+
+        // Only sealed memfds are accepted.
+        let name = CStr::from_bytes_with_nul(b"zbus-test\0").unwrap();
+        let fd = memfd_create(name, MemFdCreateFlag::MFD_CLOEXEC).unwrap();
+        Payload::from_fd(fd).unwrap_err();
+
+        let empty = Payload::new(&[]).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.as_bytes(), b"");
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn transfer() {
+        crate::utils::block_on(test_transfer());
+    }
+
+    async fn test_transfer() {
+        struct Echo;
+
+        #[interface(name = "org.zbus.Test.Payload")]
+        impl Echo {
+            fn reverse(&self, payload: Payload) -> crate::fdo::Result<Payload> {
+                let mut data = payload.to_vec();
+                data.reverse();
+
+                Payload::new(&data).map_err(|e| crate::fdo::Error::Failed(e.to_string()))
+            }
+        }
+
+        let service = Builder::session()
+            .unwrap()
+            .serve_at("/org/zbus/Test/Payload", Echo)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+
+        let data: Vec<u8> = (0..8 * 1024 * 1024).map(|i| i as u8).collect();
+        let payload = Payload::new(&data).unwrap();
+        let client = Connection::session().await.unwrap();
+        let reply = client
+            .call_method(
+                Some(service.unique_name().unwrap()),
+                "/org/zbus/Test/Payload",
+                Some("org.zbus.Test.Payload"),
+                "Reverse",
+                &payload,
+            )
+            .await
+            .unwrap();
+        let reversed: Payload = reply.body().deserialize().unwrap();
+        assert_eq!(reversed.len(), data.len());
+        assert!(reversed.iter().eq(data.iter().rev()));
+    }
+}
@@ -20,6 +20,10 @@ impl Body {
     }
 
     /// Deserialize the body using the contained signature.
+    ///
+    /// Borrowed types, such as `&str`, `&[u8]` (or [`zvariant::Bytes`]) and
+    /// [`zvariant::ObjectPath<'_>`], borrow from the body and hence don't involve any copying or
+    /// allocation.
     pub fn deserialize<'s, B>(&'s self) -> Result<B>
     where
         B: zvariant::DynamicDeserialize<'s>,
@@ -1,6 +1,6 @@
 use std::{
     io::{Cursor, Write},
-    sync::Arc,
+    sync::{Arc, OnceLock},
 };
 #[cfg(unix)]
 use zvariant::OwnedFd;
@@ -278,7 +278,7 @@ impl<'a> Builder<'a> {
 
     fn build_generic<WriteFunc>(
         self,
-        mut signature: Signature<'_>,
+        signature: Signature<'_>,
         body_size: serialized::Size,
         write_body: WriteFunc,
     ) -> Result<Message>
@@ -289,11 +289,9 @@ impl<'a> Builder<'a> {
         let mut header = self.header;
 
         if !signature.is_empty() {
-            if signature.starts_with(zvariant::STRUCT_SIG_START_STR) {
-                // Remove leading and trailing STRUCT delimiters
-                signature = signature.slice(1..signature.len() - 1);
-            }
-            header.fields_mut().add(Field::Signature(signature));
+            header
+                .fields_mut()
+                .add(Field::Signature(signature.canonicalize()));
         }
 
         let body_len_u32 = body_size.size().try_into().map_err(|_| Error::ExcessData)?;
@@ -344,6 +342,8 @@ impl<'a> Builder<'a> {
                 bytes,
                 body_offset,
                 recv_seq: Sequence::default(),
+                send_timestamp: OnceLock::new(),
+                recv_timestamp: OnceLock::new(),
             }),
         })
     }
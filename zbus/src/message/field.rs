@@ -137,27 +137,21 @@ impl<'de: 'f, 'f> Deserialize<'de> for Field<'f> {
             FieldCode::Member => {
                 Field::Member(MemberName::try_from(value).map_err(D::Error::custom)?)
             }
-            FieldCode::ErrorName => Field::ErrorName(
-                ErrorName::try_from(value)
-                    .map(Into::into)
-                    .map_err(D::Error::custom)?,
-            ),
+            FieldCode::ErrorName => {
+                Field::ErrorName(ErrorName::try_from(value).map_err(D::Error::custom)?)
+            }
             FieldCode::ReplySerial => {
                 let value = u32::try_from(value)
                     .map_err(D::Error::custom)
                     .and_then(|v| v.try_into().map_err(D::Error::custom))?;
                 Field::ReplySerial(value)
             }
-            FieldCode::Destination => Field::Destination(
-                BusName::try_from(value)
-                    .map(Into::into)
-                    .map_err(D::Error::custom)?,
-            ),
-            FieldCode::Sender => Field::Sender(
-                UniqueName::try_from(value)
-                    .map(Into::into)
-                    .map_err(D::Error::custom)?,
-            ),
+            FieldCode::Destination => {
+                Field::Destination(BusName::try_from(value).map_err(D::Error::custom)?)
+            }
+            FieldCode::Sender => {
+                Field::Sender(UniqueName::try_from(value).map_err(D::Error::custom)?)
+            }
             FieldCode::Signature => {
                 Field::Signature(Signature::try_from(value).map_err(D::Error::custom)?)
             }
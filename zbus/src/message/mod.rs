@@ -1,5 +1,10 @@
 //! D-Bus Message.
-use std::{fmt, num::NonZeroU32, sync::Arc};
+use std::{
+    fmt,
+    num::NonZeroU32,
+    sync::{Arc, OnceLock},
+    time::Instant,
+};
 
 use static_assertions::assert_impl_all;
 use zbus_names::{ErrorName, InterfaceName, MemberName};
@@ -64,6 +69,8 @@ pub(super) struct Inner {
     pub(crate) bytes: serialized::Data<'static, 'static>,
     pub(crate) body_offset: usize,
     pub(crate) recv_seq: Sequence,
+    pub(crate) send_timestamp: OnceLock<Instant>,
+    pub(crate) recv_timestamp: OnceLock<Instant>,
 }
 
 assert_impl_all!(Message: Send, Sync, Unpin);
@@ -152,6 +159,8 @@ impl Message {
                 bytes,
                 body_offset,
                 recv_seq: Sequence { recv_seq },
+                send_timestamp: OnceLock::new(),
+                recv_timestamp: OnceLock::new(),
             }),
         })
     }
@@ -278,6 +287,81 @@ impl Message {
         &self.inner.bytes
     }
 
+    /// Whether the message carries any file descriptors.
+    ///
+    /// This method is only available on Unix platforms.
+    #[cfg(unix)]
+    pub fn contains_fds(&self) -> bool {
+        !self.inner.bytes.fds().is_empty()
+    }
+
+    /// Take the file descriptors out of the message.
+    ///
+    /// The message then no longer closes them when dropped. This is only possible if `self` is
+    /// the only reference to the message contents, so `None` is returned if the message has been
+    /// cloned (e.g because it was received by multiple streams) or its [`Body`] is still around.
+    /// Since the body still refers to the file descriptors by index, deserializing them from the
+    /// message will fail afterwards.
+    ///
+    /// The file descriptors are owned, unless the message was received on a connection whose
+    /// [`FdPolicy`] is [`FdPolicy::Keep`].
+    ///
+    /// This method is only available on Unix platforms.
+    ///
+    /// [`FdPolicy`]: crate::connection::FdPolicy
+    /// [`FdPolicy::Keep`]: crate::connection::FdPolicy::Keep
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zbus::message::Message;
+    /// use std::os::fd::OwnedFd;
+    /// use zvariant::Fd;
+    ///
+    /// let stdout = std::io::stdout();
+    /// let mut message = Message::method("/", "Write")?.build(&(Fd::from(&stdout),))?;
+    /// assert!(message.contains_fds());
+    ///
+    /// let fds = message.take_fds().unwrap();
+    /// assert!(!message.contains_fds());
+    /// let fd = OwnedFd::try_from(fds.into_iter().next().unwrap())?;
+    /// # drop(fd);
+    /// # Ok::<(), zbus::Error>(())
+    /// ```
+    #[cfg(unix)]
+    pub fn take_fds(&mut self) -> Option<Vec<zvariant::Fd<'static>>> {
+        Arc::get_mut(&mut self.inner)?.bytes.take_fds()
+    }
+
+    /// Give up the ownership of the file descriptors, so they're never closed by zbus.
+    ///
+    /// This is a no-op for messages without any file descriptors.
+    #[cfg(unix)]
+    pub(crate) fn keep_fds(mut self) -> Result<Self> {
+        use std::os::fd::{BorrowedFd, IntoRawFd};
+        use zvariant::Fd;
+
+        let fds = match self.take_fds() {
+            Some(fds) if !fds.is_empty() => fds,
+            _ => return Ok(self),
+        };
+        let fds = fds.into_iter().map(|fd| match fd {
+            // SAFETY: The file descriptor is leaked, so it remains open until the application
+            // closes it, which it's responsible for with `FdPolicy::Keep`.
+            Fd::Owned(fd) => unsafe { BorrowedFd::borrow_raw(fd.into_raw_fd()) },
+            Fd::Borrowed(fd) => fd,
+        });
+        let data = self.data();
+        let bytes = serialized::Data::new_borrowed_fds(data.to_vec(), data.context(), fds);
+
+        let msg = Self::from_raw_parts(bytes, self.inner.recv_seq.recv_seq)?;
+        if let Some(timestamp) = self.recv_timestamp() {
+            msg.set_recv_timestamp(timestamp);
+        }
+
+        Ok(msg)
+    }
+
     /// Get the receive ordering of a message.
     ///
     /// This may be used to identify how two events were ordered on the bus.  It only produces a
@@ -288,6 +372,76 @@ impl Message {
     pub fn recv_position(&self) -> Sequence {
         self.inner.recv_seq
     }
+
+    /// When the message was sent, according to the monotonic clock.
+    ///
+    /// This is set when the message is first sent over a [`zbus::Connection`], and is `None` for
+    /// messages that weren't sent (yet) or that were received from a peer.
+    pub fn send_timestamp(&self) -> Option<Instant> {
+        self.inner.send_timestamp.get().copied()
+    }
+
+    /// When the message was received, according to the monotonic clock.
+    ///
+    /// This is set when the message is read from the socket of a [`zbus::Connection`], and is
+    /// `None` for messages that weren't received through a connection.
+    pub fn recv_timestamp(&self) -> Option<Instant> {
+        self.inner.recv_timestamp.get().copied()
+    }
+
+    pub(crate) fn set_send_timestamp(&self, timestamp: Instant) {
+        // Only the first time a message is sent is recorded.
+        let _ = self.inner.send_timestamp.set(timestamp);
+    }
+
+    pub(crate) fn set_recv_timestamp(&self, timestamp: Instant) {
+        let _ = self.inner.recv_timestamp.set(timestamp);
+    }
+
+    /// Display the body in a readable multi-line format, e.g for logging.
+    ///
+    /// Each argument is put on its own line, prefixed by its index, and displayed in the alternate
+    /// (`{:#}`) format of [`zvariant::Value`], where every value is annotated with its type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use zbus::message::Message;
+    /// let message = Message::method("/", "SetVolume")?.build(&("speakers", 42u32))?;
+    /// assert_eq!(
+    ///     message.display_body().to_string(),
+    ///     "[0] string \"speakers\"\n[1] uint32 42",
+    /// );
+    /// # Ok::<(), zbus::Error>(())
+    /// ```
+    pub fn display_body(&self) -> impl fmt::Display + '_ {
+        BodyDisplay(self)
+    }
+}
+
+struct BodyDisplay<'m>(&'m Message);
+
+impl fmt::Display for BodyDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let body = self.0.body();
+        if body.signature().map_or(true, |s| s.is_empty()) {
+            return Ok(());
+        }
+
+        match body.deserialize::<zvariant::Structure<'_>>() {
+            Ok(structure) => {
+                for (i, arg) in structure.fields().iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("\n")?;
+                    }
+                    write!(f, "[{i}] {arg:#}")?;
+                }
+
+                Ok(())
+            }
+            Err(e) => write!(f, "<failed to decode the body: {e}>"),
+        }
+    }
 }
 
 impl fmt::Debug for Message {
@@ -419,10 +573,44 @@ mod tests {
             .build(&("all fine!"))
             .unwrap();
         assert_eq!(r.to_string(), "Method return");
+        assert_eq!(r.display_body().to_string(), "[0] string \"all fine!\"");
         let e = Message::method_error(&m, "org.freedesktop.zbus.Error")
             .unwrap()
             .build(&("kaboom!", 32))
             .unwrap();
         assert_eq!(e.to_string(), "Error org.freedesktop.zbus.Error: kaboom!");
+        assert_eq!(
+            e.display_body().to_string(),
+            "[0] string \"kaboom!\"\n[1] int32 32"
+        );
+        let m = Message::signal("/", "org.zbus.Test", "Empty")
+            .unwrap()
+            .build(&())
+            .unwrap();
+        assert_eq!(m.display_body().to_string(), "");
+    }
+
+    #[test]
+    fn borrowed_body() {
+        let payload = vec![42u8; 1024];
+        let m = Message::method("/", "do")
+            .unwrap()
+            .build(&(
+                "foo",
+                &payload[..],
+                zvariant::ObjectPath::from_static_str_unchecked("/a"),
+            ))
+            .unwrap();
+        let body = m.body();
+        let (s, bytes, path): (&str, &[u8], zvariant::ObjectPath<'_>) = body.deserialize().unwrap();
+        assert_eq!(s, "foo");
+        assert_eq!(bytes, &payload[..]);
+        assert_eq!(path, "/a");
+
+        // Nothing was copied out of the message.
+        let range = body.data().bytes().as_ptr_range();
+        assert!(range.contains(&s.as_ptr()));
+        assert!(range.contains(&bytes.as_ptr()));
+        assert!(range.contains(&path.as_str().as_ptr()));
     }
 }
@@ -5,6 +5,7 @@ use std::{
     future::Future,
     pin::Pin,
     sync::Arc,
+    time::SystemTime,
 };
 
 use async_trait::async_trait;
@@ -80,6 +81,15 @@ pub trait Interface: Any + Send + Sync {
         true
     }
 
+    /// Initialize the interface, right after it's registered on the object server.
+    ///
+    /// Method calls and property accesses are only handled once this returns, so this is the place
+    /// to spawn tasks, subscribe to other services or emit initial signals. The default
+    /// implementation does nothing.
+    async fn init(&mut self, connection: &Connection, ctxt: SignalContext<'_>) {
+        let _ = (connection, ctxt);
+    }
+
     /// Get a property value. Returns `None` if the property doesn't exist.
     async fn get(&self, property_name: &str) -> Option<fdo::Result<OwnedValue>>;
 
@@ -140,6 +150,15 @@ pub trait Interface: Any + Send + Sync {
 
     /// Write introspection XML to the writer, with the given indentation level.
     fn introspect_to_writer(&self, writer: &mut dyn Write, level: usize);
+
+    /// Write the introspection XML of the methods and properties that other interfaces composing
+    /// this one (see the `compose` attribute of the [`crate::interface`] macro) take over.
+    ///
+    /// Unlike [`Interface::introspect_to_writer`], the `<interface>` element itself is not written.
+    /// The default implementation writes nothing.
+    fn introspect_composable_to_writer(&self, writer: &mut dyn Write, level: usize) {
+        let _ = (writer, level);
+    }
 }
 
 /// A type for a reference counted Interface trait-object, with associated run-time details and a
@@ -148,6 +167,8 @@ pub trait Interface: Any + Send + Sync {
 pub(crate) struct ArcInterface {
     pub instance: Arc<RwLock<dyn Interface>>,
     pub spawn_tasks_for_methods: bool,
+    /// When the interface was added to its object. Set by the object tree on insertion.
+    pub registered_at: SystemTime,
 }
 
 impl ArcInterface {
@@ -159,6 +180,7 @@ impl ArcInterface {
         Self {
             instance: Arc::new(RwLock::new(iface)),
             spawn_tasks_for_methods,
+            registered_at: SystemTime::now(),
         }
     }
 }
@@ -8,6 +8,7 @@ use std::{
     marker::PhantomData,
     ops::{Deref, DerefMut},
     sync::Arc,
+    time::SystemTime,
 };
 use tracing::{debug, instrument, trace, trace_span, Instrument};
 
@@ -28,6 +29,9 @@ mod interface;
 pub(crate) use interface::ArcInterface;
 pub use interface::{DispatchResult, Interface};
 
+mod registry;
+pub use registry::{RegisteredInterface, RegisteredObject};
+
 mod signal_context;
 pub use signal_context::SignalContext;
 
@@ -277,9 +281,14 @@ impl Node {
         self.children.remove(node).is_some()
     }
 
-    fn add_arc_interface(&mut self, name: InterfaceName<'static>, arc_iface: ArcInterface) -> bool {
+    fn add_arc_interface(
+        &mut self,
+        name: InterfaceName<'static>,
+        mut arc_iface: ArcInterface,
+    ) -> bool {
         match self.interfaces.entry(name) {
             Entry::Vacant(e) => {
+                arc_iface.registered_at = SystemTime::now();
                 e.insert(arc_iface);
                 true
             }
@@ -372,6 +381,38 @@ impl Node {
         xml
     }
 
+    fn registered_interfaces(&self) -> Vec<RegisteredInterface> {
+        let mut interfaces: Vec<_> = self
+            .interfaces
+            .iter()
+            .map(|(name, iface)| RegisteredInterface {
+                name: name.clone(),
+                registered_at: iface.registered_at,
+            })
+            .collect();
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
+        interfaces
+    }
+
+    /// Collect this node and its descendants that serve any interface besides the ones every node
+    /// implements.
+    fn registered_objects(&self, objects: &mut Vec<RegisteredObject>) {
+        let serves_any = self.interfaces.keys().any(|k| {
+            *k != Peer::name() && *k != Introspectable::name() && *k != Properties::name()
+        });
+        if serves_any {
+            objects.push(RegisteredObject {
+                path: self.path.clone(),
+                interfaces: self.registered_interfaces(),
+            });
+        }
+
+        for node in self.children.values() {
+            node.registered_objects(objects);
+        }
+    }
+
     pub(crate) async fn get_managed_objects(&self) -> fdo::Result<ManagedObjects> {
         let mut managed_objects = ManagedObjects::new();
 
@@ -517,6 +558,7 @@ impl ObjectServer {
         P::Error: Into<Error>,
     {
         let path = path.try_into().map_err(Into::into)?;
+        let instance = arc_iface.instance.clone();
         let mut root = self.root().write().await;
         let (node, manager_path) = root.get_child_mut(&path, true);
         let node = node.unwrap();
@@ -524,7 +566,7 @@ impl ObjectServer {
         if added {
             if name == ObjectManager::name() {
                 // Just added an object manager. Need to signal all managed objects under it.
-                let ctxt = SignalContext::new(&self.connection(), path)?;
+                let ctxt = SignalContext::new(&self.connection(), &path)?;
                 let objects = node.get_managed_objects().await?;
                 for (path, owned_interfaces) in objects {
                     let interfaces = owned_interfaces
@@ -552,6 +594,18 @@ impl ObjectServer {
                 ObjectManager::interfaces_added(&ctxt, &path, &interfaces).await?;
             }
         }
+        if !added {
+            return Ok(added);
+        }
+
+        // Lock the interface before it becomes reachable, so that no method call or property
+        // access is handled until it's initialized. The tree itself is released so that `init` can
+        // make use of the object server.
+        let mut iface = instance.write().await;
+        drop(root);
+        let conn = self.connection();
+        let ctxt = SignalContext::from_parts(conn.clone(), path);
+        iface.init(&conn, ctxt).await;
 
         Ok(added)
     }
@@ -667,6 +721,37 @@ impl ObjectServer {
         })
     }
 
+    /// The objects registered on the server, sorted by path.
+    ///
+    /// Only the objects serving at least one interface besides the standard ones that every object
+    /// implements (`org.freedesktop.DBus.Peer`, `org.freedesktop.DBus.Introspectable` and
+    /// `org.freedesktop.DBus.Properties`) are listed. The returned list is a snapshot and won't
+    /// reflect any later changes.
+    pub async fn objects(&self) -> Vec<RegisteredObject> {
+        let mut objects = Vec::new();
+        self.root.read().await.registered_objects(&mut objects);
+        objects.sort_by(|a, b| a.path.as_str().cmp(b.path.as_str()));
+
+        objects
+    }
+
+    /// The interfaces registered at the given path, sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// If there is no object at the given path, `Error::InterfaceNotFound` error is returned.
+    pub async fn interfaces<'p, P>(&self, path: P) -> Result<Vec<RegisteredInterface>>
+    where
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        let path = path.try_into().map_err(Into::into)?;
+        let root = self.root.read().await;
+        let node = root.get_child(&path).ok_or(Error::InterfaceNotFound)?;
+
+        Ok(node.registered_interfaces())
+    }
+
     async fn dispatch_call_to_iface(
         &self,
         iface: Arc<RwLock<dyn Interface>>,
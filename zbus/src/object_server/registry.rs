@@ -0,0 +1,59 @@
+use std::time::SystemTime;
+
+use zbus_names::InterfaceName;
+use zvariant::OwnedObjectPath;
+
+/// An object registered on an [`ObjectServer`], along with the interfaces it serves.
+///
+/// See [`ObjectServer::objects`] for details.
+///
+/// [`ObjectServer`]: crate::ObjectServer
+/// [`ObjectServer::objects`]: crate::ObjectServer::objects
+#[derive(Debug, Clone)]
+pub struct RegisteredObject {
+    pub(crate) path: OwnedObjectPath,
+    pub(crate) interfaces: Vec<RegisteredInterface>,
+}
+
+impl RegisteredObject {
+    /// The path of the object.
+    pub fn path(&self) -> &OwnedObjectPath {
+        &self.path
+    }
+
+    /// The interfaces served at the path, sorted by name.
+    ///
+    /// This includes the standard interfaces (e.g `org.freedesktop.DBus.Introspectable`) that are
+    /// implemented on your behalf.
+    pub fn interfaces(&self) -> &[RegisteredInterface] {
+        &self.interfaces
+    }
+
+    /// Whether the interface named `name` is served at the path.
+    pub fn has_interface(&self, name: &str) -> bool {
+        self.interfaces.iter().any(|i| i.name == name)
+    }
+}
+
+/// An interface registered on an [`ObjectServer`].
+///
+/// [`ObjectServer`]: crate::ObjectServer
+#[derive(Debug, Clone)]
+pub struct RegisteredInterface {
+    pub(crate) name: InterfaceName<'static>,
+    pub(crate) registered_at: SystemTime,
+}
+
+impl RegisteredInterface {
+    /// The name of the interface.
+    pub fn name(&self) -> &InterfaceName<'static> {
+        &self.name
+    }
+
+    /// When the interface was registered on the object server.
+    ///
+    /// For the standard interfaces, that is when the object was created.
+    pub fn registered_at(&self) -> SystemTime {
+        self.registered_at
+    }
+}
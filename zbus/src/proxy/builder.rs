@@ -1,9 +1,11 @@
-use std::{collections::HashSet, marker::PhantomData, sync::Arc};
+use std::{collections::HashSet, marker::PhantomData, sync::Arc, time::Duration};
 
 use static_assertions::assert_impl_all;
 use zbus_names::{BusName, InterfaceName};
 use zvariant::{ObjectPath, Str};
 
+#[cfg(feature = "verify-signatures")]
+use crate::proxy::VerifySignatures;
 use crate::{proxy::ProxyInner, Connection, Error, Proxy, Result};
 
 /// The properties caching mode.
@@ -30,6 +32,9 @@ pub struct Builder<'a, T = ()> {
     proxy_type: PhantomData<T>,
     cache: CacheProperties,
     uncached_properties: Option<HashSet<Str<'a>>>,
+    method_timeout: Option<Duration>,
+    #[cfg(feature = "verify-signatures")]
+    verify_signatures: VerifySignatures,
 }
 
 impl<'a, T> Clone for Builder<'a, T> {
@@ -41,6 +46,9 @@ impl<'a, T> Clone for Builder<'a, T> {
             interface: self.interface.clone(),
             cache: self.cache,
             uncached_properties: self.uncached_properties.clone(),
+            method_timeout: self.method_timeout,
+            #[cfg(feature = "verify-signatures")]
+            verify_signatures: self.verify_signatures,
             proxy_type: PhantomData,
         }
     }
@@ -95,6 +103,33 @@ impl<'a, T> Builder<'a, T> {
         self
     }
 
+    /// Set the maximum time to wait for the reply to a method call.
+    ///
+    /// If the reply isn't received in time, the call fails with an [`Error::InputOutput`] error of
+    /// kind [`std::io::ErrorKind::TimedOut`]. By default, there is no timeout and calls wait for
+    /// the reply indefinitely (or until the connection is closed).
+    ///
+    /// This applies to all the method calls made through the proxy, except those made with the
+    /// `NoReplyExpected` flag.
+    #[must_use]
+    pub fn method_timeout(mut self, timeout: Duration) -> Self {
+        self.method_timeout = Some(timeout);
+        self
+    }
+
+    /// Verify calls against the introspected remote interface.
+    ///
+    /// This is meant as a development aid to catch typos and drift against the remote API. It has
+    /// no effect in release builds. See [`VerifySignatures`] for details.
+    ///
+    /// This method is only available when the `verify-signatures` feature is enabled.
+    #[cfg(feature = "verify-signatures")]
+    #[must_use]
+    pub fn verify_signatures(mut self, mode: VerifySignatures) -> Self {
+        self.verify_signatures = mode;
+        self
+    }
+
     pub(crate) fn build_internal(self) -> Result<Proxy<'a>> {
         let conn = self.conn;
         let destination = self
@@ -105,15 +140,22 @@ impl<'a, T> Builder<'a, T> {
         let cache = self.cache;
         let uncached_properties = self.uncached_properties.unwrap_or_default();
 
+        let mut inner = ProxyInner::new(
+            conn,
+            destination,
+            path,
+            interface,
+            cache,
+            uncached_properties,
+        );
+        inner.method_timeout = self.method_timeout;
+        #[cfg(feature = "verify-signatures")]
+        {
+            inner.verifier = crate::proxy::verify::Verifier::new(self.verify_signatures);
+        }
+
         Ok(Proxy {
-            inner: Arc::new(ProxyInner::new(
-                conn,
-                destination,
-                path,
-                interface,
-                cache,
-                uncached_properties,
-            )),
+            inner: Arc::new(inner),
         })
     }
 
@@ -158,6 +200,9 @@ where
                 .map(|i| InterfaceName::from_static_str(i).expect("invalid interface name")),
             cache: CacheProperties::default(),
             uncached_properties: None,
+            method_timeout: None,
+            #[cfg(feature = "verify-signatures")]
+            verify_signatures: VerifySignatures::default(),
             proxy_type: PhantomData,
         }
     }
@@ -0,0 +1,371 @@
+use futures_core::{ready, stream};
+use static_assertions::assert_impl_all;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use zbus_names::{BusName, InterfaceName, MemberName};
+use zvariant::ObjectPath;
+
+use crate::{
+    fdo::PropertiesChanged, message::Message, AsyncDrop, Connection, Error, Proxy, Result,
+};
+
+use super::{Builder, CacheProperties, SignalPaths, SignalStream};
+
+/// Proxies for many objects implementing the same interface on one destination.
+///
+/// Services exposing a large number of objects (e.g through the
+/// `org.freedesktop.DBus.ObjectManager` interface) are expensive to track with one [`Proxy`] per
+/// object, since each proxy installs its own match rules on the bus to receive signals and
+/// property changes.
+///
+/// Instead, the streams created by a `ProxyGroup` share a single match rule for all the objects of
+/// the group and filter the signals by object path on the client side. Streams for the same signal
+/// share the same match rule, no matter the path they're for.
+///
+/// The proxies handed out by [`ProxyGroup::proxy`] don't cache properties, as the cache of each
+/// proxy would install a match rule of its own. Use [`ProxyGroup::receive_properties_changed`] or
+/// [`ProxyGroup::receive_properties_changed_at`] to be notified of property changes instead.
+///
+/// # Example
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use zbus::{proxy::ProxyGroup, Connection, Proxy};
+///
+/// # zbus::block_on(async {
+/// let conn = Connection::system().await?;
+/// let group = ProxyGroup::new(&conn, "org.bluez", "org.bluez.Device1")?
+///     .with_path_namespace("/org/bluez/hci0")?;
+///
+/// let device: Proxy<'_> = group.proxy("/org/bluez/hci0/dev_00_11_22_33_44_55").await?;
+/// let _connected: bool = device.get_property("Connected").await?;
+///
+/// // Property changes of all the devices, through a single match rule.
+/// let mut changes = group.receive_properties_changed().await?;
+/// while let Some(signal) = changes.next().await {
+///     let header = signal.message().header();
+///     let args = signal.args()?;
+///     println!("{:?} changed: {:?}", header.path(), args.changed_properties());
+/// }
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProxyGroup<'a> {
+    conn: Connection,
+    destination: BusName<'a>,
+    interface: InterfaceName<'a>,
+    path_namespace: Option<ObjectPath<'a>>,
+}
+
+assert_impl_all!(ProxyGroup<'_>: Send, Sync, Unpin);
+
+impl<'a> ProxyGroup<'a> {
+    /// Create a new `ProxyGroup` for objects implementing `interface` on `destination`.
+    pub fn new<D, I>(conn: &Connection, destination: D, interface: I) -> Result<Self>
+    where
+        D: TryInto<BusName<'a>>,
+        I: TryInto<InterfaceName<'a>>,
+        D::Error: Into<Error>,
+        I::Error: Into<Error>,
+    {
+        Ok(Self {
+            conn: conn.clone(),
+            destination: destination.try_into().map_err(Into::into)?,
+            interface: interface.try_into().map_err(Into::into)?,
+            path_namespace: None,
+        })
+    }
+
+    /// Restrict the group to the objects in the given path namespace.
+    ///
+    /// The match rules of the group are limited to the namespace, so the bus doesn't send the
+    /// signals of the objects outside of it.
+    pub fn with_path_namespace<P>(mut self, path_namespace: P) -> Result<Self>
+    where
+        P: TryInto<ObjectPath<'a>>,
+        P::Error: Into<Error>,
+    {
+        self.path_namespace = Some(path_namespace.try_into().map_err(Into::into)?);
+
+        Ok(self)
+    }
+
+    /// Get a reference to the associated connection.
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Get a reference to the destination service name.
+    pub fn destination(&self) -> &BusName<'a> {
+        &self.destination
+    }
+
+    /// Get a reference to the interface.
+    pub fn interface(&self) -> &InterfaceName<'a> {
+        &self.interface
+    }
+
+    /// Get a reference to the path namespace, if any.
+    pub fn path_namespace(&self) -> Option<&ObjectPath<'a>> {
+        self.path_namespace.as_ref()
+    }
+
+    /// Create a proxy for the object at `path`.
+    ///
+    /// The proxy can be of any type that can be converted from a [`Proxy`], such as the ones
+    /// generated by the [`proxy`] macro.
+    ///
+    /// # Errors
+    ///
+    /// If the group has a path namespace and `path` is not in it, [`Error::Failure`] is returned.
+    ///
+    /// [`proxy`]: attr.proxy.html
+    pub async fn proxy<P, T>(&self, path: P) -> Result<T>
+    where
+        P: TryInto<ObjectPath<'a>>,
+        P::Error: Into<Error>,
+        T: From<Proxy<'a>>,
+    {
+        let path = self.object_path(path)?;
+
+        Builder::<Proxy<'a>>::new(&self.conn)
+            .destination(self.destination.clone())?
+            .path(path)?
+            .interface(self.interface.clone())?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await
+            .map(T::from)
+    }
+
+    /// Create a stream for signal named `signal_name`, emitted by any object of the group.
+    ///
+    /// Use the path in the header of the signal messages to tell the objects apart.
+    pub async fn receive_signal<'m, M>(&self, signal_name: M) -> Result<GroupSignalStream<'m>>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+    {
+        let signal_name = signal_name.try_into().map_err(Into::into)?;
+
+        self.receive_signals(&self.interface, Some(signal_name), &[], None)
+            .await
+    }
+
+    /// Create a stream for signal named `signal_name`, emitted by the object at `path`.
+    ///
+    /// The underlying match rule is the same as for [`ProxyGroup::receive_signal`], so having
+    /// streams for many objects doesn't add any match rule on the bus.
+    pub async fn receive_signal_at<'m, P, M>(
+        &self,
+        path: P,
+        signal_name: M,
+    ) -> Result<GroupSignalStream<'m>>
+    where
+        P: TryInto<ObjectPath<'a>>,
+        P::Error: Into<Error>,
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+    {
+        let path = self.object_path(path)?;
+        let signal_name = signal_name.try_into().map_err(Into::into)?;
+
+        self.receive_signals(&self.interface, Some(signal_name), &[], Some(path))
+            .await
+    }
+
+    /// Create a stream for property changes of any object of the group.
+    ///
+    /// Only changes to the properties of the group's interface are received. Use the path in the
+    /// header of the signal messages to tell the objects apart.
+    pub async fn receive_properties_changed(&self) -> Result<GroupPropertiesChangedStream> {
+        self.receive_properties_changed_(None).await
+    }
+
+    /// Create a stream for property changes of the object at `path`.
+    ///
+    /// The underlying match rule is the same as for [`ProxyGroup::receive_properties_changed`],
+    /// so having streams for many objects doesn't add any match rule on the bus.
+    pub async fn receive_properties_changed_at<P>(
+        &self,
+        path: P,
+    ) -> Result<GroupPropertiesChangedStream>
+    where
+        P: TryInto<ObjectPath<'a>>,
+        P::Error: Into<Error>,
+    {
+        let path = self.object_path(path)?;
+
+        self.receive_properties_changed_(Some(path)).await
+    }
+
+    async fn receive_properties_changed_(
+        &self,
+        path: Option<ObjectPath<'_>>,
+    ) -> Result<GroupPropertiesChangedStream> {
+        let properties_iface =
+            InterfaceName::from_static_str_unchecked("org.freedesktop.DBus.Properties");
+        let signal_name = MemberName::from_static_str_unchecked("PropertiesChanged");
+        let stream = self
+            .receive_signals(
+                &properties_iface,
+                Some(signal_name),
+                &[(0, self.interface.as_str())],
+                path,
+            )
+            .await?;
+
+        Ok(GroupPropertiesChangedStream { stream })
+    }
+
+    async fn receive_signals<'m>(
+        &self,
+        interface: &InterfaceName<'_>,
+        signal_name: Option<MemberName<'m>>,
+        args: &[(u8, &str)],
+        path: Option<ObjectPath<'_>>,
+    ) -> Result<GroupSignalStream<'m>> {
+        let paths = match &self.path_namespace {
+            Some(namespace) => SignalPaths::Namespace(namespace),
+            None => SignalPaths::All,
+        };
+        let stream = SignalStream::for_paths(
+            &self.conn,
+            &self.destination,
+            paths,
+            interface,
+            signal_name,
+            args,
+        )
+        .await?;
+
+        Ok(GroupSignalStream {
+            stream,
+            path: path.map(ObjectPath::into_owned),
+        })
+    }
+
+    fn object_path<P>(&self, path: P) -> Result<ObjectPath<'a>>
+    where
+        P: TryInto<ObjectPath<'a>>,
+        P::Error: Into<Error>,
+    {
+        let path = path.try_into().map_err(Into::into)?;
+        match &self.path_namespace {
+            Some(namespace) if !path.starts_with_path(namespace) => Err(Error::Failure(format!(
+                "object path `{path}` is not in the `{namespace}` namespace of the group"
+            ))),
+            _ => Ok(path),
+        }
+    }
+}
+
+/// A [`stream::Stream`] implementation that yields signal [messages](`Message`) of a
+/// [`ProxyGroup`].
+///
+/// Use [`ProxyGroup::receive_signal`] or [`ProxyGroup::receive_signal_at`] to create an instance
+/// of this type.
+#[derive(Debug)]
+pub struct GroupSignalStream<'a> {
+    stream: SignalStream<'a>,
+    path: Option<ObjectPath<'static>>,
+}
+
+assert_impl_all!(GroupSignalStream<'_>: Send, Sync, Unpin);
+
+impl<'a> GroupSignalStream<'a> {
+    /// The signal name.
+    pub fn name(&self) -> Option<&MemberName<'a>> {
+        self.stream.name()
+    }
+
+    /// The path of the object this stream is for, if it's only for one object.
+    pub fn path(&self) -> Option<&ObjectPath<'static>> {
+        self.path.as_ref()
+    }
+}
+
+impl stream::Stream for GroupSignalStream<'_> {
+    type Item = Message;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let Some(msg) = ready!(stream::Stream::poll_next(Pin::new(&mut this.stream), cx))
+            else {
+                return Poll::Ready(None);
+            };
+            let Some(path) = &this.path else {
+                return Poll::Ready(Some(msg));
+            };
+            if msg.header().path() == Some(path) {
+                return Poll::Ready(Some(msg));
+            }
+        }
+    }
+}
+
+impl stream::FusedStream for GroupSignalStream<'_> {
+    fn is_terminated(&self) -> bool {
+        stream::FusedStream::is_terminated(&self.stream)
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncDrop for GroupSignalStream<'_> {
+    async fn async_drop(self) {
+        self.stream.async_drop().await
+    }
+}
+
+/// A [`stream::Stream`] implementation that yields the [`PropertiesChanged`] signals of a
+/// [`ProxyGroup`].
+///
+/// Use [`ProxyGroup::receive_properties_changed`] or
+/// [`ProxyGroup::receive_properties_changed_at`] to create an instance of this type.
+#[derive(Debug)]
+pub struct GroupPropertiesChangedStream {
+    stream: GroupSignalStream<'static>,
+}
+
+assert_impl_all!(GroupPropertiesChangedStream: Send, Sync, Unpin);
+
+impl GroupPropertiesChangedStream {
+    /// The path of the object this stream is for, if it's only for one object.
+    pub fn path(&self) -> Option<&ObjectPath<'static>> {
+        self.stream.path()
+    }
+}
+
+impl stream::Stream for GroupPropertiesChangedStream {
+    type Item = PropertiesChanged;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let Some(msg) = ready!(stream::Stream::poll_next(Pin::new(&mut this.stream), cx))
+            else {
+                return Poll::Ready(None);
+            };
+            if let Some(signal) = PropertiesChanged::from_message(msg) {
+                return Poll::Ready(Some(signal));
+            }
+        }
+    }
+}
+
+impl stream::FusedStream for GroupPropertiesChangedStream {
+    fn is_terminated(&self) -> bool {
+        stream::FusedStream::is_terminated(&self.stream)
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncDrop for GroupPropertiesChangedStream {
+    async fn async_drop(self) {
+        self.stream.async_drop().await
+    }
+}
@@ -10,10 +10,12 @@ use std::{
     collections::{HashMap, HashSet},
     fmt,
     future::Future,
+    hash::{Hash, Hasher},
     ops::Deref,
     pin::Pin,
     sync::{Arc, OnceLock, RwLock, RwLockReadGuard},
     task::{Context, Poll},
+    time::Duration,
 };
 use tracing::{debug, info_span, instrument, trace, Instrument};
 
@@ -28,6 +30,12 @@ use crate::{
 
 mod builder;
 pub use builder::{Builder, CacheProperties, ProxyDefault};
+mod group;
+pub use group::{GroupPropertiesChangedStream, GroupSignalStream, ProxyGroup};
+#[cfg(feature = "verify-signatures")]
+mod verify;
+#[cfg(feature = "verify-signatures")]
+pub use verify::VerifySignatures;
 
 /// A client-side interface proxy.
 ///
@@ -64,6 +72,12 @@ pub use builder::{Builder, CacheProperties, ProxyDefault};
 /// It is recommended to use the [`proxy`] macro, which provides a more convenient and
 /// type-safe *façade* `Proxy` derived from a Rust trait.
 ///
+/// # Equality
+///
+/// Two proxies are considered equal if they use the same connection (i-e clones of the same
+/// [`Connection`]) and target the same destination, path and interface. This allows proxies to be
+/// used as keys of maps and sets.
+///
 /// [`futures` crate]: https://crates.io/crates/futures
 /// [`proxy`]: attr.proxy.html
 #[derive(Clone, Debug)]
@@ -73,6 +87,26 @@ pub struct Proxy<'a> {
 
 assert_impl_all!(Proxy<'_>: Send, Sync, Unpin);
 
+impl PartialEq for Proxy<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.connection().inner, &other.connection().inner)
+            && self.inner.destination == other.inner.destination
+            && self.inner.path == other.inner.path
+            && self.inner.interface == other.inner.interface
+    }
+}
+
+impl Eq for Proxy<'_> {}
+
+impl Hash for Proxy<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.connection().inner).hash(state);
+        self.inner.destination.hash(state);
+        self.inner.path.hash(state);
+        self.inner.interface.hash(state);
+    }
+}
+
 /// This is required to avoid having the Drop impl extend the lifetime 'a, which breaks zbus_xmlgen
 /// (and possibly other crates).
 pub(crate) struct ProxyInnerStatic {
@@ -103,6 +137,11 @@ pub(crate) struct ProxyInner<'a> {
     /// Set of properties which do not get cached, by name.
     /// This overrides proxy-level caching behavior.
     uncached_properties: HashSet<Str<'a>>,
+    /// How long to wait for the reply to a method call, if at all.
+    pub(crate) method_timeout: Option<Duration>,
+    /// Verifier of calls against the introspected remote interface.
+    #[cfg(feature = "verify-signatures")]
+    pub(crate) verifier: Option<verify::Verifier>,
 }
 
 impl Drop for ProxyInnerStatic {
@@ -253,6 +292,46 @@ where
     }
 }
 
+struct PropertyWatchState<'a, T> {
+    name: &'a str,
+    proxy: Proxy<'a>,
+    changes: PropertyStream<'a, T>,
+    owner_changes: Option<fdo::NameOwnerChangedStream<'static>>,
+    last: Option<T>,
+    started: bool,
+}
+
+/// A [`stream::Stream`] implementation that yields the values of a property.
+///
+/// Use [`Proxy::watch_property`] to create an instance of this type.
+pub struct PropertyWatchStream<'a, T> {
+    name: &'a str,
+    stream: Pin<Box<dyn stream::Stream<Item = Result<T>> + Send + 'a>>,
+}
+
+impl<T> PropertyWatchStream<'_, T> {
+    /// The name of the property being watched.
+    pub fn name(&self) -> &str {
+        self.name
+    }
+}
+
+impl<T> fmt::Debug for PropertyWatchStream<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PropertyWatchStream")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> stream::Stream for PropertyWatchStream<'_, T> {
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().stream.as_mut().poll_next(cx)
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct PropertiesCache {
     values: RwLock<HashMap<String, PropertyValue>>,
@@ -509,6 +588,9 @@ impl<'a> ProxyInner<'a> {
             interface,
             property_cache,
             uncached_properties,
+            method_timeout: None,
+            #[cfg(feature = "verify-signatures")]
+            verifier: None,
         }
     }
 
@@ -719,9 +801,20 @@ impl<'a> Proxy<'a> {
         T: TryFrom<OwnedValue>,
         T::Error: Into<Error>,
     {
-        self.cached_property_raw(property_name)
-            .as_deref()
-            .map(|v| T::try_from(OwnedValue::try_from(v)?).map_err(Into::into))
+        let value = self
+            .inner
+            .property_cache
+            .as_ref()
+            .and_then(OnceLock::get)
+            .and_then(|c| {
+                let values = c.0.values.read().expect("lock poisoned");
+
+                // Cloning an `OwnedValue` is cheap so we don't hold the lock during conversion.
+                values.get(property_name).and_then(|e| e.value.clone())
+            });
+
+        value
+            .map(|v| T::try_from(v).map_err(Into::into))
             .transpose()
     }
 
@@ -788,6 +881,11 @@ impl<'a> Proxy<'a> {
         T: TryFrom<OwnedValue>,
         T::Error: Into<Error>,
     {
+        #[cfg(feature = "verify-signatures")]
+        if let Some(verifier) = &self.inner.verifier {
+            verifier.verify_property(self, property_name, None).await;
+        }
+
         if let Some(cache) = self.get_property_cache() {
             cache.ready().await?;
         }
@@ -806,8 +904,16 @@ impl<'a> Proxy<'a> {
     where
         T: 't + Into<Value<'t>>,
     {
+        let value = value.into();
+        #[cfg(feature = "verify-signatures")]
+        if let Some(verifier) = &self.inner.verifier {
+            verifier
+                .verify_property(self, property_name, Some(&value.value_signature()))
+                .await;
+        }
+
         self.properties_proxy()
-            .set(self.inner.interface.as_ref(), property_name, &value.into())
+            .set(self.inner.interface.as_ref(), property_name, &value)
             .await
     }
 
@@ -824,17 +930,23 @@ impl<'a> Proxy<'a> {
         M::Error: Into<Error>,
         B: serde::ser::Serialize + zvariant::DynamicType,
     {
-        self.inner
-            .inner_without_borrows
-            .conn
-            .call_method(
-                Some(&self.inner.destination),
-                self.inner.path.as_str(),
-                Some(&self.inner.interface),
-                method_name,
-                body,
-            )
-            .await
+        let method_name = method_name.try_into().map_err(Into::into)?;
+        #[cfg(feature = "verify-signatures")]
+        if let Some(verifier) = &self.inner.verifier {
+            verifier
+                .verify_method(self, &method_name, &body.dynamic_signature())
+                .await;
+        }
+
+        let reply = self.inner.inner_without_borrows.conn.call_method(
+            Some(&self.inner.destination),
+            self.inner.path.as_str(),
+            Some(&self.inner.interface),
+            method_name,
+            body,
+        );
+
+        self.with_method_timeout(reply).await
     }
 
     /// Call a method and return the reply body.
@@ -854,6 +966,51 @@ impl<'a> Proxy<'a> {
         reply.body().deserialize()
     }
 
+    /// Call a method and return the reply body, along with the round-trip time of the call.
+    ///
+    /// The round-trip time is measured with the monotonic clock, from the moment the method call is
+    /// sent until its reply is received (see [`Message::send_timestamp`] and
+    /// [`Message::recv_timestamp`]). It doesn't include the time it takes to serialize the
+    /// arguments or to deserialize the reply.
+    pub async fn call_timed<'m, M, B, R>(&self, method_name: M, body: &B) -> Result<(R, Duration)>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        R: for<'d> zvariant::DynamicDeserialize<'d>,
+    {
+        let method_name = method_name.try_into().map_err(Into::into)?;
+        #[cfg(feature = "verify-signatures")]
+        if let Some(verifier) = &self.inner.verifier {
+            verifier
+                .verify_method(self, &method_name, &body.dynamic_signature())
+                .await;
+        }
+
+        let pending = self
+            .inner
+            .inner_without_borrows
+            .conn
+            .call_method_raw(
+                Some(self.destination()),
+                self.path(),
+                Some(self.interface()),
+                method_name,
+                BitFlags::empty(),
+                body,
+            )
+            .await?
+            .expect("no reply");
+        let sent = pending.send_timestamp();
+        let reply = self.with_method_timeout(pending).await?;
+        let round_trip = match (sent, reply.recv_timestamp()) {
+            (Some(sent), Some(received)) => received.saturating_duration_since(sent),
+            _ => Duration::ZERO,
+        };
+
+        reply.body().deserialize().map(|r| (r, round_trip))
+    }
+
     /// Call a method and return the reply body, optionally supplying a set of
     /// method flags to control the way the method call message is sent and handled.
     ///
@@ -875,6 +1032,14 @@ impl<'a> Proxy<'a> {
         B: serde::ser::Serialize + zvariant::DynamicType,
         R: for<'d> zvariant::DynamicDeserialize<'d>,
     {
+        let method_name = method_name.try_into().map_err(Into::into)?;
+        #[cfg(feature = "verify-signatures")]
+        if let Some(verifier) = &self.inner.verifier {
+            verifier
+                .verify_method(self, &method_name, &body.dynamic_signature())
+                .await;
+        }
+
         let flags = flags.iter().map(Flags::from).collect::<BitFlags<_>>();
         match self
             .inner
@@ -890,11 +1055,34 @@ impl<'a> Proxy<'a> {
             )
             .await?
         {
-            Some(reply) => reply.await?.body().deserialize().map(Some),
+            Some(reply) => self
+                .with_method_timeout(reply)
+                .await?
+                .body()
+                .deserialize()
+                .map(Some),
             None => Ok(None),
         }
     }
 
+    /// The maximum time to wait for the reply to a method call, if any.
+    ///
+    /// See [`Builder::method_timeout`] for details.
+    pub fn method_timeout(&self) -> Option<Duration> {
+        self.inner.method_timeout
+    }
+
+    /// Await the reply to a method call, within the method timeout (if any).
+    async fn with_method_timeout<F>(&self, reply: F) -> Result<Message>
+    where
+        F: Future<Output = Result<Message>>,
+    {
+        match self.inner.method_timeout {
+            Some(duration) => crate::abstractions::timeout(reply, duration).await,
+            None => reply.await,
+        }
+    }
+
     /// Call a method without expecting a reply
     ///
     /// This sets the `NoReplyExpected` flag on the calling message and does not wait for a reply.
@@ -909,6 +1097,69 @@ impl<'a> Proxy<'a> {
         Ok(())
     }
 
+    /// Call a method that returns an object path and wait for a completion signal on that path.
+    ///
+    /// Many D-Bus APIs (e.g [XDG Desktop Portals] or PackageKit) don't return the result of a
+    /// method call in its reply but instead return the path of an object, that later emits a signal
+    /// (e.g `org.freedesktop.portal.Request.Response`) carrying the actual result. This method
+    /// makes such a call and returns the body of the first signal named `signal_name` from
+    /// `signal_interface`, that is emitted on the returned path by the peer that replied.
+    ///
+    /// The subscription for the signal is set up before the method is called so the signal can't be
+    /// missed, even if it's emitted before the method reply is received.
+    ///
+    /// [XDG Desktop Portals]: https://flatpak.github.io/xdg-desktop-portal/
+    pub async fn call_with_response_signal<'m, 'i, 's, M, I, S, B, R>(
+        &self,
+        method_name: M,
+        body: &B,
+        signal_interface: I,
+        signal_name: S,
+    ) -> Result<R>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        I: TryInto<InterfaceName<'i>>,
+        I::Error: Into<Error>,
+        S: TryInto<MemberName<'s>>,
+        S::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        R: for<'d> zvariant::DynamicDeserialize<'d>,
+    {
+        use futures_util::StreamExt;
+
+        let signal_interface = signal_interface.try_into().map_err(Into::into)?;
+        let signal_name = signal_name.try_into().map_err(Into::into)?;
+        let rule = MatchRule::builder()
+            .msg_type(Type::Signal)
+            .sender(self.destination())?
+            .interface(&signal_interface)?
+            .member(&signal_name)?
+            .build()
+            .to_owned();
+        let mut stream = MessageStream::for_match_rule(rule, self.connection(), None).await?;
+
+        let reply = self.call_method(method_name, body).await?;
+        let path: zvariant::OwnedObjectPath = reply.body().deserialize()?;
+        let reply_header = reply.header();
+        let sender = reply_header.sender();
+
+        while let Some(msg) = stream.next().await {
+            let msg = msg?;
+            let header = msg.header();
+            if header.path() == Some(&*path) && header.sender() == sender {
+                return msg.body().deserialize();
+            }
+        }
+
+        // The stream only ends when the connection is closed.
+        Err(std::io::Error::new(
+            std::io::ErrorKind::BrokenPipe,
+            "connection closed before the response signal was received",
+        )
+        .into())
+    }
+
     /// Create a stream for signal named `signal_name`.
     pub async fn receive_signal<'m, M>(&self, signal_name: M) -> Result<SignalStream<'m>>
     where
@@ -983,6 +1234,107 @@ impl<'a> Proxy<'a> {
         }
     }
 
+    /// Get a stream that watches the value of the property `name`.
+    ///
+    /// Unlike [`Proxy::receive_property_changed`], the resulting stream yields the property values
+    /// directly. The current value of the property is yielded first, followed by its subsequent
+    /// values. Consecutive equal values are deduplicated so no two successive items are the same.
+    ///
+    /// Whenever the destination name gets a new owner, the property value is fetched anew from it.
+    /// If the destination is a unique name, the stream ends once the peer disconnects from the bus.
+    ///
+    /// If caching is not enabled on this proxy, the resulting stream will end after yielding the
+    /// current value.
+    pub async fn watch_property<'name: 'a, T>(
+        &self,
+        name: &'name str,
+    ) -> Result<PropertyWatchStream<'a, T>>
+    where
+        T: TryFrom<OwnedValue> + PartialEq + Clone + Unpin + Send + Sync + 'a,
+        T::Error: Into<Error>,
+    {
+        use futures_util::{future::select, StreamExt};
+
+        let owner_changes = if self.connection().is_bus() {
+            let dbus_proxy = fdo::DBusProxy::builder(self.connection())
+                .cache_properties(CacheProperties::No)
+                .build()
+                .await?;
+            let stream = dbus_proxy
+                .receive_name_owner_changed_with_args(&[(0, self.destination().as_str())])
+                .await?;
+
+            Some(stream)
+        } else {
+            None
+        };
+        let state = PropertyWatchState {
+            name,
+            proxy: self.clone(),
+            changes: self.receive_property_changed(name).await,
+            owner_changes,
+            last: None,
+            started: false,
+        };
+
+        let stream = futures_util::stream::unfold(state, |mut state| async move {
+            loop {
+                let value = if !state.started {
+                    state.started = true;
+
+                    state.proxy.get_property::<T>(state.name).await
+                } else {
+                    let changes = state.changes.next();
+                    let event = match &mut state.owner_changes {
+                        Some(owner_changes) => match select(changes, owner_changes.next()).await {
+                            Either::Left((changed, _)) => Either::Left(changed),
+                            Either::Right((owner_changed, _)) => Either::Right(owner_changed),
+                        },
+                        None => Either::Left(changes.await),
+                    };
+
+                    match event {
+                        Either::Left(Some(changed)) => changed.get().await,
+                        Either::Right(Some(owner_changed)) => {
+                            let has_owner = match owner_changed.args() {
+                                Ok(args) => args.new_owner().is_some(),
+                                Err(e) => return Some((Err(e), state)),
+                            };
+                            if has_owner {
+                                state
+                                    .proxy
+                                    .get_proxy_property(state.name)
+                                    .await
+                                    .and_then(|v| T::try_from(v).map_err(Into::into))
+                            } else if let BusName::Unique(_) = state.proxy.destination() {
+                                // A unique name never gets a new owner.
+                                return None;
+                            } else {
+                                continue;
+                            }
+                        }
+                        Either::Left(None) | Either::Right(None) => return None,
+                    }
+                };
+
+                match value {
+                    Ok(value) if state.last.as_ref() == Some(&value) => continue,
+                    Ok(value) => {
+                        state.last = Some(value.clone());
+
+                        return Some((Ok(value), state));
+                    }
+                    Err(e) => return Some((Err(e), state)),
+                }
+            }
+        });
+
+        Ok(PropertyWatchStream {
+            name,
+            stream: Box::pin(stream),
+        })
+    }
+
     /// Get a stream to receive destination owner changed events.
     ///
     /// If the proxy destination is a unique name, the stream will be notified of the peer
@@ -1119,12 +1471,35 @@ impl<'a> SignalStream<'a> {
         proxy: Proxy<'_>,
         signal_name: Option<MemberName<'a>>,
         args: &[(u8, &str)],
+    ) -> Result<SignalStream<'a>> {
+        Self::for_paths(
+            proxy.connection(),
+            proxy.destination(),
+            SignalPaths::Path(proxy.path()),
+            proxy.interface(),
+            signal_name,
+            args,
+        )
+        .await
+    }
+
+    async fn for_paths(
+        conn: &Connection,
+        destination: &BusName<'_>,
+        paths: SignalPaths<'_>,
+        interface: &InterfaceName<'_>,
+        signal_name: Option<MemberName<'a>>,
+        args: &[(u8, &str)],
     ) -> Result<SignalStream<'a>> {
         let mut rule_builder = MatchRule::builder()
             .msg_type(Type::Signal)
-            .sender(proxy.destination())?
-            .path(proxy.path())?
-            .interface(proxy.interface())?;
+            .sender(destination)?;
+        rule_builder = match paths {
+            SignalPaths::Path(path) => rule_builder.path(path)?,
+            SignalPaths::Namespace(namespace) => rule_builder.path_namespace(namespace)?,
+            SignalPaths::All => rule_builder,
+        };
+        rule_builder = rule_builder.interface(interface)?;
         if let Some(name) = &signal_name {
             rule_builder = rule_builder.member(name)?;
         }
@@ -1132,9 +1507,8 @@ impl<'a> SignalStream<'a> {
             rule_builder = rule_builder.arg(*i, *arg)?;
         }
         let signal_rule: OwnedMatchRule = rule_builder.build().to_owned().into();
-        let conn = proxy.connection();
 
-        let (src_unique_name, stream) = match proxy.destination().to_owned() {
+        let (src_unique_name, stream) = match destination.to_owned() {
             BusName::Unique(name) => (
                 Some(name),
                 join_streams(
@@ -1267,6 +1641,17 @@ impl<'a> SignalStream<'a> {
 
 assert_impl_all!(SignalStream<'_>: Send, Sync, Unpin);
 
+/// The object paths a [`SignalStream`] receives signals from.
+#[derive(Debug, Clone, Copy)]
+enum SignalPaths<'p> {
+    /// Only this path.
+    Path(&'p ObjectPath<'p>),
+    /// All paths in this namespace.
+    Namespace(&'p ObjectPath<'p>),
+    /// All paths.
+    All,
+}
+
 impl<'a> stream::Stream for SignalStream<'a> {
     type Item = Message;
 
@@ -1423,6 +1808,245 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[timeout(15000)]
+    fn watch_property() {
+        block_on(test_watch_property()).unwrap();
+    }
+
+    async fn test_watch_property() -> Result<()> {
+        struct Speaker {
+            volume: u32,
+        }
+
+        #[interface(name = "org.zbus.Test.Speaker")]
+        impl Speaker {
+            #[zbus(property)]
+            fn volume(&self) -> u32 {
+                self.volume
+            }
+
+            #[zbus(property)]
+            fn set_volume(&mut self, volume: u32) {
+                self.volume = volume;
+            }
+        }
+
+        let service_conn = connection::Builder::session()?
+            .serve_at("/org/zbus/Test/Speaker", Speaker { volume: 50 })?
+            .build()
+            .await?;
+        let unique_name = service_conn.unique_name().unwrap().to_owned();
+
+        let client_conn = Connection::session().await?;
+        let proxy = Proxy::new(
+            &client_conn,
+            unique_name,
+            "/org/zbus/Test/Speaker",
+            "org.zbus.Test.Speaker",
+        )
+        .await?;
+        let mut values = proxy.watch_property::<u32>("Volume").await?;
+        assert_eq!(values.name(), "Volume");
+
+        // The current value comes first.
+        assert_eq!(values.next().await.unwrap()?, 50);
+
+        // Setting the same value doesn't yield anything.
+        proxy.set_property("Volume", 50u32).await?;
+        proxy.set_property("Volume", 70u32).await?;
+        assert_eq!(values.next().await.unwrap()?, 70);
+
+        // The destination is a unique name so the stream ends when the peer goes away.
+        drop(service_conn);
+        assert!(values.next().await.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn proxy_group() {
+        block_on(test_proxy_group()).unwrap();
+    }
+
+    async fn test_proxy_group() -> Result<()> {
+        struct Speaker {
+            volume: u32,
+        }
+
+        #[interface(name = "org.zbus.Test.GroupSpeaker")]
+        impl Speaker {
+            #[zbus(property)]
+            fn volume(&self) -> u32 {
+                self.volume
+            }
+
+            #[zbus(property)]
+            fn set_volume(&mut self, volume: u32) {
+                self.volume = volume;
+            }
+        }
+
+        let service_conn = connection::Builder::session()?
+            .serve_at("/org/zbus/Test/Speakers/1", Speaker { volume: 50 })?
+            .serve_at("/org/zbus/Test/Speakers/2", Speaker { volume: 50 })?
+            .build()
+            .await?;
+        let unique_name = service_conn.unique_name().unwrap().to_owned();
+
+        let client_conn = Connection::session().await?;
+        let group = ProxyGroup::new(&client_conn, unique_name, "org.zbus.Test.GroupSpeaker")?
+            .with_path_namespace("/org/zbus/Test/Speakers")?;
+        assert!(group.proxy::<_, Proxy<'_>>("/org/zbus/Test").await.is_err());
+        let speaker1: Proxy<'_> = group.proxy("/org/zbus/Test/Speakers/1").await?;
+        let speaker2: Proxy<'_> = group.proxy("/org/zbus/Test/Speakers/2").await?;
+
+        let mut all_changes = group.receive_properties_changed().await?;
+        let mut speaker2_changes = group
+            .receive_properties_changed_at("/org/zbus/Test/Speakers/2")
+            .await?;
+
+        speaker1.set_property("Volume", 10u32).await?;
+        speaker2.set_property("Volume", 20u32).await?;
+
+        for (path, volume) in [
+            ("/org/zbus/Test/Speakers/1", 10u32),
+            ("/org/zbus/Test/Speakers/2", 20),
+        ] {
+            let signal = all_changes.next().await.unwrap();
+            assert_eq!(signal.message().header().path().unwrap(), path);
+            let args = signal.args()?;
+            assert_eq!(args.changed_properties()["Volume"], Value::from(volume));
+        }
+        // The stream for the second speaker skips the change of the first one.
+        let signal = speaker2_changes.next().await.unwrap();
+        assert_eq!(
+            signal.message().header().path().unwrap(),
+            "/org/zbus/Test/Speakers/2"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn call_with_response_signal() {
+        block_on(test_call_with_response_signal()).unwrap();
+    }
+
+    async fn test_call_with_response_signal() -> Result<()> {
+        struct Portal;
+
+        #[interface(name = "org.zbus.Test.Portal")]
+        impl Portal {
+            async fn start(
+                &self,
+                value: u32,
+                #[zbus(connection)] conn: &Connection,
+            ) -> fdo::Result<zvariant::OwnedObjectPath> {
+                // A response for another request, that must be ignored.
+                conn.emit_signal(
+                    None::<BusName<'_>>,
+                    "/org/zbus/Test/Request/0",
+                    "org.zbus.Test.Request",
+                    "Response",
+                    &(1u32, 0u32),
+                )
+                .await?;
+                // Emit the response before replying to ensure it's not missed.
+                let path = ObjectPath::try_from("/org/zbus/Test/Request/1").unwrap();
+                conn.emit_signal(
+                    None::<BusName<'_>>,
+                    &path,
+                    "org.zbus.Test.Request",
+                    "Response",
+                    &(0u32, value),
+                )
+                .await?;
+
+                Ok(path.into())
+            }
+        }
+
+        let service_conn = connection::Builder::session()?
+            .serve_at("/org/zbus/Test/Portal", Portal)?
+            .build()
+            .await?;
+        let unique_name = service_conn.unique_name().unwrap().to_owned();
+
+        let client_conn = Connection::session().await?;
+        let proxy = Proxy::new(
+            &client_conn,
+            unique_name,
+            "/org/zbus/Test/Portal",
+            "org.zbus.Test.Portal",
+        )
+        .await?;
+        let response: (u32, u32) = proxy
+            .call_with_response_signal("Start", &42u32, "org.zbus.Test.Request", "Response")
+            .await?;
+        assert_eq!(response, (0, 42));
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn call_timed() {
+        block_on(test_call_timed()).unwrap();
+    }
+
+    async fn test_call_timed() -> Result<()> {
+        let conn = Connection::session().await?;
+        let proxy = Proxy::new(
+            &conn,
+            "org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+        )
+        .await?;
+
+        let before = std::time::Instant::now();
+        let (id, round_trip): (String, _) = proxy.call_timed("GetId", &()).await?;
+        assert!(!id.is_empty());
+        assert!(round_trip <= before.elapsed());
+
+        let reply = proxy.call_method("GetId", &()).await?;
+        let received = reply.recv_timestamp().unwrap();
+        assert!(received >= before);
+        assert!(reply.send_timestamp().is_none());
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "verify-signatures", debug_assertions))]
+    // No `timeout` here, as it replaces the panic message with its own.
+    #[test]
+    #[should_panic(expected = "called with arguments of signature `u` but the remote expects `s`")]
+    fn verify_signatures() {
+        block_on(test_verify_signatures()).unwrap();
+    }
+
+    #[cfg(all(feature = "verify-signatures", debug_assertions))]
+    async fn test_verify_signatures() -> Result<()> {
+        let conn = Connection::session().await?;
+        let proxy: Proxy<'_> = Builder::new(&conn)
+            .destination("org.freedesktop.DBus")?
+            .path("/org/freedesktop/DBus")?
+            .interface("org.freedesktop.DBus")?
+            .verify_signatures(VerifySignatures::Panic)
+            .build()
+            .await?;
+
+        // A matching call goes through.
+        let _id: String = proxy.call("GetId", &()).await?;
+        // While a mismatching one panics, even though the bus would just reply with an error.
+        let _ = proxy.call::<_, _, String>("GetNameOwner", &42u32).await;
+
+        Ok(())
+    }
+
     #[test]
     #[timeout(15000)]
     fn signal_stream_deadlock() {
@@ -1483,7 +2107,7 @@ mod tests {
             let server_fut = async move {
                 use std::time::Duration;
 
-                #[cfg(not(feature = "tokio"))]
+                #[cfg(all(feature = "async-io", not(feature = "tokio")))]
                 use async_io::Timer;
 
                 #[cfg(feature = "tokio")]
@@ -1503,9 +2127,13 @@ mod tests {
                             .unwrap();
                     }
 
-                    #[cfg(not(feature = "tokio"))]
+                    #[cfg(all(feature = "async-io", not(feature = "tokio")))]
                     Timer::after(Duration::from_millis(5)).await;
 
+                    // Each task has its own thread in the `blocking-only` build.
+                    #[cfg(all(not(feature = "async-io"), not(feature = "tokio")))]
+                    std::thread::sleep(Duration::from_millis(5));
+
                     #[cfg(feature = "tokio")]
                     sleep(Duration::from_millis(5)).await;
                 }
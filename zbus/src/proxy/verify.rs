@@ -0,0 +1,225 @@
+use std::{fmt::Write, sync::OnceLock};
+
+use tracing::{debug, warn};
+use zbus_xml::{ArgDirection, Interface, Node};
+use zvariant::Signature;
+
+use crate::Proxy;
+
+/// How a proxy reacts to calls that don't match the remote API.
+///
+/// When enabled through [`Builder::verify_signatures`], the proxy introspects its destination on
+/// the first call and then checks every method call and property access against the interface
+/// description, reporting any unknown member or argument signature mismatch.
+///
+/// Verification only happens in debug builds (i-e when `debug_assertions` are enabled). In release
+/// builds, the chosen mode is ignored and no introspection is done.
+///
+/// [`Builder::verify_signatures`]: crate::proxy::Builder::verify_signatures
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VerifySignatures {
+    /// Don't verify calls (default).
+    #[default]
+    No,
+    /// Log mismatches as warnings.
+    Warn,
+    /// Panic on mismatches.
+    Panic,
+}
+
+/// The introspected description of the remote interface, fetched on first use.
+#[derive(Debug)]
+enum Remote {
+    Interface(Interface<'static>),
+    /// The remote object doesn't implement the interface.
+    Missing,
+    /// Introspection failed, so nothing can be verified.
+    Unavailable,
+}
+
+#[derive(Debug)]
+pub(crate) struct Verifier {
+    mode: VerifySignatures,
+    remote: OnceLock<Remote>,
+}
+
+impl Verifier {
+    pub(crate) fn new(mode: VerifySignatures) -> Option<Self> {
+        if !cfg!(debug_assertions) || mode == VerifySignatures::No {
+            return None;
+        }
+
+        Some(Self {
+            mode,
+            remote: OnceLock::new(),
+        })
+    }
+
+    /// Verify that `method` exists and takes arguments of signature `signature`.
+    pub(crate) async fn verify_method(
+        &self,
+        proxy: &Proxy<'_>,
+        method: &str,
+        signature: &Signature<'_>,
+    ) {
+        let iface = match self.remote(proxy).await {
+            Remote::Interface(iface) => iface,
+            Remote::Missing => return self.report(missing_interface(proxy)),
+            Remote::Unavailable => return,
+        };
+        let Some(m) = iface.methods().iter().find(|m| m.name() == method) else {
+            let names = iface.methods().iter().map(|m| m.name().to_string());
+            return self.report(unknown_member(proxy, "method", method, names));
+        };
+
+        let args: Vec<_> = m
+            .args()
+            .iter()
+            .filter(|a| a.direction() != Some(ArgDirection::Out))
+            .collect();
+        let expected: String = args.iter().map(|a| a.ty().signature().as_str()).collect();
+        let expected = Signature::from_str_unchecked(&expected);
+        if expected.canonicalize() == signature.canonicalize() {
+            return;
+        }
+
+        let mut msg = format!(
+            "{} called with arguments of signature `{}` but the remote expects `{}`:",
+            member(proxy, "method", method),
+            signature.canonicalize(),
+            expected,
+        );
+        for (i, arg) in args.iter().enumerate() {
+            let name = arg
+                .name()
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("arg_{i}"));
+            write!(msg, "\n  {name}: {}", arg.ty()).unwrap();
+        }
+        self.report(msg);
+    }
+
+    /// Verify that `property` exists and, if `signature` is given, is of that type.
+    pub(crate) async fn verify_property(
+        &self,
+        proxy: &Proxy<'_>,
+        property: &str,
+        signature: Option<&Signature<'_>>,
+    ) {
+        let iface = match self.remote(proxy).await {
+            Remote::Interface(iface) => iface,
+            Remote::Missing => return self.report(missing_interface(proxy)),
+            Remote::Unavailable => return,
+        };
+        let Some(p) = iface.properties().iter().find(|p| p.name() == property) else {
+            let names = iface.properties().iter().map(|p| p.name().to_string());
+            return self.report(unknown_member(proxy, "property", property, names));
+        };
+
+        match signature {
+            Some(signature) if signature != p.ty().signature() => self.report(format!(
+                "{} set with a value of type `{signature}` but the remote expects `{}`",
+                member(proxy, "property", property),
+                p.ty(),
+            )),
+            _ => (),
+        }
+    }
+
+    async fn remote(&self, proxy: &Proxy<'_>) -> &Remote {
+        if let Some(remote) = self.remote.get() {
+            return remote;
+        }
+
+        let remote = match introspect(proxy).await {
+            Ok(xml) => match Node::from_reader(xml.as_bytes()) {
+                Ok(node) => node
+                    .interfaces()
+                    .iter()
+                    .find(|i| i.name() == *proxy.interface())
+                    .cloned()
+                    .map(Remote::Interface)
+                    .unwrap_or(Remote::Missing),
+                Err(e) => {
+                    debug!("Failed to parse introspection data, not verifying calls: {e}");
+                    Remote::Unavailable
+                }
+            },
+            Err(e) => {
+                debug!("Failed to introspect remote object, not verifying calls: {e}");
+                Remote::Unavailable
+            }
+        };
+
+        // We might have raced another call, in which case we just use the first result.
+        let _ = self.remote.set(remote);
+        self.remote.get().expect("remote interface not set")
+    }
+
+    fn report(&self, msg: String) {
+        match self.mode {
+            VerifySignatures::Warn => warn!("{msg}"),
+            VerifySignatures::Panic => panic!("{msg}"),
+            VerifySignatures::No => unreachable!("verifier created with verification disabled"),
+        }
+    }
+}
+
+/// Introspect the remote object of `proxy`.
+///
+/// Unlike [`Proxy::introspect`], this calls the method directly on the connection so that the
+/// call itself isn't verified, which would make the future type recursive.
+async fn introspect(proxy: &Proxy<'_>) -> crate::Result<String> {
+    proxy
+        .connection()
+        .call_method(
+            Some(proxy.destination()),
+            proxy.path(),
+            Some("org.freedesktop.DBus.Introspectable"),
+            "Introspect",
+            &(),
+        )
+        .await?
+        .body()
+        .deserialize()
+}
+
+fn member(proxy: &Proxy<'_>, kind: &str, name: &str) -> String {
+    format!(
+        "{kind} `{}.{name}` on `{}` at `{}`",
+        proxy.interface(),
+        proxy.destination(),
+        proxy.path(),
+    )
+}
+
+fn missing_interface(proxy: &Proxy<'_>) -> String {
+    format!(
+        "interface `{}` is not implemented by `{}` at `{}`",
+        proxy.interface(),
+        proxy.destination(),
+        proxy.path(),
+    )
+}
+
+fn unknown_member(
+    proxy: &Proxy<'_>,
+    kind: &str,
+    name: &str,
+    available: impl Iterator<Item = String>,
+) -> String {
+    let available: Vec<_> = available.collect();
+    let mut msg = format!("{} does not exist", member(proxy, kind, name));
+    if let Some(similar) = available
+        .iter()
+        .find(|a| a.eq_ignore_ascii_case(name) || a.contains(name) || name.contains(a.as_str()))
+    {
+        write!(msg, ", did you mean `{similar}`?").unwrap();
+    }
+    if !available.is_empty() {
+        write!(msg, " (available: {})", available.join(", ")).unwrap();
+    }
+
+    msg
+}
@@ -28,12 +28,18 @@ impl<T, E> ResultAdapter for Result<T, E> {
     type Err = E;
 }
 
-#[cfg(not(feature = "tokio"))]
+#[cfg(all(feature = "async-io", not(feature = "tokio")))]
 #[doc(hidden)]
 pub fn block_on<F: std::future::Future>(future: F) -> F::Output {
     async_io::block_on(future)
 }
 
+#[cfg(all(not(feature = "async-io"), not(feature = "tokio")))]
+#[doc(hidden)]
+pub fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    crate::abstractions::threaded::block_on(future)
+}
+
 #[cfg(feature = "tokio")]
 #[doc(hidden)]
 pub fn block_on<F: std::future::Future>(future: F) -> F::Output {
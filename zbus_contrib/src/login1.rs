@@ -0,0 +1,8 @@
+//! Proxies for `systemd-logind`, the login manager.
+//!
+//! The [`manager`] interface lives on the `/org/freedesktop/login1` object, while each session has
+//! its own object (e.g `/org/freedesktop/login1/session/_32`). The latter can be looked up through
+//! [`manager::ManagerProxy::get_session`] and passed to [`session::SessionProxy::builder`].
+
+pub mod manager;
+pub mod session;
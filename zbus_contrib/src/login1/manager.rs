@@ -0,0 +1,207 @@
+//! # D-Bus interface proxy for: `org.freedesktop.login1.Manager`
+//!
+//! This code was generated by `zbus-xmlgen` `4.1.0` from D-Bus introspection data.
+//! Source: `xml/org.freedesktop.login1.Manager.xml`.
+//!
+//! You may prefer to adapt it, instead of using it verbatim.
+//!
+//! More information can be found in the [Writing a client proxy] section of the zbus
+//! documentation.
+//!
+//!
+//! [Writing a client proxy]: https://dbus2.github.io/zbus/client.html
+//! [D-Bus standard interfaces]: https://dbus.freedesktop.org/doc/dbus-specification.html#standard-interfaces,
+use zbus::proxy;
+#[proxy(
+    interface = "org.freedesktop.login1.Manager",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1"
+)]
+trait Manager {
+    /// CanHibernate method
+    fn can_hibernate(&self) -> zbus::Result<String>;
+
+    /// CanPowerOff method
+    fn can_power_off(&self) -> zbus::Result<String>;
+
+    /// CanReboot method
+    fn can_reboot(&self) -> zbus::Result<String>;
+
+    /// CanSuspend method
+    fn can_suspend(&self) -> zbus::Result<String>;
+
+    /// GetSeat method
+    fn get_seat(&self, seat_id: &str) -> zbus::Result<zbus::zvariant::OwnedObjectPath>;
+
+    /// GetSession method
+    fn get_session(&self, session_id: &str) -> zbus::Result<zbus::zvariant::OwnedObjectPath>;
+
+    /// GetSessionByPID method
+    #[zbus(name = "GetSessionByPID")]
+    fn get_session_by_pid(&self, pid: u32) -> zbus::Result<zbus::zvariant::OwnedObjectPath>;
+
+    /// GetUser method
+    fn get_user(&self, uid: u32) -> zbus::Result<zbus::zvariant::OwnedObjectPath>;
+
+    /// Hibernate method
+    fn hibernate(&self, interactive: bool) -> zbus::Result<()>;
+
+    /// Inhibit method
+    fn inhibit(
+        &self,
+        what: &str,
+        who: &str,
+        why: &str,
+        mode: &str,
+    ) -> zbus::Result<zbus::zvariant::OwnedFd>;
+
+    /// ListSeats method
+    fn list_seats(&self) -> zbus::Result<Vec<(String, zbus::zvariant::OwnedObjectPath)>>;
+
+    /// ListSessions method
+    #[allow(clippy::type_complexity)]
+    fn list_sessions(
+        &self,
+    ) -> zbus::Result<Vec<(String, u32, String, String, zbus::zvariant::OwnedObjectPath)>>;
+
+    /// ListUsers method
+    fn list_users(&self) -> zbus::Result<Vec<(u32, String, zbus::zvariant::OwnedObjectPath)>>;
+
+    /// LockSession method
+    fn lock_session(&self, session_id: &str) -> zbus::Result<()>;
+
+    /// LockSessions method
+    fn lock_sessions(&self) -> zbus::Result<()>;
+
+    /// PowerOff method
+    fn power_off(&self, interactive: bool) -> zbus::Result<()>;
+
+    /// Reboot method
+    fn reboot(&self, interactive: bool) -> zbus::Result<()>;
+
+    /// Suspend method
+    fn suspend(&self, interactive: bool) -> zbus::Result<()>;
+
+    /// TerminateSession method
+    fn terminate_session(&self, session_id: &str) -> zbus::Result<()>;
+
+    /// UnlockSession method
+    fn unlock_session(&self, session_id: &str) -> zbus::Result<()>;
+
+    /// UnlockSessions method
+    fn unlock_sessions(&self) -> zbus::Result<()>;
+
+    /// PrepareForShutdown signal
+    #[zbus(signal)]
+    fn prepare_for_shutdown(&self, start: bool) -> zbus::Result<()>;
+
+    /// PrepareForSleep signal
+    #[zbus(signal)]
+    fn prepare_for_sleep(&self, start: bool) -> zbus::Result<()>;
+
+    /// SeatNew signal
+    #[zbus(signal)]
+    fn seat_new(
+        &self,
+        seat_id: &str,
+        object_path: zbus::zvariant::ObjectPath<'_>,
+    ) -> zbus::Result<()>;
+
+    /// SeatRemoved signal
+    #[zbus(signal)]
+    fn seat_removed(
+        &self,
+        seat_id: &str,
+        object_path: zbus::zvariant::ObjectPath<'_>,
+    ) -> zbus::Result<()>;
+
+    /// SessionNew signal
+    #[zbus(signal)]
+    fn session_new(
+        &self,
+        session_id: &str,
+        object_path: zbus::zvariant::ObjectPath<'_>,
+    ) -> zbus::Result<()>;
+
+    /// SessionRemoved signal
+    #[zbus(signal)]
+    fn session_removed(
+        &self,
+        session_id: &str,
+        object_path: zbus::zvariant::ObjectPath<'_>,
+    ) -> zbus::Result<()>;
+
+    /// UserNew signal
+    #[zbus(signal)]
+    fn user_new(&self, uid: u32, object_path: zbus::zvariant::ObjectPath<'_>) -> zbus::Result<()>;
+
+    /// UserRemoved signal
+    #[zbus(signal)]
+    fn user_removed(
+        &self,
+        uid: u32,
+        object_path: zbus::zvariant::ObjectPath<'_>,
+    ) -> zbus::Result<()>;
+
+    /// BlockInhibited property
+    #[zbus(property)]
+    fn block_inhibited(&self) -> zbus::Result<String>;
+
+    /// DelayInhibited property
+    #[zbus(property)]
+    fn delay_inhibited(&self) -> zbus::Result<String>;
+
+    /// Docked property
+    #[zbus(property)]
+    fn docked(&self) -> zbus::Result<bool>;
+
+    /// IdleHint property
+    #[zbus(property)]
+    fn idle_hint(&self) -> zbus::Result<bool>;
+
+    /// IdleSinceHint property
+    #[zbus(property)]
+    fn idle_since_hint(&self) -> zbus::Result<u64>;
+
+    /// KillUserProcesses property
+    #[zbus(property)]
+    fn kill_user_processes(&self) -> zbus::Result<bool>;
+
+    /// LidClosed property
+    #[zbus(property)]
+    fn lid_closed(&self) -> zbus::Result<bool>;
+
+    /// NAutoVTs property
+    #[zbus(property, name = "NAutoVTs")]
+    fn nauto_vts(&self) -> zbus::Result<u32>;
+
+    /// OnExternalPower property
+    #[zbus(property)]
+    fn on_external_power(&self) -> zbus::Result<bool>;
+
+    /// PreparingForShutdown property
+    #[zbus(property)]
+    fn preparing_for_shutdown(&self) -> zbus::Result<bool>;
+
+    /// PreparingForSleep property
+    #[zbus(property)]
+    fn preparing_for_sleep(&self) -> zbus::Result<bool>;
+}
+
+impl ManagerProxy<'_> {
+    /// The name of the D-Bus interface.
+    pub const INTERFACE: &'static str = "org.freedesktop.login1.Manager";
+    /// The name of the service.
+    pub const DESTINATION: &'static str = "org.freedesktop.login1";
+    /// The path of the object.
+    pub const PATH: &'static str = "/org/freedesktop/login1";
+}
+
+impl ManagerProxyBlocking<'_> {
+    /// The name of the D-Bus interface.
+    pub const INTERFACE: &'static str = "org.freedesktop.login1.Manager";
+    /// The name of the service.
+    pub const DESTINATION: &'static str = "org.freedesktop.login1";
+    /// The path of the object.
+    pub const PATH: &'static str = "/org/freedesktop/login1";
+}
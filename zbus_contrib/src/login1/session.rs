@@ -0,0 +1,142 @@
+//! # D-Bus interface proxy for: `org.freedesktop.login1.Session`
+//!
+//! This code was generated by `zbus-xmlgen` `4.1.0` from D-Bus introspection data.
+//! Source: `xml/org.freedesktop.login1.Session.xml`.
+//!
+//! You may prefer to adapt it, instead of using it verbatim.
+//!
+//! More information can be found in the [Writing a client proxy] section of the zbus
+//! documentation.
+//!
+//!
+//! [Writing a client proxy]: https://dbus2.github.io/zbus/client.html
+//! [D-Bus standard interfaces]: https://dbus.freedesktop.org/doc/dbus-specification.html#standard-interfaces,
+use zbus::proxy;
+#[proxy(
+    interface = "org.freedesktop.login1.Session",
+    default_service = "org.freedesktop.login1",
+    assume_defaults = true
+)]
+trait Session {
+    /// Activate method
+    fn activate(&self) -> zbus::Result<()>;
+
+    /// Lock method
+    fn lock(&self) -> zbus::Result<()>;
+
+    /// ReleaseControl method
+    fn release_control(&self) -> zbus::Result<()>;
+
+    /// SetIdleHint method
+    fn set_idle_hint(&self, idle: bool) -> zbus::Result<()>;
+
+    /// SetLockedHint method
+    fn set_locked_hint(&self, locked: bool) -> zbus::Result<()>;
+
+    /// TakeControl method
+    fn take_control(&self, force: bool) -> zbus::Result<()>;
+
+    /// Terminate method
+    fn terminate(&self) -> zbus::Result<()>;
+
+    /// Unlock method
+    fn unlock(&self) -> zbus::Result<()>;
+
+    /// Lock signal
+    #[zbus(signal)]
+    fn lock(&self) -> zbus::Result<()>;
+
+    /// Unlock signal
+    #[zbus(signal)]
+    fn unlock(&self) -> zbus::Result<()>;
+
+    /// Active property
+    #[zbus(property)]
+    fn active(&self) -> zbus::Result<bool>;
+
+    /// Class property
+    #[zbus(property)]
+    fn class(&self) -> zbus::Result<String>;
+
+    /// Desktop property
+    #[zbus(property)]
+    fn desktop(&self) -> zbus::Result<String>;
+
+    /// Display property
+    #[zbus(property)]
+    fn display(&self) -> zbus::Result<String>;
+
+    /// Id property
+    #[zbus(property)]
+    fn id(&self) -> zbus::Result<String>;
+
+    /// IdleHint property
+    #[zbus(property)]
+    fn idle_hint(&self) -> zbus::Result<bool>;
+
+    /// Leader property
+    #[zbus(property)]
+    fn leader(&self) -> zbus::Result<u32>;
+
+    /// LockedHint property
+    #[zbus(property)]
+    fn locked_hint(&self) -> zbus::Result<bool>;
+
+    /// Name property
+    #[zbus(property)]
+    fn name(&self) -> zbus::Result<String>;
+
+    /// Remote property
+    #[zbus(property)]
+    fn remote(&self) -> zbus::Result<bool>;
+
+    /// Scope property
+    #[zbus(property)]
+    fn scope(&self) -> zbus::Result<String>;
+
+    /// Seat property
+    #[zbus(property)]
+    fn seat(&self) -> zbus::Result<(String, zbus::zvariant::OwnedObjectPath)>;
+
+    /// Service property
+    #[zbus(property)]
+    fn service(&self) -> zbus::Result<String>;
+
+    /// State property
+    #[zbus(property)]
+    fn state(&self) -> zbus::Result<String>;
+
+    /// TTY property
+    #[zbus(property, name = "TTY")]
+    fn tty(&self) -> zbus::Result<String>;
+
+    /// Timestamp property
+    #[zbus(property)]
+    fn timestamp(&self) -> zbus::Result<u64>;
+
+    /// Type property
+    #[zbus(property)]
+    fn type_(&self) -> zbus::Result<String>;
+
+    /// User property
+    #[zbus(property)]
+    fn user(&self) -> zbus::Result<(u32, zbus::zvariant::OwnedObjectPath)>;
+
+    /// VTNr property
+    #[zbus(property, name = "VTNr")]
+    fn vtnr(&self) -> zbus::Result<u32>;
+}
+
+impl SessionProxy<'_> {
+    /// The name of the D-Bus interface.
+    pub const INTERFACE: &'static str = "org.freedesktop.login1.Session";
+    /// The name of the service.
+    pub const DESTINATION: &'static str = "org.freedesktop.login1";
+}
+
+impl SessionProxyBlocking<'_> {
+    /// The name of the D-Bus interface.
+    pub const INTERFACE: &'static str = "org.freedesktop.login1.Session";
+    /// The name of the service.
+    pub const DESTINATION: &'static str = "org.freedesktop.login1";
+}
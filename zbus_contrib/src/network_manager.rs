@@ -0,0 +1,166 @@
+//! # D-Bus interface proxy for: `org.freedesktop.NetworkManager`
+//!
+//! This code was generated by `zbus-xmlgen` `4.1.0` from D-Bus introspection data.
+//! Source: `xml/org.freedesktop.NetworkManager.xml`.
+//!
+//! You may prefer to adapt it, instead of using it verbatim.
+//!
+//! More information can be found in the [Writing a client proxy] section of the zbus
+//! documentation.
+//!
+//!
+//! [Writing a client proxy]: https://dbus2.github.io/zbus/client.html
+//! [D-Bus standard interfaces]: https://dbus.freedesktop.org/doc/dbus-specification.html#standard-interfaces,
+use zbus::proxy;
+#[proxy(
+    interface = "org.freedesktop.NetworkManager",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager"
+)]
+trait NetworkManager {
+    /// ActivateConnection method
+    fn activate_connection(
+        &self,
+        connection: &zbus::zvariant::ObjectPath<'_>,
+        device: &zbus::zvariant::ObjectPath<'_>,
+        specific_object: &zbus::zvariant::ObjectPath<'_>,
+    ) -> zbus::Result<zbus::zvariant::OwnedObjectPath>;
+
+    /// CheckConnectivity method
+    fn check_connectivity(&self) -> zbus::Result<u32>;
+
+    /// DeactivateConnection method
+    fn deactivate_connection(
+        &self,
+        active_connection: &zbus::zvariant::ObjectPath<'_>,
+    ) -> zbus::Result<()>;
+
+    /// Enable method
+    fn enable(&self, enable: bool) -> zbus::Result<()>;
+
+    /// GetAllDevices method
+    fn get_all_devices(&self) -> zbus::Result<Vec<zbus::zvariant::OwnedObjectPath>>;
+
+    /// GetDeviceByIpIface method
+    fn get_device_by_ip_iface(&self, iface: &str) -> zbus::Result<zbus::zvariant::OwnedObjectPath>;
+
+    /// GetDevices method
+    fn get_devices(&self) -> zbus::Result<Vec<zbus::zvariant::OwnedObjectPath>>;
+
+    /// GetPermissions method
+    fn get_permissions(&self) -> zbus::Result<std::collections::HashMap<String, String>>;
+
+    /// Reload method
+    fn reload(&self, flags: u32) -> zbus::Result<()>;
+
+    /// Sleep method
+    fn sleep(&self, sleep: bool) -> zbus::Result<()>;
+
+    /// CheckPermissions signal
+    #[zbus(signal)]
+    fn check_permissions(&self) -> zbus::Result<()>;
+
+    /// DeviceAdded signal
+    #[zbus(signal)]
+    fn device_added(&self, device_path: zbus::zvariant::ObjectPath<'_>) -> zbus::Result<()>;
+
+    /// DeviceRemoved signal
+    #[zbus(signal)]
+    fn device_removed(&self, device_path: zbus::zvariant::ObjectPath<'_>) -> zbus::Result<()>;
+
+    /// ActivatingConnection property
+    #[zbus(property)]
+    fn activating_connection(&self) -> zbus::Result<zbus::zvariant::OwnedObjectPath>;
+
+    /// ActiveConnections property
+    #[zbus(property)]
+    fn active_connections(&self) -> zbus::Result<Vec<zbus::zvariant::OwnedObjectPath>>;
+
+    /// AllDevices property
+    #[zbus(property)]
+    fn all_devices(&self) -> zbus::Result<Vec<zbus::zvariant::OwnedObjectPath>>;
+
+    /// Connectivity property
+    #[zbus(property)]
+    fn connectivity(&self) -> zbus::Result<u32>;
+
+    /// ConnectivityCheckAvailable property
+    #[zbus(property)]
+    fn connectivity_check_available(&self) -> zbus::Result<bool>;
+
+    /// ConnectivityCheckEnabled property
+    #[zbus(property)]
+    fn connectivity_check_enabled(&self) -> zbus::Result<bool>;
+    #[zbus(property)]
+    fn set_connectivity_check_enabled(&self, value: bool) -> zbus::Result<()>;
+
+    /// Devices property
+    #[zbus(property)]
+    fn devices(&self) -> zbus::Result<Vec<zbus::zvariant::OwnedObjectPath>>;
+
+    /// Metered property
+    #[zbus(property)]
+    fn metered(&self) -> zbus::Result<u32>;
+
+    /// NetworkingEnabled property
+    #[zbus(property)]
+    fn networking_enabled(&self) -> zbus::Result<bool>;
+
+    /// PrimaryConnection property
+    #[zbus(property)]
+    fn primary_connection(&self) -> zbus::Result<zbus::zvariant::OwnedObjectPath>;
+
+    /// PrimaryConnectionType property
+    #[zbus(property)]
+    fn primary_connection_type(&self) -> zbus::Result<String>;
+
+    /// Startup property
+    #[zbus(property)]
+    fn startup(&self) -> zbus::Result<bool>;
+
+    /// State property
+    #[zbus(property)]
+    fn state(&self) -> zbus::Result<u32>;
+
+    /// Version property
+    #[zbus(property)]
+    fn version(&self) -> zbus::Result<String>;
+
+    /// WirelessEnabled property
+    #[zbus(property)]
+    fn wireless_enabled(&self) -> zbus::Result<bool>;
+    #[zbus(property)]
+    fn set_wireless_enabled(&self, value: bool) -> zbus::Result<()>;
+
+    /// WirelessHardwareEnabled property
+    #[zbus(property)]
+    fn wireless_hardware_enabled(&self) -> zbus::Result<bool>;
+
+    /// WwanEnabled property
+    #[zbus(property)]
+    fn wwan_enabled(&self) -> zbus::Result<bool>;
+    #[zbus(property)]
+    fn set_wwan_enabled(&self, value: bool) -> zbus::Result<()>;
+
+    /// WwanHardwareEnabled property
+    #[zbus(property)]
+    fn wwan_hardware_enabled(&self) -> zbus::Result<bool>;
+}
+
+impl NetworkManagerProxy<'_> {
+    /// The name of the D-Bus interface.
+    pub const INTERFACE: &'static str = "org.freedesktop.NetworkManager";
+    /// The name of the service.
+    pub const DESTINATION: &'static str = "org.freedesktop.NetworkManager";
+    /// The path of the object.
+    pub const PATH: &'static str = "/org/freedesktop/NetworkManager";
+}
+
+impl NetworkManagerProxyBlocking<'_> {
+    /// The name of the D-Bus interface.
+    pub const INTERFACE: &'static str = "org.freedesktop.NetworkManager";
+    /// The name of the service.
+    pub const DESTINATION: &'static str = "org.freedesktop.NetworkManager";
+    /// The path of the object.
+    pub const PATH: &'static str = "/org/freedesktop/NetworkManager";
+}
@@ -0,0 +1,88 @@
+//! # D-Bus interface proxy for: `org.freedesktop.timedate1`
+//!
+//! This code was generated by `zbus-xmlgen` `4.1.0` from D-Bus introspection data.
+//! Source: `xml/org.freedesktop.timedate1.xml`.
+//!
+//! You may prefer to adapt it, instead of using it verbatim.
+//!
+//! More information can be found in the [Writing a client proxy] section of the zbus
+//! documentation.
+//!
+//!
+//! [Writing a client proxy]: https://dbus2.github.io/zbus/client.html
+//! [D-Bus standard interfaces]: https://dbus.freedesktop.org/doc/dbus-specification.html#standard-interfaces,
+use zbus::proxy;
+#[proxy(
+    interface = "org.freedesktop.timedate1",
+    default_service = "org.freedesktop.timedate1",
+    default_path = "/org/freedesktop/timedate1"
+)]
+trait Timedate1 {
+    /// ListTimezones method
+    fn list_timezones(&self) -> zbus::Result<Vec<String>>;
+
+    /// SetLocalRTC method
+    #[zbus(name = "SetLocalRTC")]
+    fn set_local_rtc(
+        &self,
+        local_rtc: bool,
+        fix_system: bool,
+        interactive: bool,
+    ) -> zbus::Result<()>;
+
+    /// SetNTP method
+    #[zbus(name = "SetNTP")]
+    fn set_ntp(&self, use_ntp: bool, interactive: bool) -> zbus::Result<()>;
+
+    /// SetTime method
+    fn set_time(&self, usec_utc: i64, relative: bool, interactive: bool) -> zbus::Result<()>;
+
+    /// SetTimezone method
+    fn set_timezone(&self, timezone: &str, interactive: bool) -> zbus::Result<()>;
+
+    /// CanNTP property
+    #[zbus(property, name = "CanNTP")]
+    fn can_ntp(&self) -> zbus::Result<bool>;
+
+    /// LocalRTC property
+    #[zbus(property, name = "LocalRTC")]
+    fn local_rtc(&self) -> zbus::Result<bool>;
+
+    /// NTP property
+    #[zbus(property, name = "NTP")]
+    fn ntp(&self) -> zbus::Result<bool>;
+
+    /// NTPSynchronized property
+    #[zbus(property, name = "NTPSynchronized")]
+    fn ntpsynchronized(&self) -> zbus::Result<bool>;
+
+    /// RTCTimeUSec property
+    #[zbus(property, name = "RTCTimeUSec")]
+    fn rtctime_usec(&self) -> zbus::Result<u64>;
+
+    /// TimeUSec property
+    #[zbus(property, name = "TimeUSec")]
+    fn time_usec(&self) -> zbus::Result<u64>;
+
+    /// Timezone property
+    #[zbus(property)]
+    fn timezone(&self) -> zbus::Result<String>;
+}
+
+impl Timedate1Proxy<'_> {
+    /// The name of the D-Bus interface.
+    pub const INTERFACE: &'static str = "org.freedesktop.timedate1";
+    /// The name of the service.
+    pub const DESTINATION: &'static str = "org.freedesktop.timedate1";
+    /// The path of the object.
+    pub const PATH: &'static str = "/org/freedesktop/timedate1";
+}
+
+impl Timedate1ProxyBlocking<'_> {
+    /// The name of the D-Bus interface.
+    pub const INTERFACE: &'static str = "org.freedesktop.timedate1";
+    /// The name of the service.
+    pub const DESTINATION: &'static str = "org.freedesktop.timedate1";
+    /// The path of the object.
+    pub const PATH: &'static str = "/org/freedesktop/timedate1";
+}
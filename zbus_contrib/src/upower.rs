@@ -0,0 +1,10 @@
+//! Proxies for the UPower daemon.
+//!
+//! The daemon interface lives on the `/org/freedesktop/UPower` object, while each power source
+//! has its own object. The latter can be enumerated through [`UPowerProxy::enumerate_devices`] and
+//! passed to [`device::DeviceProxy::builder`].
+
+mod daemon;
+pub use daemon::*;
+
+pub mod device;
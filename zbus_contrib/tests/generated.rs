@@ -0,0 +1,115 @@
+use pretty_assertions::assert_eq;
+use std::{env, error::Error, path::Path};
+
+use zbus::{names::BusName, zvariant::ObjectPath};
+use zbus_xml::Node;
+use zbus_xmlgen::GenOptions;
+
+// Keep in sync with the `zbus_xmlgen` version in the workspace.
+const XMLGEN_VERSION: &str = "4.1.0";
+
+macro_rules! gen_diff {
+    ($infile:literal, $outfile:literal, $service:literal, $path:expr) => {{
+        let input = include_str!(concat!("../xml/", $infile));
+        let expected = include_str!(concat!("../src/", $outfile));
+        #[cfg(windows)]
+        let expected = expected.replace("\r\n", "\n");
+        let node = Node::from_reader(input.as_bytes())?;
+        let path: Option<&str> = $path;
+        let options = GenOptions {
+            service: Some(BusName::try_from($service)?),
+            path: path.map(ObjectPath::try_from).transpose()?,
+            ..Default::default()
+        };
+        let gen = zbus_xmlgen::write_interfaces(
+            node.interfaces(),
+            &[],
+            concat!("xml/", $infile),
+            "zbus-xmlgen",
+            XMLGEN_VERSION,
+            &options,
+        )?;
+
+        if env::var("TEST_OVERWRITE").is_ok() {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("src")
+                .join($outfile);
+            std::fs::write(path, gen)?;
+
+            return Ok(());
+        }
+
+        assert_eq!(gen, expected);
+        Ok(())
+    }};
+}
+
+#[test]
+fn hostname1() -> Result<(), Box<dyn Error>> {
+    gen_diff!(
+        "org.freedesktop.hostname1.xml",
+        "hostname1.rs",
+        "org.freedesktop.hostname1",
+        Some("/org/freedesktop/hostname1")
+    )
+}
+
+#[test]
+fn timedate1() -> Result<(), Box<dyn Error>> {
+    gen_diff!(
+        "org.freedesktop.timedate1.xml",
+        "timedate1.rs",
+        "org.freedesktop.timedate1",
+        Some("/org/freedesktop/timedate1")
+    )
+}
+
+#[test]
+fn login1_manager() -> Result<(), Box<dyn Error>> {
+    gen_diff!(
+        "org.freedesktop.login1.Manager.xml",
+        "login1/manager.rs",
+        "org.freedesktop.login1",
+        Some("/org/freedesktop/login1")
+    )
+}
+
+#[test]
+fn login1_session() -> Result<(), Box<dyn Error>> {
+    gen_diff!(
+        "org.freedesktop.login1.Session.xml",
+        "login1/session.rs",
+        "org.freedesktop.login1",
+        None
+    )
+}
+
+#[test]
+fn upower() -> Result<(), Box<dyn Error>> {
+    gen_diff!(
+        "org.freedesktop.UPower.xml",
+        "upower/daemon.rs",
+        "org.freedesktop.UPower",
+        Some("/org/freedesktop/UPower")
+    )
+}
+
+#[test]
+fn upower_device() -> Result<(), Box<dyn Error>> {
+    gen_diff!(
+        "org.freedesktop.UPower.Device.xml",
+        "upower/device.rs",
+        "org.freedesktop.UPower",
+        None
+    )
+}
+
+#[test]
+fn network_manager() -> Result<(), Box<dyn Error>> {
+    gen_diff!(
+        "org.freedesktop.NetworkManager.xml",
+        "network_manager.rs",
+        "org.freedesktop.NetworkManager",
+        Some("/org/freedesktop/NetworkManager")
+    )
+}
@@ -48,6 +48,7 @@ def_attrs! {
         interface str,
         name str,
         spawn bool,
+        compose [str],
         proxy {
             // Keep this in sync with proxy's method attributes.
             // TODO: Find a way to share code with proxy module.
@@ -72,6 +73,7 @@ def_attrs! {
             }
         },
         out_args [str],
+        init none,
         proxy {
             // Keep this in sync with proxy's method attributes.
             // TODO: Find a way to share code with proxy module.
@@ -97,7 +99,7 @@ def_attrs! {
 old_new!(ImplAttrs, old::ImplAttributes, ImplAttributes);
 old_new!(MethodAttrs, old::MethodAttributes, MethodAttributes);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Property<'a> {
     read: bool,
     write: bool,
@@ -342,6 +344,8 @@ pub fn expand<T: AttrParse + Into<ImplAttrs>, M: AttrParse + Into<MethodAttrs>>(
     let mut call_dispatch = quote!();
     let mut call_mut_dispatch = quote!();
     let mut introspect = quote!();
+    // The introspection of the members that can be composed into other interfaces.
+    let mut composable_introspect = quote!();
     let mut generated_signals = quote!();
 
     // the impl Type
@@ -356,12 +360,17 @@ pub fn expand<T: AttrParse + Into<ImplAttrs>, M: AttrParse + Into<MethodAttrs>>(
         _ => return Err(Error::new_spanned(&input.self_ty, "Invalid type")),
     };
 
-    let (iface_name, with_spawn, mut proxy) = {
-        let (name, interface, spawn, proxy) = match T::parse_nested_metas(args)?.into() {
-            ImplAttrs::New(new) => (new.name, new.interface, new.spawn, new.proxy),
-            // New proxy attributes are not supported for old `dbus_interface`.
-            ImplAttrs::Old(old) => (old.name, old.interface, old.spawn, None),
+    let (iface_name, with_spawn, mut proxy, composed) = {
+        let (name, interface, spawn, proxy, compose) = match T::parse_nested_metas(args)?.into() {
+            ImplAttrs::New(new) => (new.name, new.interface, new.spawn, new.proxy, new.compose),
+            // New proxy & compose attributes are not supported for old `dbus_interface`.
+            ImplAttrs::Old(old) => (old.name, old.interface, old.spawn, None, None),
         };
+        let composed = compose
+            .unwrap_or_default()
+            .iter()
+            .map(|ty| syn::parse_str::<Type>(ty))
+            .collect::<syn::Result<Vec<_>>>()?;
 
         let name =
             match (name, interface) {
@@ -374,11 +383,12 @@ pub fn expand<T: AttrParse + Into<ImplAttrs>, M: AttrParse + Into<MethodAttrs>>(
             };
         let proxy = proxy.map(|p| Proxy::new(ty, &name, p, &zbus));
 
-        (name, !spawn.unwrap_or(false), proxy)
+        (name, !spawn.unwrap_or(false), proxy, composed)
     };
 
     // Store parsed information about each method
     let mut methods = vec![];
+    let mut init = None;
     for item in &mut input.items {
         let (method, is_signal) = match item {
             ImplItem::Fn(m) => (m, false),
@@ -419,6 +429,24 @@ pub fn expand<T: AttrParse + Into<ImplAttrs>, M: AttrParse + Into<MethodAttrs>>(
             ));
         }
 
+        if matches!(&attrs, MethodAttrs::New(attrs) if attrs.init) {
+            if init.is_some() {
+                return Err(syn::Error::new_spanned(
+                    method,
+                    "there can only be one `init` method",
+                ));
+            }
+            if method.sig.asyncness.is_none() {
+                return Err(syn::Error::new_spanned(
+                    method,
+                    "the `init` method must be async",
+                ));
+            }
+            init = Some(method.sig.ident.clone());
+
+            continue;
+        }
+
         let cfg_attrs: Vec<_> = method
             .attrs
             .iter()
@@ -748,8 +776,11 @@ pub fn expand<T: AttrParse + Into<ImplAttrs>, M: AttrParse + Into<MethodAttrs>>(
                 }
             }
             MethodType::Other => {
-                introspect.extend(doc_comments);
-                introspect.extend(introspect_method(&member_name, &intro_args));
+                let method_introspect = introspect_method(&member_name, &intro_args);
+                introspect.extend(doc_comments.clone());
+                introspect.extend(method_introspect.clone());
+                composable_introspect.extend(doc_comments);
+                composable_introspect.extend(method_introspect);
 
                 let m = quote! {
                     #(#cfg_attrs)*
@@ -782,8 +813,97 @@ pub fn expand<T: AttrParse + Into<ImplAttrs>, M: AttrParse + Into<MethodAttrs>>(
         }
     }
 
+    introspect_properties(&mut composable_introspect, properties.clone())?;
     introspect_properties(&mut introspect, properties)?;
 
+    // The members of the composed interfaces are looked up if the interface doesn't have them.
+    let interface = quote! { #zbus::object_server::Interface };
+    let as_ref = |ty: &Type| quote! { ::std::convert::AsRef::<#ty>::as_ref(self) };
+    let as_mut = |ty: &Type| quote! { ::std::convert::AsMut::<#ty>::as_mut(self) };
+    let composed_get = composed.iter().map(|ty| {
+        let base = as_ref(ty);
+        quote! {
+            if let ::std::option::Option::Some(r) =
+                <#ty as #interface>::get(#base, property_name).await
+            {
+                return ::std::option::Option::Some(r);
+            }
+        }
+    });
+    let composed_get_all = composed.iter().map(|ty| {
+        let base = as_ref(ty);
+        quote! {
+            props.extend(<#ty as #interface>::get_all(#base).await?);
+        }
+    });
+    let composed_set = composed.iter().map(|ty| {
+        let base = as_ref(ty);
+        quote! {
+            let r = <#ty as #interface>::set(#base, property_name, value, signal_context);
+            if !::std::matches!(r, #zbus::object_server::DispatchResult::NotFound) {
+                return r;
+            }
+        }
+    });
+    let composed_set_mut = composed.iter().map(|ty| {
+        let base = as_mut(ty);
+        quote! {
+            if let ::std::option::Option::Some(r) =
+                <#ty as #interface>::set_mut(#base, property_name, value, signal_context).await
+            {
+                return ::std::option::Option::Some(r);
+            }
+        }
+    });
+    let composed_call = composed.iter().map(|ty| {
+        let base = as_ref(ty);
+        quote! {
+            let r = <#ty as #interface>::call(#base, s, c, m, name.clone());
+            if !::std::matches!(r, #zbus::object_server::DispatchResult::NotFound) {
+                return r;
+            }
+        }
+    });
+    // Dispatching to `call_mut` of the first composed interface that has the method, as told by
+    // `call`, since the result of `call_mut` borrows `self` mutably.
+    let composed_call_mut = composed.iter().map(|ty| {
+        let base = as_ref(ty);
+        let base_mut = as_mut(ty);
+        quote! {
+            if !::std::matches!(
+                <#ty as #interface>::call(#base, s, c, m, name.clone()),
+                #zbus::object_server::DispatchResult::NotFound,
+            ) {
+                return <#ty as #interface>::call_mut(#base_mut, s, c, m, name);
+            }
+        }
+    });
+    let composed_introspect = composed.iter().map(|ty| {
+        let base = as_ref(ty);
+        quote! {
+            <#ty as #interface>::introspect_composable_to_writer(#base, writer, level);
+        }
+    });
+    let composed_introspect = quote! { #(#composed_introspect)* };
+    let introspect_composable =
+        if composable_introspect.is_empty() && composed_introspect.is_empty() {
+            quote!()
+        } else {
+            quote! {
+                fn introspect_composable_to_writer(
+                    &self,
+                    writer: &mut dyn ::std::fmt::Write,
+                    level: usize,
+                ) {
+                    #[allow(unused_imports)]
+                    use #zbus::zvariant::Type;
+
+                    #composable_introspect
+                    #composed_introspect
+                }
+            }
+        };
+
     let generics = &input.generics;
     let where_clause = &generics.where_clause;
 
@@ -801,6 +921,18 @@ pub fn expand<T: AttrParse + Into<ImplAttrs>, M: AttrParse + Into<MethodAttrs>>(
 
     let proxy = proxy.map(|proxy| proxy.gen());
 
+    let init = init.map(|ident| {
+        quote! {
+            async fn init(
+                &mut self,
+                connection: &#zbus::Connection,
+                ctxt: #zbus::object_server::SignalContext<'_>,
+            ) {
+                self.#ident(connection, ctxt).await
+            }
+        }
+    });
+
     Ok(quote! {
         #input
 
@@ -818,13 +950,18 @@ pub fn expand<T: AttrParse + Into<ImplAttrs>, M: AttrParse + Into<MethodAttrs>>(
                 #with_spawn
             }
 
+            #init
+
             async fn get(
                 &self,
                 property_name: &str,
             ) -> ::std::option::Option<#zbus::fdo::Result<#zbus::zvariant::OwnedValue>> {
                 match property_name {
                     #get_dispatch
-                    _ => ::std::option::Option::None,
+                    _ => {
+                        #(#composed_get)*
+                        ::std::option::Option::None
+                    }
                 }
             }
 
@@ -838,6 +975,7 @@ pub fn expand<T: AttrParse + Into<ImplAttrs>, M: AttrParse + Into<MethodAttrs>>(
                     ::std::string::String,
                     #zbus::zvariant::OwnedValue,
                 > = ::std::collections::HashMap::new();
+                #(#composed_get_all)*
                 #get_all
                 Ok(props)
             }
@@ -850,7 +988,10 @@ pub fn expand<T: AttrParse + Into<ImplAttrs>, M: AttrParse + Into<MethodAttrs>>(
             ) -> #zbus::object_server::DispatchResult<'call> {
                 match property_name {
                     #set_dispatch
-                    _ => #zbus::object_server::DispatchResult::NotFound,
+                    _ => {
+                        #(#composed_set)*
+                        #zbus::object_server::DispatchResult::NotFound
+                    }
                 }
             }
 
@@ -862,7 +1003,10 @@ pub fn expand<T: AttrParse + Into<ImplAttrs>, M: AttrParse + Into<MethodAttrs>>(
             ) -> ::std::option::Option<#zbus::fdo::Result<()>> {
                 match property_name {
                     #set_mut_dispatch
-                    _ => ::std::option::Option::None,
+                    _ => {
+                        #(#composed_set_mut)*
+                        ::std::option::Option::None
+                    }
                 }
             }
 
@@ -875,7 +1019,10 @@ pub fn expand<T: AttrParse + Into<ImplAttrs>, M: AttrParse + Into<MethodAttrs>>(
             ) -> #zbus::object_server::DispatchResult<'call> {
                 match name.as_str() {
                     #call_dispatch
-                    _ => #zbus::object_server::DispatchResult::NotFound,
+                    _ => {
+                        #(#composed_call)*
+                        #zbus::object_server::DispatchResult::NotFound
+                    }
                 }
             }
 
@@ -888,7 +1035,10 @@ pub fn expand<T: AttrParse + Into<ImplAttrs>, M: AttrParse + Into<MethodAttrs>>(
             ) -> #zbus::object_server::DispatchResult<'call> {
                 match name.as_str() {
                     #call_mut_dispatch
-                    _ => #zbus::object_server::DispatchResult::NotFound,
+                    _ => {
+                        #(#composed_call_mut)*
+                        #zbus::object_server::DispatchResult::NotFound
+                    }
                 }
             }
 
@@ -905,9 +1055,12 @@ pub fn expand<T: AttrParse + Into<ImplAttrs>, M: AttrParse + Into<MethodAttrs>>(
 
                     let level = level + 2;
                     #introspect
+                    #composed_introspect
                 }
                 ::std::writeln!(writer, r#"{:indent$}</interface>"#, "", indent = level).unwrap();
             }
+
+            #introspect_composable
         }
 
         #proxy
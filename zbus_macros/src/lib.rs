@@ -30,9 +30,16 @@ mod utils;
 /// former doesn't take any argument and uses the default service name and path. The later allows
 /// you to specify non-default proxy arguments.
 ///
+/// The generated proxy types implement `Debug`, showing the destination, path and interface, as
+/// well as `PartialEq`, `Eq` and `Hash`, based on the identity of the connection and the
+/// destination, path and interface. Hence proxies can be logged and stored in sets and maps.
+///
 /// The following attributes are supported:
 ///
-/// * `interface` - the name of the D-Bus interface this proxy is for.
+/// * `interface` - the name of the D-Bus interface this proxy is for. If set to `"dynamic"`, the
+///   interface name is instead taken as the first argument of the generated `new` method (or set
+///   through the builder), and validated at runtime. This allows reusing a single trait for a
+///   family of interfaces with the same members, e.g versioned vendor interfaces.
 ///
 /// * `default_service` - the default service this proxy should connect to.
 ///
@@ -53,6 +60,13 @@ mod utils;
 ///   attribute nor one of the default values are specified. Please make sure to explicitly set
 ///   either this attribute or the default values, according to your needs.
 ///
+/// * `gen_trait` - Whether or not to also generate object-safe traits, implemented by the proxy
+///   types (default: `false`). The trait for the asynchronous proxy takes the name of the input
+///   trait and the one for the blocking proxy gets a `Blocking` suffix (unless the asynchronous
+///   proxy is not generated). They contain the methods and the property getters & setters, so you
+///   can e.g keep different proxies in a `Vec<Box<dyn TraitName>>` or inject a mock in place of a
+///   proxy. Signals, generic methods and methods with the `object` attribute are left out.
+///
 /// Each trait method will be expanded to call to the associated D-Bus remote interface.
 ///
 /// Trait methods accept `proxy` attributes:
@@ -228,14 +242,22 @@ pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///   behavior.
 ///
 ///   - **When True (Default):** Suitable for interfaces where method calls are independent of each
-///   other or can be processed asynchronously without strict ordering. In scenarios where a client
-///   must wait for a reply before making further dependent calls, this default behavior is
-///   appropriate.
+///     other or can be processed asynchronously without strict ordering. In scenarios where a
+///     client must wait for a reply before making further dependent calls, this default behavior
+///     is appropriate.
 ///
 ///   - **When False:** Use this setting to ensure methods are handled in the order they are
-///   received, which is crucial for interfaces requiring sequential processing of method calls.
-///   However, care must be taken to avoid making D-Bus method calls from within your interface
-///   methods when this setting is false, as it may lead to deadlocks under certain conditions.
+///     received, which is crucial for interfaces requiring sequential processing of method calls.
+///     However, care must be taken to avoid making D-Bus method calls from within your interface
+///     methods when this setting is false, as it may lead to deadlocks under certain conditions.
+///
+/// * `compose` - A list of types, whose `interface` implementations are composed into this one.
+///   The methods and properties of the composed interfaces are served (and introspected) as part of
+///   this interface, which must not have members of the same names. The type the macro is applied on
+///   must implement `AsRef` and `AsMut` for each of the composed types, e.g
+///   `#[interface(name = "org.myservice.Player", compose("Common", "Versioned"))]`. Signals are
+///   not composed: the signal methods and property change notifications of the composed types
+///   keep using their own interface name.
 ///
 /// * `proxy` - If specified, a proxy type will also be generated for the interface. This attribute
 ///   supports all the [`macro@proxy`]-specific sub-attributes (e.g `gen_async`). The common
@@ -252,12 +274,12 @@ pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///   * `emits_changed_signal` - specifies how property changes are signaled. Valid values are those
 ///     documented in [DBus specifications][dbus_emits_changed_signal]:
 ///     * `"true"` - (default) the change signal is always emitted when the property's setter is
-///     called. The value of the property is included in the signal.
+///       called. The value of the property is included in the signal.
 ///     * `"invalidates"` - the change signal is emitted, but the value is not included in the
-///     signal.
+///       signal.
 ///     * `"const"` - the property never changes, thus no signal is ever emitted for it.
 ///     * `"false"` - the change signal is not emitted if the property changes.
-
+///
 /// * `signal` - the method is a "signal". It must be a method declaration (without body). Its code
 ///   block will be expanded to emit the signal from the object path associated with the interface
 ///   instance.
@@ -268,6 +290,12 @@ pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// * `out_args` - When returning multiple values from a method, naming the out arguments become
 ///   important. You can use `out_args` to specify their names.
 ///
+/// * `init` - the method is not exposed on the bus but called by the [`ObjectServer`] right after
+///   the interface is registered, before any method call or property access is handled. It must
+///   be async and have the signature of [`Interface::init`], i.e take `&mut self`, a
+///   `&Connection` and a `SignalContext<'_>`. This is useful for spawning tasks, subscribing to
+///   other services or emitting initial signals. There can be only one such method.
+///
 /// * `proxy` - Use this to specify the [`macro@proxy`]-specific method sub-attributes (e.g
 ///   `object`). The common sub-attributes (e.g `name`) are automatically forworded to the
 ///   [`macro@proxy`] macro.
@@ -370,6 +398,7 @@ pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// [`Connection::emit_signal()`]: https://docs.rs/zbus/latest/zbus/connection/struct.Connection.html#method.emit_signal
 /// [`SignalContext`]: https://docs.rs/zbus/latest/zbus/object_server/struct.SignalContext.html
 /// [`Interface`]: https://docs.rs/zbus/latest/zbus/object_server/trait.Interface.html
+/// [`Interface::init`]: https://docs.rs/zbus/latest/zbus/object_server/trait.Interface.html#method.init
 /// [dbus_emits_changed_signal]: https://dbus.freedesktop.org/doc/dbus-specification.html#introspection-format
 #[proc_macro_attribute]
 pub fn interface(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -55,7 +55,8 @@ def_attrs! {
         async_name str,
         blocking_name str,
         gen_async bool,
-        gen_blocking bool
+        gen_blocking bool,
+        gen_trait bool
     };
 
     // Keep this in sync with interface's proxy method attributes.
@@ -79,6 +80,11 @@ def_attrs! {
 old_new!(TraitAttrs, old::TraitAttributes, TraitAttributes);
 old_new!(MethodAttrs, old::MethodAttributes, MethodAttributes);
 
+/// The `interface` value that makes the interface name a parameter of the proxy constructor.
+///
+/// It's not a valid interface name, so it can't clash with one.
+const DYNAMIC_INTERFACE: &str = "dynamic";
+
 struct AsyncOpts {
     blocking: bool,
     usage: TokenStream,
@@ -114,6 +120,7 @@ pub fn expand<I: AttrParse + Into<TraitAttrs>, M: AttrParse + Into<MethodAttrs>>
         blocking_name,
         gen_async,
         gen_blocking,
+        gen_trait,
     ) = match I::parse_nested_metas(args)?.into() {
         TraitAttrs::Old(old) => (
             old.interface,
@@ -125,6 +132,7 @@ pub fn expand<I: AttrParse + Into<TraitAttrs>, M: AttrParse + Into<MethodAttrs>>
             old.blocking_name,
             old.gen_async,
             old.gen_blocking,
+            None,
         ),
         TraitAttrs::New(new) => (
             new.interface,
@@ -136,6 +144,7 @@ pub fn expand<I: AttrParse + Into<TraitAttrs>, M: AttrParse + Into<MethodAttrs>>
             new.blocking_name,
             new.gen_async,
             new.gen_blocking,
+            new.gen_trait,
         ),
     };
 
@@ -149,6 +158,7 @@ pub fn expand<I: AttrParse + Into<TraitAttrs>, M: AttrParse + Into<MethodAttrs>>
     }?;
     let gen_async = gen_async.unwrap_or(true);
     let gen_blocking = gen_blocking.unwrap_or(true);
+    let gen_trait = gen_trait.unwrap_or(false);
 
     // Some sanity checks
     assert!(
@@ -173,6 +183,14 @@ pub fn expand<I: AttrParse + Into<TraitAttrs>, M: AttrParse + Into<MethodAttrs>>
                 format!("{}Proxy", input.ident)
             }
         });
+        // Same naming scheme as for the proxy types.
+        let trait_name = gen_trait.then(|| {
+            if gen_async {
+                format!("{}Blocking", input.ident)
+            } else {
+                input.ident.to_string()
+            }
+        });
         create_proxy::<M>(
             &input,
             iface_name.as_deref(),
@@ -180,6 +198,7 @@ pub fn expand<I: AttrParse + Into<TraitAttrs>, M: AttrParse + Into<MethodAttrs>>
             default_path.as_deref(),
             default_service.as_deref(),
             &proxy_name,
+            trait_name.as_deref(),
             true,
             // Signal args structs are shared between the two proxies so always generate it for
             // async proxy only unless async proxy generation is disabled.
@@ -190,6 +209,7 @@ pub fn expand<I: AttrParse + Into<TraitAttrs>, M: AttrParse + Into<MethodAttrs>>
     };
     let async_proxy = if gen_async {
         let proxy_name = async_name.unwrap_or_else(|| format!("{}Proxy", input.ident));
+        let trait_name = gen_trait.then(|| input.ident.to_string());
         create_proxy::<M>(
             &input,
             iface_name.as_deref(),
@@ -197,6 +217,7 @@ pub fn expand<I: AttrParse + Into<TraitAttrs>, M: AttrParse + Into<MethodAttrs>>
             default_path.as_deref(),
             default_service.as_deref(),
             &proxy_name,
+            trait_name.as_deref(),
             false,
             true,
         )?
@@ -219,6 +240,7 @@ pub fn create_proxy<M: AttrParse + Into<MethodAttrs>>(
     default_path: Option<&str>,
     default_service: Option<&str>,
     proxy_name: &str,
+    trait_name: Option<&str>,
     blocking: bool,
     gen_sig_args: bool,
 ) -> Result<TokenStream, Error> {
@@ -231,10 +253,18 @@ pub fn create_proxy<M: AttrParse + Into<MethodAttrs>>(
         .collect();
     let proxy_name = Ident::new(proxy_name, Span::call_site());
     let ident = input.ident.to_string();
+    let dynamic_interface = iface_name == Some(DYNAMIC_INTERFACE);
     let iface_name = iface_name
         .map(ToString::to_string)
         .unwrap_or(format!("org.freedesktop.{ident}"));
     let assume_defaults = assume_defaults.unwrap_or(false);
+    if assume_defaults && dynamic_interface && default_service.is_none() {
+        return Err(Error::new(
+            input.span(),
+            "`assume_defaults` can't derive the default service from a dynamic interface, \
+             specify `default_service` explicitly",
+        ));
+    }
     let (default_path, default_service) = if assume_defaults {
         let path = default_path
             .map(ToString::to_string)
@@ -249,6 +279,8 @@ pub fn create_proxy<M: AttrParse + Into<MethodAttrs>>(
         (path, svc)
     };
     let mut methods = TokenStream::new();
+    let mut trait_methods = TokenStream::new();
+    let mut trait_impl_methods = TokenStream::new();
     let mut stream_types = TokenStream::new();
     let mut has_properties = false;
     let mut uncached_properties: Vec<String> = vec![];
@@ -288,6 +320,23 @@ pub fn create_proxy<M: AttrParse + Into<MethodAttrs>>(
                 )
             });
 
+            // Signals, generic methods and methods returning other proxies can't be part of an
+            // object-safe trait.
+            let object = match <M>::parse(&m.attrs)?.into() {
+                MethodAttrs::Old(old) => old.object,
+                MethodAttrs::New(new) => new.object,
+            };
+            if trait_name.is_some()
+                && !is_signal
+                && object.is_none()
+                && m.sig.generics.params.is_empty()
+            {
+                let (trait_method, trait_impl_method) =
+                    gen_trait_method(&proxy_name, m, &async_opts);
+                trait_methods.extend(trait_method);
+                trait_impl_methods.extend(trait_impl_method);
+            }
+
             let m = if let Some(prop_attrs) = &property {
                 has_properties = true;
 
@@ -311,7 +360,7 @@ pub fn create_proxy<M: AttrParse + Into<MethodAttrs>>(
             } else if is_signal {
                 let (method, types) = gen_proxy_signal(
                     &proxy_name,
-                    &iface_name,
+                    (!dynamic_interface).then_some(iface_name.as_str()),
                     &member_name,
                     &method_name,
                     m,
@@ -351,12 +400,31 @@ pub fn create_proxy<M: AttrParse + Into<MethodAttrs>>(
         (proxy, connection, builder, proxy_trait)
     };
 
+    // With a dynamic interface, `new` takes the interface name as its first argument.
+    let (iface_generic, iface_arg, iface_bounds, iface_set, iface_doc) = if dynamic_interface {
+        (
+            quote! { I, },
+            quote! { interface: I, },
+            quote! {
+                I: ::std::convert::TryInto<#zbus::names::InterfaceName<'p>>,
+                I::Error: ::std::convert::Into<#zbus::Error>,
+            },
+            quote! { .interface(interface)? },
+            " for the given interface",
+        )
+    } else {
+        (quote! {}, quote! {}, quote! {}, quote! {}, "")
+    };
     let proxy_method_new = match (&default_path, &default_service) {
         (None, None) => {
+            let doc = format!(
+                "Creates a new proxy{iface_doc} with the given service destination and path."
+            );
             quote! {
-                /// Creates a new proxy with the given service destination and path.
-                pub #usage fn new<D, P>(conn: &#connection, destination: D, path: P) -> #zbus::Result<#proxy_name<'p>>
+                #[doc = #doc]
+                pub #usage fn new<#iface_generic D, P>(conn: &#connection, #iface_arg destination: D, path: P) -> #zbus::Result<#proxy_name<'p>>
                 where
+                    #iface_bounds
                     D: ::std::convert::TryInto<#zbus::names::BusName<'static>>,
                     D::Error: ::std::convert::Into<#zbus::Error>,
                     P: ::std::convert::TryInto<#zbus::zvariant::ObjectPath<'static>>,
@@ -365,6 +433,7 @@ pub fn create_proxy<M: AttrParse + Into<MethodAttrs>>(
                     let obj_path = path.try_into().map_err(::std::convert::Into::into)?;
                     let obj_destination = destination.try_into().map_err(::std::convert::Into::into)?;
                     Self::builder(conn)
+                        #iface_set
                         .path(obj_path)?
                         .destination(obj_destination)?
                         .build()#wait
@@ -372,35 +441,58 @@ pub fn create_proxy<M: AttrParse + Into<MethodAttrs>>(
             }
         }
         (Some(_), None) => {
+            let doc = format!(
+                "Creates a new proxy{iface_doc} with the given destination, and the default path."
+            );
             quote! {
-                /// Creates a new proxy with the given destination, and the default path.
-                pub #usage fn new<D>(conn: &#connection, destination: D) -> #zbus::Result<#proxy_name<'p>>
+                #[doc = #doc]
+                pub #usage fn new<#iface_generic D>(conn: &#connection, #iface_arg destination: D) -> #zbus::Result<#proxy_name<'p>>
                 where
+                    #iface_bounds
                     D: ::std::convert::TryInto<#zbus::names::BusName<'static>>,
                     D::Error: ::std::convert::Into<#zbus::Error>,
                 {
                     let obj_dest = destination.try_into().map_err(::std::convert::Into::into)?;
                     Self::builder(conn)
+                        #iface_set
                         .destination(obj_dest)?
                         .build()#wait
                 }
             }
         }
         (None, Some(_)) => {
+            let doc = format!(
+                "Creates a new proxy{iface_doc} with the given path, and the default destination."
+            );
             quote! {
-                /// Creates a new proxy with the given path, and the default destination.
-                pub #usage fn new<P>(conn: &#connection, path: P) -> #zbus::Result<#proxy_name<'p>>
+                #[doc = #doc]
+                pub #usage fn new<#iface_generic P>(conn: &#connection, #iface_arg path: P) -> #zbus::Result<#proxy_name<'p>>
                 where
+                    #iface_bounds
                     P: ::std::convert::TryInto<#zbus::zvariant::ObjectPath<'static>>,
                     P::Error: ::std::convert::Into<#zbus::Error>,
                 {
                     let obj_path = path.try_into().map_err(::std::convert::Into::into)?;
                     Self::builder(conn)
+                        #iface_set
                         .path(obj_path)?
                         .build()#wait
                 }
             }
         }
+        (Some(_), Some(_)) if dynamic_interface => {
+            quote! {
+                /// Creates a new proxy for the given interface, with the default service and path.
+                pub #usage fn new<I>(conn: &#connection, interface: I) -> #zbus::Result<#proxy_name<'p>>
+                where
+                    #iface_bounds
+                {
+                    Self::builder(conn)
+                        #iface_set
+                        .build()#wait
+                }
+            }
+        }
         (Some(_), Some(_)) => {
             quote! {
                 /// Creates a new proxy with the default service and path.
@@ -410,6 +502,35 @@ pub fn create_proxy<M: AttrParse + Into<MethodAttrs>>(
             }
         }
     };
+    let proxy_dyn_trait = trait_name.map(|trait_name| {
+        let trait_name = Ident::new(trait_name, Span::call_site());
+        let doc = format!(
+            "Object-safe counterpart of [`{proxy_name}`], implemented by it.\n\n\
+             It contains the D-Bus methods and the property getters & setters of the proxy, so \
+             that proxies can be used as trait objects (e.g for mocking)."
+        );
+        let (async_trait, bounds) = if blocking {
+            (quote! {}, quote! {})
+        } else {
+            (
+                quote! { #[#zbus::export::async_trait::async_trait] },
+                quote! { : ::std::marker::Send + ::std::marker::Sync },
+            )
+        };
+
+        quote! {
+            #[doc = #doc]
+            #async_trait
+            pub trait #trait_name #bounds {
+                #trait_methods
+            }
+
+            #async_trait
+            impl<'p> #trait_name for #proxy_name<'p> {
+                #trait_impl_methods
+            }
+        }
+    });
     let default_path = match default_path {
         Some(p) => quote! { Some(#p) },
         None => quote! { None },
@@ -418,18 +539,33 @@ pub fn create_proxy<M: AttrParse + Into<MethodAttrs>>(
         Some(d) => quote! { Some(#d) },
         None => quote! { None },
     };
+    let default_interface = if dynamic_interface {
+        quote! { None }
+    } else {
+        quote! { Some(#iface_name) }
+    };
 
     Ok(quote! {
         impl<'a> #zbus::proxy::ProxyDefault for #proxy_name<'a> {
-            const INTERFACE: Option<&'static str> = Some(#iface_name);
+            const INTERFACE: Option<&'static str> = #default_interface;
             const DESTINATION: Option<&'static str> = #default_service;
             const PATH: Option<&'static str> = #default_path;
         }
 
         #(#other_attrs)*
-        #[derive(Clone, Debug)]
+        #[derive(Clone, PartialEq, Eq, Hash)]
         pub struct #proxy_name<'p>(#proxy_struct<'p>);
 
+        impl<'p> ::std::fmt::Debug for #proxy_name<'p> {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.debug_struct(::std::stringify!(#proxy_name))
+                    .field("destination", self.inner().destination())
+                    .field("path", self.inner().path())
+                    .field("interface", self.inner().interface())
+                    .finish()
+            }
+        }
+
         impl<'p> #proxy_name<'p> {
             #proxy_method_new
 
@@ -513,10 +649,53 @@ pub fn create_proxy<M: AttrParse + Into<MethodAttrs>>(
             }
         }
 
+        #proxy_dyn_trait
+
         #stream_types
     })
 }
 
+/// Generate the declaration of a method in the object-safe proxy trait, and its implementation
+/// forwarding to the proxy's method.
+fn gen_trait_method(
+    proxy_name: &Ident,
+    m: &TraitItemFn,
+    async_opts: &AsyncOpts,
+) -> (TokenStream, TokenStream) {
+    let AsyncOpts { usage, wait, .. } = async_opts;
+    let attrs: Vec<_> = m
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("doc") || a.path().is_ident("cfg"))
+        .collect();
+    let cfg_attrs: Vec<_> = m
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("cfg"))
+        .collect();
+    let method = &m.sig.ident;
+    let inputs = &m.sig.inputs;
+    let output = &m.sig.output;
+    let args: Vec<_> = inputs
+        .iter()
+        .filter_map(typed_arg)
+        .filter_map(pat_ident)
+        .collect();
+
+    (
+        quote! {
+            #(#attrs)*
+            #usage fn #method(#inputs) #output;
+        },
+        quote! {
+            #(#cfg_attrs)*
+            #usage fn #method(#inputs) #output {
+                #proxy_name::#method(self, #(#args),*)#wait
+            }
+        },
+    )
+}
+
 fn gen_proxy_method_call<M: AttrParse + Into<MethodAttrs>>(
     method_name: &str,
     snake_case_name: &str,
@@ -855,7 +1034,7 @@ impl Fold for SetLifetimeS {
 
 fn gen_proxy_signal(
     proxy_name: &Ident,
-    iface_name: &str,
+    iface_name: Option<&str>,
     signal_name: &str,
     snake_case_name: &str,
     method: &TraitItemFn,
@@ -992,6 +1171,11 @@ fn gen_proxy_signal(
     );
     let signal_args_gen_doc = format!("`{signal_name}` signal arguments.");
     let args_struct_gen_doc = format!("A `{signal_name}` signal.");
+    // The interface of a dynamic proxy is only known at runtime, so we can only match the member.
+    let iface_pattern = match iface_name {
+        Some(iface_name) => quote! { Some(#iface_name) },
+        None => quote! { Some(_) },
+    };
     let args_struct_decl = if gen_sig_args {
         quote! {
             #[doc = #args_struct_gen_doc]
@@ -1015,7 +1199,7 @@ fn gen_proxy_signal(
                     let member = member.as_ref().map(|m| m.as_str());
 
                     match (message_type, interface, member) {
-                        (#zbus::message::Type::Signal, Some(#iface_name), Some(#signal_name)) => {
+                        (#zbus::message::Type::Signal, #iface_pattern, Some(#signal_name)) => {
                             Some(Self(msg.body()))
                         }
                         _ => None,
@@ -107,8 +107,162 @@ fn test_proxy() {
     });
 }
 
+#[test]
+fn test_proxy_identity() {
+    use std::collections::HashSet;
+
+    block_on(async move {
+        let connection = zbus::Connection::session().await.unwrap();
+        let other_connection = zbus::Connection::session().await.unwrap();
+        let build = |conn, path| async move {
+            test::TestProxy::builder(conn)
+                .path(path)
+                .unwrap()
+                .cache_properties(CacheProperties::No)
+                .build()
+                .await
+                .unwrap()
+        };
+        let proxy = build(&connection, "/org/freedesktop/zbus_macros/test").await;
+        let same = build(&connection, "/org/freedesktop/zbus_macros/test").await;
+        let other_path = build(&connection, "/org/freedesktop/zbus_macros/other").await;
+        let other_conn = build(&other_connection, "/org/freedesktop/zbus_macros/test").await;
+
+        assert_eq!(proxy, same);
+        assert_ne!(proxy, other_path);
+        assert_ne!(proxy, other_conn);
+        // Hashing only depends on the identity of the proxy, not its mutable state.
+        #[allow(clippy::mutable_key_type)]
+        let set: HashSet<_> = [proxy.clone(), same, other_path, other_conn].into();
+        assert_eq!(set.len(), 3);
+
+        let debug = format!("{proxy:?}");
+        assert!(debug.starts_with("TestProxy {"));
+        assert!(debug.contains("\"/org/freedesktop/zbus_macros/test\""));
+        assert!(debug.contains("\"org.freedesktop.zbus_macros.Test\""));
+    });
+}
+
+#[test]
+fn test_proxy_trait() {
+    #[proxy(
+        interface = "org.freedesktop.zbus_macros.Player",
+        default_path = "/org/freedesktop/zbus_macros/Player",
+        gen_trait = true
+    )]
+    trait Player {
+        fn play(&self, uri: &str) -> zbus::Result<bool>;
+
+        #[zbus(property)]
+        fn volume(&self) -> fdo::Result<f64>;
+
+        #[zbus(property)]
+        fn set_volume(&self, volume: f64) -> fdo::Result<()>;
+
+        #[zbus(signal)]
+        fn stopped(&self) -> fdo::Result<()>;
+    }
+
+    struct MockPlayer;
+
+    #[zbus::export::async_trait::async_trait]
+    impl Player for MockPlayer {
+        async fn play(&self, uri: &str) -> zbus::Result<bool> {
+            Ok(uri.starts_with("file://"))
+        }
+
+        async fn volume(&self) -> fdo::Result<f64> {
+            Ok(0.5)
+        }
+
+        async fn set_volume(&self, _volume: f64) -> fdo::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn assert_blocking_impl<T: PlayerBlocking>() {}
+    assert_blocking_impl::<PlayerProxyBlocking<'_>>();
+
+    block_on(async move {
+        let connection = zbus::Connection::session().await.unwrap();
+        let proxy = PlayerProxy::new(&connection, "org.freedesktop.zbus_macros")
+            .await
+            .unwrap();
+        let players: Vec<Box<dyn Player>> = vec![Box::new(proxy), Box::new(MockPlayer)];
+
+        // Only the mock is backed by an implementation.
+        let mock = &players[1];
+        assert!(mock.play("file:///tmp/song.ogg").await.unwrap());
+        assert_eq!(mock.volume().await.unwrap(), 0.5);
+        mock.set_volume(1.0).await.unwrap();
+    });
+}
+
+#[test]
+fn test_proxy_dynamic_interface() {
+    #[proxy(
+        interface = "dynamic",
+        default_path = "/org/freedesktop/zbus_macros/Versioned"
+    )]
+    trait Versioned {
+        fn ping(&self) -> zbus::Result<()>;
+
+        #[zbus(signal)]
+        fn pinged(&self) -> zbus::Result<()>;
+    }
+
+    block_on(async move {
+        let connection = zbus::Connection::session().await.unwrap();
+        let v1 = VersionedProxy::new(
+            &connection,
+            "org.freedesktop.zbus_macros.Versioned1",
+            "org.freedesktop.zbus_macros",
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            v1.inner().interface(),
+            "org.freedesktop.zbus_macros.Versioned1"
+        );
+        let v2 = VersionedProxy::new(
+            &connection,
+            "org.freedesktop.zbus_macros.Versioned2",
+            "org.freedesktop.zbus_macros",
+        )
+        .await
+        .unwrap();
+        assert_ne!(v1, v2);
+
+        // The interface name is validated at runtime.
+        VersionedProxy::new(&connection, "dynamic", "org.freedesktop.zbus_macros")
+            .await
+            .unwrap_err();
+        // And there's no default to fall back to.
+        let err = VersionedProxy::builder(&connection)
+            .destination("org.freedesktop.zbus_macros")
+            .unwrap()
+            .build()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, zbus::Error::MissingParameter("interface")));
+    });
+
+    let connection = zbus::blocking::Connection::session().unwrap();
+    let proxy = VersionedProxyBlocking::new(
+        &connection,
+        "org.freedesktop.zbus_macros.Versioned3",
+        "org.freedesktop.zbus_macros",
+    )
+    .unwrap();
+    assert_eq!(
+        proxy.inner().interface(),
+        "org.freedesktop.zbus_macros.Versioned3"
+    );
+}
+
 #[test]
 fn test_derive_error() {
+    #[allow(dead_code)]
     #[derive(Debug, DBusError)]
     #[zbus(prefix = "org.freedesktop.zbus")]
     enum Test {
@@ -6,8 +6,9 @@ use core::{
 use std::{borrow::Cow, sync::Arc};
 
 use crate::{
-    utils::impl_str_basic, Error, OwnedUniqueName, OwnedWellKnownName, Result, UniqueName,
-    WellKnownName,
+    macros::{is_valid_unique_name, is_valid_well_known_name},
+    utils::{impl_str_basic, is_in_namespace},
+    Error, OwnedUniqueName, OwnedWellKnownName, Result, UniqueName, WellKnownName,
 };
 use serde::{de, Deserialize, Serialize};
 use static_assertions::assert_impl_all;
@@ -92,6 +93,29 @@ impl<'name> BusName<'name> {
         }
     }
 
+    /// Whether this name is `namespace` itself or a name under it.
+    ///
+    /// Names are matched element-wise, as for the `arg0namespace` key of match rules, so
+    /// `org.gnome.Shell` is in the `org.gnome` namespace but not in `org.gno`. Unique names are
+    /// never under a well-known name namespace, and vice versa.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zbus_names::BusName;
+    ///
+    /// let name = BusName::try_from("org.gnome.Shell").unwrap();
+    /// assert!(name.is_in_namespace("org.gnome"));
+    /// assert!(!name.is_in_namespace("org.gno"));
+    ///
+    /// let name = BusName::try_from(":1.42").unwrap();
+    /// assert!(name.is_in_namespace(":1"));
+    /// assert!(!name.is_in_namespace("org.gnome"));
+    /// ```
+    pub fn is_in_namespace(&self, namespace: &str) -> bool {
+        is_in_namespace(self.as_str(), namespace)
+    }
+
     /// Same as `try_from`, except it takes a `&'static str`.
     pub fn from_static_str(name: &'static str) -> Result<Self> {
         match Self::try_from(name)? {
@@ -101,6 +125,41 @@ impl<'name> BusName<'name> {
             )),
         }
     }
+
+    /// Create a new `BusName` from the given `&'static str`, without checking it for correctness.
+    ///
+    /// The name is a unique name if it's a valid one and a well-known name otherwise. Prefer using
+    /// [`BusName::from_static_str`] or [`BusName::from_static_str_checked`].
+    pub const fn from_static_str_unchecked(name: &'static str) -> Self {
+        if is_valid_unique_name(name) {
+            BusName::Unique(UniqueName::from_static_str_unchecked(name))
+        } else {
+            BusName::WellKnown(WellKnownName::from_static_str_unchecked(name))
+        }
+    }
+
+    /// Same as `from_static_str`, except it's a `const fn` that panics if `name` is invalid.
+    ///
+    /// When used in `const` and `static` items, an invalid name is therefore a compile error:
+    ///
+    /// ```
+    /// use zbus_names::BusName;
+    ///
+    /// static NAME: BusName<'static> = BusName::from_static_str_checked("org.freedesktop.DBus");
+    /// assert_eq!(NAME, "org.freedesktop.DBus");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `name` is neither a valid unique name, nor a valid well-known name.
+    pub const fn from_static_str_checked(name: &'static str) -> Self {
+        assert!(
+            is_valid_unique_name(name) || is_valid_well_known_name(name),
+            "invalid bus name"
+        );
+
+        Self::from_static_str_unchecked(name)
+    }
 }
 
 impl Deref for BusName<'_> {
@@ -368,6 +427,11 @@ impl OwnedBusName {
     pub fn inner(&self) -> &BusName<'static> {
         &self.0
     }
+
+    /// Borrow the name as a `BusName`, without any allocation.
+    pub fn as_ref(&self) -> BusName<'_> {
+        self.0.as_ref()
+    }
 }
 
 impl Deref for OwnedBusName {
@@ -413,10 +477,7 @@ impl From<OwnedBusName> for BusName<'_> {
 
 impl<'unowned, 'owned: 'unowned> From<&'owned OwnedBusName> for BusName<'unowned> {
     fn from(name: &'owned OwnedBusName) -> Self {
-        match &name.0 {
-            BusName::Unique(name) => BusName::Unique(UniqueName::from_str_unchecked(name)),
-            BusName::WellKnown(name) => BusName::WellKnown(WellKnownName::from_str_unchecked(name)),
-        }
+        name.as_ref()
     }
 }
 
@@ -500,6 +561,12 @@ impl<'de> Deserialize<'de> for OwnedBusName {
     }
 }
 
+impl PartialEq<str> for OwnedBusName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
 impl PartialEq<&str> for OwnedBusName {
     fn eq(&self, other: &&str) -> bool {
         self.as_str() == *other
@@ -9,21 +9,21 @@ use zvariant::Error as VariantError;
 #[non_exhaustive]
 pub enum Error {
     Variant(VariantError),
-    /// Invalid bus name. The strings describe why the bus name is neither a valid unique nor
+    /// Invalid bus name. The details describe why the bus name is neither a valid unique nor
     /// well-known name, respectively.
-    InvalidBusName(String, String),
+    InvalidBusName(InvalidName, InvalidName),
     /// Invalid well-known bus name.
-    InvalidWellKnownName(String),
+    InvalidWellKnownName(InvalidName),
     /// Invalid unique bus name.
-    InvalidUniqueName(String),
+    InvalidUniqueName(InvalidName),
     /// Invalid interface name.
-    InvalidInterfaceName(String),
+    InvalidInterfaceName(InvalidName),
     /// Invalid member (method or signal) name.
-    InvalidMemberName(String),
+    InvalidMemberName(InvalidName),
     /// Invalid property name.
-    InvalidPropertyName(String),
+    InvalidPropertyName(InvalidName),
     /// Invalid error name.
-    InvalidErrorName(String),
+    InvalidErrorName(InvalidName),
 }
 
 assert_impl_all!(Error: Send, Sync, Unpin);
@@ -79,6 +79,94 @@ impl fmt::Display for Error {
     }
 }
 
+/// Why and where a name is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use zbus_names::{Error, InterfaceName, InvalidNameReason};
+///
+/// match InterfaceName::try_from("org.freedesktop.2nd") {
+///     Err(Error::InvalidInterfaceName(e)) => {
+///         assert_eq!(e.reason(), InvalidNameReason::LeadingDigit);
+///         assert_eq!(e.position(), 16);
+///         assert_eq!(e.to_string(), "element starting with a digit at byte 16");
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InvalidName {
+    position: usize,
+    reason: InvalidNameReason,
+}
+
+assert_impl_all!(InvalidName: Send, Sync, Unpin);
+
+impl InvalidName {
+    pub(crate) fn new(position: usize, reason: InvalidNameReason) -> Self {
+        Self { position, reason }
+    }
+
+    /// The byte index in the name where the violation was found.
+    ///
+    /// For violations about the name as a whole, this is the length of the name, except for
+    /// [`InvalidNameReason::TooLong`] where it's the index of the first byte over the limit.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The rule that the name violates.
+    pub fn reason(&self) -> InvalidNameReason {
+        self.reason
+    }
+}
+
+impl fmt::Display for InvalidName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let position = self.position;
+        match self.reason {
+            InvalidNameReason::TooShort(min) => write!(
+                f,
+                "name is {position} characters long, which is smaller than minimum allowed ({min})"
+            ),
+            InvalidNameReason::TooLong => write!(
+                f,
+                "name is longer than maximum allowed ({position} characters)"
+            ),
+            InvalidNameReason::EmptyElement => write!(f, "empty element at byte {position}"),
+            InvalidNameReason::LeadingDigit => {
+                write!(f, "element starting with a digit at byte {position}")
+            }
+            InvalidNameReason::InvalidCharacter(c) => {
+                write!(f, "`{c}` character not allowed at byte {position}")
+            }
+            InvalidNameReason::MissingDot => write!(f, "must contain at least 1 `.`"),
+            InvalidNameReason::MissingColon => write!(f, "must start with a `:`"),
+        }
+    }
+}
+
+/// The rule violated by an [`InvalidName`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum InvalidNameReason {
+    /// The name is shorter than the given minimum length.
+    TooShort(usize),
+    /// The name is longer than the maximum length of 255.
+    TooLong,
+    /// An element of the name is empty, i-e the name starts with a `.` or contains a double `.`.
+    EmptyElement,
+    /// An element of the name starts with a digit.
+    LeadingDigit,
+    /// The character is not allowed in this kind of name.
+    InvalidCharacter(char),
+    /// The name doesn't contain any `.`.
+    MissingDot,
+    /// The (unique) name doesn't start with a `:`.
+    MissingColon,
+}
+
 impl From<VariantError> for Error {
     fn from(val: VariantError) -> Self {
         Error::Variant(val)
@@ -1,5 +1,6 @@
 use crate::{
-    utils::{impl_str_basic, impl_try_from},
+    macros::is_valid_error_name,
+    utils::{ensure_correct_dotted_name, impl_str_basic, impl_try_from, is_in_namespace},
     Error, Result,
 };
 use serde::{de, Deserialize, Serialize};
@@ -79,6 +80,20 @@ impl<'name> ErrorName<'name> {
         Self(Str::from_static(name))
     }
 
+    /// Same as `from_static_str`, except it's a `const fn` that panics if `name` is invalid.
+    ///
+    /// See [`InterfaceName::from_static_str_checked`](crate::InterfaceName::from_static_str_checked)
+    /// for details.
+    ///
+    /// # Panics
+    ///
+    /// If `name` is not a valid error name.
+    pub const fn from_static_str_checked(name: &'static str) -> Self {
+        assert!(is_valid_error_name(name), "invalid error name");
+
+        Self::from_static_str_unchecked(name)
+    }
+
     /// Same as `from_str_unchecked`, except it takes an owned `String`.
     ///
     /// Since the passed string is not checked for correctness, prefer using the
@@ -96,6 +111,25 @@ impl<'name> ErrorName<'name> {
     pub fn into_owned(self) -> ErrorName<'static> {
         ErrorName(self.0.into_owned())
     }
+
+    /// Whether this name is `namespace` itself or a name under it.
+    ///
+    /// Names are matched element-wise, as for the `arg0namespace` key of match rules, so
+    /// `org.freedesktop.DBus.Error.Failed` is in the `org.freedesktop.DBus.Error` namespace but not in `org.freedesktop.DBus.Err`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zbus_names::ErrorName;
+    ///
+    /// let name = ErrorName::try_from("org.freedesktop.DBus.Error.Failed").unwrap();
+    /// assert!(name.is_in_namespace("org.freedesktop.DBus.Error"));
+    /// assert!(name.is_in_namespace("org.freedesktop.DBus.Error.Failed"));
+    /// assert!(!name.is_in_namespace("org.freedesktop.DBus.Err"));
+    /// ```
+    pub fn is_in_namespace(&self, namespace: &str) -> bool {
+        is_in_namespace(self.as_str(), namespace)
+    }
 }
 
 impl Deref for ErrorName<'_> {
@@ -164,53 +198,7 @@ fn ensure_correct_error_name(name: &str) -> Result<()> {
     //   * not begin with a digit.
     //   * be 1 character (so name must be minimum 3 characters long).
     // * <= 255 characters.
-    if name.len() < 3 {
-        return Err(Error::InvalidErrorName(format!(
-            "`{}` is {} characters long, which is smaller than minimum allowed (3)",
-            name,
-            name.len(),
-        )));
-    } else if name.len() > 255 {
-        return Err(Error::InvalidErrorName(format!(
-            "`{}` is {} characters long, which is longer than maximum allowed (255)",
-            name,
-            name.len(),
-        )));
-    }
-
-    let mut prev = None;
-    let mut no_dot = true;
-    for c in name.chars() {
-        if c == '.' {
-            if prev.is_none() || prev == Some('.') {
-                return Err(Error::InvalidErrorName(String::from(
-                    "must not contain a double `.`",
-                )));
-            }
-
-            if no_dot {
-                no_dot = false;
-            }
-        } else if c.is_ascii_digit() && (prev.is_none() || prev == Some('.')) {
-            return Err(Error::InvalidErrorName(String::from(
-                "each element must not start with a digit",
-            )));
-        } else if !c.is_ascii_alphanumeric() && c != '_' {
-            return Err(Error::InvalidErrorName(format!(
-                "`{c}` character not allowed"
-            )));
-        }
-
-        prev = Some(c);
-    }
-
-    if no_dot {
-        return Err(Error::InvalidErrorName(String::from(
-            "must contain at least 1 `.`",
-        )));
-    }
-
-    Ok(())
+    ensure_correct_dotted_name(name, false).map_err(Error::InvalidErrorName)
 }
 
 /// This never succeeds but is provided so it's easier to pass `Option::None` values for API
@@ -261,6 +249,11 @@ impl OwnedErrorName {
     pub fn inner(&self) -> &ErrorName<'static> {
         &self.0
     }
+
+    /// Borrow the name as a `ErrorName`, without any allocation.
+    pub fn as_ref(&self) -> ErrorName<'_> {
+        self.0.as_ref()
+    }
 }
 
 impl Deref for OwnedErrorName {
@@ -285,7 +278,7 @@ impl From<OwnedErrorName> for ErrorName<'_> {
 
 impl<'unowned, 'owned: 'unowned> From<&'owned OwnedErrorName> for ErrorName<'unowned> {
     fn from(name: &'owned OwnedErrorName) -> Self {
-        ErrorName::from_str_unchecked(name.as_str())
+        name.as_ref()
     }
 }
 
@@ -312,6 +305,12 @@ impl<'de> Deserialize<'de> for OwnedErrorName {
     }
 }
 
+impl PartialEq<str> for OwnedErrorName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
 impl PartialEq<&str> for OwnedErrorName {
     fn eq(&self, other: &&str) -> bool {
         self.as_str() == *other
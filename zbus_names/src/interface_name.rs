@@ -1,5 +1,6 @@
 use crate::{
-    utils::{impl_str_basic, impl_try_from},
+    macros::is_valid_interface_name,
+    utils::{ensure_correct_dotted_name, impl_str_basic, impl_try_from, is_in_namespace},
     Error, Result,
 };
 use serde::{de, Deserialize, Serialize};
@@ -77,6 +78,27 @@ impl<'name> InterfaceName<'name> {
         Self(Str::from_static(name))
     }
 
+    /// Same as `from_static_str`, except it's a `const fn` that panics if `name` is invalid.
+    ///
+    /// When used in `const` and `static` items, an invalid name is therefore a compile error:
+    ///
+    /// ```
+    /// use zbus_names::InterfaceName;
+    ///
+    /// static NAME: InterfaceName<'static> =
+    ///     InterfaceName::from_static_str_checked("org.freedesktop.DBus.Properties");
+    /// assert_eq!(NAME, "org.freedesktop.DBus.Properties");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `name` is not a valid interface name.
+    pub const fn from_static_str_checked(name: &'static str) -> Self {
+        assert!(is_valid_interface_name(name), "invalid interface name");
+
+        Self::from_static_str_unchecked(name)
+    }
+
     /// Same as `from_str_unchecked`, except it takes an owned `String`.
     ///
     /// Since the passed string is not checked for correctness, prefer using the
@@ -94,6 +116,25 @@ impl<'name> InterfaceName<'name> {
     pub fn into_owned(self) -> InterfaceName<'static> {
         InterfaceName(self.0.into_owned())
     }
+
+    /// Whether this name is `namespace` itself or a name under it.
+    ///
+    /// Names are matched element-wise, as for the `arg0namespace` key of match rules, so
+    /// `org.freedesktop.DBus.Properties` is in the `org.freedesktop` namespace but not in `org.free`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zbus_names::InterfaceName;
+    ///
+    /// let name = InterfaceName::try_from("org.freedesktop.DBus.Properties").unwrap();
+    /// assert!(name.is_in_namespace("org.freedesktop"));
+    /// assert!(name.is_in_namespace("org.freedesktop.DBus.Properties"));
+    /// assert!(!name.is_in_namespace("org.free"));
+    /// ```
+    pub fn is_in_namespace(&self, namespace: &str) -> bool {
+        is_in_namespace(self.as_str(), namespace)
+    }
 }
 
 impl Deref for InterfaceName<'_> {
@@ -168,53 +209,7 @@ fn ensure_correct_interface_name(name: &str) -> Result<()> {
     //   * not begin with a digit.
     //   * be 1 character (so name must be minimum 3 characters long).
     // * <= 255 characters.
-    if name.len() < 3 {
-        return Err(Error::InvalidInterfaceName(format!(
-            "`{}` is {} characters long, which is smaller than minimum allowed (3)",
-            name,
-            name.len(),
-        )));
-    } else if name.len() > 255 {
-        return Err(Error::InvalidInterfaceName(format!(
-            "`{}` is {} characters long, which is longer than maximum allowed (255)",
-            name,
-            name.len(),
-        )));
-    }
-
-    let mut prev = None;
-    let mut no_dot = true;
-    for c in name.chars() {
-        if c == '.' {
-            if prev.is_none() || prev == Some('.') {
-                return Err(Error::InvalidInterfaceName(String::from(
-                    "must not contain a double `.`",
-                )));
-            }
-
-            if no_dot {
-                no_dot = false;
-            }
-        } else if c.is_ascii_digit() && (prev.is_none() || prev == Some('.')) {
-            return Err(Error::InvalidInterfaceName(String::from(
-                "each element must not start with a digit",
-            )));
-        } else if !c.is_ascii_alphanumeric() && c != '_' {
-            return Err(Error::InvalidInterfaceName(format!(
-                "`{c}` character not allowed"
-            )));
-        }
-
-        prev = Some(c);
-    }
-
-    if no_dot {
-        return Err(Error::InvalidInterfaceName(String::from(
-            "must contain at least 1 `.`",
-        )));
-    }
-
-    Ok(())
+    ensure_correct_dotted_name(name, false).map_err(Error::InvalidInterfaceName)
 }
 
 /// This never succeeds but is provided so it's easier to pass `Option::None` values for API
@@ -259,6 +254,11 @@ impl OwnedInterfaceName {
     pub fn inner(&self) -> &InterfaceName<'static> {
         &self.0
     }
+
+    /// Borrow the name as a `InterfaceName`, without any allocation.
+    pub fn as_ref(&self) -> InterfaceName<'_> {
+        self.0.as_ref()
+    }
 }
 
 impl Deref for OwnedInterfaceName {
@@ -283,7 +283,7 @@ impl From<OwnedInterfaceName> for InterfaceName<'_> {
 
 impl<'unowned, 'owned: 'unowned> From<&'owned OwnedInterfaceName> for InterfaceName<'unowned> {
     fn from(name: &'owned OwnedInterfaceName) -> Self {
-        InterfaceName::from_str_unchecked(name.as_str())
+        name.as_ref()
     }
 }
 
@@ -310,6 +310,12 @@ impl<'de> Deserialize<'de> for OwnedInterfaceName {
     }
 }
 
+impl PartialEq<str> for OwnedInterfaceName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
 impl PartialEq<&str> for OwnedInterfaceName {
     fn eq(&self, other: &&str) -> bool {
         self.as_str() == *other
@@ -36,3 +36,5 @@ mod error_name;
 pub use error_name::*;
 
 mod utils;
+
+mod macros;
@@ -0,0 +1,388 @@
+// Macros for names validated at compile time, and the `const` validation they rely on.
+//
+// The validation functions here implement the same rules as the `ensure_correct_*` functions of
+// each name type, except that they only tell if the name is valid. They're used by the
+// `from_static_str_checked` constructors, which the macros are built on.
+
+/// Create an [`InterfaceName`](crate::InterfaceName) from a string literal, validated at compile
+/// time.
+///
+/// Unlike `InterfaceName::try_from`, an invalid name is a compile error instead of a runtime
+/// error, so there is no `Result` to unwrap. The macro can be used in `const` and `static` items.
+/// It's a shorthand for [`InterfaceName::from_static_str_checked`] in a `const` context.
+///
+/// [`InterfaceName::from_static_str_checked`]: crate::InterfaceName::from_static_str_checked
+///
+/// # Examples
+///
+/// ```
+/// use zbus_names::{interface_name, InterfaceName};
+///
+/// const PROPERTIES: InterfaceName<'static> = interface_name!("org.freedesktop.DBus.Properties");
+/// let name = interface_name!("org.gnome.Shell");
+/// assert_eq!(name, "org.gnome.Shell");
+/// ```
+///
+/// ```compile_fail
+/// let name = zbus_names::interface_name!("no-dots");
+/// ```
+#[macro_export]
+macro_rules! interface_name {
+    ($name:expr) => {{
+        const NAME: $crate::InterfaceName<'static> =
+            $crate::InterfaceName::from_static_str_checked($name);
+        NAME
+    }};
+}
+
+/// Create an [`ErrorName`](crate::ErrorName) from a string literal, validated at compile time.
+///
+/// See [`interface_name!`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use zbus_names::error_name;
+///
+/// let name = error_name!("org.freedesktop.DBus.Error.Failed");
+/// assert_eq!(name, "org.freedesktop.DBus.Error.Failed");
+/// ```
+///
+/// ```compile_fail
+/// let name = zbus_names::error_name!("org..Failed");
+/// ```
+#[macro_export]
+macro_rules! error_name {
+    ($name:expr) => {{
+        const NAME: $crate::ErrorName<'static> = $crate::ErrorName::from_static_str_checked($name);
+        NAME
+    }};
+}
+
+/// Create a [`MemberName`](crate::MemberName) from a string literal, validated at compile time.
+///
+/// See [`interface_name!`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use zbus_names::member_name;
+///
+/// let name = member_name!("GetAll");
+/// assert_eq!(name, "GetAll");
+/// ```
+///
+/// ```compile_fail
+/// let name = zbus_names::member_name!("Get.All");
+/// ```
+#[macro_export]
+macro_rules! member_name {
+    ($name:expr) => {{
+        const NAME: $crate::MemberName<'static> =
+            $crate::MemberName::from_static_str_checked($name);
+        NAME
+    }};
+}
+
+/// Create a [`PropertyName`](crate::PropertyName) from a string literal, validated at compile
+/// time.
+///
+/// See [`interface_name!`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use zbus_names::property_name;
+///
+/// let name = property_name!("Volume");
+/// assert_eq!(name, "Volume");
+/// ```
+///
+/// ```compile_fail
+/// let name = zbus_names::property_name!("");
+/// ```
+#[macro_export]
+macro_rules! property_name {
+    ($name:expr) => {{
+        const NAME: $crate::PropertyName<'static> =
+            $crate::PropertyName::from_static_str_checked($name);
+        NAME
+    }};
+}
+
+/// Create a [`WellKnownName`](crate::WellKnownName) from a string literal, validated at compile
+/// time.
+///
+/// See [`interface_name!`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use zbus_names::well_known_name;
+///
+/// let name = well_known_name!("org.freedesktop.NetworkManager");
+/// assert_eq!(name, "org.freedesktop.NetworkManager");
+/// ```
+///
+/// ```compile_fail
+/// let name = zbus_names::well_known_name!(":1.42");
+/// ```
+#[macro_export]
+macro_rules! well_known_name {
+    ($name:expr) => {{
+        const NAME: $crate::WellKnownName<'static> =
+            $crate::WellKnownName::from_static_str_checked($name);
+        NAME
+    }};
+}
+
+/// Create a [`UniqueName`](crate::UniqueName) from a string literal, validated at compile time.
+///
+/// See [`interface_name!`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use zbus_names::unique_name;
+///
+/// let name = unique_name!(":1.42");
+/// assert_eq!(name, ":1.42");
+/// ```
+///
+/// ```compile_fail
+/// let name = zbus_names::unique_name!("org.freedesktop.NetworkManager");
+/// ```
+#[macro_export]
+macro_rules! unique_name {
+    ($name:expr) => {{
+        const NAME: $crate::UniqueName<'static> =
+            $crate::UniqueName::from_static_str_checked($name);
+        NAME
+    }};
+}
+
+/// Create a [`BusName`](crate::BusName) from a string literal, validated at compile time.
+///
+/// Just like `BusName::try_from`, the name is a unique name if it's a valid one and a well-known
+/// name otherwise. See [`interface_name!`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use zbus_names::{bus_name, BusName};
+///
+/// let name = bus_name!("org.freedesktop.NetworkManager");
+/// assert!(matches!(name, BusName::WellKnown(_)));
+/// let name = bus_name!(":1.42");
+/// assert!(matches!(name, BusName::Unique(_)));
+/// ```
+///
+/// ```compile_fail
+/// let name = zbus_names::bus_name!("no-dots");
+/// ```
+#[macro_export]
+macro_rules! bus_name {
+    ($name:expr) => {{
+        const NAME: $crate::BusName<'static> = $crate::BusName::from_static_str_checked($name);
+        NAME
+    }};
+}
+
+pub(crate) const fn is_valid_interface_name(name: &str) -> bool {
+    is_valid_dotted_name(name.as_bytes(), false)
+}
+
+pub(crate) const fn is_valid_error_name(name: &str) -> bool {
+    is_valid_dotted_name(name.as_bytes(), false)
+}
+
+pub(crate) const fn is_valid_well_known_name(name: &str) -> bool {
+    is_valid_dotted_name(name.as_bytes(), true)
+}
+
+pub(crate) const fn is_valid_unique_name(name: &str) -> bool {
+    let name = name.as_bytes();
+    if name.is_empty() || name.len() > 255 {
+        return false;
+    } else if bytes_eq(name, b"org.freedesktop.DBus") {
+        // Bus itself uses its well-known name as its unique name.
+        return true;
+    } else if name[0] != b':' {
+        return false;
+    }
+
+    let mut no_dot = true;
+    let mut i = 1;
+    while i < name.len() {
+        let c = name[i];
+        if c == b'.' {
+            if name[i - 1] == b'.' {
+                return false;
+            }
+            no_dot = false;
+        } else if !c.is_ascii_alphanumeric() && c != b'_' && c != b'-' {
+            return false;
+        }
+        i += 1;
+    }
+
+    !no_dot
+}
+
+pub(crate) const fn is_valid_member_name(name: &str) -> bool {
+    let name = name.as_bytes();
+    if name.is_empty() || name.len() > 255 || name[0].is_ascii_digit() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < name.len() {
+        if !name[i].is_ascii_alphanumeric() && name[i] != b'_' {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+pub(crate) const fn is_valid_property_name(name: &str) -> bool {
+    !name.is_empty() && name.len() <= 255
+}
+
+// Interface, error and well-known names: dot-separated elements, not starting with a digit.
+const fn is_valid_dotted_name(name: &[u8], allow_dash: bool) -> bool {
+    if name.len() < 3 || name.len() > 255 {
+        return false;
+    }
+
+    let mut no_dot = true;
+    let mut i = 0;
+    while i < name.len() {
+        let c = name[i];
+        let element_start = i == 0 || name[i - 1] == b'.';
+        let valid = match c {
+            b'.' | b'0'..=b'9' => !element_start,
+            b'_' => true,
+            b'-' => allow_dash,
+            c => c.is_ascii_alphabetic(),
+        };
+        if !valid {
+            return false;
+        }
+        no_dot &= c != b'.';
+        i += 1;
+    }
+
+    !no_dot
+}
+
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BusName, ErrorName, InterfaceName, MemberName, PropertyName, UniqueName, WellKnownName,
+    };
+
+    // The `const` validation must agree with the runtime one.
+    #[test]
+    fn const_validation() {
+        let names = [
+            "",
+            ".",
+            ":",
+            "a",
+            "a.",
+            ".a",
+            "a.b",
+            "a..b",
+            "org.freedesktop.DBus",
+            "org.gnome.Service-for_you",
+            "org.gnome.Interface_for_you",
+            "1st.element.starts.with.digit",
+            "the.2nd.element.starts.with.digit",
+            "no-dots",
+            "no_dots",
+            ":1.42",
+            ":1..42",
+            ":no-dots",
+            ":.start.with.dot",
+            "GetAll",
+            "Get.All",
+            "1GetAll",
+            "Volume-Level",
+            "ünicode.name",
+            &"a.b".repeat(100),
+        ];
+        for name in names {
+            assert_eq!(
+                is_valid_interface_name(name),
+                InterfaceName::try_from(name).is_ok(),
+                "{name}"
+            );
+            assert_eq!(
+                is_valid_error_name(name),
+                ErrorName::try_from(name).is_ok(),
+                "{name}"
+            );
+            assert_eq!(
+                is_valid_well_known_name(name),
+                WellKnownName::try_from(name).is_ok(),
+                "{name}"
+            );
+            assert_eq!(
+                is_valid_unique_name(name),
+                UniqueName::try_from(name).is_ok(),
+                "{name}"
+            );
+            assert_eq!(
+                is_valid_member_name(name),
+                MemberName::try_from(name).is_ok(),
+                "{name}"
+            );
+            assert_eq!(
+                is_valid_property_name(name),
+                PropertyName::try_from(name).is_ok(),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn macros() {
+        const PATH: InterfaceName<'static> = interface_name!("org.freedesktop.DBus.Properties");
+        assert_eq!(PATH, "org.freedesktop.DBus.Properties");
+        static NAME: BusName<'static> = bus_name!("org.freedesktop.DBus");
+        assert_eq!(NAME, BusName::try_from("org.freedesktop.DBus").unwrap());
+
+        assert_eq!(bus_name!("org.gnome.Shell"), "org.gnome.Shell");
+        assert!(matches!(bus_name!(":1.42"), BusName::Unique(_)));
+        assert_eq!(unique_name!(":1.42"), ":1.42");
+        assert_eq!(well_known_name!("org.gnome.Shell"), "org.gnome.Shell");
+        assert_eq!(member_name!("GetAll"), "GetAll");
+        assert_eq!(property_name!("Volume"), "Volume");
+        assert_eq!(error_name!("org.gnome.Error"), "org.gnome.Error");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid interface name")]
+    fn checked_constructor_panics() {
+        InterfaceName::from_static_str_checked("no-dots");
+    }
+}
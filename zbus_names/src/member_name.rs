@@ -1,6 +1,7 @@
 use crate::{
+    macros::is_valid_member_name,
     utils::{impl_str_basic, impl_try_from},
-    Error, Result,
+    Error, InvalidName, InvalidNameReason, Result,
 };
 use serde::{de, Deserialize, Serialize};
 use static_assertions::assert_impl_all;
@@ -75,6 +76,20 @@ impl<'name> MemberName<'name> {
         Self(Str::from_static(name))
     }
 
+    /// Same as `from_static_str`, except it's a `const fn` that panics if `name` is invalid.
+    ///
+    /// See [`InterfaceName::from_static_str_checked`](crate::InterfaceName::from_static_str_checked)
+    /// for details.
+    ///
+    /// # Panics
+    ///
+    /// If `name` is not a valid member name.
+    pub const fn from_static_str_checked(name: &'static str) -> Self {
+        assert!(is_valid_member_name(name), "invalid member name");
+
+        Self::from_static_str_unchecked(name)
+    }
+
     /// Same as `from_str_unchecked`, except it takes an owned `String`.
     ///
     /// Since the passed string is not checked for correctness, prefer using the
@@ -163,32 +178,19 @@ fn ensure_correct_member_name(name: &str) -> Result<()> {
     // * Must not begin with a digit.
     // * Must contain at least 1 character.
     // * <= 255 characters.
+    let invalid =
+        |position, reason| Err(Error::InvalidMemberName(InvalidName::new(position, reason)));
     if name.is_empty() {
-        return Err(Error::InvalidMemberName(format!(
-            "`{}` is {} characters long, which is smaller than minimum allowed (1)",
-            name,
-            name.len(),
-        )));
+        return invalid(0, InvalidNameReason::TooShort(1));
     } else if name.len() > 255 {
-        return Err(Error::InvalidMemberName(format!(
-            "`{}` is {} characters long, which is longer than maximum allowed (255)",
-            name,
-            name.len(),
-        )));
-    }
-
-    // SAFETY: We established above that there is at least 1 character so unwrap is fine.
-    if name.chars().next().unwrap().is_ascii_digit() {
-        return Err(Error::InvalidMemberName(String::from(
-            "must not start with a digit",
-        )));
+        return invalid(255, InvalidNameReason::TooLong);
     }
 
-    for c in name.chars() {
-        if !c.is_ascii_alphanumeric() && c != '_' {
-            return Err(Error::InvalidMemberName(format!(
-                "`{c}` character not allowed"
-            )));
+    for (i, c) in name.char_indices() {
+        if i == 0 && c.is_ascii_digit() {
+            return invalid(0, InvalidNameReason::LeadingDigit);
+        } else if !c.is_ascii_alphanumeric() && c != '_' {
+            return invalid(i, InvalidNameReason::InvalidCharacter(c));
         }
     }
 
@@ -237,6 +239,11 @@ impl OwnedMemberName {
     pub fn inner(&self) -> &MemberName<'static> {
         &self.0
     }
+
+    /// Borrow the name as a `MemberName`, without any allocation.
+    pub fn as_ref(&self) -> MemberName<'_> {
+        self.0.as_ref()
+    }
 }
 
 impl Deref for OwnedMemberName {
@@ -261,7 +268,7 @@ impl From<OwnedMemberName> for MemberName<'_> {
 
 impl<'unowned, 'owned: 'unowned> From<&'owned OwnedMemberName> for MemberName<'unowned> {
     fn from(name: &'owned OwnedMemberName) -> Self {
-        MemberName::from_str_unchecked(name.as_str())
+        name.as_ref()
     }
 }
 
@@ -288,6 +295,12 @@ impl<'de> Deserialize<'de> for OwnedMemberName {
     }
 }
 
+impl PartialEq<str> for OwnedMemberName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
 impl PartialEq<&str> for OwnedMemberName {
     fn eq(&self, other: &&str) -> bool {
         self.as_str() == *other
@@ -1,6 +1,7 @@
 use crate::{
+    macros::is_valid_property_name,
     utils::{impl_str_basic, impl_try_from},
-    Error, Result,
+    Error, InvalidName, InvalidNameReason, Result,
 };
 use serde::{de, Deserialize, Serialize};
 use static_assertions::assert_impl_all;
@@ -70,6 +71,20 @@ impl<'name> PropertyName<'name> {
         Self(Str::from_static(name))
     }
 
+    /// Same as `from_static_str`, except it's a `const fn` that panics if `name` is invalid.
+    ///
+    /// See [`InterfaceName::from_static_str_checked`](crate::InterfaceName::from_static_str_checked)
+    /// for details.
+    ///
+    /// # Panics
+    ///
+    /// If `name` is not a valid property name.
+    pub const fn from_static_str_checked(name: &'static str) -> Self {
+        assert!(is_valid_property_name(name), "invalid property name");
+
+        Self::from_static_str_unchecked(name)
+    }
+
     /// Same as `from_str_unchecked`, except it takes an owned `String`.
     ///
     /// Since the passed string is not checked for correctness, prefer using the
@@ -159,16 +174,14 @@ fn ensure_correct_property_name(name: &str) -> Result<()> {
     // * Must contain at least 1 character.
     // * <= 255 characters.
     if name.is_empty() {
-        return Err(Error::InvalidPropertyName(format!(
-            "`{}` is {} characters long, which is smaller than minimum allowed (1)",
-            name,
-            name.len(),
+        return Err(Error::InvalidPropertyName(InvalidName::new(
+            0,
+            InvalidNameReason::TooShort(1),
         )));
     } else if name.len() > 255 {
-        return Err(Error::InvalidPropertyName(format!(
-            "`{}` is {} characters long, which is longer than maximum allowed (255)",
-            name,
-            name.len(),
+        return Err(Error::InvalidPropertyName(InvalidName::new(
+            255,
+            InvalidNameReason::TooLong,
         )));
     }
 
@@ -217,6 +230,11 @@ impl OwnedPropertyName {
     pub fn inner(&self) -> &PropertyName<'static> {
         &self.0
     }
+
+    /// Borrow the name as a `PropertyName`, without any allocation.
+    pub fn as_ref(&self) -> PropertyName<'_> {
+        self.0.as_ref()
+    }
 }
 
 impl Deref for OwnedPropertyName {
@@ -241,7 +259,7 @@ impl From<OwnedPropertyName> for PropertyName<'_> {
 
 impl<'unowned, 'owned: 'unowned> From<&'owned OwnedPropertyName> for PropertyName<'unowned> {
     fn from(name: &'owned OwnedPropertyName) -> Self {
-        PropertyName::from_str_unchecked(name.as_str())
+        name.as_ref()
     }
 }
 
@@ -268,6 +286,12 @@ impl<'de> Deserialize<'de> for OwnedPropertyName {
     }
 }
 
+impl PartialEq<str> for OwnedPropertyName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
 impl PartialEq<&str> for OwnedPropertyName {
     fn eq(&self, other: &&str) -> bool {
         self.as_str() == *other
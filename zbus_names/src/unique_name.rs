@@ -1,6 +1,7 @@
 use crate::{
+    macros::is_valid_unique_name,
     utils::{impl_str_basic, impl_try_from},
-    Error, Result,
+    Error, InvalidName, InvalidNameReason, Result,
 };
 use serde::{de, Deserialize, Serialize};
 use static_assertions::assert_impl_all;
@@ -74,6 +75,20 @@ impl<'name> UniqueName<'name> {
         Self(Str::from_static(name))
     }
 
+    /// Same as `from_static_str`, except it's a `const fn` that panics if `name` is invalid.
+    ///
+    /// See [`InterfaceName::from_static_str_checked`](crate::InterfaceName::from_static_str_checked)
+    /// for details.
+    ///
+    /// # Panics
+    ///
+    /// If `name` is not a valid unique name.
+    pub const fn from_static_str_checked(name: &'static str) -> Self {
+        assert!(is_valid_unique_name(name), "invalid unique name");
+
+        Self::from_static_str_unchecked(name)
+    }
+
     /// Same as `from_str_unchecked`, except it takes an owned `String`.
     ///
     /// Since the passed string is not checked for correctness, prefer using the
@@ -149,57 +164,37 @@ fn ensure_correct_unique_name(name: &str) -> Result<()> {
     // * Must begin with a `:`.
     // * Must contain at least one `.`.
     // * <= 255 characters.
+    let invalid =
+        |position, reason| Err(Error::InvalidUniqueName(InvalidName::new(position, reason)));
     if name.is_empty() {
-        return Err(Error::InvalidUniqueName(String::from(
-            "must contain at least 4 characters",
-        )));
+        return invalid(0, InvalidNameReason::TooShort(4));
     } else if name.len() > 255 {
-        return Err(Error::InvalidUniqueName(format!(
-            "`{}` is {} characters long, which is longer than maximum allowed (255)",
-            name,
-            name.len(),
-        )));
+        return invalid(255, InvalidNameReason::TooLong);
     } else if name == "org.freedesktop.DBus" {
         // Bus itself uses its well-known name as its unique name.
         return Ok(());
+    } else if !name.starts_with(':') {
+        return invalid(0, InvalidNameReason::MissingColon);
     }
 
-    // SAFETY: Just checked above that we've at least 1 character.
-    let mut chars = name.chars();
-    let mut prev = match chars.next().expect("no first char") {
-        first @ ':' => first,
-        _ => {
-            return Err(Error::InvalidUniqueName(String::from(
-                "must start with a `:`",
-            )));
-        }
-    };
-
+    let mut prev = ':';
     let mut no_dot = true;
-    for c in chars {
+    for (i, c) in name.char_indices().skip(1) {
         if c == '.' {
             if prev == '.' {
-                return Err(Error::InvalidUniqueName(String::from(
-                    "must not contain a double `.`",
-                )));
+                return invalid(i, InvalidNameReason::EmptyElement);
             }
 
-            if no_dot {
-                no_dot = false;
-            }
+            no_dot = false;
         } else if !c.is_ascii_alphanumeric() && c != '_' && c != '-' {
-            return Err(Error::InvalidUniqueName(format!(
-                "`{c}` character not allowed"
-            )));
+            return invalid(i, InvalidNameReason::InvalidCharacter(c));
         }
 
         prev = c;
     }
 
     if no_dot {
-        return Err(Error::InvalidUniqueName(String::from(
-            "must contain at least 1 `.`",
-        )));
+        return invalid(name.len(), InvalidNameReason::MissingDot);
     }
 
     Ok(())
@@ -253,6 +248,11 @@ impl OwnedUniqueName {
     pub fn inner(&self) -> &UniqueName<'static> {
         &self.0
     }
+
+    /// Borrow the name as a `UniqueName`, without any allocation.
+    pub fn as_ref(&self) -> UniqueName<'_> {
+        self.0.as_ref()
+    }
 }
 
 impl Deref for OwnedUniqueName {
@@ -277,7 +277,7 @@ impl From<OwnedUniqueName> for UniqueName<'_> {
 
 impl<'unowned, 'owned: 'unowned> From<&'owned OwnedUniqueName> for UniqueName<'unowned> {
     fn from(name: &'owned OwnedUniqueName) -> Self {
-        UniqueName::from_str_unchecked(name.as_str())
+        name.as_ref()
     }
 }
 
@@ -311,6 +311,12 @@ impl<'de> Deserialize<'de> for OwnedUniqueName {
     }
 }
 
+impl PartialEq<str> for OwnedUniqueName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
 impl PartialEq<&str> for OwnedUniqueName {
     fn eq(&self, other: &&str) -> bool {
         self.as_str() == *other
@@ -1,3 +1,5 @@
+use crate::{InvalidName, InvalidNameReason};
+
 macro_rules! impl_str_basic {
     ($type:ty) => {
         impl zvariant::Basic for $type {
@@ -37,3 +39,133 @@ macro_rules! impl_try_from {
 
 pub(crate) use impl_str_basic;
 pub(crate) use impl_try_from;
+
+// Validate an interface, error or well-known name: dot-separated elements of ASCII alphanumeric or
+// `_` characters (and `-` if `allow_dash` is set), each of which must not begin with a digit.
+pub(crate) fn ensure_correct_dotted_name(
+    name: &str,
+    allow_dash: bool,
+) -> std::result::Result<(), InvalidName> {
+    if name.len() < 3 {
+        return Err(InvalidName::new(name.len(), InvalidNameReason::TooShort(3)));
+    } else if name.len() > 255 {
+        return Err(InvalidName::new(255, InvalidNameReason::TooLong));
+    }
+
+    let mut prev = None;
+    let mut no_dot = true;
+    for (i, c) in name.char_indices() {
+        let element_start = prev.is_none() || prev == Some('.');
+        let reason = if c == '.' {
+            no_dot = false;
+
+            element_start.then_some(InvalidNameReason::EmptyElement)
+        } else if c.is_ascii_digit() && element_start {
+            Some(InvalidNameReason::LeadingDigit)
+        } else if !c.is_ascii_alphanumeric() && c != '_' && !(allow_dash && c == '-') {
+            Some(InvalidNameReason::InvalidCharacter(c))
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return Err(InvalidName::new(i, reason));
+        }
+
+        prev = Some(c);
+    }
+
+    if no_dot {
+        return Err(InvalidName::new(name.len(), InvalidNameReason::MissingDot));
+    }
+
+    Ok(())
+}
+
+// Whether `name` is `namespace` itself or one of the names under it, i-e `namespace` followed by a
+// `.` and more elements. This is the `arg0namespace` semantics of match rules.
+pub(crate) fn is_in_namespace(name: &str, namespace: &str) -> bool {
+    match name.strip_prefix(namespace) {
+        Some(rest) => rest.is_empty() || (!namespace.is_empty() && rest.starts_with('.')),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_in_namespace;
+    use crate::{
+        BusName, Error, InterfaceName, InvalidName, InvalidNameReason, MemberName,
+        OwnedInterfaceName, UniqueName, WellKnownName,
+    };
+
+    #[test]
+    fn namespace() {
+        assert!(is_in_namespace("org.gnome.Shell", "org.gnome"));
+        assert!(is_in_namespace("org.gnome.Shell", "org.gnome.Shell"));
+        assert!(!is_in_namespace("org.gnome.Shell", "org.gno"));
+        assert!(!is_in_namespace("org.gnome", "org.gnome.Shell"));
+        assert!(!is_in_namespace("org.gnome.Shell", ""));
+        assert!(is_in_namespace(":1.42", ":1"));
+    }
+
+    #[test]
+    fn validation_errors() {
+        let details = |e| match e {
+            Error::InvalidInterfaceName(e)
+            | Error::InvalidWellKnownName(e)
+            | Error::InvalidUniqueName(e)
+            | Error::InvalidMemberName(e) => (e.position(), e.reason()),
+            e => panic!("unexpected error: {e}"),
+        };
+
+        let e = InterfaceName::try_from("a").unwrap_err();
+        assert_eq!(details(e), (1, InvalidNameReason::TooShort(3)));
+        let e = InterfaceName::try_from("a".repeat(256)).unwrap_err();
+        assert_eq!(details(e), (255, InvalidNameReason::TooLong));
+        let e = InterfaceName::try_from("org..gnome").unwrap_err();
+        assert_eq!(details(e), (4, InvalidNameReason::EmptyElement));
+        let e = InterfaceName::try_from("org.gnome.3rd").unwrap_err();
+        assert_eq!(details(e), (10, InvalidNameReason::LeadingDigit));
+        let e = InterfaceName::try_from("org.gno-me").unwrap_err();
+        assert_eq!(details(e), (7, InvalidNameReason::InvalidCharacter('-')));
+        let e = WellKnownName::try_from("no-dots").unwrap_err();
+        assert_eq!(details(e), (7, InvalidNameReason::MissingDot));
+        let e = UniqueName::try_from("1.42").unwrap_err();
+        assert_eq!(details(e), (0, InvalidNameReason::MissingColon));
+        let e = UniqueName::try_from(":1.4!2").unwrap_err();
+        assert_eq!(details(e), (4, InvalidNameReason::InvalidCharacter('!')));
+        let e = MemberName::try_from("Get_Ällo").unwrap_err();
+        assert_eq!(details(e), (4, InvalidNameReason::InvalidCharacter('Ä')));
+
+        match BusName::try_from("1.42").unwrap_err() {
+            Error::InvalidBusName(unique, well_known) => {
+                assert_eq!(unique, InvalidName::new(0, InvalidNameReason::MissingColon));
+                assert_eq!(
+                    well_known,
+                    InvalidName::new(0, InvalidNameReason::LeadingDigit)
+                );
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn owned_borrowed_conversions() {
+        const NAME: &str = "org.freedesktop.DBus.Properties";
+
+        // Static names stay static through the conversions.
+        let owned = OwnedInterfaceName::from(InterfaceName::from_static_str(NAME).unwrap());
+        assert_eq!(owned.as_str().as_ptr(), NAME.as_ptr());
+        let name = InterfaceName::from(&owned).into_owned();
+        assert_eq!(name.as_str().as_ptr(), NAME.as_ptr());
+
+        // Owned names share their storage.
+        let owned = OwnedInterfaceName::try_from(NAME.to_string()).unwrap();
+        let ptr = owned.as_str().as_ptr();
+        assert_eq!(owned.as_ref().as_str().as_ptr(), ptr);
+        let name = InterfaceName::from(owned.clone());
+        assert_eq!(OwnedInterfaceName::from(name).as_str().as_ptr(), ptr);
+        assert_eq!(owned, *NAME);
+        assert_eq!(owned.as_ref(), owned);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
-    utils::{impl_str_basic, impl_try_from},
+    macros::is_valid_well_known_name,
+    utils::{ensure_correct_dotted_name, impl_str_basic, impl_try_from, is_in_namespace},
     Error, Result,
 };
 use serde::{de, Deserialize, Serialize};
@@ -75,6 +76,20 @@ impl<'name> WellKnownName<'name> {
         Self(Str::from_static(name))
     }
 
+    /// Same as `from_static_str`, except it's a `const fn` that panics if `name` is invalid.
+    ///
+    /// See [`InterfaceName::from_static_str_checked`](crate::InterfaceName::from_static_str_checked)
+    /// for details.
+    ///
+    /// # Panics
+    ///
+    /// If `name` is not a valid well-known name.
+    pub const fn from_static_str_checked(name: &'static str) -> Self {
+        assert!(is_valid_well_known_name(name), "invalid well-known name");
+
+        Self::from_static_str_unchecked(name)
+    }
+
     /// Same as `from_str_unchecked`, except it takes an owned `String`.
     ///
     /// Since the passed string is not checked for correctness, prefer using the
@@ -92,6 +107,25 @@ impl<'name> WellKnownName<'name> {
     pub fn into_owned(self) -> WellKnownName<'static> {
         WellKnownName(self.0.into_owned())
     }
+
+    /// Whether this name is `namespace` itself or a name under it.
+    ///
+    /// Names are matched element-wise, as for the `arg0namespace` key of match rules, so
+    /// `org.gnome.Shell` is in the `org.gnome` namespace but not in `org.gno`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zbus_names::WellKnownName;
+    ///
+    /// let name = WellKnownName::try_from("org.gnome.Shell").unwrap();
+    /// assert!(name.is_in_namespace("org.gnome"));
+    /// assert!(name.is_in_namespace("org.gnome.Shell"));
+    /// assert!(!name.is_in_namespace("org.gno"));
+    /// ```
+    pub fn is_in_namespace(&self, namespace: &str) -> bool {
+        is_in_namespace(self.as_str(), namespace)
+    }
 }
 
 impl Deref for WellKnownName<'_> {
@@ -153,57 +187,7 @@ fn ensure_correct_well_known_name(name: &str) -> Result<()> {
     //  * not begin with a digit.
     //  * be 1 character (so name must be minimum 3 characters long).
     // * <= 255 characters.
-    if name.is_empty() {
-        return Err(Error::InvalidWellKnownName(String::from(
-            "must contain at least 3 characters",
-        )));
-    } else if name.len() < 3 {
-        return Err(Error::InvalidWellKnownName(format!(
-            "`{}` is {} characters long, which is smaller than minimum allowed (3)",
-            name,
-            name.len(),
-        )));
-    } else if name.len() > 255 {
-        return Err(Error::InvalidWellKnownName(format!(
-            "`{}` is {} characters long, which is longer than maximum allowed (255)",
-            name,
-            name.len(),
-        )));
-    }
-
-    let mut prev = None;
-    let mut no_dot = true;
-    for c in name.chars() {
-        if c == '.' {
-            if prev.is_none() || prev == Some('.') {
-                return Err(Error::InvalidWellKnownName(String::from(
-                    "must not contain a double `.`",
-                )));
-            }
-
-            if no_dot {
-                no_dot = false;
-            }
-        } else if c.is_ascii_digit() && (prev.is_none() || prev == Some('.')) {
-            return Err(Error::InvalidWellKnownName(String::from(
-                "each element must not start with a digit",
-            )));
-        } else if !c.is_ascii_alphanumeric() && c != '_' && c != '-' {
-            return Err(Error::InvalidWellKnownName(format!(
-                "`{c}` character not allowed"
-            )));
-        }
-
-        prev = Some(c);
-    }
-
-    if no_dot {
-        return Err(Error::InvalidWellKnownName(String::from(
-            "must contain at least 1 `.`",
-        )));
-    }
-
-    Ok(())
+    ensure_correct_dotted_name(name, true).map_err(Error::InvalidWellKnownName)
 }
 
 /// This never succeeds but is provided so it's easier to pass `Option::None` values for API
@@ -252,6 +236,11 @@ impl OwnedWellKnownName {
     pub fn inner(&self) -> &WellKnownName<'static> {
         &self.0
     }
+
+    /// Borrow the name as a `WellKnownName`, without any allocation.
+    pub fn as_ref(&self) -> WellKnownName<'_> {
+        self.0.as_ref()
+    }
 }
 
 impl_str_basic!(OwnedWellKnownName);
@@ -298,7 +287,7 @@ impl From<OwnedWellKnownName> for WellKnownName<'_> {
 
 impl<'unowned, 'owned: 'unowned> From<&'owned OwnedWellKnownName> for WellKnownName<'unowned> {
     fn from(name: &'owned OwnedWellKnownName) -> Self {
-        WellKnownName::from_str_unchecked(name.as_str())
+        name.as_ref()
     }
 }
 
@@ -332,6 +321,12 @@ impl<'de> Deserialize<'de> for OwnedWellKnownName {
     }
 }
 
+impl PartialEq<str> for OwnedWellKnownName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
 impl PartialEq<&str> for OwnedWellKnownName {
     fn eq(&self, other: &&str) -> bool {
         self.as_str() == *other
@@ -45,6 +45,56 @@ impl Annotation {
     }
 }
 
+/// Documentation, from a `<doc:doc>` element.
+///
+/// These elements belong to the `http://www.freedesktop.org/dbus/1.0/doc.dtd` namespace, used by
+/// many services (and generated by some tools) to document their interfaces and members:
+///
+/// ```xml
+/// <doc:doc>
+///   <doc:summary>Short description</doc:summary>
+///   <doc:description>
+///     <doc:para>Longer description.</doc:para>
+///   </doc:description>
+/// </doc:doc>
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Doc {
+    #[serde(
+        rename(serialize = "doc:summary", deserialize = "summary"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    summary: Option<String>,
+    #[serde(
+        rename(serialize = "doc:description", deserialize = "description"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    description: Option<Description>,
+}
+
+assert_impl_all!(Doc: Send, Sync, Unpin);
+
+impl Doc {
+    /// Return the summary, if any.
+    pub fn summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    /// Return the paragraphs of the description.
+    pub fn paragraphs(&self) -> &[String] {
+        self.description
+            .as_ref()
+            .map(|d| d.paragraphs.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+struct Description {
+    #[serde(rename(serialize = "doc:para", deserialize = "para"), default)]
+    paragraphs: Vec<String>,
+}
+
 /// A direction of an argument
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum ArgDirection {
@@ -100,6 +150,11 @@ pub struct Method<'a> {
     args: Vec<Arg<'a>>,
     #[serde(rename = "annotation", default)]
     annotations: Vec<Annotation>,
+    #[serde(
+        rename(serialize = "doc:doc", deserialize = "doc"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    doc: Option<Doc>,
 }
 
 assert_impl_all!(Method<'_>: Send, Sync, Unpin);
@@ -119,6 +174,11 @@ impl<'a> Method<'a> {
     pub fn annotations(&self) -> &[Annotation] {
         &self.annotations
     }
+
+    /// Return the documentation, if any.
+    pub fn doc(&self) -> Option<&Doc> {
+        self.doc.as_ref()
+    }
 }
 
 /// A signal
@@ -131,6 +191,11 @@ pub struct Signal<'a> {
     args: Vec<Arg<'a>>,
     #[serde(rename = "annotation", default)]
     annotations: Vec<Annotation>,
+    #[serde(
+        rename(serialize = "doc:doc", deserialize = "doc"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    doc: Option<Doc>,
 }
 
 assert_impl_all!(Signal<'_>: Send, Sync, Unpin);
@@ -150,6 +215,11 @@ impl<'a> Signal<'a> {
     pub fn annotations(&self) -> &[Annotation] {
         &self.annotations
     }
+
+    /// Return the documentation, if any.
+    pub fn doc(&self) -> Option<&Doc> {
+        self.doc.as_ref()
+    }
 }
 
 /// The possible property access types
@@ -186,6 +256,11 @@ pub struct Property<'a> {
 
     #[serde(rename = "annotation", default)]
     annotations: Vec<Annotation>,
+    #[serde(
+        rename(serialize = "doc:doc", deserialize = "doc"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    doc: Option<Doc>,
 }
 
 assert_impl_all!(Property<'_>: Send, Sync, Unpin);
@@ -210,6 +285,11 @@ impl<'a> Property<'a> {
     pub fn annotations(&self) -> &[Annotation] {
         &self.annotations
     }
+
+    /// Return the documentation, if any.
+    pub fn doc(&self) -> Option<&Doc> {
+        self.doc.as_ref()
+    }
 }
 
 /// An interface
@@ -226,6 +306,11 @@ pub struct Interface<'a> {
     signals: Vec<Signal<'a>>,
     #[serde(rename = "annotation", default)]
     annotations: Vec<Annotation>,
+    #[serde(
+        rename(serialize = "doc:doc", deserialize = "doc"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    doc: Option<Doc>,
 }
 
 assert_impl_all!(Interface<'_>: Send, Sync, Unpin);
@@ -255,6 +340,11 @@ impl<'a> Interface<'a> {
     pub fn annotations(&self) -> &[Annotation] {
         &self.annotations
     }
+
+    /// Return the documentation, if any.
+    pub fn doc(&self) -> Option<&Doc> {
+        self.doc.as_ref()
+    }
 }
 
 /// An introspection tree node (typically the root of the XML document).
@@ -18,6 +18,13 @@ fn serde() -> Result<(), Box<dyn Error>> {
         ArgDirection::In
     );
     assert_eq!(node.nodes().len(), 4);
+    let doc = node.interfaces()[0].methods()[0].doc().unwrap();
+    assert_eq!(doc.summary(), Some("Frobate the foo"));
+    assert_eq!(
+        doc.paragraphs(),
+        ["Returns the bar and the baz.", "Don't use it."]
+    );
+    assert!(node.interfaces()[0].methods()[1].doc().is_none());
 
     let node_str: Node<'_> = example.try_into()?;
     assert_eq!(node_str.interfaces().len(), 1);
@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use zbus_xmlgen::ZbusVersion;
+
+#[derive(Parser, Debug)]
+#[command(name = "cargo", bin_name = "cargo")]
+pub enum Cargo {
+    ZbusGen(Args),
+}
+
+/// Generate zbus proxies into a Cargo project.
+///
+/// The generated modules are written to the output directory, along with a `mod.rs` declaring
+/// them and a manifest recording their sources, so that `cargo zbus-gen update` can regenerate
+/// them later on.
+#[derive(clap::Args, Debug)]
+#[command(author, version, about, long_about)]
+pub struct Args {
+    #[clap(subcommand)]
+    pub command: Command,
+
+    /// The directory to write the generated modules to, relative to the project root.
+    #[clap(long, default_value = "src/generated", global = true)]
+    pub out_dir: PathBuf,
+
+    /// Path to the `Cargo.toml` of the project. By default, it's searched for in the current
+    /// directory and its parents.
+    #[clap(long, global = true)]
+    pub manifest_path: Option<PathBuf>,
+
+    /// Regenerate even if the sources didn't change and overwrite manually edited modules.
+    #[clap(long, global = true)]
+    pub force: bool,
+
+    /// The major version of zbus the generated code should target.
+    #[clap(long, default_value_t = ZbusVersion::V4, global = true)]
+    pub zbus_version: ZbusVersion,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub enum Command {
+    /// Generate code for interfaces in the specified files.
+    #[clap()]
+    File {
+        #[clap(required = true)]
+        paths: Vec<PathBuf>,
+    },
+
+    /// Generate code for interfaces from the specified system service.
+    #[clap()]
+    System {
+        service: String,
+        object_path: String,
+    },
+
+    /// Generate code for interfaces from the current users session.
+    #[clap()]
+    Session {
+        service: String,
+        object_path: String,
+    },
+
+    /// Generate code for interfaces from the specified address.
+    #[clap()]
+    Address {
+        address: String,
+        service: String,
+        object_path: String,
+    },
+
+    /// Regenerate code for all the sources recorded in the manifest, if they changed.
+    #[clap()]
+    Update,
+}
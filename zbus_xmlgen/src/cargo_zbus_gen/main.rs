@@ -0,0 +1,350 @@
+#![deny(rust_2018_idioms)]
+
+use std::{
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use snakecase::ascii::to_snakecase;
+use zbus::{
+    blocking::{connection, fdo::IntrospectableProxy, Connection},
+    names::BusName,
+};
+use zbus_xml::Node;
+
+use zbus_xmlgen::{write_interfaces, GenOptions, ZbusVersion};
+use zvariant::ObjectPath;
+
+mod cli;
+mod manifest;
+
+use manifest::{hash, Manifest, ModuleEntry, Source, SourceEntry};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli::Cargo::ZbusGen(args) = cli::Cargo::parse();
+
+    let root = match args.manifest_path {
+        Some(path) => path
+            .parent()
+            .map(Path::to_path_buf)
+            .ok_or("invalid manifest path")?,
+        None => project_root()?,
+    };
+    let out_dir = root.join(&args.out_dir);
+    fs::create_dir_all(&out_dir)?;
+    let mut manifest = Manifest::load(&out_dir)?;
+
+    let sources = match args.command {
+        cli::Command::File { paths } => paths
+            .iter()
+            .map(|p| relative_to_root(&root, p).map(Source::File))
+            .collect::<Result<_, _>>()?,
+        cli::Command::System {
+            service,
+            object_path,
+        } => vec![Source::System {
+            service,
+            object_path,
+        }],
+        cli::Command::Session {
+            service,
+            object_path,
+        } => vec![Source::Session {
+            service,
+            object_path,
+        }],
+        cli::Command::Address {
+            address,
+            service,
+            object_path,
+        } => vec![Source::Address {
+            address,
+            service,
+            object_path,
+        }],
+        cli::Command::Update => manifest.sources.iter().map(|e| e.source.clone()).collect(),
+    };
+
+    for source in sources {
+        generate(
+            &root,
+            &out_dir,
+            &mut manifest,
+            source,
+            args.force,
+            args.zbus_version,
+        )?;
+    }
+
+    manifest.save(&out_dir)?;
+    write_mod_rs(&out_dir, &manifest)?;
+    check_wired(&root, &args.out_dir);
+
+    Ok(())
+}
+
+/// Generate the modules for `source`, unless they are up to date.
+fn generate(
+    root: &Path,
+    out_dir: &Path,
+    manifest: &mut Manifest,
+    source: Source,
+    force: bool,
+    version: ZbusVersion,
+) -> Result<(), Box<dyn Error>> {
+    let (xml, service, path, input_src) = introspection_data(root, &source)?;
+    let source_hash = hash(&[
+        env!("CARGO_PKG_VERSION").as_bytes(),
+        version.to_string().as_bytes(),
+        source.to_string().as_bytes(),
+        xml.as_bytes(),
+    ]);
+
+    let previous = manifest.source(&source);
+    if !force {
+        if let Some(previous) = previous {
+            let unchanged = previous.modules.iter().all(|m| is_unmodified(out_dir, m));
+            if previous.hash == source_hash && unchanged {
+                println!("`{source}` is up to date");
+                return Ok(());
+            }
+        }
+    }
+    let previous_modules: Vec<String> = previous
+        .map(|e| e.modules.iter().map(|m| m.name.clone()).collect())
+        .unwrap_or_default();
+
+    let options = GenOptions {
+        service,
+        path,
+        version,
+        ..Default::default()
+    };
+    let node = Node::from_reader(xml.as_bytes())?;
+    let (fdo_standard_ifaces, needed_ifaces) = options.filter.split(node.interfaces());
+
+    let mut modules = Vec::with_capacity(needed_ifaces.len());
+    for interface in needed_ifaces {
+        let output = write_interfaces(
+            std::slice::from_ref(&interface),
+            &fdo_standard_ifaces,
+            &input_src,
+            "cargo-zbus-gen",
+            env!("CARGO_PKG_VERSION"),
+            &options,
+        )?;
+
+        let interface_name = interface.name();
+        let name = interface_name
+            .split('.')
+            .next_back()
+            .expect("Failed to split name");
+        let name = to_snakecase(name);
+        if modules.iter().any(|m: &ModuleEntry| m.name == name)
+            || owned_by_other(manifest, &source, &name)
+        {
+            return Err(format!(
+                "module `{name}` for `{interface_name}` (from `{source}`) clashes with another generated module",
+            )
+            .into());
+        }
+        let file = module_path(out_dir, &name);
+        if !force && !is_unmodified_file(out_dir, &name, manifest) {
+            return Err(format!(
+                "`{}` was not generated by `cargo zbus-gen` or was modified since, use `--force` \
+                 to overwrite it",
+                file.display(),
+            )
+            .into());
+        }
+
+        fs::write(&file, &output)?;
+        println!(
+            "Generated code for `{}` in {}",
+            interface_name,
+            file.display()
+        );
+        modules.push(ModuleEntry {
+            hash: hash(&[output.as_bytes()]),
+            name: name.into_owned(),
+        });
+    }
+
+    // Remove the modules of interfaces that are gone, unless they were edited.
+    for name in previous_modules {
+        if modules.iter().any(|m| m.name == name) {
+            continue;
+        }
+
+        let file = module_path(out_dir, &name);
+        if is_unmodified_file(out_dir, &name, manifest) {
+            if file.exists() {
+                fs::remove_file(&file)?;
+            }
+            println!("Removed {}", file.display());
+        } else {
+            eprintln!(
+                "`{}` is no longer generated but was modified, leaving it in place",
+                file.display(),
+            );
+        }
+    }
+
+    manifest.insert(SourceEntry {
+        source,
+        hash: source_hash,
+        modules,
+    });
+
+    Ok(())
+}
+
+/// Get the XML for `source`, along with the default service & path and a description of it.
+#[allow(clippy::type_complexity)]
+fn introspection_data(
+    root: &Path,
+    source: &Source,
+) -> Result<
+    (
+        String,
+        Option<BusName<'static>>,
+        Option<ObjectPath<'static>>,
+        String,
+    ),
+    Box<dyn Error>,
+> {
+    let (connection, service, object_path, bus) = match source {
+        Source::File(path) => {
+            let xml = fs::read_to_string(root.join(path))?;
+            let input_src = path.to_string_lossy().to_string();
+
+            return Ok((xml, None, None, input_src));
+        }
+        Source::System {
+            service,
+            object_path,
+        } => (Connection::system()?, service, object_path, "system bus"),
+        Source::Session {
+            service,
+            object_path,
+        } => (Connection::session()?, service, object_path, "session bus"),
+        Source::Address {
+            address,
+            service,
+            object_path,
+        } => (
+            connection::Builder::address(&**address)?.build()?,
+            service,
+            object_path,
+            address.as_str(),
+        ),
+    };
+    let service: BusName<'static> = service.clone().try_into()?;
+    let path: ObjectPath<'static> = object_path.clone().try_into()?;
+
+    let input_src = format!("Interface '{}' from service '{}' on {}", path, service, bus);
+
+    let xml = IntrospectableProxy::builder(&connection)
+        .destination(service.clone())
+        .expect("invalid destination")
+        .path(path.clone())
+        .expect("invalid path")
+        .build()
+        .unwrap()
+        .introspect()?;
+
+    Ok((xml, Some(service), Some(path), input_src))
+}
+
+/// Write a `mod.rs` declaring all the generated modules.
+fn write_mod_rs(out_dir: &Path, manifest: &Manifest) -> Result<(), Box<dyn Error>> {
+    let mut mod_rs = String::from(
+        "//! D-Bus interface proxies generated by `cargo zbus-gen`.\n\
+         //!\n\
+         //! This file is regenerated on every run, do not edit.\n\n",
+    );
+    for module in manifest.modules() {
+        mod_rs.push_str(&format!("pub mod {};\n", module.name));
+    }
+
+    fs::write(out_dir.join("mod.rs"), mod_rs)?;
+
+    Ok(())
+}
+
+/// Remind the user to declare the generated module, if it's not.
+fn check_wired(root: &Path, out_dir: &Path) {
+    let (Some(parent), Some(name)) = (out_dir.parent(), out_dir.file_name()) else {
+        return;
+    };
+    if parent != Path::new("src") {
+        return;
+    }
+
+    let name = name.to_string_lossy();
+    let declaration = format!("mod {name};");
+    let wired = ["src/lib.rs", "src/main.rs"].iter().any(|f| {
+        fs::read_to_string(root.join(f))
+            .map(|s| s.contains(&declaration))
+            .unwrap_or(false)
+    });
+    if !wired {
+        eprintln!("Add `{declaration}` to your crate root to use the generated code");
+    }
+}
+
+/// Find the root of the Cargo project we're invoked in.
+fn project_root() -> Result<PathBuf, Box<dyn Error>> {
+    let cwd = env::current_dir()?;
+    cwd.ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "could not find `Cargo.toml` in the current directory or its parents".into())
+}
+
+/// Make `path` relative to the project root, so the manifest doesn't depend on where it's located.
+fn relative_to_root(root: &Path, path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    let path = path.canonicalize()?;
+    let root = root.canonicalize()?;
+
+    Ok(path
+        .strip_prefix(&root)
+        .map(Path::to_path_buf)
+        .unwrap_or(path))
+}
+
+fn module_path(out_dir: &Path, name: &str) -> PathBuf {
+    out_dir.join(format!("{name}.rs"))
+}
+
+fn is_unmodified(out_dir: &Path, module: &ModuleEntry) -> bool {
+    fs::read(module_path(out_dir, &module.name))
+        .map(|content| hash(&[content.as_slice()]) == module.hash)
+        .unwrap_or(false)
+}
+
+/// Whether module `name` is unmodified (or gone) since it was last generated.
+fn is_unmodified_file(out_dir: &Path, name: &str, manifest: &Manifest) -> bool {
+    if !module_path(out_dir, name).exists() {
+        return true;
+    }
+
+    manifest
+        .sources
+        .iter()
+        .flat_map(|s| &s.modules)
+        .filter(|m| m.name == name)
+        .any(|m| is_unmodified(out_dir, m))
+}
+
+/// Whether module `name` is generated from another source than `source`.
+fn owned_by_other(manifest: &Manifest, source: &Source, name: &str) -> bool {
+    manifest
+        .sources
+        .iter()
+        .filter(|e| e.source != *source)
+        .any(|e| e.modules.iter().any(|m| m.name == name))
+}
@@ -0,0 +1,214 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The name of the manifest file, inside the output directory.
+pub const MANIFEST_FILE: &str = "zbus-gen.manifest";
+
+/// Record of the generated modules, used for incremental regeneration.
+///
+/// The manifest is a line-based text file. Each `source` line records where the introspection
+/// data came from and the hash of it, and is followed by one `module` line per generated module,
+/// with the hash of the generated code. The latter allows to detect (and not overwrite) manual
+/// edits of the generated files.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    pub sources: Vec<SourceEntry>,
+}
+
+#[derive(Debug)]
+pub struct SourceEntry {
+    pub source: Source,
+    pub hash: u64,
+    pub modules: Vec<ModuleEntry>,
+}
+
+#[derive(Debug)]
+pub struct ModuleEntry {
+    pub name: String,
+    pub hash: u64,
+}
+
+/// Where the introspection data comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    System {
+        service: String,
+        object_path: String,
+    },
+    Session {
+        service: String,
+        object_path: String,
+    },
+    Address {
+        address: String,
+        service: String,
+        object_path: String,
+    },
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "file {}", path.display()),
+            Source::System {
+                service,
+                object_path,
+            } => write!(f, "system {service} {object_path}"),
+            Source::Session {
+                service,
+                object_path,
+            } => write!(f, "session {service} {object_path}"),
+            Source::Address {
+                address,
+                service,
+                object_path,
+            } => write!(f, "address {address} {service} {object_path}"),
+        }
+    }
+}
+
+impl Source {
+    fn parse(s: &str) -> Option<Self> {
+        let (kind, rest) = s.split_once(' ')?;
+        let source = match kind {
+            // The path comes last so it can contain spaces.
+            "file" => Source::File(PathBuf::from(rest)),
+            "system" | "session" => {
+                let (service, object_path) = rest.split_once(' ')?;
+                let (service, object_path) = (service.to_string(), object_path.to_string());
+                if kind == "system" {
+                    Source::System {
+                        service,
+                        object_path,
+                    }
+                } else {
+                    Source::Session {
+                        service,
+                        object_path,
+                    }
+                }
+            }
+            "address" => {
+                let mut parts = rest.splitn(3, ' ');
+                Source::Address {
+                    address: parts.next()?.to_string(),
+                    service: parts.next()?.to_string(),
+                    object_path: parts.next()?.to_string(),
+                }
+            }
+            _ => return None,
+        };
+
+        Some(source)
+    }
+}
+
+impl Manifest {
+    /// Load the manifest from `dir`, or return an empty one if there is none.
+    pub fn load(dir: &Path) -> Result<Self, Box<dyn Error>> {
+        let path = dir.join(MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let mut manifest = Self::default();
+        for (i, line) in fs::read_to_string(&path)?.lines().enumerate() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || format!("{}:{}: invalid manifest entry", path.display(), i + 1);
+            let mut parts = line.splitn(3, ' ');
+            let (kind, hash, rest) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(kind), Some(hash), Some(rest)) => (kind, hash, rest),
+                _ => return Err(invalid().into()),
+            };
+            let hash = u64::from_str_radix(hash, 16).map_err(|_| invalid())?;
+            match kind {
+                "source" => manifest.sources.push(SourceEntry {
+                    source: Source::parse(rest).ok_or_else(invalid)?,
+                    hash,
+                    modules: vec![],
+                }),
+                "module" => manifest
+                    .sources
+                    .last_mut()
+                    .ok_or_else(invalid)?
+                    .modules
+                    .push(ModuleEntry {
+                        name: rest.to_string(),
+                        hash,
+                    }),
+                _ => return Err(invalid().into()),
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// Write the manifest to `dir`.
+    pub fn save(&self, dir: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(dir.join(MANIFEST_FILE), self.to_string())?;
+
+        Ok(())
+    }
+
+    pub fn source(&self, source: &Source) -> Option<&SourceEntry> {
+        self.sources.iter().find(|e| e.source == *source)
+    }
+
+    /// Add the entry, replacing any existing one for the same source.
+    pub fn insert(&mut self, entry: SourceEntry) {
+        match self.sources.iter_mut().find(|e| e.source == entry.source) {
+            Some(existing) => *existing = entry,
+            None => self.sources.push(entry),
+        }
+    }
+
+    /// All the modules, in a stable order.
+    pub fn modules(&self) -> Vec<&ModuleEntry> {
+        let mut modules: Vec<_> = self.sources.iter().flat_map(|s| &s.modules).collect();
+        modules.sort_by(|a, b| a.name.cmp(&b.name));
+
+        modules
+    }
+}
+
+impl Display for Manifest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Generated by `cargo zbus-gen`, do not edit.")?;
+        for entry in &self.sources {
+            writeln!(f, "source {:016x} {}", entry.hash, entry.source)?;
+            for module in &entry.modules {
+                writeln!(f, "module {:016x} {}", module.hash, module.name)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A 64-bit FNV-1a hash.
+///
+/// Unlike `std`'s `DefaultHasher`, the result is guaranteed to be stable across Rust releases,
+/// which we need since the hashes are stored on disk.
+pub fn hash(data: &[&[u8]]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for bytes in data {
+        for b in *bytes {
+            hash ^= u64::from(*b);
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        // Separate the chunks so that e.g `["ab", "c"]` and `["a", "bc"]` differ.
+        hash ^= 0xff;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash
+}
@@ -1,25 +1,271 @@
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{error::ErrorKind, ArgAction, CommandFactory, Parser, ValueEnum};
+use zbus_xmlgen::{CodeKind, FdPolicy, InterfaceFilter, ProxyKind, ZbusVersion};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
     #[clap(subcommand)]
-    pub command: Command,
+    pub command: Option<Command>,
+
+    /// Generate code for interfaces in the given file (`-` for stdin) or at the given `http://` or
+    /// `https://` URL. Shorthand for the `file` command.
+    #[clap(value_name = "FILE_OR_URL")]
+    pub input: Option<String>,
+
+    /// Generate code for interfaces of the given service and object path on the session bus.
+    /// Shorthand for the `session` command.
+    #[clap(long, num_args = 2, value_names = ["SERVICE", "OBJECT_PATH"], conflicts_with = "system")]
+    pub session: Option<Vec<String>>,
+
+    /// Generate code for interfaces of the given service and object path on the system bus.
+    /// Shorthand for the `system` command.
+    #[clap(long, num_args = 2, value_names = ["SERVICE", "OBJECT_PATH"])]
+    pub system: Option<Vec<String>>,
 
     /// Specify the destination for saving the output. If no argument is provided, the parsed
     /// interfaces will be stored in separate files. If a filename is provided, the output will
-    /// be saved to that file. Use '-' to print the output to stdout.
+    /// be saved to that file. Use '-' to print the output to stdout. For the `dir` command, this is
+    /// the directory to write the modules to.
     #[clap(short, long, allow_hyphen_values = true, global = true)]
     pub output: Option<String>,
+
+    /// Write one module per interface to the given directory, named after the interface (e.g.
+    /// `org_freedesktop_foo.rs`), along with a `mod.rs` declaring them and re-exporting their
+    /// types. For the `dir` command and `--recursive`, this is the same as `--output`.
+    #[clap(long, global = true, value_name = "DIR", conflicts_with = "output")]
+    pub output_dir: Option<PathBuf>,
+
+    /// The major version of zbus the generated code should target.
+    #[clap(long, default_value_t = ZbusVersion::V4, global = true)]
+    pub zbus_version: ZbusVersion,
+
+    /// The output format: Rust code, or a JSON description of the interfaces (including the Rust
+    /// identifiers and types of their members) for use by other tools. JSON is printed to stdout,
+    /// unless `--output` is given.
+    #[clap(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = Format::Rust,
+        conflicts_with_all = ["output_dir", "recursive"]
+    )]
+    pub format: Format,
+
+    /// Skip the standard `org.freedesktop.DBus.*` interfaces, which zbus provides already.
+    #[clap(
+        long,
+        global = true,
+        value_name = "BOOL",
+        default_value_t = true,
+        action = ArgAction::Set
+    )]
+    pub skip_standard: bool,
+
+    /// Only generate code for the given interface. Can be given multiple times, and end with `*`
+    /// to match all the interfaces starting with the rest, e.g `org.freedesktop.portal.*`.
+    #[clap(long = "interface", global = true, value_name = "NAME")]
+    pub interfaces: Vec<String>,
+
+    /// Don't generate code for the given interface, in the same format as `--interface`.
+    #[clap(long = "exclude-interface", global = true, value_name = "NAME")]
+    pub excluded_interfaces: Vec<String>,
+
+    /// Generate service-side skeletons (a struct and its `interface` implementation, with the
+    /// bodies left to be written) instead of client proxies.
+    #[clap(long, global = true)]
+    pub server: bool,
+
+    /// Generate service-side mocks for testing clients instead of client proxies: structs
+    /// replying to method calls and property reads with canned values, and recording the calls
+    /// they receive. Serve them on a connection like any other interface.
+    #[clap(long, global = true, conflicts_with = "server")]
+    pub mock: bool,
+
+    /// The service to use by default in the generated proxies, instead of the introspected one
+    /// (if any).
+    #[clap(
+        long,
+        global = true,
+        value_name = "SERVICE",
+        conflicts_with = "recursive"
+    )]
+    pub default_service: Option<String>,
+
+    /// The object path to use by default in the generated proxies, instead of the introspected
+    /// one (if any).
+    #[clap(
+        long,
+        global = true,
+        value_name = "OBJECT_PATH",
+        conflicts_with = "recursive"
+    )]
+    pub default_path: Option<String>,
+
+    /// Follow the child nodes of the object, generating a module tree with one module per object
+    /// path. The output (see `--output`) is the directory to write the modules to, defaulting to
+    /// the current directory.
+    #[clap(long, global = true)]
+    pub recursive: bool,
+
+    /// Only generate the blocking proxies. By default, both the asynchronous and the blocking
+    /// proxies are generated.
+    #[clap(long, global = true, conflicts_with_all = ["async_only", "server", "mock"])]
+    pub blocking: bool,
+
+    /// Only generate the asynchronous proxies.
+    #[clap(long = "async", global = true, conflicts_with_all = ["server", "mock"])]
+    pub async_only: bool,
+
+    /// A TOML (or JSON, with a `.json` extension) file mapping signatures (in a `signatures`
+    /// table) and arguments or properties (in a `members` table, keyed `interface.Member.arg` and
+    /// `interface.Property`) to the Rust types to use for them.
+    #[clap(long, global = true, value_name = "PATH")]
+    pub type_map: Option<PathBuf>,
+
+    /// Generate a named struct for each distinct struct signature, instead of using tuples.
+    #[clap(long, global = true)]
+    pub named_structs: bool,
+
+    /// Take owned types (e.g `String`, `Vec<T>` or `OwnedValue`) as the inputs of proxy methods
+    /// and property setters, instead of borrowed ones (e.g `&str`, `&[T]` or `&Value`).
+    #[clap(long, global = true)]
+    pub owned_inputs: bool,
+
+    /// The error type returned by proxy methods and properties (e.g `my_crate::Error`), instead
+    /// of `zbus::Error`. It must implement `From<zbus::Error>`.
+    #[clap(long, global = true, value_name = "TYPE")]
+    pub error_type: Option<String>,
+
+    /// Additional derives for the generated structs and enums, e.g `Clone,PartialEq`.
+    #[clap(long, global = true, value_name = "TRAITS", value_delimiter = ',')]
+    pub derive: Vec<String>,
+
+    /// An attribute to add to the generated traits, structs and enums, e.g
+    /// `#[allow(clippy::type_complexity)]`. Can be given multiple times.
+    #[clap(long = "item-attr", global = true, value_name = "ATTRIBUTE")]
+    pub item_attributes: Vec<String>,
+
+    /// How proxies handle the members with file descriptors, which don't exist on Windows: fail
+    /// (`error`), leave them out (`skip-method`) or only declare them on Unix (`cfg-unix`). They
+    /// are kept as is by default.
+    #[clap(long, global = true, value_name = "POLICY")]
+    pub fd_policy: Option<FdPolicy>,
+
+    /// Also generate a test module checking that the types of the arguments and properties have
+    /// the signatures given in the XML. The generated code then needs a `zbus_xml` dev-dependency.
+    #[clap(long, global = true, conflicts_with = "recursive")]
+    pub with_tests: bool,
+}
+
+impl Args {
+    /// Take the command to run, from the subcommand or the input, `--session` and `--system`
+    /// shorthands.
+    ///
+    /// Exits with a usage error if there is none or more than one.
+    pub fn take_command(&mut self) -> Command {
+        let shorthands = (self.input.take(), self.session.take(), self.system.take());
+        match (self.command.take(), shorthands) {
+            (Some(command), (None, None, None)) => command,
+            (None, (Some(path), None, None)) => Command::File { path },
+            (None, (None, Some(destination), None)) => {
+                let [service, object_path] = destination_args(destination);
+                Command::Session {
+                    service,
+                    object_path,
+                }
+            }
+            (None, (None, None, Some(destination))) => {
+                let [service, object_path] = destination_args(destination);
+                Command::System {
+                    service,
+                    object_path,
+                }
+            }
+            (None, (None, None, None)) => Self::command()
+                .error(
+                    ErrorKind::MissingSubcommand,
+                    "a command, an input file, `--session` or `--system` is required",
+                )
+                .exit(),
+            _ => Self::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    "only one of a command, an input file, `--session` or `--system` can be given",
+                )
+                .exit(),
+        }
+    }
+
+    /// The kind of code to generate.
+    pub fn code_kind(&self) -> CodeKind {
+        if self.server {
+            CodeKind::Skeleton
+        } else if self.mock {
+            CodeKind::Mock
+        } else {
+            CodeKind::Proxy
+        }
+    }
+
+    /// The interfaces to generate code for.
+    pub fn interface_filter(&self) -> InterfaceFilter {
+        InterfaceFilter {
+            skip_standard: self.skip_standard,
+            include: self.interfaces.clone(),
+            exclude: self.excluded_interfaces.clone(),
+        }
+    }
+
+    /// The proxy types to generate.
+    pub fn proxy_kind(&self) -> ProxyKind {
+        if self.blocking {
+            ProxyKind::Blocking
+        } else if self.async_only {
+            ProxyKind::Async
+        } else {
+            ProxyKind::Both
+        }
+    }
+}
+
+/// The output format.
+#[derive(ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Rust,
+    Json,
 }
 
 #[derive(Parser, Debug, Clone)]
 pub enum Command {
-    /// Generate code for interfaces in the specified file.
+    /// Generate code for interfaces in the specified file, `-` for stdin, or at the specified
+    /// `http://` or `https://` URL.
+    #[clap()]
+    File {
+        #[clap(value_name = "FILE_OR_URL")]
+        path: String,
+    },
+
+    /// Generate a module tree for the interface XML files in the specified directory.
+    ///
+    /// One module is generated per file, along with a root module declaring them and re-exporting
+    /// their proxies. The output (see `--output`) is the directory to write the modules to,
+    /// defaulting to the current directory.
+    #[clap()]
+    Dir {
+        path: PathBuf,
+
+        /// Name the root module `lib.rs` instead of `mod.rs`, for use as a crate root.
+        #[clap(long)]
+        lib: bool,
+    },
+
+    /// Report the changes between two versions of the interfaces in the specified files, flagging
+    /// those breaking client code. Exits with an error if there are any.
     #[clap()]
-    File { path: PathBuf },
+    Diff { old: PathBuf, new: PathBuf },
 
     /// Generate code for interfaces from the specified system service.
     #[clap()]
@@ -43,3 +289,8 @@ pub enum Command {
         object_path: String,
     },
 }
+
+fn destination_args(args: Vec<String>) -> [String; 2] {
+    args.try_into()
+        .expect("clap should have ensured exactly 2 values")
+}
@@ -0,0 +1,183 @@
+use std::fmt::{Display, Formatter};
+
+use zbus_xml::{Arg, ArgDirection, Interface};
+
+/// A change between two versions of a D-Bus API, see [`diff_interfaces`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiChange {
+    /// What changed, e.g "method `org.example.Foo.Bar` removed".
+    pub description: String,
+    /// Whether client code generated from the old version is broken by the change.
+    pub breaking: bool,
+}
+
+impl ApiChange {
+    fn new(breaking: bool, description: String) -> Self {
+        Self {
+            description,
+            breaking,
+        }
+    }
+}
+
+impl Display for ApiChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.breaking {
+            write!(f, "{} (breaking)", self.description)
+        } else {
+            write!(f, "{}", self.description)
+        }
+    }
+}
+
+/// The changes from the `old` to the `new` version of the interfaces.
+///
+/// Removed members and changed signatures are breaking, as are properties that can't be read or
+/// written anymore. Additions and renamed arguments aren't, as the generated code passes arguments
+/// by position.
+pub fn diff_interfaces(old: &[Interface<'_>], new: &[Interface<'_>]) -> Vec<ApiChange> {
+    let mut changes = vec![];
+    for old_iface in old {
+        match new.iter().find(|i| i.name() == old_iface.name()) {
+            Some(new_iface) => diff_interface(old_iface, new_iface, &mut changes),
+            None => changes.push(ApiChange::new(
+                true,
+                format!("interface `{}` removed", old_iface.name()),
+            )),
+        }
+    }
+    for new_iface in new {
+        if !old.iter().any(|i| i.name() == new_iface.name()) {
+            changes.push(ApiChange::new(
+                false,
+                format!("interface `{}` added", new_iface.name()),
+            ));
+        }
+    }
+
+    changes
+}
+
+fn diff_interface(old: &Interface<'_>, new: &Interface<'_>, changes: &mut Vec<ApiChange>) {
+    let iface = old.name().to_string();
+
+    let old_methods: Vec<_> = old.methods().iter().map(|m| (m.name(), m.args())).collect();
+    let new_methods: Vec<_> = new.methods().iter().map(|m| (m.name(), m.args())).collect();
+    diff_members("method", &iface, &old_methods, &new_methods, changes);
+    let old_signals: Vec<_> = old.signals().iter().map(|s| (s.name(), s.args())).collect();
+    let new_signals: Vec<_> = new.signals().iter().map(|s| (s.name(), s.args())).collect();
+    diff_members("signal", &iface, &old_signals, &new_signals, changes);
+
+    for old_prop in old.properties() {
+        let name = old_prop.name();
+        let Some(new_prop) = new.properties().iter().find(|p| p.name() == name) else {
+            changes.push(ApiChange::new(
+                true,
+                format!("property `{iface}.{name}` removed"),
+            ));
+            continue;
+        };
+
+        let (old_ty, new_ty) = (old_prop.ty().signature(), new_prop.ty().signature());
+        if old_ty != new_ty {
+            changes.push(ApiChange::new(
+                true,
+                format!("property `{iface}.{name}` changed from `{old_ty}` to `{new_ty}`"),
+            ));
+        }
+        let (old_access, new_access) = (old_prop.access(), new_prop.access());
+        if old_access != new_access {
+            let breaking = (old_access.read() && !new_access.read())
+                || (old_access.write() && !new_access.write());
+            changes.push(ApiChange::new(
+                breaking,
+                format!(
+                    "property `{iface}.{name}` access changed from {} to {}",
+                    access_name(old_access.read(), old_access.write()),
+                    access_name(new_access.read(), new_access.write()),
+                ),
+            ));
+        }
+    }
+    for new_prop in new.properties() {
+        let name = new_prop.name();
+        if !old.properties().iter().any(|p| p.name() == name) {
+            changes.push(ApiChange::new(
+                false,
+                format!("property `{iface}.{name}` added"),
+            ));
+        }
+    }
+}
+
+/// Diff the methods or signals (`kind`) of `iface`, given by name along with their arguments.
+fn diff_members<N>(
+    kind: &str,
+    iface: &str,
+    old: &[(N, &[Arg<'_>])],
+    new: &[(N, &[Arg<'_>])],
+    changes: &mut Vec<ApiChange>,
+) where
+    N: PartialEq + Display,
+{
+    for (name, old_args) in old {
+        let Some((_, new_args)) = new.iter().find(|(n, _)| n == name) else {
+            changes.push(ApiChange::new(
+                true,
+                format!("{kind} `{iface}.{name}` removed"),
+            ));
+            continue;
+        };
+
+        let method = kind == "method";
+        let (old_sig, new_sig) = (signature(old_args, method), signature(new_args, method));
+        if old_sig != new_sig {
+            changes.push(ApiChange::new(
+                true,
+                format!("{kind} `{iface}.{name}` changed from `{old_sig}` to `{new_sig}`"),
+            ));
+        } else if names(old_args) != names(new_args) {
+            changes.push(ApiChange::new(
+                false,
+                format!("{kind} `{iface}.{name}` arguments renamed"),
+            ));
+        }
+    }
+    for (name, _) in new {
+        if !old.iter().any(|(n, _)| n == name) {
+            changes.push(ApiChange::new(
+                false,
+                format!("{kind} `{iface}.{name}` added"),
+            ));
+        }
+    }
+}
+
+/// The signature of a member with `args`, as `inputs -> outputs` for methods.
+fn signature(args: &[Arg<'_>], method: bool) -> String {
+    let (inputs, outputs): (Vec<_>, Vec<_>) = args
+        .iter()
+        .partition(|a| !method || a.direction() != Some(ArgDirection::Out));
+    let inputs: String = inputs.iter().map(|a| a.ty().signature().as_str()).collect();
+    if outputs.is_empty() {
+        return inputs;
+    }
+    let outputs: String = outputs
+        .iter()
+        .map(|a| a.ty().signature().as_str())
+        .collect();
+
+    format!("{inputs} -> {outputs}")
+}
+
+fn names<'a>(args: &'a [Arg<'_>]) -> Vec<Option<&'a str>> {
+    args.iter().map(|a| a.name()).collect()
+}
+
+fn access_name(read: bool, write: bool) -> &'static str {
+    match (read, write) {
+        (true, true) => "readwrite",
+        (true, false) => "read",
+        _ => "write",
+    }
+}
@@ -1,239 +1,2309 @@
+use serde::Deserialize;
+use serde_json::json;
 use snakecase::ascii::to_snakecase;
 use std::{
+    collections::{HashMap, HashSet},
     error::Error,
     fmt::{Display, Formatter, Write},
+    path::Path,
     process::{Command, Stdio},
+    str::FromStr,
 };
 
 use zbus::names::BusName;
-use zbus_xml::{Arg, ArgDirection, Interface};
-use zvariant::{
-    Basic, CompleteType, ObjectPath, Signature, ARRAY_SIGNATURE_CHAR, DICT_ENTRY_SIG_END_CHAR,
-    DICT_ENTRY_SIG_START_CHAR, STRUCT_SIG_END_CHAR, STRUCT_SIG_START_CHAR, VARIANT_SIGNATURE_CHAR,
-};
+use zbus_xml::{Annotation, Arg, ArgDirection, Doc, Interface, Node, Property};
+use zvariant::{Basic, CompleteType, ObjectPath, Signature, SignatureTree};
+
+mod diff;
+pub use diff::{diff_interfaces, ApiChange};
+
+/// The zbus API version the generated code targets.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ZbusVersion {
+    /// zbus 3, where proxies are declared through the `dbus_proxy` attribute.
+    V3,
+    /// zbus 4, where proxies are declared through the `proxy` and `zbus` attributes.
+    #[default]
+    V4,
+}
+
+impl ZbusVersion {
+    /// The name of the attribute macro declaring the proxy trait.
+    fn proxy_macro(self) -> &'static str {
+        match self {
+            ZbusVersion::V3 => "dbus_proxy",
+            ZbusVersion::V4 => "proxy",
+        }
+    }
+
+    /// The name of the attribute on the proxy trait members.
+    fn member_attribute(self) -> &'static str {
+        match self {
+            ZbusVersion::V3 => "dbus_proxy",
+            ZbusVersion::V4 => "zbus",
+        }
+    }
+
+    /// The name of the attribute macro declaring a service-side interface.
+    fn interface_macro(self) -> &'static str {
+        match self {
+            ZbusVersion::V3 => "dbus_interface",
+            ZbusVersion::V4 => "interface",
+        }
+    }
+
+    /// The name of the attribute on the members of a service-side interface.
+    fn interface_member_attribute(self) -> &'static str {
+        match self {
+            ZbusVersion::V3 => "dbus_interface",
+            ZbusVersion::V4 => "zbus",
+        }
+    }
+
+    /// The path of the signal context type.
+    fn signal_context_path(self) -> &'static str {
+        match self {
+            ZbusVersion::V3 => "zbus::SignalContext",
+            ZbusVersion::V4 => "zbus::object_server::SignalContext",
+        }
+    }
+}
+
+/// How proxies handle the members with file descriptor (`h`) arguments or properties, which don't
+/// exist on Windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FdPolicy {
+    /// Fail to generate the code.
+    Error,
+    /// Leave the members out.
+    SkipMethod,
+    /// Only declare the methods and properties on Unix, with `#[cfg(unix)]`. Signals are left
+    /// out, as their argument types are generated without the attribute.
+    CfgUnix,
+}
+
+/// The proxy types the generated code declares.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyKind {
+    /// Only the asynchronous proxy.
+    Async,
+    /// Only the blocking proxy, which then takes the `Proxy` suffix.
+    Blocking,
+    /// Both the asynchronous and the blocking proxies.
+    #[default]
+    Both,
+}
+
+impl ProxyKind {
+    /// The proxy types declared for the `name` trait.
+    fn proxy_types(self, name: &str) -> Vec<String> {
+        match self {
+            ProxyKind::Async | ProxyKind::Blocking => vec![format!("{name}Proxy")],
+            ProxyKind::Both => vec![format!("{name}Proxy"), format!("{name}ProxyBlocking")],
+        }
+    }
+
+    /// The names of the proxy types declared for the `name` trait.
+    fn proxy_names(self, name: &str) -> String {
+        self.proxy_types(name).join(", ")
+    }
+}
+
+/// The code generated for the interfaces.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CodeKind {
+    /// Client proxies, see [`GenTrait`].
+    #[default]
+    Proxy,
+    /// Service-side skeletons, see [`GenImpl`].
+    Skeleton,
+    /// Service-side mocks, for testing clients, see [`GenImpl`].
+    Mock,
+}
+
+impl CodeKind {
+    /// The singular and plural names of the generated items.
+    fn names(self) -> (&'static str, &'static str) {
+        match self {
+            CodeKind::Proxy => ("proxy", "proxies"),
+            CodeKind::Skeleton => ("skeleton", "skeletons"),
+            CodeKind::Mock => ("mock", "mocks"),
+        }
+    }
+}
+
+/// User-provided Rust types to use in place of the ones derived from D-Bus signatures.
+///
+/// It's typically loaded from a TOML (or JSON) file, e.g:
+///
+/// ```toml
+/// # Types for all the arguments and properties of the given signature.
+/// [signatures]
+/// "a{sv}" = "crate::VariantDict"
+///
+/// # Types for specific arguments (`interface.Member.arg`) and properties (`interface.Property`).
+/// [members]
+/// "org.foo.Bar.Metadata" = "crate::MetadataDict"
+/// "org.foo.Bar.SetMetadata.metadata" = "crate::MetadataDict"
+/// ```
+///
+/// Member entries take precedence over signature entries. Signature entries only apply to whole
+/// argument and property types, except for structs, which are also mapped where nested in other
+/// types.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TypeMap {
+    /// Types to use for the given signatures.
+    pub signatures: HashMap<String, String>,
+    /// Types to use for the given arguments and properties.
+    pub members: HashMap<String, String>,
+    /// Generate a named struct for each distinct struct signature (not already mapped), instead
+    /// of using tuples.
+    ///
+    /// The fields are named after the `org.zbus.StructFields` annotation (a comma-separated list
+    /// of names) of the argument or property, if any, and `field_N` otherwise.
+    pub named_structs: bool,
+}
+
+impl TypeMap {
+    /// Load the type map from a file, in JSON if its extension is `json` and TOML otherwise.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let contents = std::fs::read_to_string(path)?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Ok(serde_json::from_str(&contents)?)
+        } else {
+            Ok(toml::from_str(&contents)?)
+        }
+    }
+
+    /// The type to use for the argument or property identified by `key`, of type `ty`.
+    fn lookup(&self, key: Option<&str>, ty: &CompleteType<'_>) -> Option<&str> {
+        key.and_then(|key| self.members.get(key))
+            .or_else(|| self.signatures.get(ty.signature().as_str()))
+            .map(String::as_str)
+    }
+}
+
+impl FromStr for ZbusVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "3" => Ok(ZbusVersion::V3),
+            "4" => Ok(ZbusVersion::V4),
+            _ => Err(format!(
+                "unsupported zbus version `{s}`, expected `3` or `4`"
+            )),
+        }
+    }
+}
+
+impl Display for ZbusVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ZbusVersion::V3 => write!(f, "3"),
+            ZbusVersion::V4 => write!(f, "4"),
+        }
+    }
+}
+
+impl FromStr for FdPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(FdPolicy::Error),
+            "skip-method" => Ok(FdPolicy::SkipMethod),
+            "cfg-unix" => Ok(FdPolicy::CfgUnix),
+            _ => Err(format!(
+                "unsupported fd policy `{s}`, expected `error`, `skip-method` or `cfg-unix`"
+            )),
+        }
+    }
+}
+
+impl Display for FdPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FdPolicy::Error => write!(f, "error"),
+            FdPolicy::SkipMethod => write!(f, "skip-method"),
+            FdPolicy::CfgUnix => write!(f, "cfg-unix"),
+        }
+    }
+}
 
+/// Generate the code for `interfaces`, along with a module documentation listing them and the
+/// `standard_interfaces` that zbus provides already.
+///
+/// The `filter` and `with_tests` options are left to the caller.
 pub fn write_interfaces(
     interfaces: &[Interface<'_>],
     standard_interfaces: &[Interface<'_>],
-    service: Option<BusName<'_>>,
-    path: Option<ObjectPath<'_>>,
     input_src: &str,
     cargo_bin_name: &str,
     cargo_bin_version: &str,
+    options: &GenOptions,
 ) -> Result<String, Box<dyn Error>> {
+    let code = options.code;
+    if code == CodeKind::Proxy {
+        check_fds(interfaces, options.fd_policy)?;
+    }
     let mut unformatted = String::new();
 
+    // A typed object manager proxy is generated for the proxies of the managed objects.
+    let (object_manager, standard_interfaces): (Vec<_>, Vec<_>) = standard_interfaces
+        .iter()
+        .cloned()
+        .partition(|i| code == CodeKind::Proxy && i.name() == OBJECT_MANAGER);
     write_doc_header(
         &mut unformatted,
         interfaces,
-        standard_interfaces,
+        &standard_interfaces,
+        input_src,
+        cargo_bin_name,
+        cargo_bin_version,
+        code,
+    )?;
+    write_code(
+        &mut unformatted,
+        interfaces,
+        object_manager.first(),
+        options,
+    )?;
+
+    Ok(format_or_keep(unformatted))
+}
+
+/// Generate a proxy for the `object_manager` interface, typed after the `interfaces` of the
+/// managed objects.
+///
+/// [`write_interfaces`] already does so when it's given both, this is for generating the proxies
+/// of each interface separately.
+pub fn write_object_manager_proxy(
+    object_manager: &Interface<'_>,
+    interfaces: &[Interface<'_>],
+    input_src: &str,
+    cargo_bin_name: &str,
+    cargo_bin_version: &str,
+    options: &GenOptions,
+) -> Result<String, Box<dyn Error>> {
+    let mut unformatted = String::new();
+
+    write_doc_header(
+        &mut unformatted,
+        std::slice::from_ref(object_manager),
+        &[],
         input_src,
         cargo_bin_name,
         cargo_bin_version,
+        CodeKind::Proxy,
     )?;
+    writeln!(unformatted, "use zbus::{};", options.version.proxy_macro())?;
+    write_object_manager(&mut unformatted, object_manager, interfaces, options)?;
+
+    Ok(format_or_keep(unformatted))
+}
+
+/// Write the code for `interfaces`, along with the `use` declaration it needs.
+///
+/// For proxies, the `object_manager` interface (if any) gets a proxy of its own, typed after the
+/// `interfaces` of the managed objects.
+fn write_code<W: Write>(
+    w: &mut W,
+    interfaces: &[Interface<'_>],
+    object_manager: Option<&Interface<'_>>,
+    options: &GenOptions,
+) -> std::fmt::Result {
+    let code = options.code;
+    let item_macro = if code != CodeKind::Proxy {
+        options.version.interface_macro()
+    } else {
+        options.version.proxy_macro()
+    };
+    writeln!(w, "use zbus::{item_macro};")?;
 
     for interface in interfaces {
-        let gen = GenTrait {
-            interface,
-            service: service.as_ref(),
-            path: path.as_ref(),
-            format: false,
+        if code != CodeKind::Proxy {
+            let gen = GenImpl {
+                interface,
+                format: false,
+                options,
+            };
+
+            write!(w, "{}", gen)?;
+        } else {
+            let gen = GenTrait {
+                interface,
+                format: false,
+                options,
+            };
+
+            write!(w, "{}", gen)?;
+        }
+    }
+    if let Some(object_manager) = object_manager.filter(|_| code == CodeKind::Proxy) {
+        write_object_manager(w, object_manager, interfaces, options)?;
+    }
+
+    Ok(())
+}
+
+/// The name of the standard interface for which typed proxies are generated.
+pub const OBJECT_MANAGER: &str = "org.freedesktop.DBus.ObjectManager";
+
+/// Write the proxy of the `object_manager` interface, with the managed objects deserialized into a
+/// `ManagedObject` struct holding the properties of the `interfaces` they implement.
+fn write_object_manager<W: Write>(
+    w: &mut W,
+    object_manager: &Interface<'_>,
+    interfaces: &[Interface<'_>],
+    options: &GenOptions,
+) -> std::fmt::Result {
+    let type_map = options.type_map.as_ref();
+    let managed: Vec<_> = interfaces
+        .iter()
+        .map(|iface| DictStruct {
+            member: iface.name().to_string(),
+            name: format!("{}Properties", trait_name(iface)),
+            doc: format!("The properties of the `{}` interface.", iface.name()),
+            keys: iface
+                .properties()
+                .iter()
+                .filter(|p| p.access().read())
+                .map(|p| (p.name().to_string(), p.ty().signature().to_string()))
+                .collect(),
+        })
+        .collect();
+    write_dict_structs(w, &managed, type_map, options)?;
+
+    let fields: Vec<_> = interfaces
+        .iter()
+        .map(|iface| to_identifier(&to_snakecase(trait_name(iface))))
+        .collect();
+    writeln!(w)?;
+    writeln!(
+        w,
+        "/// The interfaces of an object managed by an `{OBJECT_MANAGER}`, along with their \
+         properties."
+    )?;
+    write_type_attributes(w, &["Debug", "Default", "zbus::zvariant::Type"], options)?;
+    writeln!(w, "#[zvariant(signature = \"a{{sa{{sv}}}}\")]")?;
+    writeln!(w, "pub struct ManagedObject {{")?;
+    for ((iface, field), dict) in interfaces.iter().zip(&fields).zip(&managed) {
+        writeln!(
+            w,
+            "    /// The `{}` properties, if the object implements the interface.",
+            iface.name()
+        )?;
+        writeln!(w, "    pub {field}: Option<{}>,", dict.name)?;
+    }
+    writeln!(
+        w,
+        "    /// The other interfaces of the object, along with their properties.
+    pub other: std::collections::HashMap<
+        String,
+        std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
+    >,
+}}
+
+impl<'de> zbus::export::serde::Deserialize<'de> for ManagedObject {{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: zbus::export::serde::Deserializer<'de>,
+    {{
+        struct Visitor;
+
+        impl<'de> zbus::export::serde::de::Visitor<'de> for Visitor {{
+            type Value = ManagedObject;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+                f.write_str(\"a dictionary of interfaces and their properties\")
+            }}
+
+            fn visit_map<M>(self, mut map: M) -> std::result::Result<ManagedObject, M::Error>
+            where
+                M: zbus::export::serde::de::MapAccess<'de>,
+            {{
+                let mut object = ManagedObject::default();
+                while let Some(interface) = map.next_key::<String>()? {{
+                    match interface.as_str() {{"
+    )?;
+    for (iface, field) in interfaces.iter().zip(&fields) {
+        writeln!(
+            w,
+            "                        \"{}\" => object.{field} = Some(map.next_value()?),",
+            iface.name()
+        )?;
+    }
+    writeln!(
+        w,
+        "                        _ => {{
+                            object.other.insert(interface, map.next_value()?);
+                        }}
+                    }}
+                }}
+
+                Ok(object)
+            }}
+        }}
+
+        deserializer.deserialize_map(Visitor)
+    }}
+}}"
+    )?;
+
+    let mut options = options.clone();
+    let signatures = &mut options
+        .type_map
+        .get_or_insert_with(TypeMap::default)
+        .signatures;
+    signatures.insert("a{sa{sv}}".to_string(), "ManagedObject".to_string());
+    signatures.insert(
+        "a{oa{sa{sv}}}".to_string(),
+        "std::collections::HashMap<zbus::zvariant::OwnedObjectPath, ManagedObject>".to_string(),
+    );
+    let gen = GenTrait {
+        interface: object_manager,
+        format: false,
+        options: &options,
+    };
+
+    write!(w, "{}", gen)
+}
+
+/// Format the `unformatted` code, or keep it as is if that fails.
+fn format_or_keep(unformatted: String) -> String {
+    match format_generated_code(&unformatted) {
+        Ok(formatted) => formatted,
+        Err(e) => {
+            eprintln!("Failed to format generated code: {}", e);
+            unformatted
+        }
+    }
+}
+
+/// Which interfaces to generate code for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceFilter {
+    /// Skip the standard `org.freedesktop.DBus.*` interfaces, which zbus provides already.
+    pub skip_standard: bool,
+    /// Only generate code for these interfaces, unless empty. Names ending with `*` match all the
+    /// interfaces starting with the rest, e.g `org.freedesktop.portal.*`.
+    pub include: Vec<String>,
+    /// Don't generate code for these interfaces, in the same format as `include`.
+    pub exclude: Vec<String>,
+}
+
+impl Default for InterfaceFilter {
+    fn default() -> Self {
+        Self {
+            skip_standard: true,
+            include: vec![],
+            exclude: vec![],
+        }
+    }
+}
+
+impl InterfaceFilter {
+    /// Split `interfaces` into the skipped standard interfaces and the interfaces to generate code
+    /// for. The other interfaces are left out.
+    pub fn split<'a>(
+        &self,
+        interfaces: &[Interface<'a>],
+    ) -> (Vec<Interface<'a>>, Vec<Interface<'a>>) {
+        let matches = |patterns: &[String], name: &str| {
+            patterns
+                .iter()
+                .any(|pattern| match pattern.strip_suffix('*') {
+                    Some(prefix) => name.starts_with(prefix),
+                    None => name == pattern,
+                })
         };
 
-        write!(unformatted, "{}", gen)?;
+        let mut standard = vec![];
+        let mut selected = vec![];
+        for iface in interfaces {
+            let name = iface.name();
+            if self.skip_standard && name.starts_with("org.freedesktop.DBus") {
+                standard.push(iface.clone());
+            } else if (self.include.is_empty() || matches(&self.include, &name))
+                && !matches(&self.exclude, &name)
+            {
+                selected.push(iface.clone());
+            }
+        }
+
+        (standard, selected)
+    }
+}
+
+/// Options for the generated code.
+#[derive(Debug, Default, Clone)]
+pub struct GenOptions {
+    /// The service the generated proxies use by default.
+    pub service: Option<BusName<'static>>,
+    /// The object path the generated proxies use by default.
+    pub path: Option<ObjectPath<'static>>,
+    /// The zbus version the generated code targets.
+    pub version: ZbusVersion,
+    /// The code to generate.
+    pub code: CodeKind,
+    /// The proxy types to generate.
+    pub kind: ProxyKind,
+    /// Types to use instead of the default ones.
+    pub type_map: Option<TypeMap>,
+    /// The interfaces to generate code for, only used by [`generate_from_xml`].
+    pub filter: InterfaceFilter,
+    /// Also generate a test module checking the signatures of the types, see
+    /// [`write_signature_tests`]. Only used by [`generate_from_xml`].
+    pub with_tests: bool,
+    /// Take owned types (e.g `String`, `Vec<T>` or `OwnedValue`) as the inputs of proxy methods
+    /// and property setters, instead of borrowed ones (e.g `&str`, `&[T]` or `&Value`).
+    pub owned_inputs: bool,
+    /// The error type returned by proxy methods and properties (e.g `my_crate::Error`), instead
+    /// of `zbus::Error`. It must implement `From<zbus::Error>`.
+    pub error_type: Option<String>,
+    /// Additional derives (e.g `Clone`) for the generated structs and enums, besides the ones
+    /// they need already.
+    pub derives: Vec<String>,
+    /// Attributes (e.g `#[allow(clippy::type_complexity)]`) to add to the generated traits,
+    /// structs and enums.
+    pub item_attributes: Vec<String>,
+    /// How proxies handle file descriptors, which are kept as is by default.
+    pub fd_policy: Option<FdPolicy>,
+}
+
+/// Describe `interfaces` as JSON, along with the Rust identifiers and types of their members, for
+/// use by other tools.
+///
+/// The Rust types are the owned ones, used for the outputs of proxies and the inputs of services.
+pub fn write_interfaces_json(
+    interfaces: &[Interface<'_>],
+    version: ZbusVersion,
+    type_map: Option<&TypeMap>,
+) -> Result<String, Box<dyn Error>> {
+    let annotations = |annotations: &[Annotation]| {
+        annotations
+            .iter()
+            .map(|a| (a.name().to_string(), json!(a.value())))
+            .collect::<serde_json::Map<_, _>>()
+    };
+
+    let interfaces: Vec<_> = interfaces
+        .iter()
+        .map(|iface| {
+            let dicts = dict_structs(iface);
+            let enums = value_enums(iface);
+            let structs = named_structs(iface, type_map);
+            let type_map = interface_type_map(type_map, &dicts, &enums, &structs);
+            let type_map = Some(&type_map);
+            // Only the inputs of methods are given identifiers.
+            let args = |member: &str, args: &[Arg<'_>], inputs_only: bool| {
+                let member_key = format!("{}.{member}", iface.name());
+                let idents: Vec<_> = named_args(
+                    args.iter()
+                        .filter(|a| !inputs_only || a.direction() != Some(ArgDirection::Out)),
+                )
+                .collect();
+                args.iter()
+                    .map(|a| {
+                        let ident = idents
+                            .iter()
+                            .find(|(_, arg)| std::ptr::eq(*arg, a))
+                            .map(|(ident, _)| ident);
+                        json!({
+                            "name": a.name(),
+                            "ident": ident,
+                            "type": a.ty().signature().as_str(),
+                            "direction": a.direction(),
+                            "rust_type": mapped_rust_type(
+                                a, &member_key, type_map, false, false, version,
+                            ),
+                            "annotations": annotations(a.annotations()),
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let methods: Vec<_> = iface
+                .methods()
+                .iter()
+                .map(|m| {
+                    json!({
+                        "name": m.name().as_str(),
+                        "ident": to_identifier(&to_snakecase(m.name().as_str())),
+                        "args": args(m.name().as_str(), m.args(), true),
+                        "annotations": annotations(m.annotations()),
+                    })
+                })
+                .collect();
+            let signals: Vec<_> = iface
+                .signals()
+                .iter()
+                .map(|s| {
+                    json!({
+                        "name": s.name().as_str(),
+                        "ident": to_identifier(&to_snakecase(s.name().as_str())),
+                        "args": args(s.name().as_str(), s.args(), false),
+                        "annotations": annotations(s.annotations()),
+                    })
+                })
+                .collect();
+            let properties: Vec<_> = iface
+                .properties()
+                .iter()
+                .map(|p| {
+                    let key = format!("{}.{}", iface.name(), p.name());
+                    let rust_type = match type_map.and_then(|m| m.lookup(Some(&key), p.ty())) {
+                        Some(mapped) => mapped.to_string(),
+                        None => to_rust_type(p.ty(), false, false, version, type_map),
+                    };
+                    json!({
+                        "name": p.name().as_str(),
+                        "ident": to_identifier(&to_snakecase(p.name().as_str())),
+                        "type": p.ty().signature().as_str(),
+                        "access": p.access(),
+                        "rust_type": rust_type,
+                        "annotations": annotations(p.annotations()),
+                    })
+                })
+                .collect();
+
+            json!({
+                "name": iface.name().as_str(),
+                "methods": methods,
+                "signals": signals,
+                "properties": properties,
+                "annotations": annotations(iface.annotations()),
+            })
+        })
+        .collect();
+
+    Ok(serde_json::to_string_pretty(
+        &json!({ "interfaces": interfaces }),
+    )?)
+}
+
+/// Generate a test module checking that the Rust types used for the arguments and properties of
+/// `interfaces` have the signatures given in `xml`, the introspection XML they were generated from.
+///
+/// The XML is embedded in the module and parsed by the test, so the generated code needs a
+/// `zbus_xml` dev-dependency (or none, for zbus 3). The types can only drift from the XML through
+/// the type map, or when editing the generated code or the XML by hand.
+pub fn write_signature_tests(
+    xml: &str,
+    interfaces: &[Interface<'_>],
+    version: ZbusVersion,
+    type_map: Option<&TypeMap>,
+) -> Result<String, Box<dyn Error>> {
+    let node = match version {
+        ZbusVersion::V3 => "zbus::xml::Node",
+        ZbusVersion::V4 => "zbus_xml::Node",
+    };
+    // `into_iter` on the temporary vectors returned by zbus 3.
+    let iter = match version {
+        ZbusVersion::V3 => "into_iter",
+        ZbusVersion::V4 => "iter",
+    };
+    // Enough `#`s for the raw string not to end in the XML.
+    let hashes = (1..)
+        .map(|n| "#".repeat(n))
+        .find(|hashes| !xml.contains(&format!("\"{hashes}")))
+        .unwrap();
+
+    let mut unformatted = String::new();
+    writeln!(unformatted, "#[cfg(test)]")?;
+    writeln!(unformatted, "mod signature_tests {{")?;
+    writeln!(unformatted, "    #[allow(unused_imports)]")?;
+    writeln!(unformatted, "    use super::*;")?;
+    writeln!(unformatted)?;
+    writeln!(
+        unformatted,
+        "    /// The introspection XML the code was generated from."
+    )?;
+    writeln!(
+        unformatted,
+        "    const XML: &str = r{hashes}\"{xml}\"{hashes};"
+    )?;
+    writeln!(
+        unformatted,
+        "
+    /// The signatures of the arguments (keyed `Member[N]`) and properties of `interface` in the
+    /// XML.
+    fn xml_signatures(interface: &str) -> Vec<(String, String)> {{
+        let node = {node}::from_reader(XML.as_bytes()).unwrap();
+        let interface = node
+            .interfaces()
+            .{iter}()
+            .find(|i| i.name() == interface)
+            .expect(\"interface missing from the XML\");
+        let members = interface
+            .methods()
+            .{iter}()
+            .map(|m| (m.name().to_string(), m.args()))
+            .chain(
+                interface
+                    .signals()
+                    .{iter}()
+                    .map(|s| (s.name().to_string(), s.args())),
+            );
+        let mut signatures = vec![];
+        for (member, args) in members {{
+            for (i, arg) in args.{iter}().enumerate() {{
+                signatures.push((format!(\"{{member}}[{{i}}]\"), arg.ty().to_string()));
+            }}
+        }}
+        for p in interface.properties() {{
+            signatures.push((p.name().to_string(), p.ty().to_string()));
+        }}
+
+        signatures
+    }}
+
+    fn signature<T: zbus::zvariant::Type>() -> String {{
+        T::signature().to_string()
+    }}"
+    )?;
+
+    for iface in interfaces {
+        let dicts = dict_structs(iface);
+        let enums = value_enums(iface);
+        let structs = named_structs(iface, type_map);
+        let type_map = interface_type_map(type_map, &dicts, &enums, &structs);
+        let type_map = Some(&type_map);
+
+        // Same order as `xml_signatures`.
+        let mut signatures = vec![];
+        let members = iface
+            .methods()
+            .iter()
+            .map(|m| (m.name(), m.args()))
+            .chain(iface.signals().iter().map(|s| (s.name(), s.args())));
+        for (member, args) in members {
+            let member_key = format!("{}.{member}", iface.name());
+            for (i, a) in args.iter().enumerate() {
+                let ty = mapped_rust_type(a, &member_key, type_map, false, false, version);
+                signatures.push((format!("{member}[{i}]"), ty));
+            }
+        }
+        for p in iface.properties() {
+            let key = format!("{}.{}", iface.name(), p.name());
+            let ty = match type_map.and_then(|m| m.lookup(Some(&key), p.ty())) {
+                Some(mapped) => mapped.to_string(),
+                None => to_rust_type(p.ty(), false, false, version, type_map),
+            };
+            signatures.push((p.name().to_string(), ty));
+        }
+        // There's nothing to check.
+        if signatures.is_empty() {
+            continue;
+        }
+
+        writeln!(unformatted)?;
+        writeln!(unformatted, "    #[test]")?;
+        writeln!(
+            unformatted,
+            "    fn {}_signatures() {{",
+            to_snakecase(trait_name(iface))
+        )?;
+        writeln!(unformatted, "        let generated = [")?;
+        for (key, ty) in signatures {
+            writeln!(
+                unformatted,
+                "            (\"{key}\".to_string(), signature::<{ty}>()),"
+            )?;
+        }
+        writeln!(unformatted, "        ];")?;
+        writeln!(
+            unformatted,
+            "        assert_eq!(xml_signatures(\"{}\"), generated);",
+            iface.name()
+        )?;
+        writeln!(unformatted, "    }}")?;
+    }
+    writeln!(unformatted, "}}")?;
+
+    Ok(format_or_keep(unformatted))
+}
+
+/// Generate the code for the interfaces described in the XML file at `path`.
+///
+/// The standard `org.freedesktop.DBus` interfaces are skipped by default, as zbus provides them
+/// already. Unlike [`write_interfaces`], no module documentation is generated, so the code can be
+/// included anywhere. This is meant for build scripts, to generate the code from checked-in XML
+/// files instead of committing it:
+///
+/// ```no_run
+/// # use std::{env, error::Error, fs, path::Path};
+/// use zbus_xmlgen::{generate_from_xml, GenOptions};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// println!("cargo:rerun-if-changed=org.example.Foo.xml");
+/// let code = generate_from_xml("org.example.Foo.xml", &GenOptions::default())?;
+/// let out_dir = env::var("OUT_DIR")?;
+/// fs::write(Path::new(&out_dir).join("foo.rs"), code)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The code can then be included with `include!(concat!(env!("OUT_DIR"), "/foo.rs"));`.
+pub fn generate_from_xml(
+    path: impl AsRef<Path>,
+    options: &GenOptions,
+) -> Result<String, Box<dyn Error>> {
+    let xml = std::fs::read_to_string(path)?;
+    let node = Node::from_reader(xml.as_bytes())?;
+    let (standard_interfaces, interfaces) = options.filter.split(node.interfaces());
+    if options.code == CodeKind::Proxy {
+        check_fds(&interfaces, options.fd_policy)?;
+    }
+    let object_manager = standard_interfaces
+        .iter()
+        .find(|i| i.name() == OBJECT_MANAGER);
+    let mut unformatted = String::new();
+    write_code(&mut unformatted, &interfaces, object_manager, options)?;
+    let mut code = format_or_keep(unformatted);
+    if options.with_tests {
+        code.push('\n');
+        code.push_str(&write_signature_tests(
+            &xml,
+            &interfaces,
+            options.version,
+            options.type_map.as_ref(),
+        )?);
+    }
+
+    Ok(code)
+}
+
+/// Write a doc header, listing the included Interfaces and how the
+/// code was generated.
+fn write_doc_header<W: std::fmt::Write>(
+    w: &mut W,
+    interfaces: &[Interface<'_>],
+    standard_interfaces: &[Interface<'_>],
+    input_src: &str,
+    cargo_bin_name: &str,
+    cargo_bin_version: &str,
+    code: CodeKind,
+) -> std::fmt::Result {
+    let server = code != CodeKind::Proxy;
+    let (kind, kinds) = code.names();
+    if let Some((first_iface, following_ifaces)) = interfaces.split_first() {
+        if following_ifaces.is_empty() {
+            writeln!(
+                w,
+                "//! # D-Bus interface {kind} for: `{}`",
+                first_iface.name()
+            )?;
+        } else {
+            write!(
+                w,
+                "//! # D-Bus interface {kinds} for: `{}`",
+                first_iface.name()
+            )?;
+            for iface in following_ifaces {
+                write!(w, ", `{}`", iface.name())?;
+            }
+            writeln!(w)?;
+        }
+    }
+
+    let (book_section, book_page) = if server {
+        ("Writing a server interface", "server")
+    } else {
+        ("Writing a client proxy", "client")
+    };
+    write!(
+        w,
+        "//!
+         //! This code was generated by `{}` `{}` from D-Bus introspection data.
+         //! Source: `{}`.
+         //!
+         //! You may prefer to adapt it, instead of using it verbatim.
+         //!
+         //! More information can be found in the [{}] section of the zbus
+         //! documentation.
+         //!
+        ",
+        cargo_bin_name, cargo_bin_version, input_src, book_section,
+    )?;
+
+    if !standard_interfaces.is_empty() && server {
+        write!(w,
+            "//! This object also implements the following [D-Bus standard interfaces], which zbus provides
+             //! on its own:
+             //!
+            ")?;
+        for iface in standard_interfaces {
+            writeln!(w, "//! * `{}`", iface.name())?;
+        }
+        write!(
+            w,
+            "//!
+             //! Consequently `{}` did not generate code for the above interfaces.
+            ",
+            cargo_bin_name,
+        )?;
+    } else if !standard_interfaces.is_empty() {
+        write!(w,
+            "//! This type implements the [D-Bus standard interfaces], (`org.freedesktop.DBus.*`) for which the
+             //! following zbus API can be used:
+             //!
+            ")?;
+        for iface in standard_interfaces {
+            let idx = iface.name().rfind('.').unwrap() + 1;
+            let name = &iface.name()[idx..];
+            writeln!(w, "//! * [`zbus::fdo::{name}Proxy`]")?;
+        }
+        write!(
+            w,
+            "//!
+             //! Consequently `{}` did not generate code for the above interfaces.
+            ",
+            cargo_bin_name,
+        )?;
+    }
+
+    write!(
+        w,
+        "//!
+        //! [{}]: https://dbus2.github.io/zbus/{}.html
+        //! [D-Bus standard interfaces]: https://dbus.freedesktop.org/doc/dbus-specification.html#standard-interfaces,
+        ",
+        book_section, book_page,
+    )?;
+
+    Ok(())
+}
+
+/// A module of a generated module tree, see [`write_root_module`].
+pub struct GenModule<'i> {
+    /// The name of the module.
+    pub name: String,
+    /// The interfaces the module has proxies for.
+    pub interfaces: &'i [Interface<'i>],
+}
+
+/// Pick the names of the modules generated for interface XML files, from the file stems.
+///
+/// Interface files are conventionally named after the interface they describe (e.g
+/// `org.freedesktop.portal.Screenshot.xml`), so modules are named after the last component of the
+/// file stem (`screenshot`). If that's ambiguous, the whole stem is used instead
+/// (`org_freedesktop_portal_screenshot`).
+pub fn module_names(file_stems: &[&str]) -> Vec<String> {
+    let short_name = |stem: &str| {
+        let last = stem.rsplit('.').next().unwrap_or(stem);
+        to_identifier(&to_snakecase(last))
+    };
+
+    file_stems
+        .iter()
+        .map(|stem| {
+            let name = short_name(stem);
+            let ambiguous = file_stems
+                .iter()
+                .filter(|other| short_name(other) == name)
+                .count()
+                > 1;
+            if ambiguous {
+                full_module_name(stem)
+            } else {
+                name
+            }
+        })
+        .collect()
+}
+
+/// The name of the module holding the code of a single interface.
+///
+/// This is the snake case form of the whole interface name, e.g `org_freedesktop_foo` for
+/// `org.freedesktop.Foo`.
+pub fn interface_module_name(interface: &Interface<'_>) -> String {
+    full_module_name(interface.name().as_str())
+}
+
+fn full_module_name(name: &str) -> String {
+    let components: Vec<_> = name.split('.').map(to_snakecase).collect();
+    to_identifier(&components.join("_"))
+}
+
+/// Write the root module (`lib.rs` or `mod.rs`) of a generated module tree.
+///
+/// It declares the `modules` and re-exports their types (for proxies, those of the given `kind`),
+/// except the ones whose names are provided by more than one module.
+pub fn write_root_module(
+    modules: &[GenModule<'_>],
+    input_src: &str,
+    cargo_bin_name: &str,
+    cargo_bin_version: &str,
+    code: CodeKind,
+    kind: ProxyKind,
+) -> Result<String, Box<dyn Error>> {
+    let (item, items) = code.names();
+    let mut unformatted = String::new();
+
+    write!(
+        unformatted,
+        "//! # D-Bus interface {items}
+         //!
+         //! This code was generated by `{}` `{}` from D-Bus introspection data.
+         //! Source: `{}`.
+         //!
+         //! Each module holds the {items} for one or more interfaces. The {item} types are
+         //! re-exported here, unless several modules have {items} of the same name.
+        ",
+        cargo_bin_name, cargo_bin_version, input_src,
+    )?;
+    writeln!(unformatted)?;
+    for module in modules {
+        writeln!(unformatted, "pub mod {};", module.name)?;
+    }
+
+    let trait_names: Vec<(&str, String)> = modules
+        .iter()
+        .flat_map(|m| {
+            m.interfaces
+                .iter()
+                .map(|i| (m.name.as_str(), trait_name(i)))
+        })
+        .collect();
+    let mut reexports = String::new();
+    for module in modules {
+        let names: Vec<_> = trait_names
+            .iter()
+            .filter(|(m, name)| {
+                *m == module.name && trait_names.iter().filter(|(_, n)| n == name).count() == 1
+            })
+            .map(|(_, name)| match code {
+                CodeKind::Proxy => kind.proxy_names(name),
+                CodeKind::Skeleton => name.clone(),
+                CodeKind::Mock => format!("{name}Call, {name}Mock"),
+            })
+            .collect();
+        if !names.is_empty() {
+            writeln!(
+                reexports,
+                "pub use {}::{{{}}};",
+                module.name,
+                names.join(", ")
+            )?;
+        }
+    }
+    if !reexports.is_empty() {
+        writeln!(unformatted)?;
+        unformatted.push_str(&reexports);
+    }
+
+    let formatted = match format_generated_code(&unformatted) {
+        Ok(formatted) => formatted,
+        Err(e) => {
+            eprintln!("Failed to format generated code: {}", e);
+            unformatted
+        }
+    };
+
+    Ok(formatted)
+}
+
+/// The name of the proxy trait generated for `interface`.
+fn trait_name(interface: &Interface<'_>) -> String {
+    let idx = interface.name().rfind('.').unwrap() + 1;
+
+    pascal_case(&interface.name()[idx..])
+}
+
+pub struct GenTrait<'i> {
+    pub interface: &'i Interface<'i>,
+    pub format: bool,
+    pub options: &'i GenOptions,
+}
+
+impl<'i> Display for GenTrait<'i> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.format {
+            let mut unformatted = String::new();
+            self.write_interface(&mut unformatted)?;
+
+            let formatted = format_generated_code(&unformatted).unwrap_or(unformatted);
+
+            write!(f, "{}", formatted)
+        } else {
+            self.write_interface(f)
+        }
+    }
+}
+
+impl<'i> GenTrait<'i> {
+    fn write_interface<W: Write>(&self, w: &mut W) -> std::fmt::Result {
+        let iface = self.interface;
+        let name = trait_name(iface);
+        let version = self.options.version;
+        let attr = version.member_attribute();
+        let dicts = dict_structs(iface);
+        let enums = value_enums(iface);
+        let type_map = self.options.type_map.as_ref();
+        let structs = named_structs(iface, type_map);
+        let type_map = interface_type_map(type_map, &dicts, &enums, &structs);
+        let type_map = Some(&type_map);
+
+        write_doc(w, "", iface.doc(), iface.annotations(), None)?;
+        write!(
+            w,
+            "#[{}(interface = \"{}\"",
+            version.proxy_macro(),
+            iface.name()
+        )?;
+        let (service, path) = (&self.options.service, &self.options.path);
+        if let Some(service) = service {
+            write!(w, ", default_service = \"{service}\"")?;
+        }
+        if let Some(path) = path {
+            write!(w, ", default_path = \"{path}\"")?;
+        }
+        if path.is_none() || service.is_none() {
+            write!(w, ", assume_defaults = true")?;
+        }
+        match self.options.kind {
+            ProxyKind::Async => write!(w, ", gen_blocking = false")?,
+            ProxyKind::Blocking => write!(w, ", gen_async = false")?,
+            ProxyKind::Both => (),
+        }
+        writeln!(w, ")]")?;
+        write_item_attributes(w, self.options)?;
+        writeln!(w, "trait {name} {{")?;
+
+        // Whether to skip a member with file descriptors, or only declare it on Unix.
+        let (skip_fds, cfg_unix) = match self.options.fd_policy {
+            Some(FdPolicy::SkipMethod) => (true, false),
+            Some(FdPolicy::CfgUnix) => (false, true),
+            Some(FdPolicy::Error) | None => (false, false),
+        };
+
+        let mut methods = iface.methods().to_vec();
+        methods.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+        for m in &methods {
+            let fds = has_fds(m.args());
+            if fds && skip_fds {
+                continue;
+            }
+            let member_key = format!("{}.{}", iface.name(), m.name());
+            let (inputs, output) =
+                inputs_output_from_args(m.args(), &member_key, type_map, self.options);
+            let name = to_identifier(&to_snakecase(m.name().as_str()));
+            // No reply is expected, so there's no output to return.
+            let no_reply = m
+                .annotations()
+                .iter()
+                .any(|a| a.name() == "org.freedesktop.DBus.Method.NoReply" && a.value() == "true");
+            let output = if no_reply {
+                format!(" -> {}", result_type("()", self.options))
+            } else {
+                output
+            };
+            writeln!(w)?;
+            let default = format!("{} method", m.name());
+            write_doc(w, "    ", m.doc(), m.annotations(), Some(&default))?;
+            if fds && cfg_unix {
+                writeln!(w, "    #[cfg(unix)]")?;
+            }
+            let mut method_attrs = vec![];
+            if pascal_case(&name) != m.name().as_str() {
+                method_attrs.push(format!("name = \"{}\"", m.name()));
+            }
+            if no_reply {
+                method_attrs.push("no_reply".to_string());
+            }
+            if !method_attrs.is_empty() {
+                writeln!(w, "    #[{attr}({})]", method_attrs.join(", "))?;
+            }
+            write_deprecated(w, m.annotations())?;
+            hide_clippy_lints(w, m, !self.options.owned_inputs)?;
+            writeln!(w, "    fn {name}({inputs}){output};")?;
+        }
+
+        let mut signals = iface.signals().to_vec();
+        signals.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+        for signal in &signals {
+            if has_fds(signal.args()) && (skip_fds || cfg_unix) {
+                continue;
+            }
+            let member_key = format!("{}.{}", iface.name(), signal.name());
+            let args = parse_signal_args(signal.args(), &member_key, type_map, version);
+            let name = to_identifier(&to_snakecase(signal.name().as_str()));
+            writeln!(w)?;
+            let default = format!("{} signal", signal.name());
+            write_doc(
+                w,
+                "    ",
+                signal.doc(),
+                signal.annotations(),
+                Some(&default),
+            )?;
+            if pascal_case(&name) != signal.name().as_str() {
+                writeln!(w, "    #[{attr}(signal, name = \"{}\")]", signal.name())?;
+            } else {
+                writeln!(w, "    #[{attr}(signal)]")?;
+            }
+            write_deprecated(w, signal.annotations())?;
+            writeln!(w, "    fn {name}({args}) -> zbus::Result<()>;",)?;
+        }
+
+        let mut props = iface.properties().to_vec();
+        props.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+        for p in props {
+            let fds = has_fd(p.ty());
+            if fds && skip_fds {
+                continue;
+            }
+            let name = to_identifier(&to_snakecase(p.name().as_str()));
+            let property = match emits_changed_signal(iface, &p) {
+                Some(value) => format!("property(emits_changed_signal = \"{value}\")"),
+                None => "property".to_string(),
+            };
+            let mut fn_attribute = if pascal_case(&name) != p.name().as_str() {
+                format!("    #[{attr}({property}, name = \"{}\")]", p.name())
+            } else {
+                format!("    #[{attr}({property})]")
+            };
+            if fds && cfg_unix {
+                fn_attribute.insert_str(0, "    #[cfg(unix)]\n");
+            }
+
+            let key = format!("{}.{}", iface.name(), p.name());
+            let mapped = type_map.and_then(|m| m.lookup(Some(&key), p.ty()));
+
+            writeln!(w)?;
+            let default = format!("{} property", p.name());
+            write_doc(w, "    ", p.doc(), p.annotations(), Some(&default))?;
+            if p.access().read() {
+                writeln!(w, "{}", fn_attribute)?;
+                write_deprecated(w, p.annotations())?;
+                let output = match mapped {
+                    Some(mapped) => mapped.to_string(),
+                    None => to_rust_type(p.ty(), false, false, version, type_map),
+                };
+                let complexity = 10 + type_complexity(p.ty().signature(), false, false, 2);
+                hide_clippy_type_complexity_lint(w, complexity)?;
+                let output = result_type(&output, self.options);
+                writeln!(w, "    fn {name}(&self) -> {output};",)?;
+            }
+
+            if p.access().write() {
+                writeln!(w, "{}", fn_attribute)?;
+                write_deprecated(w, p.annotations())?;
+                // Property values are converted into `Value`, so mapped types are taken by value.
+                let borrowed = !self.options.owned_inputs;
+                let input = match mapped {
+                    Some(mapped) => mapped.to_string(),
+                    None => to_rust_type(p.ty(), borrowed, borrowed, version, type_map),
+                };
+                let complexity = type_complexity(p.ty().signature(), borrowed, borrowed, 1);
+                hide_clippy_type_complexity_lint(w, complexity)?;
+                let output = result_type("()", self.options);
+                writeln!(w, "    fn set_{name}(&self, value: {input}) -> {output};",)?;
+            }
+        }
+        writeln!(w, "}}")?;
+        self.write_constants(w, &name)?;
+        write_dict_structs(w, &dicts, type_map, self.options)?;
+        write_value_enums(w, &enums, self.options)?;
+        write_named_structs(w, &structs, type_map, self.options)
+    }
+
+    /// Write the interface name, and the service and path when known, as constants of the proxy
+    /// types, so they don't need to be repeated. `{Name}Proxy::builder` is already seeded with
+    /// them by the proxy macro.
+    fn write_constants<W: Write>(&self, w: &mut W, name: &str) -> std::fmt::Result {
+        for proxy in self.options.kind.proxy_types(name) {
+            writeln!(w)?;
+            writeln!(w, "impl {proxy}<'_> {{")?;
+            writeln!(w, "    /// The name of the D-Bus interface.")?;
+            writeln!(
+                w,
+                "    pub const INTERFACE: &'static str = \"{}\";",
+                self.interface.name()
+            )?;
+            if let Some(service) = &self.options.service {
+                writeln!(w, "    /// The name of the service.")?;
+                writeln!(
+                    w,
+                    "    pub const DESTINATION: &'static str = \"{service}\";"
+                )?;
+            }
+            if let Some(path) = &self.options.path {
+                writeln!(w, "    /// The path of the object.")?;
+                writeln!(w, "    pub const PATH: &'static str = \"{path}\";")?;
+            }
+            writeln!(w, "}}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Generates the skeleton of a service-side implementation of an interface.
+///
+/// This is a unit struct, along with an `interface` impl block for it, where the method and
+/// property bodies are left to be written.
+///
+/// If the `code` option is [`CodeKind::Mock`], a mock implementation is generated instead, for
+/// testing clients: a `{Name}Mock` struct replying to method calls and property reads with canned
+/// values, and recording the method calls and property writes it receives as `{Name}Call` values.
+pub struct GenImpl<'i> {
+    pub interface: &'i Interface<'i>,
+    pub format: bool,
+    pub options: &'i GenOptions,
+}
+
+impl<'i> Display for GenImpl<'i> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.format {
+            let mut unformatted = String::new();
+            self.write_impl(&mut unformatted)?;
+
+            let formatted = format_generated_code(&unformatted).unwrap_or(unformatted);
+
+            write!(f, "{}", formatted)
+        } else {
+            self.write_impl(f)
+        }
+    }
+}
+
+impl<'i> GenImpl<'i> {
+    fn write_impl<W: Write>(&self, w: &mut W) -> std::fmt::Result {
+        let iface = self.interface;
+        let name = trait_name(iface);
+        let version = self.options.version;
+        let attr = version.interface_member_attribute();
+        let dicts = dict_structs(iface);
+        let enums = value_enums(iface);
+        let type_map = self.options.type_map.as_ref();
+        let structs = named_structs(iface, type_map);
+        let type_map = interface_type_map(type_map, &dicts, &enums, &structs);
+        let type_map = Some(&type_map);
+        let mock = self.options.code == CodeKind::Mock;
+        let (name, call) = if mock {
+            let call = format!("{name}Call");
+            self.write_mock_types(w, &format!("{name}Mock"), &call, type_map)?;
+            (format!("{name}Mock"), call)
+        } else {
+            write_doc(w, "", iface.doc(), iface.annotations(), None)?;
+            write_item_attributes(w, self.options)?;
+            writeln!(w, "pub struct {name};")?;
+            (name, String::new())
+        };
+        writeln!(w)?;
+        writeln!(
+            w,
+            "#[{}(name = \"{}\")]",
+            version.interface_macro(),
+            iface.name()
+        )?;
+        writeln!(w, "impl {name} {{")?;
+
+        let mut methods = iface.methods().to_vec();
+        methods.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+        for m in &methods {
+            let member_key = format!("{}.{}", iface.name(), m.name());
+            let (inputs, output) =
+                impl_inputs_output_from_args(m.args(), &member_key, type_map, version);
+            let name = to_identifier(&to_snakecase(m.name().as_str()));
+            writeln!(w)?;
+            let default = format!("{} method", m.name());
+            write_doc(w, "    ", m.doc(), m.annotations(), Some(&default))?;
+            if pascal_case(&name) != m.name().as_str() {
+                writeln!(w, "    #[{attr}(name = \"{}\")]", m.name())?;
+            }
+            hide_clippy_lints(w, m, true)?;
+            let args: Vec<_> = std::iter::once("&self".to_string())
+                .chain(inputs.iter().map(|(arg, ty)| format!("{arg}: {ty}")))
+                .collect();
+            writeln!(
+                w,
+                "    async fn {name}({}) -> zbus::fdo::Result<{output}> {{",
+                args.join(", ")
+            )?;
+            if mock {
+                let variant = pascal_case(&name);
+                if inputs.is_empty() {
+                    writeln!(
+                        w,
+                        "        self.calls.lock().unwrap().push({call}::{variant});"
+                    )?;
+                } else {
+                    let fields: Vec<_> = inputs.iter().map(|(arg, _)| arg.as_str()).collect();
+                    writeln!(
+                        w,
+                        "        self.calls.lock().unwrap().push({call}::{variant} {{ {} }});",
+                        fields.join(", ")
+                    )?;
+                }
+                writeln!(w, "        (self.{name}_reply)()")?;
+            } else {
+                writeln!(w, "        todo!()")?;
+            }
+            writeln!(w, "    }}")?;
+        }
+
+        let mut signals = iface.signals().to_vec();
+        signals.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+        for signal in &signals {
+            let member_key = format!("{}.{}", iface.name(), signal.name());
+            let mut args = vec![format!("ctxt: &{}<'_>", version.signal_context_path())];
+            args.extend(named_args(signal.args()).map(|(ident, a)| {
+                let ty = mapped_rust_type(a, &member_key, type_map, true, true, version);
+                // Only arrays are borrowed by `to_rust_type`, let's not take dicts by value either.
+                if ty.starts_with("std::collections::HashMap") {
+                    format!("{ident}: &{ty}")
+                } else {
+                    format!("{ident}: {ty}")
+                }
+            }));
+            let name = to_identifier(&to_snakecase(signal.name().as_str()));
+            writeln!(w)?;
+            let default = format!("{} signal", signal.name());
+            write_doc(
+                w,
+                "    ",
+                signal.doc(),
+                signal.annotations(),
+                Some(&default),
+            )?;
+            if pascal_case(&name) != signal.name().as_str() {
+                writeln!(w, "    #[{attr}(signal, name = \"{}\")]", signal.name())?;
+            } else {
+                writeln!(w, "    #[{attr}(signal)]")?;
+            }
+            writeln!(
+                w,
+                "    async fn {name}({}) -> zbus::Result<()>;",
+                args.join(", ")
+            )?;
+        }
+
+        let mut props = iface.properties().to_vec();
+        props.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+        for p in props {
+            let name = to_identifier(&to_snakecase(p.name().as_str()));
+            let property = match emits_changed_signal(iface, &p) {
+                Some(value) => format!("property(emits_changed_signal = \"{value}\")"),
+                None => "property".to_string(),
+            };
+            let fn_attribute = if pascal_case(&name) != p.name().as_str() {
+                format!("    #[{attr}({property}, name = \"{}\")]", p.name())
+            } else {
+                format!("    #[{attr}({property})]")
+            };
+            let key = format!("{}.{}", iface.name(), p.name());
+            let ty = match type_map.and_then(|m| m.lookup(Some(&key), p.ty())) {
+                Some(mapped) => mapped.to_string(),
+                None => to_rust_type(p.ty(), false, false, version, type_map),
+            };
+            let complexity = 10 + type_complexity(p.ty().signature(), false, false, 2);
+
+            writeln!(w)?;
+            let default = format!("{} property", p.name());
+            write_doc(w, "    ", p.doc(), p.annotations(), Some(&default))?;
+            if p.access().read() {
+                writeln!(w, "{}", fn_attribute)?;
+                hide_clippy_type_complexity_lint(w, complexity)?;
+                writeln!(
+                    w,
+                    "    async fn {name}(&self) -> zbus::fdo::Result<{ty}> {{"
+                )?;
+                if mock {
+                    writeln!(w, "        (self.{name}_reply)()")?;
+                } else {
+                    writeln!(w, "        todo!()")?;
+                }
+                writeln!(w, "    }}")?;
+            }
+
+            if p.access().write() {
+                writeln!(w, "{}", fn_attribute)?;
+                hide_clippy_type_complexity_lint(w, complexity)?;
+                writeln!(
+                    w,
+                    "    async fn set_{name}(&mut self, value: {ty}) -> zbus::fdo::Result<()> {{",
+                )?;
+                if mock {
+                    let variant = pascal_case(&format!("set_{name}"));
+                    writeln!(
+                        w,
+                        "        self.calls.lock().unwrap().push({call}::{variant}(value));"
+                    )?;
+                    writeln!(w, "        Ok(())")?;
+                } else {
+                    writeln!(w, "        todo!()")?;
+                }
+                writeln!(w, "    }}")?;
+            }
+        }
+        writeln!(w, "}}")?;
+        write_dict_structs(w, &dicts, type_map, self.options)?;
+        write_value_enums(w, &enums, self.options)?;
+        write_named_structs(w, &structs, type_map, self.options)
+    }
+
+    /// Write the mock struct, its `Default` implementation and the enum of the calls it records.
+    fn write_mock_types<W: Write>(
+        &self,
+        w: &mut W,
+        name: &str,
+        call: &str,
+        type_map: Option<&TypeMap>,
+    ) -> std::fmt::Result {
+        let iface = self.interface;
+        let version = self.options.version;
+
+        let mut methods = iface.methods().to_vec();
+        methods.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+        let mut props = iface.properties().to_vec();
+        props.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+
+        // The canned replies (field name, member, type and default value) and the recorded calls
+        // (variant and fields).
+        let mut replies = vec![];
+        let mut calls = vec![];
+        for m in &methods {
+            let member_key = format!("{}.{}", iface.name(), m.name());
+            let (inputs, output) =
+                impl_inputs_output_from_args(m.args(), &member_key, type_map, version);
+            let name = to_identifier(&to_snakecase(m.name().as_str()));
+            let default = if output == "()" {
+                "Ok(())".to_string()
+            } else {
+                format!(
+                    "Err(zbus::fdo::Error::Failed(\"no reply set for `{}`\".to_string()))",
+                    m.name()
+                )
+            };
+            replies.push((
+                format!("{name}_reply"),
+                format!("`{}` method calls", m.name()),
+                output,
+                default,
+            ));
+            let fields: Vec<_> = inputs
+                .iter()
+                .map(|(arg, ty)| format!("{arg}: {ty}"))
+                .collect();
+            let fields = if fields.is_empty() {
+                String::new()
+            } else {
+                format!(" {{ {} }}", fields.join(", "))
+            };
+            calls.push((
+                format!("A `{}` method call.", m.name()),
+                format!("{}{fields}", pascal_case(&name)),
+            ));
+        }
+        for p in &props {
+            let name = to_identifier(&to_snakecase(p.name().as_str()));
+            let key = format!("{}.{}", iface.name(), p.name());
+            let ty = match type_map.and_then(|m| m.lookup(Some(&key), p.ty())) {
+                Some(mapped) => mapped.to_string(),
+                None => to_rust_type(p.ty(), false, false, version, type_map),
+            };
+            if p.access().read() {
+                replies.push((
+                    format!("{name}_reply"),
+                    format!("`{}` property reads", p.name()),
+                    ty.clone(),
+                    format!(
+                        "Err(zbus::fdo::Error::Failed(\"no value set for `{}`\".to_string()))",
+                        p.name()
+                    ),
+                ));
+            }
+            if p.access().write() {
+                calls.push((
+                    format!("A `{}` property write.", p.name()),
+                    format!("{}({ty})", pascal_case(&format!("set_{name}"))),
+                ));
+            }
+        }
+
+        writeln!(
+            w,
+            "/// Mock implementation of the `{}` interface.",
+            iface.name()
+        )?;
+        writeln!(w, "///")?;
+        writeln!(
+            w,
+            "/// It replies to method calls and property reads with the `*_reply` functions, and"
+        )?;
+        writeln!(
+            w,
+            "/// records the method calls and property writes it receives in `calls`."
+        )?;
+        writeln!(w, "#[allow(clippy::type_complexity)]")?;
+        write_item_attributes(w, self.options)?;
+        writeln!(w, "pub struct {name} {{")?;
+        writeln!(w, "    /// The calls received, in order.")?;
+        writeln!(
+            w,
+            "    pub calls: std::sync::Arc<std::sync::Mutex<Vec<{call}>>>,"
+        )?;
+        for (field, doc, ty, _) in &replies {
+            writeln!(w, "    /// The reply to {doc}.")?;
+            writeln!(
+                w,
+                "    pub {field}: Box<dyn Fn() -> zbus::fdo::Result<{ty}> + Send + Sync>,"
+            )?;
+        }
+        writeln!(w, "}}")?;
+        writeln!(w)?;
+
+        writeln!(w, "impl Default for {name} {{")?;
+        writeln!(w, "    fn default() -> Self {{")?;
+        writeln!(w, "        Self {{")?;
+        writeln!(w, "            calls: Default::default(),")?;
+        for (field, _, _, default) in &replies {
+            writeln!(w, "            {field}: Box::new(|| {default}),")?;
+        }
+        writeln!(w, "        }}")?;
+        writeln!(w, "    }}")?;
+        writeln!(w, "}}")?;
+        writeln!(w)?;
+
+        writeln!(w, "/// A call received by [`{name}`].")?;
+        writeln!(w, "#[derive(Debug)]")?;
+        write_item_attributes(w, self.options)?;
+        writeln!(w, "pub enum {call} {{")?;
+        for (doc, variant) in &calls {
+            writeln!(w, "    /// {doc}")?;
+            writeln!(w, "    {variant},")?;
+        }
+        writeln!(w, "}}")
+    }
+}
+
+/// The prefix of the annotations declaring the keys of `a{sv}` arguments and properties, e.g.
+/// `org.zbus.DictKey.handle_token`, valued with the signature of the key's values.
+const DICT_KEY_ANNOTATION: &str = "org.zbus.DictKey.";
+
+/// A struct generated for an `a{sv}` argument or property, with its keys as fields.
+struct DictStruct {
+    /// The argument (`interface.Member.arg`) or property (`interface.Property`) it's for.
+    member: String,
+    name: String,
+    doc: String,
+    /// The keys, along with the signature of their values.
+    keys: Vec<(String, String)>,
+}
+
+/// The structs to generate for the `a{sv}` arguments and properties of `iface` whose keys are
+/// declared.
+fn dict_structs(iface: &Interface<'_>) -> Vec<DictStruct> {
+    let mut structs = vec![];
+    for_each_member(iface, |member, name, ty, annotations| {
+        let keys: Vec<_> = annotations
+            .iter()
+            .filter_map(|a| {
+                let key = a.name().strip_prefix(DICT_KEY_ANNOTATION)?;
+                Some((key.to_string(), a.value().to_string()))
+            })
+            .collect();
+        if ty.signature().as_str() == "a{sv}" && !keys.is_empty() {
+            let doc = format!("The `{member}` dictionary.");
+            structs.push(DictStruct {
+                member,
+                name,
+                doc,
+                keys,
+            });
+        }
+    });
+
+    structs
+}
+
+/// The prefix of the annotations declaring the values of integer and string arguments and
+/// properties, e.g. `org.zbus.Enum.Off`, valued with the D-Bus value of the variant.
+const ENUM_VALUE_ANNOTATION: &str = "org.zbus.Enum.";
+
+/// An enum generated for an integer or string argument or property, with its values as variants.
+struct ValueEnum {
+    /// The argument (`interface.Member.arg`) or property (`interface.Property`) it's for.
+    member: String,
+    name: String,
+    /// The signature of the values, `s` or an integer type.
+    signature: String,
+    /// The variants, along with their values.
+    variants: Vec<(String, String)>,
+}
+
+/// The enums to generate for the integer and string arguments and properties of `iface` whose
+/// values are declared.
+fn value_enums(iface: &Interface<'_>) -> Vec<ValueEnum> {
+    let mut enums = vec![];
+    for_each_member(iface, |member, name, ty, annotations| {
+        let variants: Vec<_> = annotations
+            .iter()
+            .filter_map(|a| {
+                let variant = a.name().strip_prefix(ENUM_VALUE_ANNOTATION)?;
+                let variant = pascal_case(&to_identifier(&to_snakecase(variant)));
+                Some((variant, a.value().trim().to_string()))
+            })
+            .collect();
+        let signature = ty.signature().as_str();
+        let valid = match signature {
+            "s" => true,
+            "y" | "n" | "q" | "i" | "u" | "x" | "t" => variants
+                .iter()
+                .all(|(_, value)| value.parse::<i128>().is_ok()),
+            _ => false,
+        };
+        if valid && !variants.is_empty() {
+            enums.push(ValueEnum {
+                member,
+                name,
+                signature: signature.to_string(),
+                variants,
+            });
+        }
+    });
+
+    enums
+}
+
+/// Call `f` for each named argument and each property of `iface`, with its key
+/// (`interface.Member.arg` or `interface.Property`), the name of the types generated for it, its
+/// type and its annotations.
+fn for_each_member<F>(iface: &Interface<'_>, mut f: F)
+where
+    F: FnMut(String, String, &CompleteType<'_>, &[Annotation]),
+{
+    let members = iface
+        .methods()
+        .iter()
+        .map(|m| (m.name(), m.args()))
+        .chain(iface.signals().iter().map(|s| (s.name(), s.args())));
+    for (member, args) in members {
+        for a in args {
+            let Some(arg) = a.name() else {
+                continue;
+            };
+            f(
+                format!("{}.{member}.{arg}", iface.name()),
+                pascal_case(&format!(
+                    "{}_{}",
+                    to_snakecase(member.as_str()),
+                    to_snakecase(arg)
+                )),
+                a.ty(),
+                a.annotations(),
+            );
+        }
+    }
+    for p in iface.properties() {
+        f(
+            format!("{}.{}", iface.name(), p.name()),
+            pascal_case(&to_identifier(&to_snakecase(p.name().as_str()))),
+            p.ty(),
+            p.annotations(),
+        );
+    }
+}
+
+/// A struct generated for a struct signature, see [`TypeMap::named_structs`].
+struct NamedStruct {
+    signature: String,
+    name: String,
+    /// The field names, along with their signatures.
+    fields: Vec<(String, String)>,
+}
+
+/// The structs to generate for the struct signatures of the arguments and properties of `iface`,
+/// if requested by the `type_map`.
+fn named_structs(iface: &Interface<'_>, type_map: Option<&TypeMap>) -> Vec<NamedStruct> {
+    let mut structs = vec![];
+    if !type_map.is_some_and(|m| m.named_structs) {
+        return structs;
+    }
+
+    let field_names = |annotations: &[Annotation]| {
+        annotations
+            .iter()
+            .find(|a| a.name() == "org.zbus.StructFields")
+            .map(|a| a.value().to_string())
+    };
+    let mut methods = iface.methods().to_vec();
+    methods.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+    let mut signals = iface.signals().to_vec();
+    signals.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+    let members = methods
+        .iter()
+        .map(|m| (m.name(), m.args()))
+        .chain(signals.iter().map(|s| (s.name(), s.args())));
+    for (member, args) in members {
+        for (i, a) in args.iter().enumerate() {
+            let arg = match a.name() {
+                Some(arg) => to_snakecase(arg).into_owned(),
+                None => format!("arg_{i}"),
+            };
+            let name = pascal_case(&format!("{}_{arg}", to_snakecase(member.as_str())));
+            let tree = SignatureTree::try_from(a.ty().signature()).unwrap();
+            let field_names = field_names(a.annotations());
+            collect_structs(&tree, name, field_names.as_deref(), type_map, &mut structs);
+        }
+    }
+    let mut props = iface.properties().to_vec();
+    props.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+    for p in &props {
+        let name = pascal_case(&to_identifier(&to_snakecase(p.name().as_str())));
+        let tree = SignatureTree::try_from(p.ty().signature()).unwrap();
+        let field_names = field_names(p.annotations());
+        collect_structs(&tree, name, field_names.as_deref(), type_map, &mut structs);
+    }
+
+    structs
+}
+
+/// Add the structs of `tree` to `structs`, naming them after `name`.
+fn collect_structs(
+    tree: &SignatureTree,
+    name: String,
+    field_names: Option<&str>,
+    type_map: Option<&TypeMap>,
+    structs: &mut Vec<NamedStruct>,
+) {
+    match tree {
+        SignatureTree::Struct(fields) => {
+            let signature = tree.to_string();
+            let known = structs.iter().any(|s| s.signature == signature)
+                || type_map.is_some_and(|m| m.signatures.contains_key(&signature));
+            if !known {
+                let names: Vec<_> = field_names
+                    .map(|names| {
+                        names
+                            .split(',')
+                            .map(|n| to_identifier(&to_snakecase(n.trim())))
+                            .collect()
+                    })
+                    .filter(|names: &Vec<_>| names.len() == fields.len())
+                    .unwrap_or_else(|| (0..fields.len()).map(|i| format!("field_{i}")).collect());
+                let mut unique_name = name.clone();
+                let mut n = 1;
+                while structs.iter().any(|s| s.name == unique_name) {
+                    n += 1;
+                    unique_name = format!("{name}{n}");
+                }
+                structs.push(NamedStruct {
+                    signature,
+                    name: unique_name,
+                    fields: names
+                        .into_iter()
+                        .zip(fields.iter().map(|f| f.to_string()))
+                        .collect(),
+                });
+            }
+            for (i, field) in fields.iter().enumerate() {
+                collect_structs(field, format!("{name}Field{i}"), None, type_map, structs);
+            }
+        }
+        SignatureTree::Array(element) => match &**element {
+            SignatureTree::DictEntry(_, value) => {
+                collect_structs(value, format!("{name}Value"), None, type_map, structs)
+            }
+            element => collect_structs(element, format!("{name}Item"), None, type_map, structs),
+        },
+        _ => (),
+    }
+}
+
+/// The user-provided `type_map`, completed with the generated `dicts` and `structs`.
+fn interface_type_map(
+    type_map: Option<&TypeMap>,
+    dicts: &[DictStruct],
+    enums: &[ValueEnum],
+    structs: &[NamedStruct],
+) -> TypeMap {
+    let mut type_map = type_map.cloned().unwrap_or_default();
+    let members = dicts
+        .iter()
+        .map(|d| (&d.member, &d.name))
+        .chain(enums.iter().map(|e| (&e.member, &e.name)));
+    for (member, name) in members {
+        type_map
+            .members
+            .entry(member.clone())
+            .or_insert_with(|| name.clone());
+    }
+    for s in structs {
+        type_map
+            .signatures
+            .insert(s.signature.clone(), s.name.clone());
+    }
+
+    type_map
+}
+
+fn write_named_structs<W: Write>(
+    w: &mut W,
+    structs: &[NamedStruct],
+    type_map: Option<&TypeMap>,
+    options: &GenOptions,
+) -> std::fmt::Result {
+    let version = options.version;
+    for s in structs {
+        writeln!(w)?;
+        writeln!(w, "/// The `{}` struct.", s.signature)?;
+        write_type_attributes(
+            w,
+            &[
+                "Debug",
+                "zbus::export::serde::Serialize",
+                "zbus::export::serde::Deserialize",
+                "zbus::zvariant::Type",
+            ],
+            options,
+        )?;
+        writeln!(w, "#[serde(crate = \"zbus::export::serde\")]")?;
+        writeln!(w, "pub struct {} {{", s.name)?;
+        for (field, signature) in &s.fields {
+            let ty = Signature::try_from(signature.as_str())
+                .and_then(CompleteType::try_from)
+                .expect("invalid struct field signature");
+            let ty = to_rust_type(&ty, false, false, version, type_map);
+            writeln!(w, "    pub {field}: {ty},")?;
+        }
+        writeln!(w, "}}")?;
     }
 
-    let formatted = match format_generated_code(&unformatted) {
-        Ok(formatted) => formatted,
-        Err(e) => {
-            eprintln!("Failed to format generated code: {}", e);
-            unformatted
+    Ok(())
+}
+
+fn write_dict_structs<W: Write>(
+    w: &mut W,
+    dicts: &[DictStruct],
+    type_map: Option<&TypeMap>,
+    options: &GenOptions,
+) -> std::fmt::Result {
+    let version = options.version;
+    for dict in dicts {
+        writeln!(w)?;
+        writeln!(w, "/// {}", dict.doc)?;
+        write_type_attributes(
+            w,
+            &[
+                "Debug",
+                "Default",
+                "zbus::zvariant::DeserializeDict",
+                "zbus::zvariant::SerializeDict",
+                "zbus::zvariant::Type",
+            ],
+            options,
+        )?;
+        writeln!(w, "#[zvariant(signature = \"a{{sv}}\")]")?;
+        writeln!(w, "pub struct {} {{", dict.name)?;
+        for (key, signature) in &dict.keys {
+            let field = to_identifier(&to_snakecase(key));
+            // Values of unknown types are kept as is.
+            let ty = Signature::try_from(signature.as_str())
+                .ok()
+                .and_then(|s| CompleteType::try_from(s).ok())
+                .map(|ty| to_rust_type(&ty, false, false, version, type_map))
+                .unwrap_or_else(|| "zbus::zvariant::OwnedValue".to_string());
+            if field != *key {
+                writeln!(w, "    #[zvariant(rename = \"{key}\")]")?;
+            }
+            writeln!(w, "    pub {field}: Option<{ty}>,")?;
         }
-    };
+        writeln!(w, "}}")?;
+    }
 
-    Ok(formatted)
+    Ok(())
 }
 
-/// Write a doc header, listing the included Interfaces and how the
-/// code was generated.
-fn write_doc_header<W: std::fmt::Write>(
+fn write_value_enums<W: Write>(
     w: &mut W,
-    interfaces: &[Interface<'_>],
-    standard_interfaces: &[Interface<'_>],
-    input_src: &str,
-    cargo_bin_name: &str,
-    cargo_bin_version: &str,
+    enums: &[ValueEnum],
+    options: &GenOptions,
 ) -> std::fmt::Result {
-    if let Some((first_iface, following_ifaces)) = interfaces.split_first() {
-        if following_ifaces.is_empty() {
-            writeln!(
-                w,
-                "//! # D-Bus interface proxy for: `{}`",
-                first_iface.name()
-            )?;
+    let version = options.version;
+    for e in enums {
+        let name = &e.name;
+        let ty = Signature::try_from(e.signature.as_str())
+            .and_then(CompleteType::try_from)
+            .expect("invalid enum signature");
+        let ty = to_rust_type(&ty, false, false, version, None);
+        // String values are converted from `String`, but given as `&'static str`.
+        let string = e.signature == "s";
+        let (value_ty, value) = if string {
+            ("&'static str", "value.as_str()")
         } else {
-            write!(
-                w,
-                "//! # D-Bus interface proxies for: `{}`",
-                first_iface.name()
-            )?;
-            for iface in following_ifaces {
-                write!(w, ", `{}`", iface.name())?;
+            (ty.as_str(), "value")
+        };
+        let literal = |v: &str| {
+            if string {
+                format!("{v:?}")
+            } else {
+                v.to_string()
             }
-            writeln!(w)?;
-        }
-    }
+        };
 
-    write!(
-        w,
-        "//!
-         //! This code was generated by `{}` `{}` from D-Bus introspection data.
-         //! Source: `{}`.
-         //!
-         //! You may prefer to adapt it, instead of using it verbatim.
-         //!
-         //! More information can be found in the [Writing a client proxy] section of the zbus
-         //! documentation.
-         //!
-        ",
-        cargo_bin_name, cargo_bin_version, input_src,
-    )?;
+        writeln!(w)?;
+        writeln!(w, "/// The values of `{}`.", e.member)?;
+        write_type_attributes(
+            w,
+            &["Debug", "Clone", "Copy", "PartialEq", "Eq", "Hash"],
+            options,
+        )?;
+        writeln!(w, "pub enum {name} {{")?;
+        for (variant, _) in &e.variants {
+            writeln!(w, "    {variant},")?;
+        }
+        writeln!(
+            w,
+            "}}
 
-    if !standard_interfaces.is_empty() {
-        write!(w,
-            "//! This type implements the [D-Bus standard interfaces], (`org.freedesktop.DBus.*`) for which the
-             //! following zbus API can be used:
-             //!
-            ")?;
-        for iface in standard_interfaces {
-            let idx = iface.name().rfind('.').unwrap() + 1;
-            let name = &iface.name()[idx..];
-            writeln!(w, "//! * [`zbus::fdo::{name}Proxy`]")?;
+impl {name} {{
+    /// The D-Bus value of the variant.
+    pub fn value(self) -> {value_ty} {{
+        match self {{"
+        )?;
+        for (variant, value) in &e.variants {
+            writeln!(w, "            Self::{variant} => {},", literal(value))?;
         }
-        write!(
+        writeln!(
             w,
-            "//!
-             //! Consequently `{}` did not generate code for the above interfaces.
-            ",
-            cargo_bin_name,
+            "        }}
+    }}
+}}
+
+impl TryFrom<{ty}> for {name} {{
+    type Error = zbus::zvariant::Error;
+
+    fn try_from(value: {ty}) -> std::result::Result<Self, Self::Error> {{
+        match {value} {{"
         )?;
-    }
+        for (variant, value) in &e.variants {
+            writeln!(w, "            {} => Ok(Self::{variant}),", literal(value))?;
+        }
+        writeln!(
+            w,
+            "            _ => Err(zbus::zvariant::Error::Message(format!(
+                \"invalid `{name}` value: {{value}}\"
+            ))),
+        }}
+    }}
+}}
 
-    write!(
-        w,
-        "//!
-        //! [Writing a client proxy]: https://dbus2.github.io/zbus/client.html
-        //! [D-Bus standard interfaces]: https://dbus.freedesktop.org/doc/dbus-specification.html#standard-interfaces,
-        use zbus::proxy;
-        "
-    )?;
+impl TryFrom<zbus::zvariant::OwnedValue> for {name} {{
+    type Error = zbus::zvariant::Error;
 
-    Ok(())
-}
+    fn try_from(value: zbus::zvariant::OwnedValue) -> std::result::Result<Self, Self::Error> {{
+        Self::try_from(<{ty}>::try_from(value)?)
+    }}
+}}
 
-pub struct GenTrait<'i> {
-    pub interface: &'i Interface<'i>,
-    pub service: Option<&'i BusName<'i>>,
-    pub path: Option<&'i ObjectPath<'i>>,
-    pub format: bool,
-}
+impl From<{name}> for zbus::zvariant::Value<'_> {{
+    fn from(value: {name}) -> Self {{
+        Self::from(value.value())
+    }}
+}}
 
-impl<'i> Display for GenTrait<'i> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if self.format {
-            let mut unformatted = String::new();
-            self.write_interface(&mut unformatted)?;
+impl zbus::zvariant::Type for {name} {{
+    fn signature() -> zbus::zvariant::Signature<'static> {{
+        <{ty} as zbus::zvariant::Type>::signature()
+    }}
+}}
 
-            let formatted = format_generated_code(&unformatted).unwrap_or(unformatted);
+impl zbus::export::serde::Serialize for {name} {{
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: zbus::export::serde::Serializer,
+    {{
+        zbus::export::serde::Serialize::serialize(&self.value(), serializer)
+    }}
+}}
 
-            write!(f, "{}", formatted)
+impl<'de> zbus::export::serde::Deserialize<'de> for {name} {{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: zbus::export::serde::Deserializer<'de>,
+    {{
+        let value = <{ty} as zbus::export::serde::Deserialize>::deserialize(deserializer)?;
+
+        Self::try_from(value).map_err(zbus::export::serde::de::Error::custom)
+    }}
+}}"
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Write the attributes of the options to add to a generated item.
+fn write_item_attributes<W: Write>(w: &mut W, options: &GenOptions) -> std::fmt::Result {
+    for attribute in &options.item_attributes {
+        let attribute = attribute.trim();
+        if attribute.starts_with("#[") {
+            writeln!(w, "{attribute}")?;
         } else {
-            self.write_interface(f)
+            writeln!(w, "#[{attribute}]")?;
         }
     }
-}
 
-impl<'i> GenTrait<'i> {
-    fn write_interface<W: Write>(&self, w: &mut W) -> std::fmt::Result {
-        let iface = self.interface;
-        let idx = iface.name().rfind('.').unwrap() + 1;
-        let name = &iface.name()[idx..];
+    Ok(())
+}
 
-        write!(w, "#[proxy(interface = \"{}\"", iface.name())?;
-        if let Some(service) = self.service {
-            write!(w, ", default_service = \"{service}\"")?;
-        }
-        if let Some(path) = self.path {
-            write!(w, ", default_path = \"{path}\"")?;
-        }
-        if self.path.is_none() || self.service.is_none() {
-            write!(w, ", assume_defaults = true")?;
+/// Write the attributes of a generated struct or enum: those of the type map, and the `derives`
+/// it needs along with the additional ones of the type map.
+fn write_type_attributes<W: Write>(
+    w: &mut W,
+    derives: &[&str],
+    options: &GenOptions,
+) -> std::fmt::Result {
+    write_item_attributes(w, options)?;
+    // Derives are compared by name, whatever their path.
+    fn name(derive: &str) -> Option<&str> {
+        derive.rsplit("::").next().map(str::trim)
+    }
+    let mut derives: Vec<String> = derives.iter().map(|d| d.to_string()).collect();
+    for derive in &options.derives {
+        if !derives.iter().any(|d| name(d) == name(derive)) {
+            derives.push(derive.trim().to_string());
         }
-        writeln!(w, ")]")?;
-        writeln!(w, "trait {name} {{")?;
+    }
 
-        let mut methods = iface.methods().to_vec();
-        methods.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
-        for m in &methods {
-            let (inputs, output) = inputs_output_from_args(m.args());
-            let name = to_identifier(&to_snakecase(m.name().as_str()));
-            writeln!(w)?;
-            writeln!(w, "    /// {} method", m.name())?;
-            if pascal_case(&name) != m.name().as_str() {
-                writeln!(w, "    #[zbus(name = \"{}\")]", m.name())?;
-            }
-            hide_clippy_lints(w, m)?;
-            writeln!(w, "    fn {name}({inputs}){output};")?;
-        }
+    writeln!(w, "#[derive({})]", derives.join(", "))
+}
 
-        let mut signals = iface.signals().to_vec();
-        signals.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
-        for signal in &signals {
-            let args = parse_signal_args(signal.args());
-            let name = to_identifier(&to_snakecase(signal.name().as_str()));
-            writeln!(w)?;
-            writeln!(w, "    /// {} signal", signal.name())?;
-            if pascal_case(&name) != signal.name().as_str() {
-                writeln!(w, "    #[zbus(signal, name = \"{}\")]", signal.name())?;
+/// Write the doc comment of an interface or member, from its `<doc:doc>` element or its DocString
+/// annotation, falling back to `default`.
+fn write_doc<W: Write>(
+    write: &mut W,
+    indent: &str,
+    doc: Option<&Doc>,
+    annotations: &[Annotation],
+    default: Option<&str>,
+) -> std::fmt::Result {
+    let mut paragraphs: Vec<&str> = doc
+        .into_iter()
+        .flat_map(|doc| {
+            doc.summary()
+                .into_iter()
+                .chain(doc.paragraphs().iter().map(String::as_str))
+        })
+        .collect();
+    if paragraphs.is_empty() {
+        paragraphs.extend(
+            annotations
+                .iter()
+                .filter(|a| {
+                    a.name() == "org.freedesktop.DBus.DocString"
+                        || a.name() == "org.gtk.GDBus.DocString"
+                })
+                .map(|a| a.value()),
+        );
+    }
+    if paragraphs.is_empty() {
+        paragraphs.extend(default);
+    }
+
+    for (i, paragraph) in paragraphs.iter().enumerate() {
+        if i > 0 {
+            writeln!(write, "{indent}///")?;
+        }
+        // The text is usually indented along with the XML, so the indentation is dropped.
+        for line in paragraph.trim().lines().map(str::trim) {
+            if line.is_empty() {
+                writeln!(write, "{indent}///")?;
             } else {
-                writeln!(w, "    #[zbus(signal)]")?;
+                writeln!(write, "{indent}/// {line}")?;
             }
-            writeln!(w, "    fn {name}({args}) -> zbus::Result<()>;",)?;
         }
+    }
+    Ok(())
+}
 
-        let mut props = iface.properties().to_vec();
-        props.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
-        for p in props {
-            let name = to_identifier(&to_snakecase(p.name().as_str()));
-            let fn_attribute = if pascal_case(&name) != p.name().as_str() {
-                format!("    #[zbus(property, name = \"{}\")]", p.name())
-            } else {
-                "    #[zbus(property)]".to_string()
-            };
+/// The value of the `EmitsChangedSignal` annotation of property `p`, or else of its interface,
+/// unless it's the default (`true`).
+fn emits_changed_signal<'a>(iface: &'a Interface<'_>, p: &'a Property<'_>) -> Option<&'a str> {
+    let annotation = |annotations: &'a [Annotation]| {
+        annotations
+            .iter()
+            .find(|a| a.name() == "org.freedesktop.DBus.Property.EmitsChangedSignal")
+            .map(|a| a.value())
+    };
 
-            writeln!(w)?;
-            writeln!(w, "    /// {} property", p.name())?;
-            if p.access().read() {
-                writeln!(w, "{}", fn_attribute)?;
-                let output = to_rust_type(p.ty(), false, false);
-                hide_clippy_type_complexity_lint(w, p.ty().signature())?;
-                writeln!(w, "    fn {name}(&self) -> zbus::Result<{output}>;",)?;
-            }
+    annotation(p.annotations())
+        .or_else(|| annotation(iface.annotations()))
+        .filter(|value| matches!(*value, "invalidates" | "const" | "false"))
+}
 
-            if p.access().write() {
-                writeln!(w, "{}", fn_attribute)?;
-                let input = to_rust_type(p.ty(), true, true);
-                writeln!(
-                    w,
-                    "    fn set_{name}(&self, value: {input}) -> zbus::Result<()>;",
-                )?;
-            }
+/// Mark the proxy function of a member as deprecated if its D-Bus annotations say so.
+fn write_deprecated<W: Write>(write: &mut W, annotations: &[Annotation]) -> std::fmt::Result {
+    let deprecated = annotations
+        .iter()
+        .any(|a| a.name() == "org.freedesktop.DBus.Deprecated" && a.value() == "true");
+    if deprecated {
+        writeln!(
+            write,
+            "    #[deprecated(note = \"deprecated by the D-Bus interface\")]"
+        )?;
+    }
+    Ok(())
+}
+
+/// The result type of proxy methods and properties returning `output`, see
+/// [`GenOptions::error_type`].
+fn result_type(output: &str, options: &GenOptions) -> String {
+    match options.error_type.as_deref() {
+        Some(error) => format!("std::result::Result<{output}, {error}>"),
+        None => format!("zbus::Result<{output}>"),
+    }
+}
+
+/// Whether `ty` is or contains a file descriptor.
+fn has_fd(ty: &CompleteType<'_>) -> bool {
+    ty.signature().as_str().contains('h')
+}
+
+/// Whether any of `args` is or contains a file descriptor.
+fn has_fds(args: &[Arg<'_>]) -> bool {
+    args.iter().any(|a| has_fd(a.ty()))
+}
+
+/// Fail if a member of `interfaces` has file descriptors while the policy is
+/// [`FdPolicy::Error`].
+fn check_fds(
+    interfaces: &[Interface<'_>],
+    fd_policy: Option<FdPolicy>,
+) -> Result<(), Box<dyn Error>> {
+    if fd_policy != Some(FdPolicy::Error) {
+        return Ok(());
+    }
+    for iface in interfaces {
+        let mut members = vec![];
+        let methods = iface.methods().iter().filter(|m| has_fds(m.args()));
+        members.extend(methods.map(|m| m.name().to_string()));
+        let signals = iface.signals().iter().filter(|s| has_fds(s.args()));
+        members.extend(signals.map(|s| s.name().to_string()));
+        let properties = iface.properties().iter().filter(|p| has_fd(p.ty()));
+        members.extend(properties.map(|p| p.name().to_string()));
+        if let Some(member) = members.first() {
+            return Err(format!(
+                "`{}.{member}` has file descriptors, which don't exist on Windows",
+                iface.name()
+            )
+            .into());
         }
-        writeln!(w, "}}")
     }
+
+    Ok(())
 }
 
-fn hide_clippy_lints<W: Write>(write: &mut W, method: &zbus_xml::Method<'_>) -> std::fmt::Result {
+fn hide_clippy_lints<W: Write>(
+    write: &mut W,
+    method: &zbus_xml::Method<'_>,
+    borrowed_inputs: bool,
+) -> std::fmt::Result {
     // check for <https://rust-lang.github.io/rust-clippy/master/index.html#/too_many_arguments>
     // triggers when a functions has at least 7 paramters
     if method.args().len() >= 7 {
@@ -241,130 +2311,256 @@ fn hide_clippy_lints<W: Write>(write: &mut W, method: &zbus_xml::Method<'_>) ->
     }
 
     // check for <https://rust-lang.github.io/rust-clippy/master/index.html#/type_complexity>
-    for arg in method.args() {
-        let signature = arg.ty().signature();
-        hide_clippy_type_complexity_lint(write, signature)?;
-    }
-
-    Ok(())
+    let (inputs, outputs): (Vec<_>, Vec<_>) = method
+        .args()
+        .iter()
+        .partition(|a| a.direction() != Some(ArgDirection::Out));
+    let mut complexity = inputs
+        .iter()
+        .map(|a| type_complexity(a.ty().signature(), borrowed_inputs, borrowed_inputs, 1))
+        .max()
+        .unwrap_or(0);
+    // The outputs are wrapped in `zbus::Result` (and a tuple if there are multiple of them).
+    let output_complexity = match outputs.as_slice() {
+        [] => 30,
+        [output] => 10 + type_complexity(output.ty().signature(), false, false, 2),
+        outputs => {
+            30 + outputs
+                .iter()
+                .map(|a| type_complexity(a.ty().signature(), false, false, 3))
+                .sum::<u32>()
+        }
+    };
+    complexity = complexity.max(output_complexity);
+    hide_clippy_type_complexity_lint(write, complexity)
 }
 
-fn hide_clippy_type_complexity_lint<W: Write>(
-    write: &mut W,
-    signature: &zvariant::Signature,
-) -> std::fmt::Result {
-    let mut it = signature.as_bytes().iter().peekable();
-    let complexity = estimate_type_complexity(&mut it);
-    if complexity >= 1700 {
+fn hide_clippy_type_complexity_lint<W: Write>(write: &mut W, complexity: u32) -> std::fmt::Result {
+    // Same as the default `type-complexity-threshold` of clippy.
+    if complexity > 250 {
         writeln!(write, "    #[allow(clippy::type_complexity)]")?;
     }
     Ok(())
 }
 
-fn inputs_output_from_args(args: &[Arg]) -> (String, String) {
-    let mut inputs = vec!["&self".to_string()];
-    let mut output = vec![];
-    let mut n = 0;
-    let mut gen_name = || {
-        n += 1;
-        format!("arg_{n}")
+fn inputs_output_from_args(
+    args: &[Arg],
+    member_key: &str,
+    type_map: Option<&TypeMap>,
+    options: &GenOptions,
+) -> (String, String) {
+    let version = options.version;
+    let (inputs, outputs): (Vec<_>, Vec<_>) = args
+        .iter()
+        .partition(|a| a.direction() != Some(ArgDirection::Out));
+    let borrowed = !options.owned_inputs;
+    let inputs: Vec<_> = ["&self".to_string()]
+        .into_iter()
+        .chain(named_args(inputs).map(|(ident, a)| {
+            let ty = mapped_rust_type(a, member_key, type_map, borrowed, borrowed, version);
+            format!("{ident}: {ty}")
+        }))
+        .collect();
+    let output: Vec<_> = outputs
+        .iter()
+        .map(|a| mapped_rust_type(a, member_key, type_map, false, false, version))
+        .collect();
+
+    let output = match output.len() {
+        0 => "()".to_string(),
+        1 => output[0].to_string(),
+        _ => format!("({})", output.join(", ")),
     };
 
-    for a in args {
-        match a.direction() {
-            None | Some(ArgDirection::In) => {
-                let ty = to_rust_type(a.ty(), true, true);
-                let arg = if let Some(name) = a.name() {
-                    to_identifier(name)
-                } else {
-                    gen_name()
-                };
-                inputs.push(format!("{arg}: {ty}"));
-            }
-            Some(ArgDirection::Out) => {
-                let ty = to_rust_type(a.ty(), false, false);
-                output.push(ty);
-            }
-        }
-    }
+    (
+        inputs.join(", "),
+        format!(" -> {}", result_type(&output, options)),
+    )
+}
 
+/// The inputs (identifiers and types) and the output type of a service-side method.
+///
+/// Unlike on the proxy side, the inputs are deserialized so they're owned types.
+fn impl_inputs_output_from_args(
+    args: &[Arg],
+    member_key: &str,
+    type_map: Option<&TypeMap>,
+    version: ZbusVersion,
+) -> (Vec<(String, String)>, String) {
+    let (inputs, outputs): (Vec<_>, Vec<_>) = args
+        .iter()
+        .partition(|a| a.direction() != Some(ArgDirection::Out));
+    let inputs = named_args(inputs)
+        .map(|(ident, a)| {
+            let ty = mapped_rust_type(a, member_key, type_map, false, false, version);
+            (ident, ty)
+        })
+        .collect();
+    let output: Vec<_> = outputs
+        .iter()
+        .map(|a| mapped_rust_type(a, member_key, type_map, false, false, version))
+        .collect();
     let output = match output.len() {
         0 => "()".to_string(),
         1 => output[0].to_string(),
         _ => format!("({})", output.join(", ")),
     };
 
-    (inputs.join(", "), format!(" -> zbus::Result<{output}>"))
+    (inputs, output)
 }
 
-fn parse_signal_args(args: &[Arg]) -> String {
-    let mut inputs = vec!["&self".to_string()];
-    let mut n = 0;
-    let mut gen_name = || {
-        n += 1;
-        format!("arg_{n}")
+/// The Rust identifiers of `args`, along with the arguments.
+///
+/// Unnamed arguments are named after their type, e.g `path_1` for the first unnamed object path,
+/// and arguments sharing the name of a previous one get a numeric suffix, e.g `bar_2`. Generated
+/// identifiers never clash with the names of other arguments.
+fn named_args<'a, I>(args: I) -> impl Iterator<Item = (String, &'a Arg<'a>)>
+where
+    I: IntoIterator<Item = &'a Arg<'a>>,
+{
+    let args: Vec<_> = args.into_iter().collect();
+    let mut used: HashSet<_> = args
+        .iter()
+        .filter_map(|a| a.name().map(to_identifier))
+        .collect();
+    let mut unique = |base: &str, first: usize| {
+        (first..)
+            .map(|n| format!("{base}_{n}"))
+            .find(|ident| used.insert(ident.clone()))
+            .unwrap()
     };
+    let mut seen = HashSet::new();
+    let named: Vec<_> = args
+        .into_iter()
+        .map(|a| {
+            let arg = match a.name().map(to_identifier) {
+                Some(ident) if seen.insert(ident.clone()) => ident,
+                Some(ident) => unique(&ident, 2),
+                None => unique(type_based_name(a.ty()), 1),
+            };
 
-    for a in args {
-        let ty = to_rust_type(a.ty(), true, false);
-        let arg = if let Some(name) = a.name() {
-            to_identifier(name)
-        } else {
-            gen_name()
-        };
+            (arg, a)
+        })
+        .collect();
+
+    named.into_iter()
+}
+
+/// The base of the identifiers of unnamed arguments of type `ty`.
+fn type_based_name(ty: &CompleteType<'_>) -> &'static str {
+    let signature = ty.signature().as_str();
+    if signature.starts_with("a{") {
+        return "dict";
+    }
+    match signature.chars().next() {
+        Some('a') => "array",
+        Some('(') => "structure",
+        Some('y') => "byte",
+        Some('b') => "flag",
+        Some('n' | 'q' | 'i' | 'u' | 'x' | 't' | 'd') => "number",
+        Some('s') => "string",
+        Some('o') => "path",
+        Some('g') => "signature",
+        Some('h') => "fd",
+        Some('v') => "value",
+        _ => "arg",
+    }
+}
+
+/// The Rust type of the argument `a` of the member identified by `member_key`
+/// (`interface.Member`), honoring the user-provided `type_map`.
+///
+/// Mapped types are borrowed where [`to_rust_type`] would borrow the argument.
+fn mapped_rust_type(
+    a: &Arg<'_>,
+    member_key: &str,
+    type_map: Option<&TypeMap>,
+    input: bool,
+    as_ref: bool,
+    version: ZbusVersion,
+) -> String {
+    let key = a.name().map(|name| format!("{member_key}.{name}"));
+    match type_map.and_then(|m| m.lookup(key.as_deref(), a.ty())) {
+        Some(mapped) if input && as_ref => format!("&{mapped}"),
+        Some(mapped) => mapped.to_string(),
+        None => to_rust_type(a.ty(), input, as_ref, version, type_map),
+    }
+}
+
+fn parse_signal_args(
+    args: &[Arg],
+    member_key: &str,
+    type_map: Option<&TypeMap>,
+    version: ZbusVersion,
+) -> String {
+    let mut inputs = vec!["&self".to_string()];
+    for (arg, a) in named_args(args) {
+        let ty = mapped_rust_type(a, member_key, type_map, true, false, version);
         inputs.push(format!("{arg}: {ty}"));
     }
 
     inputs.join(", ")
 }
 
-fn to_rust_type(ty: &CompleteType, input: bool, as_ref: bool) -> String {
-    // can't haz recursive closure, yet
-    fn iter_to_rust_type(
-        it: &mut std::iter::Peekable<std::slice::Iter<'_, u8>>,
+fn to_rust_type(
+    ty: &CompleteType,
+    input: bool,
+    as_ref: bool,
+    version: ZbusVersion,
+    type_map: Option<&TypeMap>,
+) -> String {
+    fn tree_to_rust_type(
+        tree: &SignatureTree,
         input: bool,
         as_ref: bool,
+        version: ZbusVersion,
+        type_map: Option<&TypeMap>,
     ) -> String {
-        let c = it.next().unwrap();
-        match *c as char {
-            u8::SIGNATURE_CHAR => "u8".into(),
-            bool::SIGNATURE_CHAR => "bool".into(),
-            i16::SIGNATURE_CHAR => "i16".into(),
-            u16::SIGNATURE_CHAR => "u16".into(),
-            i32::SIGNATURE_CHAR => "i32".into(),
-            u32::SIGNATURE_CHAR => "u32".into(),
-            i64::SIGNATURE_CHAR => "i64".into(),
-            u64::SIGNATURE_CHAR => "u64".into(),
-            f64::SIGNATURE_CHAR => "f64".into(),
-            // xmlgen accepts 'h' on Windows, only for code generation
-            'h' => (if input {
-                "zbus::zvariant::Fd<'_>"
-            } else {
-                "zbus::zvariant::OwnedFd"
-            })
-            .into(),
-            <&str>::SIGNATURE_CHAR => (if input || as_ref { "&str" } else { "String" }).into(),
-            ObjectPath::SIGNATURE_CHAR => (if input {
-                if as_ref {
-                    "&zbus::zvariant::ObjectPath<'_>"
+        match tree {
+            SignatureTree::Basic(c) => match *c {
+                u8::SIGNATURE_CHAR => "u8".into(),
+                bool::SIGNATURE_CHAR => "bool".into(),
+                i16::SIGNATURE_CHAR => "i16".into(),
+                u16::SIGNATURE_CHAR => "u16".into(),
+                i32::SIGNATURE_CHAR => "i32".into(),
+                u32::SIGNATURE_CHAR => "u32".into(),
+                i64::SIGNATURE_CHAR => "i64".into(),
+                u64::SIGNATURE_CHAR => "u64".into(),
+                f64::SIGNATURE_CHAR => "f64".into(),
+                // xmlgen accepts 'h' on Windows, only for code generation
+                // `Fd` only gained a lifetime in zvariant 4.
+                'h' => (if input && version == ZbusVersion::V3 {
+                    "zbus::zvariant::Fd"
+                } else if input {
+                    "zbus::zvariant::Fd<'_>"
                 } else {
-                    "zbus::zvariant::ObjectPath<'_>"
-                }
-            } else {
-                "zbus::zvariant::OwnedObjectPath"
-            })
-            .into(),
-            Signature::SIGNATURE_CHAR => (if input {
-                if as_ref {
-                    "&zbus::zvariant::Signature<'_>"
+                    "zbus::zvariant::OwnedFd"
+                })
+                .into(),
+                <&str>::SIGNATURE_CHAR => (if input || as_ref { "&str" } else { "String" }).into(),
+                ObjectPath::SIGNATURE_CHAR => (if input {
+                    if as_ref {
+                        "&zbus::zvariant::ObjectPath<'_>"
+                    } else {
+                        "zbus::zvariant::ObjectPath<'_>"
+                    }
                 } else {
-                    "zbus::zvariant::Signature<'_>"
-                }
-            } else {
-                "zbus::zvariant::OwnedSignature"
-            })
-            .into(),
-            VARIANT_SIGNATURE_CHAR => (if input {
+                    "zbus::zvariant::OwnedObjectPath"
+                })
+                .into(),
+                Signature::SIGNATURE_CHAR => (if input {
+                    if as_ref {
+                        "&zbus::zvariant::Signature<'_>"
+                    } else {
+                        "zbus::zvariant::Signature<'_>"
+                    }
+                } else {
+                    "zbus::zvariant::OwnedSignature"
+                })
+                .into(),
+                _ => unimplemented!(),
+            },
+            SignatureTree::Variant => (if input {
                 if as_ref {
                     "&zbus::zvariant::Value<'_>"
                 } else {
@@ -374,51 +2570,43 @@ fn to_rust_type(ty: &CompleteType, input: bool, as_ref: bool) -> String {
                 "zbus::zvariant::OwnedValue"
             })
             .into(),
-            ARRAY_SIGNATURE_CHAR => {
-                let c = it.peek().unwrap();
-                match **c as char {
-                    '{' => format!(
-                        "std::collections::HashMap<{}>",
-                        iter_to_rust_type(it, input, as_ref)
-                    ),
-                    _ => {
-                        let ty = iter_to_rust_type(it, input, as_ref);
-                        if input && as_ref {
-                            format!("&[{ty}]")
-                        } else {
-                            format!("Vec<{ty}>")
-                        }
+            SignatureTree::Array(element) => match &**element {
+                SignatureTree::DictEntry(key, value) => format!(
+                    "std::collections::HashMap<{}, {}>",
+                    tree_to_rust_type(key, input, as_ref, version, type_map),
+                    tree_to_rust_type(value, input, as_ref, version, type_map)
+                ),
+                _ => {
+                    let ty = tree_to_rust_type(element, input, as_ref, version, type_map);
+                    if input && as_ref {
+                        format!("&[{ty}]")
+                    } else {
+                        format!("Vec<{ty}>")
                     }
                 }
-            }
-            c @ STRUCT_SIG_START_CHAR | c @ DICT_ENTRY_SIG_START_CHAR => {
-                let dict = c == '{';
-                let mut vec = vec![];
-                loop {
-                    let c = it.peek().unwrap();
-                    match **c as char {
-                        STRUCT_SIG_END_CHAR | DICT_ENTRY_SIG_END_CHAR => {
-                            // consume the closing character
-                            it.next().unwrap();
-                            break;
-                        }
-                        _ => vec.push(iter_to_rust_type(it, input, as_ref)),
-                    }
+            },
+            SignatureTree::Struct(fields) => {
+                let reference = if as_ref { "&" } else { "" };
+                let mapped = type_map.and_then(|m| m.signatures.get(&tree.to_string()));
+                if let Some(mapped) = mapped {
+                    return format!("{reference}{mapped}");
                 }
-                if dict {
-                    vec.join(", ")
-                } else if vec.len() > 1 {
-                    format!("{}({})", if as_ref { "&" } else { "" }, vec.join(", "))
+                let fields: Vec<_> = fields
+                    .iter()
+                    .map(|f| tree_to_rust_type(f, input, as_ref, version, type_map))
+                    .collect();
+                if fields.len() > 1 {
+                    format!("{reference}({})", fields.join(", "))
                 } else {
-                    format!("{}({},)", if as_ref { "&" } else { "" }, vec[0])
+                    format!("{reference}({},)", fields[0])
                 }
             }
             _ => unimplemented!(),
         }
     }
 
-    let mut it = ty.signature().as_bytes().iter().peekable();
-    iter_to_rust_type(&mut it, input, as_ref)
+    let tree = SignatureTree::try_from(ty.signature()).unwrap();
+    tree_to_rust_type(&tree, input, as_ref, version, type_map)
 }
 
 static KWORDS: &[&str] = &[
@@ -454,75 +2642,83 @@ pub fn pascal_case(s: &str) -> String {
     pascal
 }
 
-fn estimate_type_complexity(it: &mut std::iter::Peekable<std::slice::Iter<'_, u8>>) -> u32 {
-    let mut score = 0;
-    let c = it.next().unwrap();
-    match *c as char {
-        u8::SIGNATURE_CHAR
-        | bool::SIGNATURE_CHAR
-        | i16::SIGNATURE_CHAR
-        | u16::SIGNATURE_CHAR
-        | i32::SIGNATURE_CHAR
-        | u32::SIGNATURE_CHAR
-        | i64::SIGNATURE_CHAR
-        | u64::SIGNATURE_CHAR
-        | f64::SIGNATURE_CHAR
-        | <&str>::SIGNATURE_CHAR => {
-            score += 1;
-        }
-        'h' => score += 10,
-        Signature::SIGNATURE_CHAR | VARIANT_SIGNATURE_CHAR | ObjectPath::SIGNATURE_CHAR => {
-            score *= 10
-        }
-        ARRAY_SIGNATURE_CHAR => {
-            let c = it.peek().unwrap();
-            match **c as char {
-                '{' => {
-                    score *= 10;
-                    score += estimate_type_complexity(it);
+/// Compute the clippy `type_complexity` score of the Rust type generated for `signature` by
+/// [`to_rust_type`], starting at the given nesting level.
+fn type_complexity(
+    signature: &zvariant::Signature<'_>,
+    input: bool,
+    as_ref: bool,
+    nest: u32,
+) -> u32 {
+    // Mirrors the scoring of clippy: every path, tuple or slice costs `10 * nest`, references cost
+    // 1 and generic arguments and tuple/slice elements are one level deeper.
+    fn tree_type_complexity(tree: &SignatureTree, input: bool, as_ref: bool, nest: u32) -> u32 {
+        match tree {
+            SignatureTree::Basic(<&str>::SIGNATURE_CHAR)
+            | SignatureTree::Basic(ObjectPath::SIGNATURE_CHAR)
+            | SignatureTree::Basic(Signature::SIGNATURE_CHAR)
+            | SignatureTree::Variant
+                if input && as_ref =>
+            {
+                1 + 10 * nest
+            }
+            SignatureTree::Basic(<&str>::SIGNATURE_CHAR) if input => 1 + 10 * nest,
+            SignatureTree::Array(element) => match &**element {
+                // `HashMap<K, V>`, the dict entry itself isn't a type.
+                SignatureTree::DictEntry(key, value) => {
+                    10 * nest
+                        + tree_type_complexity(key, input, as_ref, nest + 1)
+                        + tree_type_complexity(value, input, as_ref, nest + 1)
                 }
+                // `&[T]` or `Vec<T>`.
                 _ => {
-                    score += 5 * estimate_type_complexity(it);
+                    let reference = if input && as_ref { 1 } else { 0 };
+                    reference + 10 * nest + tree_type_complexity(element, input, as_ref, nest + 1)
                 }
+            },
+            SignatureTree::Struct(fields) => {
+                let reference = if as_ref { 1 } else { 0 };
+                fields.iter().fold(reference + 10 * nest, |score, field| {
+                    score + tree_type_complexity(field, input, as_ref, nest + 1)
+                })
             }
+            _ => 10 * nest,
         }
-        STRUCT_SIG_START_CHAR | DICT_ENTRY_SIG_START_CHAR => {
-            score += 50;
-            loop {
-                let c = it.peek().unwrap();
-                match **c as char {
-                    STRUCT_SIG_END_CHAR | DICT_ENTRY_SIG_END_CHAR => {
-                        // consume the closing character
-                        it.next().unwrap();
-                        break;
-                    }
-                    _ => score += 5 * estimate_type_complexity(it),
-                }
-            }
-        }
-        _ => {}
-    };
-    score
+    }
+
+    let tree = SignatureTree::try_from(signature).unwrap();
+    tree_type_complexity(&tree, input, as_ref, nest)
 }
 
+/// Format the generated code with `rustfmt`, or the formatter given in the `RUSTFMT` environment
+/// variable.
 fn format_generated_code(generated_code: &str) -> std::io::Result<String> {
     use std::io::{Read, Write};
 
-    let mut process = Command::new("rustfmt")
+    let rustfmt = std::env::var_os("RUSTFMT").unwrap_or_else(|| "rustfmt".into());
+    let mut process = Command::new(rustfmt)
+        // Server skeletons have `async` methods.
+        .args(["--edition", "2021"])
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         // rustfmt may post warnings about features not being enabled on stable rust
         // these can be distracting and are irrevelant to the user, so we hide them
         .stderr(Stdio::null())
         .spawn()?;
-    let rustfmt_stdin = process.stdin.as_mut().unwrap();
+    let mut rustfmt_stdin = process.stdin.take().unwrap();
     let mut rustfmt_stdout = process.stdout.take().unwrap();
     writeln!(rustfmt_stdin)?;
     rustfmt_stdin.write_all(generated_code.as_bytes())?;
+    // Close stdin, for rustfmt to know the input is complete.
+    drop(rustfmt_stdin);
 
-    process.wait()?;
     let mut formatted = String::new();
     rustfmt_stdout.read_to_string(&mut formatted)?;
+    // On failure (e.g. a syntax error), rustfmt doesn't output anything.
+    let status = process.wait()?;
+    if !status.success() {
+        return Err(std::io::Error::other(format!("rustfmt failed ({status})")));
+    }
 
     Ok(formatted)
 }
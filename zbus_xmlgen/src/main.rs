@@ -2,8 +2,9 @@
 
 use std::{
     error::Error,
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{Read, Write},
+    path::{Path, PathBuf},
 };
 
 use clap::Parser;
@@ -12,9 +13,13 @@ use zbus::{
     blocking::{connection, fdo::IntrospectableProxy, Connection},
     names::BusName,
 };
-use zbus_xml::{Interface, Node};
+use zbus_xml::Node;
 
-use zbus_xmlgen::write_interfaces;
+use zbus_xmlgen::{
+    diff_interfaces, interface_module_name, module_names, write_interfaces, write_interfaces_json,
+    write_object_manager_proxy, write_root_module, write_signature_tests, CodeKind, GenModule,
+    GenOptions, TypeMap, OBJECT_MANAGER,
+};
 use zvariant::ObjectPath;
 
 mod cli;
@@ -23,50 +28,143 @@ enum OutputTarget {
     SingleFile(File),
     Stdout,
     MultipleFiles,
+    Directory(PathBuf),
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let args = cli::Args::parse();
+    let mut args = cli::Args::parse();
 
-    let DBusInfo(node, service, path, input_src) = match args.command {
+    let mut type_map = args.type_map.as_deref().map(TypeMap::load).transpose()?;
+    if args.named_structs {
+        type_map.get_or_insert_with(TypeMap::default).named_structs = true;
+    }
+    let mut options = GenOptions {
+        version: args.zbus_version,
+        code: args.code_kind(),
+        kind: args.proxy_kind(),
+        type_map,
+        filter: args.interface_filter(),
+        owned_inputs: args.owned_inputs,
+        error_type: args.error_type.take(),
+        derives: std::mem::take(&mut args.derive),
+        item_attributes: std::mem::take(&mut args.item_attributes),
+        fd_policy: args.fd_policy,
+        ..Default::default()
+    };
+    let default_service = args
+        .default_service
+        .take()
+        .map(BusName::try_from)
+        .transpose()?;
+    let default_path = args
+        .default_path
+        .take()
+        .map(ObjectPath::try_from)
+        .transpose()?;
+    let DBusInfo(node, service, path, input_src, connection) = match args.take_command() {
         cli::Command::System {
             service,
             object_path,
-        } => DBusInfo::new(Connection::system()?, service, object_path)?,
+        } => DBusInfo::new(Connection::system()?, "system bus", service, object_path)?,
         cli::Command::Session {
             service,
             object_path,
-        } => DBusInfo::new(Connection::session()?, service, object_path)?,
+        } => DBusInfo::new(Connection::session()?, "session bus", service, object_path)?,
         cli::Command::Address {
             address,
             service,
             object_path,
         } => DBusInfo::new(
             connection::Builder::address(&*address)?.build()?,
+            &format!("bus at `{address}`"),
             service,
             object_path,
         )?,
+        cli::Command::Diff { old, new } => {
+            let old = Node::from_reader(File::open(old)?)?;
+            let new = Node::from_reader(File::open(new)?)?;
+            let changes = diff_interfaces(old.interfaces(), new.interfaces());
+            for change in &changes {
+                println!("{change}");
+            }
+            if changes.iter().any(|change| change.breaking) {
+                eprintln!("The changes break the generated client code");
+                std::process::exit(1);
+            }
+
+            return Ok(());
+        }
+        cli::Command::Dir { .. } if args.format == cli::Format::Json => {
+            return Err("JSON output isn't supported by the `dir` command".into());
+        }
+        cli::Command::Dir { .. } if args.with_tests => {
+            return Err("`--with-tests` isn't supported by the `dir` command".into());
+        }
+        cli::Command::Dir { path, lib } => {
+            options.service = default_service.map(BusName::into_owned);
+            options.path = default_path.map(ObjectPath::into_owned);
+
+            return generate_dir(&path, lib, &tree_out_dir(&args)?, &options);
+        }
         cli::Command::File { path } => {
-            let input_src = path.file_name().unwrap().to_string_lossy().to_string();
-            let f = File::open(path)?;
-            DBusInfo(Node::from_reader(f)?, None, None, input_src)
+            let (xml, input_src) = read_input(&path)?;
+            let node = Node::from_reader(xml.as_bytes())?;
+            DBusInfo(node, None, None, input_src, None)
         }
     };
 
-    let fdo_iface_prefix = "org.freedesktop.DBus";
-    let (fdo_standard_ifaces, needed_ifaces): (Vec<Interface<'_>>, Vec<Interface<'_>>) = node
-        .interfaces()
-        .iter()
-        .cloned()
-        .partition(|i| i.name().starts_with(fdo_iface_prefix));
+    if args.format == cli::Format::Json {
+        let json = write_interfaces_json(
+            node.interfaces(),
+            options.version,
+            options.type_map.as_ref(),
+        )?;
+        match args.output.as_deref() {
+            None | Some("-") => println!("{json}"),
+            Some(path) => fs::write(path, json)?,
+        }
+
+        return Ok(());
+    }
+
+    if args.recursive {
+        let out_dir = tree_out_dir(&args)?;
+        // Files don't necessarily give the absolute path of their root node.
+        let path = match path {
+            Some(path) => path,
+            None => node
+                .name()
+                .and_then(|name| ObjectPath::try_from(name).ok())
+                .unwrap_or_else(|| ObjectPath::from_static_str_unchecked("/")),
+        };
+
+        options.service = service.map(BusName::into_owned);
+        options.path = Some(path.into_owned());
+
+        return generate_tree(
+            &node,
+            connection.as_ref(),
+            &out_dir.join("mod.rs"),
+            &input_src,
+            &options,
+        );
+    }
+
+    options.service = default_service.or(service).map(BusName::into_owned);
+    options.path = default_path.or(path).map(ObjectPath::into_owned);
+    let (fdo_standard_ifaces, needed_ifaces) = options.filter.split(node.interfaces());
 
     if !fdo_standard_ifaces.is_empty() {
         eprintln!("Skipping `org.freedesktop.DBus` interfaces, please use https://docs.rs/zbus/latest/zbus/fdo/index.html")
     }
 
-    let mut output_target = match args.output.as_deref() {
-        Some("-") => OutputTarget::Stdout,
-        Some(path) => {
+    let mut output_target = match (args.output.as_deref(), &args.output_dir) {
+        (_, Some(dir)) => {
+            fs::create_dir_all(dir)?;
+            OutputTarget::Directory(dir.clone())
+        }
+        (Some("-"), None) => OutputTarget::Stdout,
+        (Some(path), None) => {
             let file = OpenOptions::new()
                 .create(true)
                 .truncate(true)
@@ -77,17 +175,52 @@ fn main() -> Result<(), Box<dyn Error>> {
         _ => OutputTarget::MultipleFiles,
     };
 
-    for interface in needed_ifaces {
-        let output = write_interfaces(
-            &[interface.clone()],
+    // The object manager proxy is typed after all the other interfaces, so it's generated on its
+    // own.
+    let (object_manager, fdo_standard_ifaces): (Vec<_>, Vec<_>) = fdo_standard_ifaces
+        .into_iter()
+        .partition(|i| options.code == CodeKind::Proxy && i.name() == OBJECT_MANAGER);
+    let xml = if args.with_tests {
+        let mut xml = vec![];
+        node.to_writer(&mut xml)?;
+        Some(String::from_utf8(xml)?)
+    } else {
+        None
+    };
+    let mut outputs = Vec::with_capacity(needed_ifaces.len() + object_manager.len());
+    for interface in &needed_ifaces {
+        let mut output = write_interfaces(
+            std::slice::from_ref(interface),
             &fdo_standard_ifaces,
-            service.clone(),
-            path.clone(),
             &input_src,
             env!("CARGO_BIN_NAME"),
             env!("CARGO_PKG_VERSION"),
+            &options,
+        )?;
+        if let Some(xml) = &xml {
+            output.push('\n');
+            output.push_str(&write_signature_tests(
+                xml,
+                std::slice::from_ref(interface),
+                options.version,
+                options.type_map.as_ref(),
+            )?);
+        }
+        outputs.push((interface, output));
+    }
+    for interface in &object_manager {
+        let output = write_object_manager_proxy(
+            interface,
+            &needed_ifaces,
+            &input_src,
+            env!("CARGO_BIN_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            &options,
         )?;
+        outputs.push((interface, output));
+    }
 
+    for (interface, output) in outputs {
         let interface_name = interface.name();
         match output_target {
             OutputTarget::Stdout => println!("{}", output),
@@ -98,53 +231,275 @@ fn main() -> Result<(), Box<dyn Error>> {
             OutputTarget::MultipleFiles => {
                 let filename = interface_name
                     .split('.')
-                    .last()
+                    .next_back()
                     .expect("Failed to split name");
                 let filename = to_snakecase(filename);
                 std::fs::write(format!("{}.rs", &filename), output)?;
                 println!("Generated code for `{}` in {}.rs", interface_name, filename);
             }
+            OutputTarget::Directory(ref dir) => {
+                let module = interface_module_name(interface);
+                fs::write(dir.join(format!("{module}.rs")), output)?;
+                println!("Generated code for `{}` in {}.rs", interface_name, module);
+            }
         };
     }
 
+    if let OutputTarget::Directory(dir) = output_target {
+        let modules: Vec<_> = needed_ifaces
+            .iter()
+            .chain(&object_manager)
+            .map(|interface| GenModule {
+                name: interface_module_name(interface),
+                interfaces: std::slice::from_ref(interface),
+            })
+            .collect();
+        let root = write_root_module(
+            &modules,
+            &input_src,
+            env!("CARGO_BIN_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            options.code,
+            options.kind,
+        )?;
+        fs::write(dir.join("mod.rs"), root)?;
+        println!("Generated mod.rs declaring {} modules", modules.len());
+    }
+
     Ok(())
 }
 
+/// Read the XML document at `input`, a file path, `-` for stdin or an HTTP(S) URL.
+///
+/// Returns the document along with a description of its source, for the generated code.
+fn read_input(input: &str) -> Result<(String, String), Box<dyn Error>> {
+    if input == "-" {
+        let mut xml = String::new();
+        std::io::stdin().read_to_string(&mut xml)?;
+
+        Ok((xml, "stdin".to_string()))
+    } else if input.starts_with("http://") || input.starts_with("https://") {
+        #[cfg(feature = "http")]
+        {
+            let xml = ureq::get(input)
+                .call()
+                .map_err(|e| format!("Failed to fetch `{input}`: {e}"))?
+                .into_string()?;
+
+            Ok((xml, input.to_string()))
+        }
+        #[cfg(not(feature = "http"))]
+        Err(format!("Can't fetch `{input}`: built without the `http` feature").into())
+    } else {
+        let path = Path::new(input);
+        let input_src = path.file_name().unwrap().to_string_lossy().to_string();
+
+        Ok((fs::read_to_string(path)?, input_src))
+    }
+}
+
+/// The directory to write a module tree to, from `--output-dir` or `--output`.
+fn tree_out_dir(args: &cli::Args) -> Result<PathBuf, Box<dyn Error>> {
+    match (&args.output_dir, args.output.as_deref()) {
+        (Some(dir), _) => Ok(dir.clone()),
+        (None, Some("-")) => Err("a module tree can't be printed to stdout".into()),
+        (None, Some(dir)) => Ok(PathBuf::from(dir)),
+        (None, None) => Ok(PathBuf::from(".")),
+    }
+}
+
+/// Generate a module tree for the interface XML files in `dir`, one module per file.
+fn generate_dir(
+    dir: &Path,
+    lib: bool,
+    out_dir: &Path,
+    options: &GenOptions,
+) -> Result<(), Box<dyn Error>> {
+    let mut files = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    files.retain(|path| path.extension().is_some_and(|ext| ext == "xml"));
+    files.sort();
+    if files.is_empty() {
+        return Err(format!("No XML files found in `{}`", dir.display()).into());
+    }
+    let stems: Vec<String> = files
+        .iter()
+        .map(|path| path.file_stem().unwrap().to_string_lossy().to_string())
+        .collect();
+    let names = module_names(&stems.iter().map(String::as_str).collect::<Vec<_>>());
+
+    fs::create_dir_all(out_dir)?;
+    let mut modules = Vec::with_capacity(files.len());
+    for (file, name) in files.iter().zip(names) {
+        let node = Node::from_reader(File::open(file)?)?;
+        let (fdo_standard_ifaces, needed_ifaces) = options.filter.split(node.interfaces());
+        if needed_ifaces.is_empty() {
+            eprintln!(
+                "Skipping `{}`, it has no interfaces to generate code for",
+                file.display()
+            );
+            continue;
+        }
+
+        let input_src = file.file_name().unwrap().to_string_lossy().to_string();
+        let output = write_interfaces(
+            &needed_ifaces,
+            &fdo_standard_ifaces,
+            &input_src,
+            env!("CARGO_BIN_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            options,
+        )?;
+        fs::write(out_dir.join(format!("{name}.rs")), output)?;
+        println!("Generated code for `{}` in {}.rs", input_src, name);
+
+        modules.push((name, needed_ifaces));
+    }
+
+    let modules: Vec<_> = modules
+        .iter()
+        .map(|(name, interfaces)| GenModule {
+            name: name.clone(),
+            interfaces,
+        })
+        .collect();
+    let root = write_root_module(
+        &modules,
+        &dir.display().to_string(),
+        env!("CARGO_BIN_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        options.code,
+        options.kind,
+    )?;
+    let root_name = if lib { "lib.rs" } else { "mod.rs" };
+    fs::write(out_dir.join(root_name), root)?;
+    println!("Generated {root_name} declaring {} modules", modules.len());
+
+    Ok(())
+}
+
+/// Generate a module tree for the object at the `path` option and its children, one module per
+/// object.
+///
+/// The children of `node` that aren't described inline are introspected through `connection`, if
+/// there is one.
+fn generate_tree(
+    node: &Node<'_>,
+    connection: Option<&Connection>,
+    module_file: &Path,
+    input_src: &str,
+    options: &GenOptions,
+) -> Result<(), Box<dyn Error>> {
+    let path = options.path.as_ref().expect("object tree without a path");
+    let (fdo_standard_ifaces, needed_ifaces) = options.filter.split(node.interfaces());
+    let mut output = if needed_ifaces.is_empty() {
+        format!("//! D-Bus objects under `{path}`.\n")
+    } else {
+        write_interfaces(
+            &needed_ifaces,
+            &fdo_standard_ifaces,
+            input_src,
+            env!("CARGO_BIN_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            options,
+        )?
+    };
+
+    let dir = module_file
+        .parent()
+        .expect("module file without a parent directory");
+    let mut children = vec![];
+    for child in node.nodes() {
+        let Some(name) = child.name() else {
+            continue;
+        };
+        let child_path = if path.as_str() == "/" {
+            format!("/{name}")
+        } else {
+            format!("{path}/{name}")
+        };
+        let child_path = ObjectPath::try_from(child_path)?;
+        let child = match (connection, &options.service) {
+            (Some(connection), Some(service))
+                if child.interfaces().is_empty() && child.nodes().is_empty() =>
+            {
+                introspect(connection, service, &child_path)?
+            }
+            _ => child.clone(),
+        };
+
+        let module = module_names(&[name]).remove(0);
+        let child_file = if child.nodes().is_empty() {
+            dir.join(format!("{module}.rs"))
+        } else {
+            dir.join(&module).join("mod.rs")
+        };
+        let input_src = format!("{input_src} (object '{child_path}')");
+        let options = GenOptions {
+            path: Some(child_path),
+            ..options.clone()
+        };
+        generate_tree(&child, connection, &child_file, &input_src, &options)?;
+        children.push(module);
+    }
+    if !children.is_empty() {
+        output.push('\n');
+        for module in children {
+            output.push_str(&format!("pub mod {module};\n"));
+        }
+    }
+
+    fs::create_dir_all(dir)?;
+    fs::write(module_file, output)?;
+    println!("Generated code for `{}` in {}", path, module_file.display());
+
+    Ok(())
+}
+
+/// Introspect the object at `path` of `service`.
+fn introspect(
+    connection: &Connection,
+    service: &BusName<'_>,
+    path: &ObjectPath<'_>,
+) -> Result<Node<'static>, Box<dyn Error>> {
+    let xml = IntrospectableProxy::builder(connection)
+        .destination(service.clone())?
+        .path(path.clone())?
+        .build()?
+        .introspect()?;
+
+    Ok(Node::from_reader(xml.as_bytes())?)
+}
+
 struct DBusInfo<'a>(
     Node<'a>,
     Option<BusName<'a>>,
     Option<ObjectPath<'a>>,
     String,
+    Option<Connection>,
 );
 
 impl<'a> DBusInfo<'a> {
     fn new(
         connection: Connection,
+        bus: &str,
         service: String,
         object_path: String,
     ) -> Result<Self, Box<dyn Error>> {
         let service: BusName<'_> = service.try_into()?;
         let path: ObjectPath<'_> = object_path.try_into()?;
 
-        let input_src = format!(
-            "Interface '{}' from service '{}' on system bus",
-            path, service,
-        );
+        let input_src = format!("Interface '{}' from service '{}' on {}", path, service, bus);
 
-        let xml = IntrospectableProxy::builder(&connection)
-            .destination(service.clone())
-            .expect("invalid destination")
-            .path(path.clone())
-            .expect("invalid path")
-            .build()
-            .unwrap()
-            .introspect()?;
+        let node = introspect(&connection, &service, &path)?;
 
         Ok(DBusInfo(
-            Node::from_reader(xml.as_bytes())?,
+            node,
             Some(service),
             Some(path),
             input_src,
+            Some(connection),
         ))
     }
 }
@@ -1,6 +1,8 @@
+/// A sample interface.
 #[proxy(interface = "com.example.SampleInterface0", assume_defaults = true)]
 trait SampleInterface0 {
     /// BarplexSig method
+    #[allow(clippy::type_complexity)]
     fn barplex_sig(
         &self,
         rule: &(
@@ -16,13 +18,18 @@ trait SampleInterface0 {
         ),
     ) -> zbus::Result<Vec<(String, zbus::zvariant::OwnedObjectPath)>>;
 
-    /// Bazic method
+    /// Bazic the bar and the foo.
+    ///
+    /// Returns the baz
+    /// and the foz.
+    #[allow(clippy::type_complexity)]
     fn bazic(&self, bar: &(i32, i32), foo: &(i32,)) -> zbus::Result<((i32, i32), Vec<(i32,)>)>;
 
     /// Bazify method
     fn bazify(&self, bar: &(i32, i32, u32)) -> zbus::Result<zbus::zvariant::OwnedValue>;
 
     /// Frobate method
+    #[deprecated(note = "deprecated by the D-Bus interface")]
     fn frobate(
         &self,
         foz: i32,
@@ -30,6 +37,7 @@ trait SampleInterface0 {
     ) -> zbus::Result<(String, std::collections::HashMap<u32, String>)>;
 
     /// MogrifyMe method
+    #[zbus(no_reply)]
     fn mogrify_me(&self, bar: &(i32, i32, &[&zbus::zvariant::Value<'_>])) -> zbus::Result<()>;
 
     /// Odyssey method
@@ -68,20 +76,20 @@ trait SampleInterface0 {
     #[zbus(signal)]
     fn signal_value(&self, value: zbus::zvariant::Value<'_>) -> zbus::Result<()>;
 
-    /// Bar property
+    /// The bar.
     #[zbus(property)]
     fn bar(&self) -> zbus::Result<u8>;
     #[zbus(property)]
     fn set_bar(&self, value: u8) -> zbus::Result<()>;
 
     /// Foo-Bar property
-    #[zbus(property, name = "Foo-Bar")]
+    #[zbus(property(emits_changed_signal = "invalidates"), name = "Foo-Bar")]
     fn foo_bar(&self) -> zbus::Result<u8>;
-    #[zbus(property, name = "Foo-Bar")]
+    #[zbus(property(emits_changed_signal = "invalidates"), name = "Foo-Bar")]
     fn set_foo_bar(&self, value: u8) -> zbus::Result<()>;
 
     /// Matryoshkas property
-    #[zbus(property)]
+    #[zbus(property(emits_changed_signal = "false"))]
     #[allow(clippy::type_complexity)]
     fn matryoshkas(
         &self,
@@ -95,3 +103,13 @@ trait SampleInterface0 {
         )>,
     >;
 }
+
+impl SampleInterface0Proxy<'_> {
+    /// The name of the D-Bus interface.
+    pub const INTERFACE: &'static str = "com.example.SampleInterface0";
+}
+
+impl SampleInterface0ProxyBlocking<'_> {
+    /// The name of the D-Bus interface.
+    pub const INTERFACE: &'static str = "com.example.SampleInterface0";
+}
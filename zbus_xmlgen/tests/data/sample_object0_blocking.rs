@@ -0,0 +1,114 @@
+/// A sample interface.
+#[proxy(
+    interface = "com.example.SampleInterface0",
+    assume_defaults = true,
+    gen_async = false
+)]
+trait SampleInterface0 {
+    /// BarplexSig method
+    #[allow(clippy::type_complexity)]
+    fn barplex_sig(
+        &self,
+        rule: &(
+            &[i32],
+            i32,
+            std::collections::HashMap<&str, &str>,
+            i32,
+            &[i32],
+            i32,
+            &[&str],
+            i32,
+            bool,
+        ),
+    ) -> zbus::Result<Vec<(String, zbus::zvariant::OwnedObjectPath)>>;
+
+    /// Bazic the bar and the foo.
+    ///
+    /// Returns the baz
+    /// and the foz.
+    #[allow(clippy::type_complexity)]
+    fn bazic(&self, bar: &(i32, i32), foo: &(i32,)) -> zbus::Result<((i32, i32), Vec<(i32,)>)>;
+
+    /// Bazify method
+    fn bazify(&self, bar: &(i32, i32, u32)) -> zbus::Result<zbus::zvariant::OwnedValue>;
+
+    /// Frobate method
+    #[deprecated(note = "deprecated by the D-Bus interface")]
+    fn frobate(
+        &self,
+        foz: i32,
+        foo: i32,
+    ) -> zbus::Result<(String, std::collections::HashMap<u32, String>)>;
+
+    /// MogrifyMe method
+    #[zbus(no_reply)]
+    fn mogrify_me(&self, bar: &(i32, i32, &[&zbus::zvariant::Value<'_>])) -> zbus::Result<()>;
+
+    /// Odyssey method
+    #[allow(clippy::too_many_arguments)]
+    fn odyssey(
+        &self,
+        odysseus: i32,
+        penelope: &str,
+        telemachus: u32,
+        circe: i32,
+        athena: bool,
+        polyphemus: i32,
+        calypso: &zbus::zvariant::Value<'_>,
+    ) -> zbus::Result<()>;
+
+    /// Changed signal
+    #[zbus(signal)]
+    fn changed(&self, new_value: bool) -> zbus::Result<()>;
+
+    /// Changed2 signal
+    #[zbus(signal)]
+    fn changed2(&self, new_value: bool, new_value2: bool) -> zbus::Result<()>;
+
+    /// SignalArrayOfStrings signal
+    #[zbus(signal)]
+    fn signal_array_of_strings(&self, array: Vec<&str>) -> zbus::Result<()>;
+
+    /// SignalDictStringToValue signal
+    #[zbus(signal)]
+    fn signal_dict_string_to_value(
+        &self,
+        dict: std::collections::HashMap<&str, zbus::zvariant::Value<'_>>,
+    ) -> zbus::Result<()>;
+
+    /// SignalValue signal
+    #[zbus(signal)]
+    fn signal_value(&self, value: zbus::zvariant::Value<'_>) -> zbus::Result<()>;
+
+    /// The bar.
+    #[zbus(property)]
+    fn bar(&self) -> zbus::Result<u8>;
+    #[zbus(property)]
+    fn set_bar(&self, value: u8) -> zbus::Result<()>;
+
+    /// Foo-Bar property
+    #[zbus(property(emits_changed_signal = "invalidates"), name = "Foo-Bar")]
+    fn foo_bar(&self) -> zbus::Result<u8>;
+    #[zbus(property(emits_changed_signal = "invalidates"), name = "Foo-Bar")]
+    fn set_foo_bar(&self, value: u8) -> zbus::Result<()>;
+
+    /// Matryoshkas property
+    #[zbus(property(emits_changed_signal = "false"))]
+    #[allow(clippy::type_complexity)]
+    fn matryoshkas(
+        &self,
+    ) -> zbus::Result<
+        Vec<(
+            zbus::zvariant::OwnedObjectPath,
+            i32,
+            Vec<String>,
+            u64,
+            std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
+        )>,
+    >;
+}
+
+impl SampleInterface0Proxy<'_> {
+    /// The name of the D-Bus interface.
+    pub const INTERFACE: &'static str = "com.example.SampleInterface0";
+}
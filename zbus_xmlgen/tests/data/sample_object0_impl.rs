@@ -0,0 +1,147 @@
+/// A sample interface.
+pub struct SampleInterface0;
+
+#[interface(name = "com.example.SampleInterface0")]
+impl SampleInterface0 {
+    /// BarplexSig method
+    #[allow(clippy::type_complexity)]
+    async fn barplex_sig(
+        &self,
+        rule: (
+            Vec<i32>,
+            i32,
+            std::collections::HashMap<String, String>,
+            i32,
+            Vec<i32>,
+            i32,
+            Vec<String>,
+            i32,
+            bool,
+        ),
+    ) -> zbus::fdo::Result<Vec<(String, zbus::zvariant::OwnedObjectPath)>> {
+        todo!()
+    }
+
+    /// Bazic the bar and the foo.
+    ///
+    /// Returns the baz
+    /// and the foz.
+    #[allow(clippy::type_complexity)]
+    async fn bazic(
+        &self,
+        bar: (i32, i32),
+        foo: (i32,),
+    ) -> zbus::fdo::Result<((i32, i32), Vec<(i32,)>)> {
+        todo!()
+    }
+
+    /// Bazify method
+    async fn bazify(&self, bar: (i32, i32, u32)) -> zbus::fdo::Result<zbus::zvariant::OwnedValue> {
+        todo!()
+    }
+
+    /// Frobate method
+    async fn frobate(
+        &self,
+        foz: i32,
+        foo: i32,
+    ) -> zbus::fdo::Result<(String, std::collections::HashMap<u32, String>)> {
+        todo!()
+    }
+
+    /// MogrifyMe method
+    async fn mogrify_me(
+        &self,
+        bar: (i32, i32, Vec<zbus::zvariant::OwnedValue>),
+    ) -> zbus::fdo::Result<()> {
+        todo!()
+    }
+
+    /// Odyssey method
+    #[allow(clippy::too_many_arguments)]
+    async fn odyssey(
+        &self,
+        odysseus: i32,
+        penelope: String,
+        telemachus: u32,
+        circe: i32,
+        athena: bool,
+        polyphemus: i32,
+        calypso: zbus::zvariant::OwnedValue,
+    ) -> zbus::fdo::Result<()> {
+        todo!()
+    }
+
+    /// Changed signal
+    #[zbus(signal)]
+    async fn changed(
+        ctxt: &zbus::object_server::SignalContext<'_>,
+        new_value: bool,
+    ) -> zbus::Result<()>;
+
+    /// Changed2 signal
+    #[zbus(signal)]
+    async fn changed2(
+        ctxt: &zbus::object_server::SignalContext<'_>,
+        new_value: bool,
+        new_value2: bool,
+    ) -> zbus::Result<()>;
+
+    /// SignalArrayOfStrings signal
+    #[zbus(signal)]
+    async fn signal_array_of_strings(
+        ctxt: &zbus::object_server::SignalContext<'_>,
+        array: &[&str],
+    ) -> zbus::Result<()>;
+
+    /// SignalDictStringToValue signal
+    #[zbus(signal)]
+    async fn signal_dict_string_to_value(
+        ctxt: &zbus::object_server::SignalContext<'_>,
+        dict: &std::collections::HashMap<&str, &zbus::zvariant::Value<'_>>,
+    ) -> zbus::Result<()>;
+
+    /// SignalValue signal
+    #[zbus(signal)]
+    async fn signal_value(
+        ctxt: &zbus::object_server::SignalContext<'_>,
+        value: &zbus::zvariant::Value<'_>,
+    ) -> zbus::Result<()>;
+
+    /// The bar.
+    #[zbus(property)]
+    async fn bar(&self) -> zbus::fdo::Result<u8> {
+        todo!()
+    }
+    #[zbus(property)]
+    async fn set_bar(&mut self, value: u8) -> zbus::fdo::Result<()> {
+        todo!()
+    }
+
+    /// Foo-Bar property
+    #[zbus(property(emits_changed_signal = "invalidates"), name = "Foo-Bar")]
+    async fn foo_bar(&self) -> zbus::fdo::Result<u8> {
+        todo!()
+    }
+    #[zbus(property(emits_changed_signal = "invalidates"), name = "Foo-Bar")]
+    async fn set_foo_bar(&mut self, value: u8) -> zbus::fdo::Result<()> {
+        todo!()
+    }
+
+    /// Matryoshkas property
+    #[zbus(property(emits_changed_signal = "false"))]
+    #[allow(clippy::type_complexity)]
+    async fn matryoshkas(
+        &self,
+    ) -> zbus::fdo::Result<
+        Vec<(
+            zbus::zvariant::OwnedObjectPath,
+            i32,
+            Vec<String>,
+            u64,
+            std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
+        )>,
+    > {
+        todo!()
+    }
+}
@@ -0,0 +1,11 @@
+//! # D-Bus interface proxies
+//!
+//! This code was generated by `zbus-xmlgen` `0.0.0` from D-Bus introspection data.
+//! Source: `interfaces`.
+//!
+//! Each module holds the proxies for one or more interfaces. The proxy types are
+//! re-exported here, unless several modules have proxies of the same name.
+
+pub mod sample_object0;
+
+pub use sample_object0::{SampleInterface0Proxy, SampleInterface0ProxyBlocking};
@@ -2,10 +2,20 @@ use pretty_assertions::assert_eq;
 use std::{env, error::Error, io::Write, path::Path};
 
 use zbus_xml::Node;
-use zbus_xmlgen::GenTrait;
+use zbus_xmlgen::{
+    diff_interfaces, generate_from_xml, interface_module_name, module_names, write_interfaces,
+    write_interfaces_json, write_root_module, write_signature_tests, CodeKind, FdPolicy, GenImpl,
+    GenModule, GenOptions, GenTrait, InterfaceFilter, ProxyKind, TypeMap, ZbusVersion,
+};
 
 macro_rules! gen_diff {
-    ($infile:literal, $outfile:literal) => {{
+    ($infile:literal, $outfile:literal) => {
+        gen_diff!($infile, $outfile, ZbusVersion::V4)
+    };
+    ($infile:literal, $outfile:literal, $version:expr) => {
+        gen_diff!($infile, $outfile, $version, ProxyKind::Both)
+    };
+    ($infile:literal, $outfile:literal, $version:expr, $kind:expr) => {{
         let input = include_str!(concat!("data/", $infile));
         let expected = include_str!(concat!("data/", $outfile));
         #[cfg(windows)]
@@ -13,9 +23,12 @@ macro_rules! gen_diff {
         let node = Node::from_reader(input.as_bytes())?;
         let gen = GenTrait {
             interface: &node.interfaces()[0],
-            path: None,
-            service: None,
             format: true,
+            options: &GenOptions {
+                version: $version,
+                kind: $kind,
+                ..Default::default()
+            },
         }
         .to_string();
 
@@ -42,3 +55,797 @@ macro_rules! gen_diff {
 fn sample_object0() -> Result<(), Box<dyn Error>> {
     gen_diff!("sample_object0.xml", "sample_object0.rs")
 }
+
+#[test]
+fn sample_object0_zbus_3() -> Result<(), Box<dyn Error>> {
+    gen_diff!(
+        "sample_object0.xml",
+        "sample_object0_v3.rs",
+        ZbusVersion::V3
+    )
+}
+
+#[test]
+fn sample_object0_blocking() -> Result<(), Box<dyn Error>> {
+    gen_diff!(
+        "sample_object0.xml",
+        "sample_object0_blocking.rs",
+        ZbusVersion::V4,
+        ProxyKind::Blocking
+    )
+}
+
+#[test]
+fn sample_object0_impl() -> Result<(), Box<dyn Error>> {
+    let input = include_str!("data/sample_object0.xml");
+    let expected = include_str!("data/sample_object0_impl.rs");
+    #[cfg(windows)]
+    let expected = expected.replace("\r\n", "\n");
+    let node = Node::from_reader(input.as_bytes())?;
+    let gen = GenImpl {
+        interface: &node.interfaces()[0],
+        format: true,
+        options: &GenOptions {
+            code: CodeKind::Skeleton,
+            ..Default::default()
+        },
+    }
+    .to_string();
+
+    assert_eq!(gen, expected);
+    Ok(())
+}
+
+#[test]
+fn sample_object0_mock() -> Result<(), Box<dyn Error>> {
+    let input = include_str!("data/sample_object0.xml");
+    let node = Node::from_reader(input.as_bytes())?;
+    let mock = GenImpl {
+        interface: &node.interfaces()[0],
+        format: true,
+        options: &GenOptions {
+            code: CodeKind::Mock,
+            ..Default::default()
+        },
+    }
+    .to_string();
+
+    assert!(mock.contains("pub struct SampleInterface0Mock {"));
+    assert!(mock.contains("impl Default for SampleInterface0Mock {"));
+    assert!(mock.contains("impl SampleInterface0Mock {"));
+    // Calls are recorded.
+    assert!(mock.contains("pub enum SampleInterface0Call {"));
+    assert!(mock.contains("Frobate { foz: i32, foo: i32 },"));
+    assert!(mock.contains("SetBar(u8),"));
+    assert!(mock.contains(".push(SampleInterface0Call::Frobate { foz, foo });"));
+    // Methods without a reply succeed by default, others fail until given a reply.
+    assert!(mock.contains("(self.frobate_reply)()"));
+    assert!(mock.contains("mogrify_me_reply: Box::new(|| Ok(())),"));
+    assert!(mock.contains("\"no reply set for `Frobate`\""));
+    assert!(!mock.contains("todo!()"));
+
+    let module = GenModule {
+        name: "sample_object0".to_string(),
+        interfaces: node.interfaces(),
+    };
+    let root = write_root_module(
+        &[module],
+        "interfaces",
+        "zbus-xmlgen",
+        "0.0.0",
+        CodeKind::Mock,
+        ProxyKind::Both,
+    )?;
+    assert!(root.contains("pub use sample_object0::{SampleInterface0Call, SampleInterface0Mock};"));
+
+    Ok(())
+}
+
+#[test]
+fn dict_structs() -> Result<(), Box<dyn Error>> {
+    let input = include_str!("data/sample_dicts.xml");
+    let node = Node::from_reader(input.as_bytes())?;
+    let interface = &node.interfaces()[0];
+    let proxy = GenTrait {
+        interface,
+        format: true,
+        options: &GenOptions::default(),
+    }
+    .to_string();
+
+    assert!(proxy.contains("options: &ScreenshotOptions"));
+    assert!(proxy.contains("pub struct ScreenshotOptions {"));
+    assert!(proxy.contains("pub handle_token: Option<String>,"));
+    assert!(proxy.contains("pub modal: Option<bool>,"));
+    assert!(proxy.contains("#[zvariant(rename = \"extra-data\")]"));
+    assert!(proxy.contains("pub extra_data: Option<zbus::zvariant::OwnedValue>,"));
+    assert!(proxy.contains("fn metadata(&self) -> zbus::Result<Metadata>;"));
+    assert!(proxy.contains("pub size: Option<(u32, u32)>,"));
+    // Dictionaries without declared keys are left alone.
+    assert!(!proxy.contains("ScreenshotResults"));
+
+    Ok(())
+}
+
+#[test]
+fn value_enums() -> Result<(), Box<dyn Error>> {
+    let input = include_str!("data/sample_enums.xml");
+    let node = Node::from_reader(input.as_bytes())?;
+    let interface = &node.interfaces()[0];
+    let proxy = GenTrait {
+        interface,
+        format: true,
+        options: &GenOptions::default(),
+    }
+    .to_string();
+
+    assert!(proxy.contains("fn set_state(&self, state: &SetStateState, flags: u32)"));
+    assert!(proxy.contains(
+        "pub enum SetStateState {
+    Off,
+    Suspended,
+    On,
+}"
+    ));
+    assert!(proxy.contains("Self::Suspended => 2,"));
+    assert!(proxy.contains("5 => Ok(Self::On),"));
+    assert!(proxy.contains("impl TryFrom<u32> for SetStateState {"));
+    assert!(proxy.contains("<u32 as zbus::zvariant::Type>::signature()"));
+    assert!(proxy.contains("fn profile(&self) -> zbus::Result<Profile>;"));
+    assert!(proxy.contains("fn set_profile(&self, value: Profile)"));
+    assert!(proxy.contains("pub fn value(self) -> &'static str {"));
+    assert!(proxy.contains("Self::PowerSaver => \"power-saver\","));
+    assert!(proxy.contains("\"balanced\" => Ok(Self::Balanced),"));
+    // Values that don't match the type are ignored.
+    assert!(!proxy.contains("SetStateFlags"));
+
+    let server = GenImpl {
+        interface,
+        format: true,
+        options: &GenOptions {
+            code: CodeKind::Skeleton,
+            ..Default::default()
+        },
+    }
+    .to_string();
+    assert!(server.contains("state: SetStateState"));
+    assert!(server.contains("pub enum Profile {"));
+
+    Ok(())
+}
+
+#[test]
+fn derives_and_attributes() -> Result<(), Box<dyn Error>> {
+    let options = GenOptions {
+        // Derives the structs have already are skipped.
+        derives: vec!["Clone".into(), "Debug".into(), "zvariant::Type".into()],
+        item_attributes: vec![
+            "#[allow(clippy::type_complexity)]".into(),
+            "allow(missing_docs)".into(),
+        ],
+        ..Default::default()
+    };
+    let input = include_str!("data/sample_dicts.xml");
+    let node = Node::from_reader(input.as_bytes())?;
+    let proxy = GenTrait {
+        interface: &node.interfaces()[0],
+        format: true,
+        options: &options,
+    }
+    .to_string();
+
+    let attributes = "#[allow(clippy::type_complexity)]\n#[allow(missing_docs)]\n";
+    assert!(proxy.contains(&format!("{attributes}trait Screenshot {{")));
+    assert!(proxy.contains(&format!(
+        "{attributes}#[derive(
+    Debug,
+    Default,
+    zbus::zvariant::DeserializeDict,
+    zbus::zvariant::SerializeDict,
+    zbus::zvariant::Type,
+    Clone,
+)]
+#[zvariant(signature = \"a{{sv}}\")]
+pub struct ScreenshotOptions {{"
+    )));
+
+    Ok(())
+}
+
+#[test]
+fn type_map() -> Result<(), Box<dyn Error>> {
+    let type_map: TypeMap = toml::from_str(
+        r#"
+        [signatures]
+        "a{sv}" = "crate::Dict"
+        "(ii)" = "crate::Point"
+
+        [members]
+        "com.example.SampleInterface0.Bazic.bar" = "crate::Origin"
+        "com.example.SampleInterface0.Bar" = "crate::Byte"
+        "#,
+    )?;
+    let json: TypeMap = serde_json::from_str(
+        r#"{
+            "signatures": { "a{sv}": "crate::Dict", "(ii)": "crate::Point" },
+            "members": {
+                "com.example.SampleInterface0.Bazic.bar": "crate::Origin",
+                "com.example.SampleInterface0.Bar": "crate::Byte"
+            }
+        }"#,
+    )?;
+    assert_eq!(type_map, json);
+
+    let input = include_str!("data/sample_object0.xml");
+    let node = Node::from_reader(input.as_bytes())?;
+    let interface = &node.interfaces()[0];
+    let proxy = GenTrait {
+        interface,
+        format: true,
+        options: &GenOptions {
+            type_map: Some(type_map.clone()),
+            ..Default::default()
+        },
+    }
+    .to_string();
+    assert!(proxy.contains("bar: &crate::Origin"));
+    assert!(proxy.contains("-> zbus::Result<(crate::Point, Vec<(i32,)>)>"));
+    assert!(proxy.contains("fn signal_dict_string_to_value(&self, dict: crate::Dict)"));
+    assert!(proxy.contains("fn bar(&self) -> zbus::Result<crate::Byte>"));
+    assert!(proxy.contains("fn set_bar(&self, value: crate::Byte)"));
+    // Other members are left alone.
+    assert!(proxy.contains("fn foo_bar(&self) -> zbus::Result<u8>"));
+
+    let skeleton = GenImpl {
+        interface,
+        format: true,
+        options: &GenOptions {
+            code: CodeKind::Skeleton,
+            type_map: Some(type_map),
+            ..Default::default()
+        },
+    }
+    .to_string();
+    assert!(skeleton.contains("bar: crate::Origin"));
+    assert!(skeleton.contains("dict: &crate::Dict"));
+
+    Ok(())
+}
+
+#[test]
+fn named_structs() -> Result<(), Box<dyn Error>> {
+    let type_map = TypeMap {
+        named_structs: true,
+        ..Default::default()
+    };
+    let input = include_str!("data/sample_object0.xml");
+    let node = Node::from_reader(input.as_bytes())?;
+    let interface = &node.interfaces()[0];
+    let proxy = GenTrait {
+        interface,
+        format: true,
+        options: &GenOptions {
+            type_map: Some(type_map.clone()),
+            ..Default::default()
+        },
+    }
+    .to_string();
+    // One struct per distinct signature, named after its first use.
+    assert!(proxy.contains("bar: &BazicBar, foo: &BazicFoo"));
+    assert!(proxy.contains("-> zbus::Result<(BazicBar, Vec<BazicFoo>)>"));
+    assert!(
+        proxy.contains("pub struct BazicBar {\n    pub field_0: i32,\n    pub field_1: i32,\n}")
+    );
+    assert!(proxy.contains("fn barplex_sig(&self, rule: &BarplexSigRule)"));
+    assert!(proxy.contains("-> zbus::Result<Vec<BarplexSigArg1Item>>"));
+    assert!(proxy.contains("fn matryoshkas(&self) -> zbus::Result<Vec<MatryoshkasItem>>"));
+    assert!(proxy
+        .contains("pub field_4: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,"));
+
+    // Field names can be given through annotations.
+    let input = r#"
+        <node>
+          <interface name="org.example.Points">
+            <method name="Move">
+              <arg name="point" type="(ii)" direction="in">
+                <annotation name="org.zbus.StructFields" value="x, y"/>
+              </arg>
+            </method>
+          </interface>
+        </node>
+    "#;
+    let node = Node::from_reader(input.as_bytes())?;
+    let skeleton = GenImpl {
+        interface: &node.interfaces()[0],
+        format: true,
+        options: &GenOptions {
+            code: CodeKind::Skeleton,
+            type_map: Some(type_map),
+            ..Default::default()
+        },
+    }
+    .to_string();
+    assert!(skeleton.contains("point: MovePoint"));
+    assert!(skeleton.contains("pub x: i32,\n    pub y: i32,"));
+
+    Ok(())
+}
+
+#[test]
+fn owned_inputs() -> Result<(), Box<dyn Error>> {
+    let input = include_str!("data/sample_object0.xml");
+    let node = Node::from_reader(input.as_bytes())?;
+    let proxy = GenTrait {
+        interface: &node.interfaces()[0],
+        format: true,
+        options: &GenOptions {
+            owned_inputs: true,
+            ..Default::default()
+        },
+    }
+    .to_string();
+    assert!(proxy.contains("bar: (i32, i32, Vec<zbus::zvariant::OwnedValue>)"));
+    assert!(proxy.contains("std::collections::HashMap<String, String>,"));
+    assert!(proxy.contains("fn set_bar(&self, value: u8)"));
+    // Only the inputs are affected.
+    assert!(proxy.contains("dict: std::collections::HashMap<&str, zbus::zvariant::Value<'_>>"));
+
+    Ok(())
+}
+
+#[test]
+fn error_type() -> Result<(), Box<dyn Error>> {
+    let input = include_str!("data/sample_object0.xml");
+    let node = Node::from_reader(input.as_bytes())?;
+    let proxy = GenTrait {
+        interface: &node.interfaces()[0],
+        format: true,
+        options: &GenOptions {
+            error_type: Some("my_crate::Error".into()),
+            ..Default::default()
+        },
+    }
+    .to_string();
+    assert!(!proxy.contains("-> zbus::Result<zbus::zvariant::OwnedValue>"));
+    assert!(
+        proxy.contains(") -> std::result::Result<zbus::zvariant::OwnedValue, my_crate::Error>;")
+    );
+    assert!(proxy.contains("fn bar(&self) -> std::result::Result<u8, my_crate::Error>;"));
+    assert!(
+        proxy.contains("fn set_bar(&self, value: u8) -> std::result::Result<(), my_crate::Error>;")
+    );
+    // Signals are not affected, their return type is ignored.
+    assert!(proxy.contains(") -> zbus::Result<()>;"));
+
+    Ok(())
+}
+
+#[test]
+fn fd_policy() -> Result<(), Box<dyn Error>> {
+    let input = r#"
+        <node>
+          <interface name="org.example.Files">
+            <method name="Open">
+              <arg name="path" type="s" direction="in"/>
+              <arg name="fd" type="h" direction="out"/>
+            </method>
+            <method name="Close">
+              <arg name="path" type="s" direction="in"/>
+            </method>
+            <signal name="Opened">
+              <arg name="fds" type="ah"/>
+            </signal>
+            <property name="Log" type="h" access="read"/>
+          </interface>
+        </node>
+    "#;
+    let node = Node::from_reader(input.as_bytes())?;
+    let proxy = |fd_policy| {
+        GenTrait {
+            interface: &node.interfaces()[0],
+            format: true,
+            options: &GenOptions {
+                fd_policy: Some(fd_policy),
+                ..Default::default()
+            },
+        }
+        .to_string()
+    };
+
+    let skipped = proxy(FdPolicy::SkipMethod);
+    assert!(skipped.contains("fn close(&self, path: &str)"));
+    assert!(!skipped.contains("fn open("));
+    assert!(!skipped.contains("fn opened("));
+    assert!(!skipped.contains("fn log("));
+
+    let unix = proxy(FdPolicy::CfgUnix);
+    assert!(unix.contains("    #[cfg(unix)]\n    fn open(&self, path: &str)"));
+    assert!(unix.contains("    #[cfg(unix)]\n    #[zbus(property)]\n    fn log(&self)"));
+    assert!(!unix.contains("    #[cfg(unix)]\n    fn close("));
+    // Signal argument types can't be declared on Unix only.
+    assert!(!unix.contains("fn opened("));
+
+    let options = GenOptions {
+        fd_policy: Some(FdPolicy::Error),
+        ..Default::default()
+    };
+    let err = write_interfaces(node.interfaces(), &[], "test", "test", "0", &options).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`org.example.Files.Open` has file descriptors, which don't exist on Windows"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn constants() -> Result<(), Box<dyn Error>> {
+    let input = include_str!("data/sample_object0.xml");
+    let node = Node::from_reader(input.as_bytes())?;
+    let service = zbus::names::BusName::try_from("com.example.Sample")?;
+    let path = zbus::zvariant::ObjectPath::try_from("/com/example/Sample")?;
+    let proxy = GenTrait {
+        interface: &node.interfaces()[0],
+        format: true,
+        options: &GenOptions {
+            service: Some(service),
+            path: Some(path),
+            kind: ProxyKind::Async,
+            ..Default::default()
+        },
+    }
+    .to_string();
+    assert!(proxy.contains(
+        "impl SampleInterface0Proxy<'_> {
+    /// The name of the D-Bus interface.
+    pub const INTERFACE: &'static str = \"com.example.SampleInterface0\";
+    /// The name of the service.
+    pub const DESTINATION: &'static str = \"com.example.Sample\";
+    /// The path of the object.
+    pub const PATH: &'static str = \"/com/example/Sample\";
+}"
+    ));
+    assert!(!proxy.contains("SampleInterface0ProxyBlocking"));
+
+    Ok(())
+}
+
+#[test]
+fn arg_names() -> Result<(), Box<dyn Error>> {
+    let input = r#"
+        <node>
+          <interface name="org.example.Args">
+            <method name="Open">
+              <arg type="o" direction="in"/>
+              <arg type="a{sv}" direction="in"/>
+              <arg name="path_1" type="s" direction="in"/>
+              <arg type="o" direction="in"/>
+              <arg name="flags" type="u" direction="in"/>
+              <arg name="flags" type="u" direction="in"/>
+            </method>
+            <signal name="Opened">
+              <arg type="h"/>
+              <arg name="mode" type="s"/>
+              <arg name="mode" type="s"/>
+            </signal>
+          </interface>
+        </node>
+    "#;
+    let node = Node::from_reader(input.as_bytes())?;
+    let proxy = GenTrait {
+        interface: &node.interfaces()[0],
+        format: false,
+        options: &GenOptions::default(),
+    }
+    .to_string();
+    assert!(proxy.contains(
+        "fn open(&self, path_2: &zbus::zvariant::ObjectPath<'_>, \
+         dict_1: std::collections::HashMap<&str, &zbus::zvariant::Value<'_>>, path_1: &str, \
+         path_3: &zbus::zvariant::ObjectPath<'_>, flags: u32, flags_2: u32)"
+    ));
+    assert!(
+        proxy.contains("fn opened(&self, fd_1: zbus::zvariant::Fd<'_>, mode: &str, mode_2: &str)")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn from_xml_file() -> Result<(), Box<dyn Error>> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/sample_object0.xml");
+    let code = generate_from_xml(&path, &GenOptions::default())?;
+    // No inner doc comments, so the code can be `include!`d.
+    assert!(!code.contains("//!"));
+    assert!(code.starts_with("use zbus::proxy;"));
+    assert!(code.contains("trait SampleInterface0 {"));
+
+    let options = GenOptions {
+        code: CodeKind::Skeleton,
+        ..Default::default()
+    };
+    let code = generate_from_xml(&path, &options)?;
+    assert!(code.starts_with("use zbus::interface;"));
+    assert!(code.contains("impl SampleInterface0 {"));
+
+    Ok(())
+}
+
+#[test]
+fn interface_filter() -> Result<(), Box<dyn Error>> {
+    let xml = r#"
+<node>
+  <interface name="org.freedesktop.DBus.Properties"/>
+  <interface name="org.freedesktop.portal.Camera"/>
+  <interface name="org.freedesktop.portal.Screenshot"/>
+  <interface name="org.freedesktop.impl.portal.Camera"/>
+</node>
+"#;
+    let node = Node::from_reader(xml.as_bytes())?;
+    let names = |interfaces: Vec<zbus_xml::Interface<'_>>| -> Vec<String> {
+        interfaces.iter().map(|i| i.name().to_string()).collect()
+    };
+
+    let (standard, selected) = InterfaceFilter::default().split(node.interfaces());
+    assert_eq!(names(standard), ["org.freedesktop.DBus.Properties"]);
+    assert_eq!(
+        names(selected),
+        [
+            "org.freedesktop.portal.Camera",
+            "org.freedesktop.portal.Screenshot",
+            "org.freedesktop.impl.portal.Camera",
+        ]
+    );
+
+    let filter = InterfaceFilter {
+        include: vec!["org.freedesktop.portal.*".into()],
+        exclude: vec!["org.freedesktop.portal.Screenshot".into()],
+        ..Default::default()
+    };
+    let (_, selected) = filter.split(node.interfaces());
+    assert_eq!(names(selected), ["org.freedesktop.portal.Camera"]);
+
+    let filter = InterfaceFilter {
+        skip_standard: false,
+        include: vec!["org.freedesktop.DBus.*".into()],
+        exclude: vec![],
+    };
+    let (standard, selected) = filter.split(node.interfaces());
+    assert!(standard.is_empty());
+    assert_eq!(names(selected), ["org.freedesktop.DBus.Properties"]);
+
+    Ok(())
+}
+
+#[test]
+fn signature_tests() -> Result<(), Box<dyn Error>> {
+    let input = include_str!("data/sample_enums.xml");
+    let node = Node::from_reader(input.as_bytes())?;
+    let tests = write_signature_tests(input, node.interfaces(), ZbusVersion::V4, None)?;
+    assert!(tests.starts_with("#[cfg(test)]\nmod signature_tests {"));
+    // The XML is embedded as is.
+    assert!(tests.contains(&format!("const XML: &str = r#\"{input}\"#;")));
+    assert!(tests.contains("let node = zbus_xml::Node::from_reader(XML.as_bytes()).unwrap();"));
+    assert!(tests.contains("fn power_signatures() {"));
+    assert!(tests.contains("(\"SetState[0]\".to_string(), signature::<SetStateState>()),"));
+    assert!(tests.contains("(\"SetState[1]\".to_string(), signature::<u32>()),"));
+    assert!(tests.contains("(\"Profile\".to_string(), signature::<Profile>()),"));
+    assert!(tests.contains("assert_eq!(xml_signatures(\"org.example.Power\"), generated);"));
+
+    // Raw strings can't end in the XML.
+    let tests = write_signature_tests("<node name=\"#\"/>", &[], ZbusVersion::V3, None)?;
+    assert!(tests.contains("const XML: &str = r##\"<node name=\"#\"/>\"##;"));
+    assert!(tests.contains("zbus::xml::Node::from_reader"));
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/sample_object0.xml");
+    let options = GenOptions {
+        with_tests: true,
+        ..Default::default()
+    };
+    let code = generate_from_xml(&path, &options)?;
+    assert!(code.contains("trait SampleInterface0 {"));
+    assert!(code.contains("fn sample_interface0_signatures() {"));
+
+    Ok(())
+}
+
+#[test]
+fn object_manager() -> Result<(), Box<dyn Error>> {
+    let input = r#"
+        <node>
+          <interface name="org.freedesktop.DBus.ObjectManager">
+            <method name="GetManagedObjects">
+              <arg type="a{oa{sa{sv}}}" name="object_paths_interfaces_and_properties" direction="out"/>
+            </method>
+            <signal name="InterfacesAdded">
+              <arg type="o" name="object_path"/>
+              <arg type="a{sa{sv}}" name="interfaces_and_properties"/>
+            </signal>
+            <signal name="InterfacesRemoved">
+              <arg type="o" name="object_path"/>
+              <arg type="as" name="interfaces"/>
+            </signal>
+          </interface>
+          <interface name="org.example.Device">
+            <property name="Name" type="s" access="read"/>
+            <property name="Secret" type="s" access="write"/>
+          </interface>
+        </node>
+    "#;
+    let node = Node::from_reader(input.as_bytes())?;
+    let (standard, interfaces): (Vec<_>, Vec<_>) = node
+        .interfaces()
+        .iter()
+        .cloned()
+        .partition(|i| i.name().starts_with("org.freedesktop.DBus"));
+    let code = write_interfaces(
+        &interfaces,
+        &standard,
+        "test",
+        "zbus-xmlgen",
+        "0",
+        &GenOptions::default(),
+    )?;
+
+    assert!(code.contains("pub struct DeviceProperties {"));
+    assert!(code.contains("#[zvariant(rename = \"Name\")]"));
+    assert!(code.contains("pub name: Option<String>,"));
+    // Write-only properties aren't part of the managed objects.
+    assert!(!code.contains("secret: Option"));
+    assert!(code.contains("pub struct ManagedObject {"));
+    assert!(code.contains("pub device: Option<DeviceProperties>,"));
+    assert!(code.contains("\"org.example.Device\" => object.device = Some(map.next_value()?),"));
+    assert!(code.contains("trait ObjectManager {"));
+    assert!(code.contains("zbus::zvariant::OwnedObjectPath, ManagedObject>"));
+    assert!(code.contains("interfaces_and_properties: ManagedObject"));
+    // The object manager isn't listed as left to the `zbus::fdo` proxies.
+    assert!(!code.contains("zbus::fdo::ObjectManagerProxy"));
+
+    Ok(())
+}
+
+#[test]
+fn json() -> Result<(), Box<dyn Error>> {
+    let input = include_str!("data/sample_object0.xml");
+    let node = Node::from_reader(input.as_bytes())?;
+    let json = write_interfaces_json(node.interfaces(), ZbusVersion::V4, None)?;
+    let json: serde_json::Value = serde_json::from_str(&json)?;
+
+    let interface = &json["interfaces"][0];
+    assert_eq!(interface["name"], "com.example.SampleInterface0");
+    let frobate = &interface["methods"][0];
+    assert_eq!(frobate["name"], "Frobate");
+    assert_eq!(
+        frobate["annotations"]["org.freedesktop.DBus.Deprecated"],
+        "true"
+    );
+    let baz = &frobate["args"][3];
+    assert_eq!(baz["name"], "baz");
+    assert_eq!(baz["ident"], serde_json::Value::Null);
+    assert_eq!(baz["type"], "a{us}");
+    assert_eq!(baz["direction"], "out");
+    assert_eq!(baz["rust_type"], "std::collections::HashMap<u32, String>");
+    let foo_bar = &interface["properties"][1];
+    assert_eq!(foo_bar["ident"], "foo_bar");
+    assert_eq!(foo_bar["access"], "readwrite");
+    assert_eq!(foo_bar["rust_type"], "u8");
+
+    Ok(())
+}
+
+#[test]
+fn diff() -> Result<(), Box<dyn Error>> {
+    let old = Node::from_reader(
+        r#"
+        <node>
+          <interface name="org.example.Foo">
+            <method name="Get">
+              <arg name="key" type="s" direction="in"/>
+              <arg name="value" type="v" direction="out"/>
+            </method>
+            <method name="Gone">
+              <arg name="value" type="v" direction="in"/>
+            </method>
+            <signal name="Changed">
+              <arg name="name" type="s"/>
+            </signal>
+            <property name="Mode" type="s" access="readwrite"/>
+          </interface>
+          <interface name="org.example.Old"/>
+        </node>
+        "#
+        .as_bytes(),
+    )?;
+    let new = Node::from_reader(
+        r#"
+        <node>
+          <interface name="org.example.Foo">
+            <method name="Get">
+              <arg name="name" type="s" direction="in"/>
+              <arg name="value" type="v" direction="out"/>
+            </method>
+            <method name="Set">
+              <arg name="value" type="v" direction="in"/>
+            </method>
+            <signal name="Changed">
+              <arg name="id" type="u"/>
+            </signal>
+            <property name="Mode" type="s" access="read"/>
+            <property name="Size" type="u" access="read"/>
+          </interface>
+          <interface name="org.example.New"/>
+        </node>
+        "#
+        .as_bytes(),
+    )?;
+
+    let changes = diff_interfaces(old.interfaces(), new.interfaces());
+    let changes: Vec<_> = changes.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        changes,
+        [
+            "method `org.example.Foo.Get` arguments renamed",
+            "method `org.example.Foo.Gone` removed (breaking)",
+            "method `org.example.Foo.Set` added",
+            "signal `org.example.Foo.Changed` changed from `s` to `u` (breaking)",
+            "property `org.example.Foo.Mode` access changed from readwrite to read (breaking)",
+            "property `org.example.Foo.Size` added",
+            "interface `org.example.Old` removed (breaking)",
+            "interface `org.example.New` added",
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn module_tree() -> Result<(), Box<dyn Error>> {
+    assert_eq!(
+        module_names(&[
+            "org.freedesktop.portal.FileChooser",
+            "org.freedesktop.portal.Screenshot",
+            "org.gnome.Shell.Screenshot",
+        ]),
+        [
+            "file_chooser",
+            "org_freedesktop_portal_screenshot",
+            "org_gnome_shell_screenshot",
+        ]
+    );
+
+    let input = include_str!("data/sample_object0.xml");
+    let expected = include_str!("data/sample_root_module.rs");
+    #[cfg(windows)]
+    let expected = expected.replace("\r\n", "\n");
+    let node = Node::from_reader(input.as_bytes())?;
+    assert_eq!(
+        interface_module_name(&node.interfaces()[0]),
+        "com_example_sample_interface0"
+    );
+    let module = |name: &str| GenModule {
+        name: name.to_string(),
+        interfaces: node.interfaces(),
+    };
+    let root = write_root_module(
+        &[module("sample_object0")],
+        "interfaces",
+        "zbus-xmlgen",
+        "0.0.0",
+        CodeKind::Proxy,
+        ProxyKind::Both,
+    )?;
+    assert_eq!(root, expected);
+
+    // Proxies with clashing names are only reachable through their module.
+    let root = write_root_module(
+        &[module("sample_object0"), module("sample_object0_copy")],
+        "interfaces",
+        "zbus-xmlgen",
+        "0.0.0",
+        CodeKind::Proxy,
+        ProxyKind::Both,
+    )?;
+    assert!(root.contains("pub mod sample_object0_copy;"));
+    assert!(!root.contains("pub use"));
+
+    Ok(())
+}
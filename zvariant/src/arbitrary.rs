@@ -0,0 +1,174 @@
+//! Support for generating random values with the [`arbitrary`](::arbitrary) crate, for fuzzing.
+//!
+//! [`Value`](enum@Value), [`OwnedValue`], [`Signature`] and [`ObjectPath`] implement
+//! [`Arbitrary`]. Generated values are always valid D-Bus values: strings don't contain any nul
+//! bytes, floating point numbers are never NaN (so values can be compared after a round trip) and
+//! file descriptors are never generated.
+
+use ::arbitrary::{Arbitrary, Error, Result, Unstructured};
+
+use crate::{
+    Array, Dict, ObjectPath, OwnedValue, Signature, SignatureTree, StructureBuilder, Value,
+    GENERATED_BASIC_TYPES, GENERATED_MAX_DEPTH, GENERATED_MAX_LEN,
+};
+
+/// Generate a random value of the given `signature`.
+///
+/// This is useful to generate D-Bus encoded forms of your own types, as their signature is known.
+///
+/// # Errors
+///
+/// [`Error::IncorrectFormat`] is returned if `signature` is not a single complete type, or if it
+/// contains file descriptors.
+pub fn value_of(u: &mut Unstructured<'_>, signature: &Signature<'_>) -> Result<Value<'static>> {
+    let tree = SignatureTree::try_from(signature).map_err(|_| Error::IncorrectFormat)?;
+
+    value_of_tree(u, &tree, GENERATED_MAX_DEPTH)
+}
+
+fn signature_tree(u: &mut Unstructured<'_>, levels: u32) -> Result<SignatureTree> {
+    let kinds = if levels == 0 { 1 } else { 5 };
+    let tree = match u.choose_index(kinds)? {
+        0 => SignatureTree::Basic(*u.choose(GENERATED_BASIC_TYPES)?),
+        1 => SignatureTree::Variant,
+        2 => SignatureTree::array(signature_tree(u, levels - 1)?),
+        3 => SignatureTree::dict(
+            SignatureTree::Basic(*u.choose(GENERATED_BASIC_TYPES)?),
+            signature_tree(u, levels - 1)?,
+        ),
+        _ => {
+            let len = u.int_in_range(1..=GENERATED_MAX_LEN)?;
+            let fields = (0..len)
+                .map(|_| signature_tree(u, levels - 1))
+                .collect::<Result<Vec<_>>>()?;
+            SignatureTree::structure(fields)
+        }
+    };
+
+    Ok(tree)
+}
+
+fn value_of_tree(
+    u: &mut Unstructured<'_>,
+    tree: &SignatureTree,
+    levels: u32,
+) -> Result<Value<'static>> {
+    let levels = levels.saturating_sub(1);
+    let value = match tree {
+        SignatureTree::Basic(c) => basic_value(u, *c, levels)?,
+        SignatureTree::Variant => {
+            let tree = signature_tree(u, levels)?;
+            Value::Value(Box::new(value_of_tree(u, &tree, levels)?))
+        }
+        SignatureTree::Array(element) => {
+            let len = u.int_in_range(0..=GENERATED_MAX_LEN)?;
+            match &**element {
+                SignatureTree::DictEntry(key, value) => {
+                    let mut dict = Dict::new(signature_of(key)?, signature_of(value)?);
+                    for _ in 0..len {
+                        let k = value_of_tree(u, key, levels)?;
+                        let v = value_of_tree(u, value, levels)?;
+                        dict.append(k, v).map_err(|_| Error::IncorrectFormat)?;
+                    }
+
+                    Value::Dict(dict)
+                }
+                element => {
+                    let mut array = Array::new(signature_of(element)?);
+                    for _ in 0..len {
+                        let e = value_of_tree(u, element, levels)?;
+                        array.append(e).map_err(|_| Error::IncorrectFormat)?;
+                    }
+
+                    Value::Array(array)
+                }
+            }
+        }
+        SignatureTree::Struct(fields) => {
+            let mut builder = StructureBuilder::new();
+            for field in fields {
+                builder.push_value(value_of_tree(u, field, levels)?);
+            }
+
+            Value::Structure(builder.build())
+        }
+        // Dict entries are handled as part of the containing array.
+        SignatureTree::DictEntry(..) => return Err(Error::IncorrectFormat),
+        #[cfg(feature = "gvariant")]
+        SignatureTree::Maybe(_) => return Err(Error::IncorrectFormat),
+    };
+
+    Ok(value)
+}
+
+fn basic_value(u: &mut Unstructured<'_>, c: char, levels: u32) -> Result<Value<'static>> {
+    let value = match c {
+        'y' => Value::U8(u.arbitrary()?),
+        'b' => Value::Bool(u.arbitrary()?),
+        'n' => Value::I16(u.arbitrary()?),
+        'q' => Value::U16(u.arbitrary()?),
+        'i' => Value::I32(u.arbitrary()?),
+        'u' => Value::U32(u.arbitrary()?),
+        'x' => Value::I64(u.arbitrary()?),
+        't' => Value::U64(u.arbitrary()?),
+        'd' => {
+            let f: f64 = u.arbitrary()?;
+            Value::F64(if f.is_nan() { 0.0 } else { f })
+        }
+        's' => Value::from(u.arbitrary::<String>()?.replace('\0', "")),
+        'o' => Value::ObjectPath(object_path(u)?),
+        'g' => Value::Signature(signature_of(&signature_tree(u, levels)?)?),
+        _ => return Err(Error::IncorrectFormat),
+    };
+
+    Ok(value)
+}
+
+fn object_path(u: &mut Unstructured<'_>) -> Result<ObjectPath<'static>> {
+    const ELEMENT_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789_";
+
+    let mut path = String::new();
+    for _ in 0..u.int_in_range(0..=GENERATED_MAX_LEN)? {
+        path.push('/');
+        for _ in 0..u.int_in_range(1..=8usize)? {
+            path.push(*u.choose(ELEMENT_CHARS)? as char);
+        }
+    }
+    if path.is_empty() {
+        path.push('/');
+    }
+
+    ObjectPath::try_from(path).map_err(|_| Error::IncorrectFormat)
+}
+
+fn signature_of(tree: &SignatureTree) -> Result<Signature<'static>> {
+    tree.signature().map_err(|_| Error::IncorrectFormat)
+}
+
+impl<'a> Arbitrary<'a> for Value<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let tree = signature_tree(u, GENERATED_MAX_DEPTH)?;
+
+        value_of_tree(u, &tree, GENERATED_MAX_DEPTH)
+    }
+}
+
+impl<'a> Arbitrary<'a> for OwnedValue {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Value::arbitrary(u)?
+            .try_into()
+            .map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Signature<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        signature_of(&signature_tree(u, GENERATED_MAX_DEPTH)?)
+    }
+}
+
+impl<'a> Arbitrary<'a> for ObjectPath<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        object_path(u)
+    }
+}
@@ -64,16 +64,77 @@ impl<'a> Array<'a> {
         &self.elements
     }
 
+    pub(crate) fn into_elements(self) -> Vec<Value<'a>> {
+        self.elements
+    }
+
     /// Get the value at the given index.
     pub fn get<V>(&'a self, idx: usize) -> Result<Option<V>>
     where
-        V: ?Sized + TryFrom<&'a Value<'a>>,
+        V: TryFrom<&'a Value<'a>>,
         <V as TryFrom<&'a Value<'a>>>::Error: Into<crate::Error>,
     {
         self.elements
             .get(idx)
             .map(|v| v.downcast_ref::<V>())
             .transpose()
+    }
+
+    /// An iterator over references to the elements.
+    pub fn iter(&self) -> std::slice::Iter<'_, Value<'a>> {
+        self.elements.iter()
+    }
+
+    /// Convert all the elements to `T`, without consuming or cloning them.
+    ///
+    /// This is the borrowing counterpart of [`Array::try_into_vec`], useful for extracting e.g
+    /// string slices out of an array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Array;
+    ///
+    /// let array = Array::from(vec!["hello", "world"]);
+    /// let strings: Vec<&str> = array.try_to_vec().unwrap();
+    /// assert_eq!(strings, ["hello", "world"]);
+    /// ```
+    pub fn try_to_vec<T>(&'a self) -> Result<Vec<T>>
+    where
+        T: TryFrom<&'a Value<'a>>,
+        <T as TryFrom<&'a Value<'a>>>::Error: Into<crate::Error>,
+    {
+        self.elements.iter().map(|v| v.downcast_ref()).collect()
+    }
+
+    /// Convert all the elements to `T`, in a single pass.
+    ///
+    /// Same as the `TryFrom<Array>` implementation for [`Vec`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::{Array, Value};
+    ///
+    /// let array = Array::from(vec![
+    ///     Value::from((1u32, "one")),
+    ///     Value::from((2u32, "two")),
+    /// ]);
+    /// let pairs: Vec<(u32, String)> = array.try_into_vec().unwrap();
+    /// assert_eq!(pairs, [(1, "one".to_string()), (2, "two".to_string())]);
+    /// ```
+    pub fn try_into_vec<T>(self) -> Result<Vec<T>>
+    where
+        T: TryFrom<Value<'a>>,
+        T::Error: Into<crate::Error>,
+    {
+        self.elements
+            .into_iter()
+            .map(|e| match e {
+                Value::Value(v) => T::try_from(*v),
+                e => T::try_from(e),
+            })
+            .collect::<core::result::Result<_, _>>()
             .map_err(Into::into)
     }
 
@@ -136,6 +197,10 @@ impl<'a> Array<'a> {
 
 impl Display for Array<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return crate::pretty::array_pretty_fmt(self, f, 0);
+        }
+
         array_display_fmt(self, f, true)
     }
 }
@@ -248,6 +313,24 @@ impl<'a> std::ops::Deref for Array<'a> {
     }
 }
 
+impl<'a> IntoIterator for Array<'a> {
+    type Item = Value<'a>;
+    type IntoIter = std::vec::IntoIter<Value<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter()
+    }
+}
+
+impl<'i, 'a> IntoIterator for &'i Array<'a> {
+    type Item = &'i Value<'a>;
+    type IntoIter = std::slice::Iter<'i, Value<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<'a, T> From<Vec<T>> for Array<'a>
 where
     T: Type + Into<Value<'a>>,
@@ -294,6 +377,15 @@ where
     }
 }
 
+impl<'a, T, const N: usize> From<[T; N]> for Array<'a>
+where
+    T: Type + Into<Value<'a>>,
+{
+    fn from(values: [T; N]) -> Self {
+        Self::from(Vec::from(values))
+    }
+}
+
 impl<'a, T> TryFrom<Array<'a>> for Vec<T>
 where
     T: TryFrom<Value<'a>>,
@@ -302,19 +394,21 @@ where
     type Error = Error;
 
     fn try_from(v: Array<'a>) -> core::result::Result<Self, Self::Error> {
-        // there is no try_map yet..
-        let mut res = vec![];
-        for e in v.elements.into_iter() {
-            let value = if let Value::Value(v) = e {
-                T::try_from(*v)
-            } else {
-                T::try_from(e)
-            }
-            .map_err(Into::into)?;
+        v.try_into_vec()
+    }
+}
 
-            res.push(value);
-        }
-        Ok(res)
+impl<'a, T, const N: usize> TryFrom<Array<'a>> for [T; N]
+where
+    T: TryFrom<Value<'a>>,
+    T::Error: Into<crate::Error>,
+{
+    type Error = Error;
+
+    fn try_from(v: Array<'a>) -> core::result::Result<Self, Self::Error> {
+        Vec::<T>::try_from(v)?
+            .try_into()
+            .map_err(|_| Error::IncorrectType)
     }
 }
 
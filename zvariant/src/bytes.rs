@@ -0,0 +1,322 @@
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use static_assertions::assert_impl_all;
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use crate::{Error, OwnedValue, Signature, Type, Value};
+
+/// A byte array (`ay`) wrapper.
+///
+/// Unlike `Vec<u8>` or `&[u8]`, which are serialized and deserialized element by element, `Bytes`
+/// is (de)serialized in one go: the whole array is copied into the output on serialization and
+/// deserialization borrows directly from the encoded data, without any copies. This is the same as
+/// what [`serde_bytes`] does but without the need for the `#[serde(with = "serde_bytes")]`
+/// attribute on each field.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{serialized::Context, to_bytes, Bytes, LE};
+///
+/// let ctxt = Context::new_dbus(LE, 0);
+/// let encoded = to_bytes(ctxt, &Bytes::from(&[1u8, 2, 3][..])).unwrap();
+/// let decoded: Bytes<'_> = encoded.deserialize().unwrap().0;
+/// assert_eq!(&*decoded, &[1, 2, 3]);
+/// ```
+///
+/// [`serde_bytes`]: https://docs.rs/serde_bytes
+#[derive(Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct Bytes<'a>(Inner<'a>);
+
+#[derive(Eq, Clone)]
+enum Inner<'a> {
+    Static(&'static [u8]),
+    Borrowed(&'a [u8]),
+    Owned(Arc<[u8]>),
+}
+
+impl<'a> Default for Inner<'a> {
+    fn default() -> Self {
+        Self::Static(&[])
+    }
+}
+
+impl<'a> PartialEq for Inner<'a> {
+    fn eq(&self, other: &Inner<'a>) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<'a> Ord for Inner<'a> {
+    fn cmp(&self, other: &Inner<'a>) -> Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+impl<'a> PartialOrd for Inner<'a> {
+    fn partial_cmp(&self, other: &Inner<'a>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Hash for Inner<'a> {
+    fn hash<H: Hasher>(&self, h: &mut H) {
+        self.as_bytes().hash(h)
+    }
+}
+
+impl<'a> Inner<'a> {
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            Inner::Static(b) => b,
+            Inner::Borrowed(b) => b,
+            Inner::Owned(b) => b,
+        }
+    }
+}
+
+assert_impl_all!(Bytes<'_>: Send, Sync, Unpin);
+
+impl<'a> Bytes<'a> {
+    /// Owned bytes without allocations.
+    pub const fn from_static(b: &'static [u8]) -> Self {
+        Bytes(Inner::Static(b))
+    }
+
+    /// This is faster than `Clone::clone` when `self` contains owned data.
+    pub fn as_ref(&self) -> Bytes<'_> {
+        match &self.0 {
+            Inner::Static(b) => Bytes(Inner::Static(b)),
+            Inner::Borrowed(b) => Bytes(Inner::Borrowed(b)),
+            Inner::Owned(b) => Bytes(Inner::Borrowed(b)),
+        }
+    }
+
+    /// The underlying bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    /// Creates an owned clone of `self`.
+    pub fn to_owned(&self) -> Bytes<'static> {
+        self.clone().into_owned()
+    }
+
+    /// Creates an owned clone of `self`.
+    pub fn into_owned(self) -> Bytes<'static> {
+        match self.0 {
+            Inner::Static(b) => Bytes(Inner::Static(b)),
+            Inner::Borrowed(b) => Bytes(Inner::Owned(b.into())),
+            Inner::Owned(b) => Bytes(Inner::Owned(b)),
+        }
+    }
+}
+
+impl<'a> Type for Bytes<'a> {
+    fn signature() -> Signature<'static> {
+        <[u8]>::signature()
+    }
+}
+
+impl<'a> Serialize for Bytes<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.as_bytes())
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for Bytes<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Bytes<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a byte array")
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Bytes(Inner::Borrowed(v)))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Bytes(Inner::Owned(v.into())))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(v.into())
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+
+        Ok(bytes.into())
+    }
+}
+
+impl<'a> From<&'a [u8]> for Bytes<'a> {
+    fn from(value: &'a [u8]) -> Self {
+        Self(Inner::Borrowed(value))
+    }
+}
+
+impl<'a> From<&'a Vec<u8>> for Bytes<'a> {
+    fn from(value: &'a Vec<u8>) -> Self {
+        Self(Inner::Borrowed(value))
+    }
+}
+
+impl<'a> From<Vec<u8>> for Bytes<'a> {
+    fn from(value: Vec<u8>) -> Self {
+        Self(Inner::Owned(value.into()))
+    }
+}
+
+impl<'a> From<Arc<[u8]>> for Bytes<'a> {
+    fn from(value: Arc<[u8]>) -> Self {
+        Self(Inner::Owned(value))
+    }
+}
+
+impl<'a> From<Cow<'a, [u8]>> for Bytes<'a> {
+    fn from(value: Cow<'a, [u8]>) -> Self {
+        match value {
+            Cow::Owned(value) => value.into(),
+            Cow::Borrowed(value) => value.into(),
+        }
+    }
+}
+
+impl<'a> From<Bytes<'a>> for Vec<u8> {
+    fn from(value: Bytes<'a>) -> Vec<u8> {
+        value.as_bytes().to_vec()
+    }
+}
+
+impl<'a> From<Bytes<'a>> for Value<'a> {
+    fn from(value: Bytes<'a>) -> Self {
+        match value.0 {
+            Inner::Static(b) => Value::from(b),
+            Inner::Borrowed(b) => Value::from(b),
+            Inner::Owned(b) => Value::from(b.to_vec()),
+        }
+    }
+}
+
+impl<'a> TryFrom<Value<'a>> for Bytes<'a> {
+    type Error = Error;
+
+    fn try_from(value: Value<'a>) -> Result<Self, Error> {
+        Vec::<u8>::try_from(value).map(Into::into)
+    }
+}
+
+impl TryFrom<OwnedValue> for Bytes<'static> {
+    type Error = Error;
+
+    fn try_from(value: OwnedValue) -> Result<Self, Error> {
+        Self::try_from(value.try_into_inner()?)
+    }
+}
+
+impl<'a> std::ops::Deref for Bytes<'a> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_bytes()
+    }
+}
+
+impl<'a> AsRef<[u8]> for Bytes<'a> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<[u8]> for Bytes<'a> {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_bytes() == other
+    }
+}
+
+impl<'a> PartialEq<&[u8]> for Bytes<'a> {
+    fn eq(&self, other: &&[u8]) -> bool {
+        self.as_bytes() == *other
+    }
+}
+
+impl<'a> fmt::Debug for Bytes<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_bytes(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bytes;
+    use crate::{serialized::Context, to_bytes, Type, Value, LE};
+
+    #[test]
+    fn zero_copy() {
+        let ctxt = Context::new_dbus(LE, 0);
+        let data: Vec<u8> = (0..=255).collect();
+        let encoded = to_bytes(ctxt, &Bytes::from(&data)).unwrap();
+        assert_eq!(Bytes::signature(), "ay");
+        // Encoded the same as a `Vec<u8>`.
+        assert_eq!(encoded.bytes(), to_bytes(ctxt, &data).unwrap().bytes());
+
+        let (decoded, _): (Bytes<'_>, _) = encoded.deserialize().unwrap();
+        assert_eq!(decoded, &data[..]);
+        // Borrowed from the encoded data.
+        let range = encoded.bytes().as_ptr_range();
+        assert!(range.contains(&decoded.as_ptr()));
+
+        // In a structure.
+        let encoded = to_bytes(ctxt, &(42u32, Bytes::from_static(b"hello"), "world")).unwrap();
+        let (decoded, _): ((u32, Bytes<'_>, &str), _) = encoded.deserialize().unwrap();
+        assert_eq!(decoded.1, &b"hello"[..]);
+        assert_eq!(decoded.2, "world");
+    }
+
+    #[test]
+    fn value_conversions() {
+        let bytes = Bytes::from(vec![1u8, 2, 3]);
+        let value = Value::from(bytes.clone());
+        assert_eq!(value.value_signature(), "ay");
+        assert_eq!(Bytes::try_from(value).unwrap(), bytes);
+
+        let owned = crate::OwnedValue::try_from(Value::from(&[4u8, 5][..])).unwrap();
+        assert_eq!(Bytes::try_from(owned).unwrap(), &[4u8, 5][..]);
+        assert!(Bytes::try_from(Value::from("nope")).is_err());
+    }
+}
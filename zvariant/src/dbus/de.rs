@@ -1,13 +1,14 @@
 use serde::de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Visitor};
 use static_assertions::assert_impl_all;
 
-use std::{marker::PhantomData, str};
+use std::{borrow::Cow, marker::PhantomData, str};
 
 #[cfg(unix)]
 use std::os::fd::AsFd;
 
 use crate::{
-    de::{DeserializerCommon, ValueParseStage},
+    de::{check_tuple_array_len, DeserializerCommon, ValueParseStage},
+    fixed_array::{convert_endian, fixed_element_size},
     serialized::{Context, Format},
     signature_parser::SignatureParser,
     utils::*,
@@ -143,21 +144,22 @@ impl<'de, 'd, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> de::Deseriali
     where
         V: Visitor<'de>,
     {
-        let bytes = deserialize_ay(self)?;
-        visitor.visit_byte_buf(bytes.into())
+        let bytes = deserialize_fixed_array(self)?;
+        visitor.visit_byte_buf(bytes.into_owned())
     }
 
     fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        let bytes = deserialize_ay(self)?;
-        visitor.visit_borrowed_bytes(bytes)
+        match deserialize_fixed_array(self)? {
+            Cow::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Cow::Owned(bytes) => visitor.visit_byte_buf(bytes),
+        }
     }
 
     deserialize_as!(deserialize_char => deserialize_str);
     deserialize_as!(deserialize_string => deserialize_str);
-    deserialize_as!(deserialize_tuple(_l: usize) => deserialize_struct("", &[]));
     deserialize_as!(deserialize_tuple_struct(n: &'static str, _l: usize) => deserialize_struct(n, &[]));
     deserialize_as!(deserialize_struct(_n: &'static str, _f: &'static [&'static str]) => deserialize_seq());
     deserialize_as!(deserialize_map => deserialize_seq);
@@ -245,7 +247,8 @@ impl<'de, 'd, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> de::Deseriali
                 &"D-Bus string type must not contain interior null bytes",
             ));
         }
-        self.0.pos += 1; // skip trailing null byte
+        // skip trailing null byte
+        self.0.next_slice(1)?;
         let s = str::from_utf8(slice).map_err(Error::Utf8)?;
         self.0.sig_parser.skip_char()?;
 
@@ -354,6 +357,23 @@ impl<'de, 'd, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> de::Deseriali
         }
     }
 
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.0.sig_parser.next_char()? != ARRAY_SIGNATURE_CHAR {
+            return self.deserialize_struct("", &[], visitor);
+        }
+
+        // A fixed-size array.
+        self.0.sig_parser.skip_char()?;
+        let mut array_de = ArraySeqDeserializer(ArrayDeserializer::new(self)?);
+        let v = visitor.visit_seq(&mut array_de)?;
+        check_tuple_array_len(array_de, len)?;
+
+        Ok(v)
+    }
+
     fn deserialize_enum<V>(
         self,
         name: &'static str,
@@ -424,6 +444,7 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F>
         de.0.container_depths = de.0.container_depths.inc_array()?;
 
         let len = de.0.ctxt.endian().read_u32(de.0.next_slice(4)?) as usize;
+        de.0.ctxt.limits().check_array_len(len)?;
         let element_signature = de.0.sig_parser.next_signature()?;
         let element_alignment = alignment_for_signature(&element_signature, Format::DBus)?;
         let mut element_signature_len = element_signature.len();
@@ -451,10 +472,11 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F>
     where
         T: DeserializeSeed<'de>,
     {
-        let ctxt = Context::new_dbus(
-            self.de.0.ctxt.endian(),
-            self.de.0.ctxt.position() + self.de.0.pos,
-        );
+        let ctxt = self
+            .de
+            .0
+            .ctxt
+            .with_position(self.de.0.ctxt.position() + self.de.0.pos);
 
         let mut de = Deserializer::<F>(DeserializerCommon {
             ctxt,
@@ -464,7 +486,9 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F>
             pos: 0,
             container_depths: self.de.0.container_depths,
         });
-        let v = seed.deserialize(&mut de);
+        let v = seed
+            .deserialize(&mut de)
+            .map_err(|e| e.offset_by(self.de.0.pos));
         self.de.0.pos += de.0.pos;
         // No need for retaking the container depths as the child can't be incomplete.
 
@@ -506,18 +530,25 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F>
     }
 }
 
-fn deserialize_ay<'de, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F>(
+// Deserialize an array of `FixedSize` elements in one go, as bytes in native endianness.
+fn deserialize_fixed_array<'de, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F>(
     de: &mut Deserializer<'de, '_, '_, F>,
-) -> Result<&'de [u8]> {
-    if de.0.sig_parser.next_signature()? != "ay" {
-        return Err(de::Error::invalid_type(de::Unexpected::Seq, &"ay"));
+) -> Result<Cow<'de, [u8]>> {
+    let signature = de.0.sig_parser.next_signature()?;
+    let element_size = match signature.as_bytes() {
+        [b'a', c] => fixed_element_size(*c as char),
+        _ => None,
     }
+    .ok_or_else(|| <Error as de::Error>::invalid_type(de::Unexpected::Seq, &"ay"))?;
 
     de.0.sig_parser.skip_char()?;
     let ad = ArrayDeserializer::new(de)?;
     let len = ad.len;
     de.0.sig_parser.skip_char()?;
-    de.0.next_slice(len)
+    let bytes = de.0.next_slice(len)?;
+    de.0.container_depths = de.0.container_depths.dec_array();
+
+    convert_endian(bytes, element_size, de.0.ctxt.endian())
 }
 
 struct ArraySeqDeserializer<'d, 'de, 'sig, 'f, F>(ArrayDeserializer<'d, 'de, 'sig, 'f, F>);
@@ -636,11 +667,11 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> SeqAccess<'de
                 let signature = Signature::try_from(slice)?;
                 let sig_parser = SignatureParser::new(signature);
 
-                let ctxt = Context::new(
-                    Format::DBus,
-                    self.de.0.ctxt.endian(),
-                    self.de.0.ctxt.position() + value_start,
-                );
+                let ctxt = self
+                    .de
+                    .0
+                    .ctxt
+                    .with_position(self.de.0.ctxt.position() + value_start);
                 let mut de = Deserializer::<F>(DeserializerCommon {
                     ctxt,
                     sig_parser,
@@ -650,7 +681,10 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> SeqAccess<'de
                     container_depths: self.de.0.container_depths.inc_variant()?,
                 });
 
-                let v = seed.deserialize(&mut de).map(Some);
+                let v = seed
+                    .deserialize(&mut de)
+                    .map(Some)
+                    .map_err(|e| e.offset_by(value_start));
                 self.de.0.pos += de.0.pos;
 
                 v
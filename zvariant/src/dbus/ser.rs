@@ -62,7 +62,7 @@ macro_rules! serialize_basic {
     ($method:ident($type:ty) $write_method:ident($as:ty)) => {
         fn $method(self, v: $type) -> Result<()> {
             self.0.prep_serialize_basic::<$type>()?;
-            self.0.$write_method(self.0.ctxt.endian(), v as $as).map_err(|e| Error::InputOutput(e.into()))
+            self.0.$write_method(self.0.ctxt.endian(), v as $as).map_err(Error::from)
         }
     };
 }
@@ -97,13 +97,13 @@ where
                 let idx = self.0.add_fd(v)?;
                 self.0
                     .write_u32(self.0.ctxt.endian(), idx)
-                    .map_err(|e| Error::InputOutput(e.into()))
+                    .map_err(Error::from)
             }
             _ => {
                 self.0.prep_serialize_basic::<i32>()?;
                 self.0
                     .write_i32(self.0.ctxt.endian(), v)
-                    .map_err(|e| Error::InputOutput(e.into()))
+                    .map_err(Error::from)
             }
         }
     }
@@ -113,7 +113,7 @@ where
         // Endianness is irrelevant for single bytes.
         self.0
             .write_u8(self.0.ctxt.endian(), v)
-            .map_err(|e| Error::InputOutput(e.into()))
+            .map_err(Error::from)
     }
 
     serialize_basic!(serialize_u16(u16) write_u16);
@@ -145,12 +145,12 @@ where
                 self.0.add_padding(<&str>::alignment(Format::DBus))?;
                 self.0
                     .write_u32(self.0.ctxt.endian(), usize_to_u32(v.len()))
-                    .map_err(|e| Error::InputOutput(e.into()))?;
+                    .map_err(Error::from)?;
             }
             Signature::SIGNATURE_CHAR | VARIANT_SIGNATURE_CHAR => {
                 self.0
                     .write_u8(self.0.ctxt.endian(), usize_to_u8(v.len()))
-                    .map_err(|e| Error::InputOutput(e.into()))?;
+                    .map_err(Error::from)?;
             }
             _ => {
                 let expected = format!(
@@ -168,22 +168,15 @@ where
         }
 
         self.0.sig_parser.skip_char()?;
-        self.0
-            .write_all(v.as_bytes())
-            .map_err(|e| Error::InputOutput(e.into()))?;
-        self.0
-            .write_all(&b"\0"[..])
-            .map_err(|e| Error::InputOutput(e.into()))?;
+        self.0.write_all(v.as_bytes()).map_err(Error::from)?;
+        self.0.write_all(&b"\0"[..]).map_err(Error::from)?;
 
         Ok(())
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<()> {
         let seq = self.serialize_seq(Some(v.len()))?;
-        seq.ser
-            .0
-            .write(v)
-            .map_err(|e| Error::InputOutput(e.into()))?;
+        seq.ser.0.write_fixed_array(v)?;
         seq.end()
     }
 
@@ -272,7 +265,7 @@ where
         // initially set to 0.
         self.0
             .write_u32(self.0.ctxt.endian(), 0_u32)
-            .map_err(|e| Error::InputOutput(e.into()))?;
+            .map_err(Error::from)?;
 
         let element_signature = self.0.sig_parser.next_signature()?;
         let element_signature_len = element_signature.len();
@@ -322,7 +315,7 @@ where
     }
 
     fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
-        if len == 0 {
+        if len == 0 && self.0.sig_parser.next_char().ok() != Some(ARRAY_SIGNATURE_CHAR) {
             return StructSerializer::unit(self).map(StructSeqSerializer::Struct);
         }
 
@@ -376,23 +369,24 @@ where
 
         // Set size of array in bytes
         let array_len = self.ser.0.bytes_written - self.start;
+        self.ser.0.ctxt.limits().check_array_len(array_len)?;
         let len = usize_to_u32(array_len);
         let total_array_len = (array_len + self.first_padding + 4) as i64;
         self.ser
             .0
             .writer
             .seek(std::io::SeekFrom::Current(-total_array_len))
-            .map_err(|e| Error::InputOutput(e.into()))?;
+            .map_err(Error::from)?;
         self.ser
             .0
             .writer
             .write_u32(self.ser.0.ctxt.endian(), len)
-            .map_err(|e| Error::InputOutput(e.into()))?;
+            .map_err(Error::from)?;
         self.ser
             .0
             .writer
             .seek(std::io::SeekFrom::Current(total_array_len - 4))
-            .map_err(|e| Error::InputOutput(e.into()))?;
+            .map_err(Error::from)?;
 
         self.ser.0.container_depths = self.ser.0.container_depths.dec_array();
 
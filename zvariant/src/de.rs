@@ -1,4 +1,4 @@
-use serde::de::{self, DeserializeSeed, VariantAccess, Visitor};
+use serde::de::{self, DeserializeSeed, SeqAccess, VariantAccess, Visitor};
 use static_assertions::assert_impl_all;
 
 use std::{marker::PhantomData, str};
@@ -60,14 +60,23 @@ where
 }
 
 impl<'de, 'sig, 'f, F> DeserializerCommon<'de, 'sig, 'f, F> {
+    // In strict mode, ensure that the (framed) value deserialized took up all the bytes.
+    pub fn check_all_consumed(&self, what: &str) -> Result<()> {
+        if self.ctxt.strict() && self.pos != self.bytes.len() {
+            return Err(Error::NonCanonical(format!(
+                "{} unused bytes after {what}",
+                self.bytes.len() - self.pos,
+            )));
+        }
+
+        Ok(())
+    }
+
     pub fn parse_padding(&mut self, alignment: usize) -> Result<usize> {
         let padding = padding_for_n_bytes(self.abs_pos(), alignment);
         if padding > 0 {
             if self.pos + padding > self.bytes.len() {
-                return Err(serde::de::Error::invalid_length(
-                    self.bytes.len(),
-                    &format!(">= {}", self.pos + padding).as_str(),
-                ));
+                return Err(Error::InsufficientData(self.pos + padding));
             }
 
             for i in 0..padding {
@@ -93,11 +102,9 @@ impl<'de, 'sig, 'f, F> DeserializerCommon<'de, 'sig, 'f, F> {
     }
 
     pub fn next_slice(&mut self, len: usize) -> Result<&'de [u8]> {
+        self.ctxt.limits().check_total_len(self.abs_pos() + len)?;
         if self.pos + len > self.bytes.len() {
-            return Err(serde::de::Error::invalid_length(
-                self.bytes.len(),
-                &format!(">= {}", self.pos + len).as_str(),
-            ));
+            return Err(Error::InsufficientData(self.pos + len));
         }
 
         let slice = &self.bytes[self.pos..self.pos + len];
@@ -229,6 +236,26 @@ where
     }
 }
 
+// Ensure there are no elements left of the array a fixed-size (Rust) array of `len` elements was
+// deserialized from.
+pub(crate) fn check_tuple_array_len<'de, A>(mut seq: A, len: usize) -> Result<()>
+where
+    A: SeqAccess<'de, Error = Error>,
+{
+    let mut found = len;
+    while seq.next_element::<de::IgnoredAny>()?.is_some() {
+        found += 1;
+    }
+    if found != len {
+        return Err(de::Error::invalid_length(
+            found,
+            &format!("an array of {len} elements").as_str(),
+        ));
+    }
+
+    Ok(())
+}
+
 // Enum handling is very generic so it can be here and specific deserializers can use this.
 pub(crate) struct Enum<D, F> {
     pub(crate) de: D,
@@ -25,7 +25,7 @@ use crate::{Signature, Type, Value};
 /// [`Value`]: enum.Value.html
 pub struct DeserializeValue<'de, T: Type + Deserialize<'de>>(
     pub T,
-    std::marker::PhantomData<&'de T>,
+    std::marker::PhantomData<&'de ()>,
 );
 
 assert_impl_all!(DeserializeValue<'_, i32>: Send, Sync, Unpin);
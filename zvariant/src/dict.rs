@@ -1,23 +1,42 @@
+#[cfg(feature = "indexmap")]
+use std::{cmp::Ordering, hash::Hasher};
 use std::{
     collections::{BTreeMap, HashMap},
     fmt::{Display, Write},
     hash::{BuildHasher, Hash},
+    marker::PhantomData,
 };
 
+#[cfg(feature = "indexmap")]
+use indexmap::IndexMap;
 use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
 use static_assertions::assert_impl_all;
 
 use crate::{value_display_fmt, Basic, DynamicType, Error, Signature, Type, Value};
 
+#[cfg(not(feature = "indexmap"))]
+type Map<'k, 'v> = BTreeMap<Value<'k>, Value<'v>>;
+#[cfg(feature = "indexmap")]
+type Map<'k, 'v> = IndexMap<Value<'k>, Value<'v>>;
+
 /// A helper type to wrap dictionaries in a [`Value`].
 ///
 /// API is provided to convert from, and to a [`HashMap`].
 ///
+/// The entries are sorted by their keys, unless the `indexmap` feature is enabled, in which case
+/// they're kept in the order of insertion. This is the order in which they're iterated over and
+/// serialized, so with this feature, (de)serializing a `Dict` preserves the order of entries on the
+/// wire. Either way, the order doesn't affect comparison.
+///
 /// [`Value`]: enum.Value.html#variant.Dict
 /// [`HashMap`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html
-#[derive(Debug, Hash, PartialEq, PartialOrd, Eq, Ord)]
+#[derive(Debug)]
+#[cfg_attr(
+    not(feature = "indexmap"),
+    derive(Hash, PartialEq, PartialOrd, Eq, Ord)
+)]
 pub struct Dict<'k, 'v> {
-    map: BTreeMap<Value<'k>, Value<'v>>,
+    map: Map<'k, 'v>,
     key_signature: Signature<'k>,
     value_signature: Signature<'v>,
     // should use a separate lifetime or everything should use the same but API break.
@@ -32,13 +51,24 @@ impl<'k, 'v> Dict<'k, 'v> {
         let signature = create_signature(&key_signature, &value_signature);
 
         Self {
-            map: BTreeMap::new(),
+            map: Map::new(),
             key_signature,
             value_signature,
             signature,
         }
     }
 
+    /// Create a [`DictBuilder`] for keys of type `K` and values of type `V`.
+    ///
+    /// The signature of the resulting `Dict` is inferred from `K` and `V`.
+    pub fn builder<K, V>() -> DictBuilder<'k, 'v, K, V>
+    where
+        K: Basic + Into<Value<'k>>,
+        V: Type + Into<Value<'v>>,
+    {
+        DictBuilder::new()
+    }
+
     /// Append `key` and `value` as a new entry.
     ///
     /// # Errors
@@ -144,7 +174,7 @@ impl<'k, 'v> Dict<'k, 'v> {
         let value_signature = signature.slice(3..signature.len() - 1);
 
         Self {
-            map: BTreeMap::new(),
+            map: Map::new(),
             key_signature,
             value_signature,
             signature,
@@ -162,15 +192,147 @@ impl<'k, 'v> Dict<'k, 'v> {
     // TODO: Provide more API like https://docs.rs/toml/0.5.5/toml/map/struct.Map.html
 }
 
+/// Use this to build a [`Dict`] from statically-typed keys and values.
+///
+/// Since the signature is inferred from the key and value types, adding entries can't fail.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{Dict, Value};
+///
+/// let dict = Dict::builder::<&str, Value<'_>>()
+///     .add("id", 42u32)
+///     .add("name", "foo")
+///     .build();
+/// assert_eq!(dict.signature(), "a{sv}");
+/// assert_eq!(dict.get::<_, u32>(&"id").unwrap(), Some(42));
+/// ```
+#[derive(Debug)]
+pub struct DictBuilder<'k, 'v, K, V> {
+    dict: Dict<'k, 'v>,
+    phantom: PhantomData<fn(K, V)>,
+}
+
+assert_impl_all!(DictBuilder<'_, '_, String, Value<'_>>: Send, Sync, Unpin);
+
+impl<'k, 'v, K, V> DictBuilder<'k, 'v, K, V>
+where
+    K: Basic + Into<Value<'k>>,
+    V: Type + Into<Value<'v>>,
+{
+    /// Create a new `DictBuilder`.
+    ///
+    /// Same as [`Dict::builder`].
+    pub fn new() -> Self {
+        Self {
+            dict: Dict::new(K::signature(), V::signature()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Add a new entry.
+    ///
+    /// This method returns `Self` so that you can use the builder pattern to create a complex
+    /// dictionary.
+    #[must_use]
+    pub fn add(mut self, key: impl Into<K>, value: impl Into<V>) -> Self {
+        self.push(key, value);
+
+        self
+    }
+
+    /// Add a new entry.
+    ///
+    /// Identical to `add`, except it makes changes in-place.
+    pub fn push(&mut self, key: impl Into<K>, value: impl Into<V>) {
+        let key: K = key.into();
+        let value: V = value.into();
+        self.dict.map.insert(Value::new(key), Value::new(value));
+    }
+
+    /// Build the `Dict`.
+    pub fn build(self) -> Dict<'k, 'v> {
+        self.dict
+    }
+}
+
+impl<'k, 'v, K, V> Default for DictBuilder<'k, 'v, K, V>
+where
+    K: Basic + Into<Value<'k>>,
+    V: Type + Into<Value<'v>>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// `IndexMap` implements neither `Hash` nor `Ord`, so these are implemented in terms of the entries
+// sorted by key. Just like with `BTreeMap`, the order of insertion doesn't affect comparison.
+#[cfg(feature = "indexmap")]
+impl Dict<'_, '_> {
+    fn sorted_entries(&self) -> Vec<(&Value<'_>, &Value<'_>)> {
+        let mut entries: Vec<_> = self.map.iter().collect();
+        entries.sort_unstable_by_key(|(k, _)| *k);
+
+        entries
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl PartialEq for Dict<'_, '_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+            && self.key_signature == other.key_signature
+            && self.value_signature == other.value_signature
+            && self.signature == other.signature
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl Eq for Dict<'_, '_> {}
+
+#[cfg(feature = "indexmap")]
+impl PartialOrd for Dict<'_, '_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl Ord for Dict<'_, '_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sorted_entries()
+            .cmp(&other.sorted_entries())
+            .then_with(|| self.key_signature.cmp(&other.key_signature))
+            .then_with(|| self.value_signature.cmp(&other.value_signature))
+            .then_with(|| self.signature.cmp(&other.signature))
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl Hash for Dict<'_, '_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.sorted_entries().hash(state);
+        self.key_signature.hash(state);
+        self.value_signature.hash(state);
+        self.signature.hash(state);
+    }
+}
+
 impl Display for Dict<'_, '_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return crate::pretty::dict_pretty_fmt(self, f, 0);
+        }
+
         dict_display_fmt(self, f, true)
     }
 }
 
 impl<'k, 'v> IntoIterator for Dict<'k, 'v> {
     type Item = (Value<'k>, Value<'v>);
-    type IntoIter = <BTreeMap<Value<'k>, Value<'v>> as IntoIterator>::IntoIter;
+    type IntoIter = <Map<'k, 'v> as IntoIterator>::IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
         self.map.into_iter()
@@ -261,6 +423,8 @@ macro_rules! from_dict {
 }
 from_dict!(HashMap<K: Eq + Hash, V, H>);
 from_dict!(BTreeMap<K: Ord, V>);
+#[cfg(feature = "indexmap")]
+from_dict!(IndexMap<K: Eq + Hash, V, H>);
 
 // TODO: this could be useful
 // impl<'d, 'k, 'v, K, V, H> TryFrom<&'d Dict<'k, 'v>> for HashMap<&'k K, &'v V, H>
@@ -295,6 +459,8 @@ macro_rules! to_dict {
 }
 to_dict!(HashMap<K: Eq + Hash, V, H>);
 to_dict!(BTreeMap<K: Ord, V>);
+#[cfg(feature = "indexmap")]
+to_dict!(IndexMap<K: Eq + Hash, V, H>);
 
 #[derive(Debug)]
 struct DictEntry<'kref, 'k, 'vref, 'v> {
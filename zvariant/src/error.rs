@@ -32,6 +32,27 @@ impl fmt::Display for MaxDepthExceeded {
     }
 }
 
+/// Enum representing the max size exceeded error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaxSizeExceeded {
+    /// The maximum allowed length of an array in encoding was exceeded.
+    Array,
+    /// The maximum allowed total length of the encoding was exceeded.
+    Total,
+}
+
+impl fmt::Display for MaxSizeExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Array => write!(
+                f,
+                "Maximum allowed length for arrays in encoding was exceeded"
+            ),
+            Self::Total => write!(f, "Maximum allowed length of encoding was exceeded"),
+        }
+    }
+}
+
 /// Error type used by zvariant API.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -60,10 +81,32 @@ pub enum Error {
     OutOfBounds,
     /// The maximum allowed depth for containers in encoding was exceeded.
     MaxDepthExceeded(MaxDepthExceeded),
+    /// The encoded data ended prematurely. The argument is the minimum total length of the data
+    /// (in bytes) needed to continue.
+    InsufficientData(usize),
+    /// The maximum allowed size of an array or the entire encoding was exceeded.
+    ///
+    /// See [`Limits`](crate::serialized::Limits) for details.
+    MaxSizeExceeded(MaxSizeExceeded),
+    /// The encoding is valid but not canonical, which is only an error in [strict] mode. The
+    /// argument describes the issue.
+    ///
+    /// [strict]: crate::serialized::Context::with_strict
+    NonCanonical(String),
 }
 
 assert_impl_all!(Error: Send, Sync, Unpin);
 
+impl Error {
+    /// Make the lengths in the error relative to data starting `offset` bytes earlier.
+    pub(crate) fn offset_by(self, offset: usize) -> Self {
+        match self {
+            Error::InsufficientData(n) => Error::InsufficientData(n + offset),
+            e => e,
+        }
+    }
+}
+
 impl PartialEq for Error {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -74,6 +117,9 @@ impl PartialEq for Error {
             (Error::PaddingNot0(p), Error::PaddingNot0(other)) => p == other,
             (Error::UnknownFd, Error::UnknownFd) => true,
             (Error::MaxDepthExceeded(max1), Error::MaxDepthExceeded(max2)) => max1 == max2,
+            (Error::InsufficientData(n1), Error::InsufficientData(n2)) => n1 == n2,
+            (Error::MaxSizeExceeded(max1), Error::MaxSizeExceeded(max2)) => max1 == max2,
+            (Error::NonCanonical(msg), Error::NonCanonical(other)) => msg == other,
             (_, _) => false,
         }
     }
@@ -115,6 +161,11 @@ impl fmt::Display for Error {
                 "Out of bounds range specified",
             ),
             Error::MaxDepthExceeded(max) => write!(f, "{max}"),
+            Error::InsufficientData(n) => {
+                write!(f, "Insufficient data: at least {n} bytes needed")
+            }
+            Error::MaxSizeExceeded(max) => write!(f, "{max}"),
+            Error::NonCanonical(s) => write!(f, "Non-canonical encoding: {s}"),
         }
     }
 }
@@ -137,6 +188,9 @@ impl Clone for Error {
             }
             Error::OutOfBounds => Error::OutOfBounds,
             Error::MaxDepthExceeded(max) => Error::MaxDepthExceeded(*max),
+            Error::InsufficientData(n) => Error::InsufficientData(*n),
+            Error::MaxSizeExceeded(max) => Error::MaxSizeExceeded(*max),
+            Error::NonCanonical(s) => Error::NonCanonical(s.clone()),
         }
     }
 }
@@ -169,6 +223,14 @@ impl ser::Error for Error {
 
 impl From<io::Error> for Error {
     fn from(val: io::Error) -> Self {
+        // Our serializers report their own errors through `io::Write` sometimes, e.g when the size
+        // limit is exceeded.
+        if val.get_ref().is_some_and(|e| e.is::<Error>()) {
+            let inner = val.into_inner().expect("no inner error");
+
+            return *inner.downcast::<Error>().expect("not a zvariant error");
+        }
+
         Error::InputOutput(Arc::new(val))
     }
 }
@@ -10,6 +10,12 @@ use crate::{serialized::Format, Basic, Signature, Type};
 /// do not implement  [`Serialize`] and [`Deserialize`]. So we provide a
 /// wrapper for both that implements these traits.
 ///
+/// The ownership semantics are those of the wrapped type: an owned file descriptor is closed when
+/// dropped, while a borrowed one can't outlive its owner. Hence, a deserialized `Fd` borrows from
+/// the file descriptors of the [`Data`](crate::serialized::Data) it was deserialized from. Since
+/// duplicating a file descriptor can fail, `Fd` doesn't implement `Clone`. Use
+/// [`Fd::try_clone`] or [`Fd::try_to_owned`] instead.
+///
 /// [`Serialize`]: https://docs.serde.rs/serde/trait.Serialize.html
 /// [`Deserialize`]: https://docs.serde.rs/serde/de/trait.Deserialize.html
 #[derive(Debug)]
@@ -28,6 +34,8 @@ impl<'f> Fd<'f> {
     }
 
     /// Try to clone `self`.
+    ///
+    /// An owned file descriptor is duplicated, while a borrowed one is just copied.
     pub fn try_clone(&self) -> crate::Result<Self> {
         Ok(match self {
             Self::Borrowed(fd) => Self::Borrowed(*fd),
@@ -50,7 +58,7 @@ impl From<fd::OwnedFd> for Fd<'_> {
 
 impl From<OwnedFd> for Fd<'_> {
     fn from(owned: OwnedFd) -> Self {
-        owned.inner
+        Self::Owned(owned.inner)
     }
 }
 
@@ -133,11 +141,26 @@ impl<'de> Deserialize<'de> for Fd<'de> {
         D: Deserializer<'de>,
     {
         let raw = i32::deserialize(deserializer)?;
-        // SAFETY: The `'de` lifetimes will ensure the borrow won't outlive the raw FD.
-        let fd = unsafe { BorrowedFd::borrow_raw(raw) };
 
-        Ok(Fd::Borrowed(fd))
+        borrow_raw(raw).map(Fd::Borrowed)
+    }
+}
+
+// Borrow a deserialized raw file descriptor.
+//
+// Our deserializers resolve the fd index to the file descriptor, which is borrowed from the
+// serialized data and hence lives at least as long as `'de`. The `-1` and other negative values
+// can't come from there and would violate the invariants of `BorrowedFd`.
+pub(crate) fn borrow_raw<'de, E: Error>(raw: RawFd) -> Result<BorrowedFd<'de>, E> {
+    if raw < 0 {
+        return Err(E::invalid_value(
+            serde::de::Unexpected::Signed(raw.into()),
+            &"a file descriptor",
+        ));
     }
+
+    // SAFETY: The `'de` lifetimes will ensure the borrow won't outlive the raw FD.
+    Ok(unsafe { BorrowedFd::borrow_raw(raw) })
 }
 
 impl PartialEq for Fd<'_> {
@@ -167,20 +190,29 @@ impl std::hash::Hash for Fd<'_> {
 
 /// A file-descriptor type wrapper.
 ///
-/// This is the same as [`Fd`] type, except it only keeps an owned file descriptor.
-#[derive(Debug, PartialEq, Eq, Hash)]
+/// This is the same as [`Fd`] type, except it only keeps an owned file descriptor, which is closed
+/// when dropped. Deserializing an `OwnedFd` duplicates the file descriptor, so it doesn't borrow
+/// from the serialized data.
+#[derive(Debug)]
 pub struct OwnedFd {
-    inner: Fd<'static>,
+    inner: fd::OwnedFd,
 }
 
 fd_impl!(OwnedFd);
 
+impl OwnedFd {
+    /// Try to clone `self`, by duplicating the file descriptor.
+    pub fn try_clone(&self) -> crate::Result<Self> {
+        self.inner.try_clone().map(Self::from).map_err(Into::into)
+    }
+}
+
 impl Serialize for OwnedFd {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        self.inner.serialize(serializer)
+        serializer.serialize_i32(self.as_raw_fd())
     }
 }
 
@@ -190,13 +222,10 @@ impl<'de> Deserialize<'de> for OwnedFd {
         D: Deserializer<'de>,
     {
         let fd = Fd::deserialize(deserializer)?;
-        Ok(OwnedFd {
-            inner: fd
-                .as_fd()
-                .try_clone_to_owned()
-                .map(Fd::Owned)
-                .map_err(D::Error::custom)?,
-        })
+        fd.as_fd()
+            .try_clone_to_owned()
+            .map(Self::from)
+            .map_err(D::Error::custom)
     }
 }
 
@@ -214,29 +243,40 @@ impl AsRawFd for OwnedFd {
 
 impl From<fd::OwnedFd> for OwnedFd {
     fn from(value: fd::OwnedFd) -> Self {
-        Self {
-            inner: Fd::Owned(value),
-        }
+        Self { inner: value }
     }
 }
 
 impl From<OwnedFd> for fd::OwnedFd {
     fn from(value: OwnedFd) -> fd::OwnedFd {
-        match value.inner {
-            Fd::Owned(fd) => fd,
-            Fd::Borrowed(_) => unreachable!(),
-        }
+        value.inner
     }
 }
 
-impl From<Fd<'static>> for OwnedFd {
-    fn from(value: Fd<'static>) -> Self {
-        Self { inner: value }
+impl TryFrom<Fd<'_>> for OwnedFd {
+    type Error = crate::Error;
+
+    /// Take the owned file descriptor or duplicate the borrowed one.
+    fn try_from(value: Fd<'_>) -> crate::Result<Self> {
+        fd::OwnedFd::try_from(value).map(Self::from)
+    }
+}
+
+impl PartialEq for OwnedFd {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_raw_fd().eq(&other.as_raw_fd())
+    }
+}
+impl Eq for OwnedFd {}
+
+impl std::hash::Hash for OwnedFd {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_raw_fd().hash(state)
     }
 }
 
 impl std::fmt::Display for OwnedFd {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.inner.fmt(f)
+        self.as_raw_fd().fmt(f)
     }
 }
@@ -0,0 +1,247 @@
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use static_assertions::assert_impl_all;
+use std::{borrow::Cow, fmt, marker::PhantomData, ops::Deref};
+
+use crate::{Basic, Endian, Error, Result, Signature, Type, NATIVE_ENDIAN};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Basic types of a fixed size, that can be (de)serialized in bulk.
+///
+/// This trait is sealed and implemented for `u8`, `i16`, `u16`, `i32`, `u32`, `i64`, `u64` and
+/// `f64`.
+pub trait FixedSize: Basic + Copy + 'static + private::Sealed {
+    #[doc(hidden)]
+    const SIZE: usize;
+
+    #[doc(hidden)]
+    fn from_ne_slice(bytes: &[u8]) -> Self;
+}
+
+macro_rules! fixed_size {
+    ($($ty:ty)+) => {
+        $(
+            impl private::Sealed for $ty {}
+
+            impl FixedSize for $ty {
+                const SIZE: usize = std::mem::size_of::<$ty>();
+
+                fn from_ne_slice(bytes: &[u8]) -> Self {
+                    <$ty>::from_ne_bytes(bytes.try_into().expect("slice of incorrect length"))
+                }
+            }
+        )+
+    };
+}
+
+fixed_size!(u8 i16 u16 i32 u32 i64 u64 f64);
+
+// The size of elements of the given array element signature character, if it's `FixedSize`.
+pub(crate) fn fixed_element_size(c: char) -> Option<usize> {
+    match c {
+        u8::SIGNATURE_CHAR => Some(u8::SIZE),
+        i16::SIGNATURE_CHAR | u16::SIGNATURE_CHAR => Some(u16::SIZE),
+        i32::SIGNATURE_CHAR | u32::SIGNATURE_CHAR => Some(u32::SIZE),
+        i64::SIGNATURE_CHAR | u64::SIGNATURE_CHAR | f64::SIGNATURE_CHAR => Some(u64::SIZE),
+        _ => None,
+    }
+}
+
+// Convert the elements of a `FixedSize` array between native endianness and `endian`.
+pub(crate) fn convert_endian(
+    bytes: &[u8],
+    element_size: usize,
+    endian: Endian,
+) -> Result<Cow<'_, [u8]>> {
+    if bytes.len() % element_size != 0 {
+        return Err(Error::Message(format!(
+            "array length {} is not a multiple of the element size {element_size}",
+            bytes.len(),
+        )));
+    }
+    if element_size == 1 || endian == NATIVE_ENDIAN {
+        return Ok(Cow::Borrowed(bytes));
+    }
+
+    let mut swapped = bytes.to_vec();
+    for element in swapped.chunks_exact_mut(element_size) {
+        element.reverse();
+    }
+
+    Ok(Cow::Owned(swapped))
+}
+
+/// An array of fixed-size basic values, (de)serialized in bulk.
+///
+/// `Vec<T>` and `&[T]` are (de)serialized element by element, which is needlessly slow for large
+/// arrays of numbers. With this wrapper, the whole array is copied in one go instead, swapping the
+/// bytes of each element only if the encoding endianness differs from the native one. The encoding
+/// is the same as that of `Vec<T>`.
+///
+/// With other binary serde formats, the array is (de)serialized as bytes in native endianness.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{serialized::Context, to_bytes, FixedArray, BE};
+///
+/// let samples: Vec<u32> = (0..1024).collect();
+/// let ctxt = Context::new_dbus(BE, 0);
+/// let encoded = to_bytes(ctxt, &FixedArray::from(&samples[..])).unwrap();
+/// assert_eq!(encoded.bytes(), to_bytes(ctxt, &samples).unwrap().bytes());
+///
+/// let decoded: FixedArray<'_, u32> = encoded.deserialize().unwrap().0;
+/// assert_eq!(&*decoded, &samples[..]);
+/// assert_eq!(decoded.into_vec(), samples);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd)]
+pub struct FixedArray<'a, T: FixedSize>(Cow<'a, [T]>);
+
+assert_impl_all!(FixedArray<'_, u32>: Send, Sync, Unpin);
+
+impl<'a, T: FixedSize> FixedArray<'a, T> {
+    /// The elements.
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// Convert into a `Vec` of the elements, copying them only if borrowed.
+    pub fn into_vec(self) -> Vec<T> {
+        self.0.into_owned()
+    }
+
+    /// Creates an owned clone of `self`.
+    pub fn into_owned(self) -> FixedArray<'static, T> {
+        FixedArray(Cow::Owned(self.0.into_owned()))
+    }
+
+    // The elements as bytes, in native endianness.
+    fn as_ne_bytes(&self) -> &[u8] {
+        let elements = self.as_slice();
+
+        // SAFETY: All `FixedSize` types are plain numbers without any padding or invalid bit
+        // patterns and `u8` has no alignment requirements.
+        unsafe {
+            std::slice::from_raw_parts(
+                elements.as_ptr() as *const u8,
+                std::mem::size_of_val(elements),
+            )
+        }
+    }
+}
+
+impl<'a, T: FixedSize> Type for FixedArray<'a, T> {
+    fn signature() -> Signature<'static> {
+        <[T]>::signature()
+    }
+}
+
+impl<'a, T> Serialize for FixedArray<'a, T>
+where
+    T: FixedSize + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return self.as_slice().serialize(serializer);
+        }
+
+        serializer.serialize_bytes(self.as_ne_bytes())
+    }
+}
+
+impl<'de, 'a, T> Deserialize<'de> for FixedArray<'a, T>
+where
+    T: FixedSize + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let visitor = FixedArrayVisitor(PhantomData);
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_seq(visitor)
+        } else {
+            deserializer.deserialize_bytes(visitor)
+        }
+    }
+}
+
+struct FixedArrayVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for FixedArrayVisitor<T>
+where
+    T: FixedSize + Deserialize<'de>,
+{
+    type Value = FixedArray<'static, T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "an array of `{}`", T::SIGNATURE_CHAR)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> std::result::Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if v.len() % T::SIZE != 0 {
+            return Err(de::Error::invalid_length(v.len(), &self));
+        }
+        let elements = v.chunks_exact(T::SIZE).map(T::from_ne_slice).collect();
+
+        Ok(FixedArray(Cow::Owned(elements)))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(e) = seq.next_element()? {
+            elements.push(e);
+        }
+
+        Ok(FixedArray(Cow::Owned(elements)))
+    }
+}
+
+impl<'a, T: FixedSize> From<&'a [T]> for FixedArray<'a, T> {
+    fn from(value: &'a [T]) -> Self {
+        Self(Cow::Borrowed(value))
+    }
+}
+
+impl<'a, T: FixedSize> From<&'a Vec<T>> for FixedArray<'a, T> {
+    fn from(value: &'a Vec<T>) -> Self {
+        Self(Cow::Borrowed(value))
+    }
+}
+
+impl<'a, T: FixedSize> From<Vec<T>> for FixedArray<'a, T> {
+    fn from(value: Vec<T>) -> Self {
+        Self(Cow::Owned(value))
+    }
+}
+
+impl<'a, T: FixedSize> From<FixedArray<'a, T>> for Vec<T> {
+    fn from(value: FixedArray<'a, T>) -> Self {
+        value.into_vec()
+    }
+}
+
+impl<'a, T: FixedSize> Deref for FixedArray<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<'a, T: FixedSize> AsRef<[T]> for FixedArray<'a, T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
 #[cfg(unix)]
 use crate::Fd;
 
-use std::{collections::HashMap, hash::BuildHasher};
+use std::{collections::HashMap, hash::BuildHasher, sync::Arc};
 
 macro_rules! value_try_from {
     ($kind:ident, $to:ty) => {
@@ -80,6 +80,8 @@ value_try_from_all!(Str, Str<'a>);
 value_try_from_all!(Signature, Signature<'a>);
 value_try_from_all!(ObjectPath, ObjectPath<'a>);
 value_try_from!(Str, String);
+value_try_from!(Str, Arc<str>);
+value_try_from_ref_clone!(Str, Arc<str>);
 value_try_from_ref!(Str, str);
 
 macro_rules! value_try_from_ref_try_clone {
@@ -148,6 +150,22 @@ where
     }
 }
 
+impl<'a, T, const N: usize> TryFrom<Value<'a>> for [T; N]
+where
+    T: TryFrom<Value<'a>>,
+    T::Error: Into<crate::Error>,
+{
+    type Error = Error;
+
+    fn try_from(value: Value<'a>) -> Result<Self, Self::Error> {
+        if let Value::Array(v) = value {
+            Self::try_from(v)
+        } else {
+            Err(Error::IncorrectType)
+        }
+    }
+}
+
 impl TryFrom<Value<'_>> for OwnedObjectPath {
     type Error = Error;
 
@@ -180,6 +198,57 @@ where
     }
 }
 
+#[cfg(feature = "uuid")]
+impl TryFrom<&Value<'_>> for uuid::Uuid {
+    type Error = Error;
+
+    fn try_from(value: &Value<'_>) -> Result<Self, Self::Error> {
+        let array = match value {
+            Value::Array(array) if array.element_signature() == "y" => array,
+            _ => return Err(Error::IncorrectType),
+        };
+        let bytes = array
+            .inner()
+            .iter()
+            .map(|v| match v {
+                Value::U8(b) => Ok(*b),
+                _ => Err(Error::IncorrectType),
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        uuid::Uuid::from_slice(&bytes).map_err(|e| Error::Message(e.to_string()))
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl TryFrom<Value<'_>> for uuid::Uuid {
+    type Error = Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+#[cfg(feature = "url")]
+impl TryFrom<&Value<'_>> for url::Url {
+    type Error = Error;
+
+    fn try_from(value: &Value<'_>) -> Result<Self, Self::Error> {
+        let s = <&str>::try_from(value)?;
+
+        url::Url::parse(s).map_err(|e| Error::Message(e.to_string()))
+    }
+}
+
+#[cfg(feature = "url")]
+impl TryFrom<Value<'_>> for url::Url {
+    type Error = Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
 impl<'a, K, V, H> TryFrom<Value<'a>> for HashMap<K, V, H>
 where
     K: crate::Basic + TryFrom<Value<'a>> + std::hash::Hash + std::cmp::Eq,
@@ -199,6 +268,26 @@ where
     }
 }
 
+#[cfg(feature = "indexmap")]
+impl<'a, K, V, H> TryFrom<Value<'a>> for indexmap::IndexMap<K, V, H>
+where
+    K: crate::Basic + TryFrom<Value<'a>> + std::hash::Hash + std::cmp::Eq,
+    V: TryFrom<Value<'a>>,
+    H: BuildHasher + Default,
+    K::Error: Into<crate::Error>,
+    V::Error: Into<crate::Error>,
+{
+    type Error = crate::Error;
+
+    fn try_from(value: Value<'a>) -> Result<Self, Self::Error> {
+        if let Value::Dict(v) = value {
+            Self::try_from(v)
+        } else {
+            Err(crate::Error::IncorrectType)
+        }
+    }
+}
+
 impl<'a, T> TryFrom<Value<'a>> for Optional<T>
 where
     T: TryFrom<Value<'a>> + NoneValue + PartialEq<<T as NoneValue>::NoneType>,
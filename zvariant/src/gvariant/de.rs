@@ -1,13 +1,14 @@
 use serde::de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Visitor};
 use static_assertions::assert_impl_all;
 
-use std::{ffi::CStr, marker::PhantomData, str};
+use std::{borrow::Cow, ffi::CStr, marker::PhantomData, str};
 
 #[cfg(unix)]
 use std::os::fd::AsFd;
 
 use crate::{
-    de::{DeserializerCommon, ValueParseStage},
+    de::{check_tuple_array_len, DeserializerCommon, ValueParseStage},
+    fixed_array::{convert_endian, fixed_element_size},
     framing_offset_size::FramingOffsetSize,
     framing_offsets::FramingOffsets,
     serialized::{Context, Format},
@@ -127,21 +128,22 @@ impl<'de, 'd, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> de::Deseriali
     where
         V: Visitor<'de>,
     {
-        let bytes = deserialize_ay(self)?;
-        visitor.visit_byte_buf(bytes.into())
+        let bytes = deserialize_fixed_array(self)?;
+        visitor.visit_byte_buf(bytes.into_owned())
     }
 
     fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        let bytes = deserialize_ay(self)?;
-        visitor.visit_borrowed_bytes(bytes)
+        match deserialize_fixed_array(self)? {
+            Cow::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Cow::Owned(bytes) => visitor.visit_byte_buf(bytes),
+        }
     }
 
     deserialize_as!(deserialize_char => deserialize_str);
     deserialize_as!(deserialize_string => deserialize_str);
-    deserialize_as!(deserialize_tuple(_l: usize) => deserialize_struct("", &[]));
     deserialize_as!(deserialize_tuple_struct(n: &'static str, _l: usize) => deserialize_struct(n, &[]));
     deserialize_as!(deserialize_struct(_n: &'static str, _f: &'static [&'static str]) => deserialize_seq());
     deserialize_as!(deserialize_map => deserialize_seq);
@@ -212,11 +214,10 @@ impl<'de, 'd, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> de::Deseriali
 
             visitor.visit_none()
         } else {
-            let ctxt = Context::new(
-                self.0.ctxt.format(),
-                self.0.ctxt.endian(),
-                self.0.ctxt.position() + self.0.pos,
-            );
+            let ctxt = self
+                .0
+                .ctxt
+                .with_position(self.0.ctxt.position() + self.0.pos);
             let end = if fixed_sized_child {
                 self.0.bytes.len()
             } else {
@@ -235,6 +236,7 @@ impl<'de, 'd, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> de::Deseriali
             let v = visitor.visit_some(&mut de)?;
             self.0.pos += de.0.pos;
             // No need for retaking the container depths as the underlying type can't be incomplete.
+            de.0.check_all_consumed("a maybe value")?;
 
             if !fixed_sized_child {
                 let byte = *subslice(self.0.bytes, self.0.pos)?;
@@ -353,6 +355,23 @@ impl<'de, 'd, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> de::Deseriali
         }
     }
 
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.0.sig_parser.next_char()? != ARRAY_SIGNATURE_CHAR {
+            return self.deserialize_struct("", &[], visitor);
+        }
+
+        // A fixed-size array.
+        self.0.sig_parser.skip_char()?;
+        let mut array_de = ArrayDeserializer::new(self)?;
+        let v = visitor.visit_seq(&mut array_de)?;
+        check_tuple_array_len(array_de, len)?;
+
+        Ok(v)
+    }
+
     fn deserialize_enum<V>(
         self,
         name: &'static str,
@@ -394,17 +413,25 @@ impl<'de, 'd, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> de::Deseriali
     }
 }
 
-fn deserialize_ay<'de, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F>(
+// Deserialize an array of `FixedSize` elements in one go, as bytes in native endianness.
+fn deserialize_fixed_array<'de, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F>(
     de: &mut Deserializer<'de, '_, '_, F>,
-) -> Result<&'de [u8]> {
-    if de.0.sig_parser.next_signature()? != "ay" {
-        return Err(de::Error::invalid_type(de::Unexpected::Seq, &"ay"));
+) -> Result<Cow<'de, [u8]>> {
+    let signature = de.0.sig_parser.next_signature()?;
+    let element_size = match signature.as_bytes() {
+        [b'a', c] => fixed_element_size(*c as char),
+        _ => None,
     }
+    .ok_or_else(|| <Error as de::Error>::invalid_type(de::Unexpected::Seq, &"ay"))?;
 
     de.0.sig_parser.skip_char()?;
     let ad = ArrayDeserializer::new(de)?;
-    let len = dbg!(ad.len);
-    de.0.next_slice(len)
+    let len = ad.len;
+    de.0.sig_parser.skip_char()?;
+    let bytes = de.0.next_slice(len)?;
+    de.0.container_depths = de.0.container_depths.dec_array();
+
+    convert_endian(bytes, element_size, de.0.ctxt.endian())
 }
 
 struct ArrayDeserializer<'d, 'de, 'sig, 'f, F> {
@@ -466,6 +493,7 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F>
         } else {
             (None, 0, None)
         };
+        de.0.ctxt.limits().check_array_len(len)?;
         let start = de.0.pos;
 
         if de.0.sig_parser.next_char()? == DICT_ENTRY_SIG_START_CHAR {
@@ -528,11 +556,11 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> SeqAccess<'de
             return Ok(None);
         }
 
-        let ctxt = Context::new(
-            self.de.0.ctxt.format(),
-            self.de.0.ctxt.endian(),
-            self.de.0.ctxt.position() + self.de.0.pos,
-        );
+        let ctxt = self
+            .de
+            .0
+            .ctxt
+            .with_position(self.de.0.ctxt.position() + self.de.0.pos);
         let end = self.element_end(true)?;
 
         let mut de = Deserializer::<F>(DeserializerCommon {
@@ -547,6 +575,9 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> SeqAccess<'de
         let v = seed.deserialize(&mut de).map(Some);
         self.de.0.pos += de.0.pos;
         // No need for retaking the container depths as the child can't be incomplete.
+        if v.is_ok() && self.offsets.is_some() {
+            de.0.check_all_consumed("an array element")?;
+        }
 
         if self.de.0.pos > self.start + self.len {
             return Err(serde::de::Error::invalid_length(
@@ -581,11 +612,11 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> MapAccess<'de
 
         self.de.0.parse_padding(self.element_alignment)?;
 
-        let ctxt = Context::new(
-            self.de.0.ctxt.format(),
-            self.de.0.ctxt.endian(),
-            self.de.0.ctxt.position() + self.de.0.pos,
-        );
+        let ctxt = self
+            .de
+            .0
+            .ctxt
+            .with_position(self.de.0.ctxt.position() + self.de.0.pos);
         let element_end = self.element_end(false)?;
 
         let key_end = match self.key_offset_size {
@@ -612,6 +643,9 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> MapAccess<'de
         let v = seed.deserialize(&mut de).map(Some);
         self.de.0.pos += de.0.pos;
         // No need for retaking the container depths as the key can't be incomplete.
+        if v.is_ok() && self.key_offset_size.is_some() {
+            de.0.check_all_consumed("a dictionary key")?;
+        }
 
         if self.de.0.pos > self.start + self.len {
             return Err(serde::de::Error::invalid_length(
@@ -627,11 +661,11 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> MapAccess<'de
     where
         V: DeserializeSeed<'de>,
     {
-        let ctxt = Context::new(
-            self.de.0.ctxt.format(),
-            self.de.0.ctxt.endian(),
-            self.de.0.ctxt.position() + self.de.0.pos,
-        );
+        let ctxt = self
+            .de
+            .0
+            .ctxt
+            .with_position(self.de.0.ctxt.position() + self.de.0.pos);
         let element_end = self.element_end(true)?;
         let value_end = match self.key_offset_size {
             Some(key_offset_size) => element_end - key_offset_size as usize,
@@ -652,6 +686,9 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> MapAccess<'de
         let v = seed.deserialize(&mut de);
         self.de.0.pos += de.0.pos;
         // No need for retaking the container depths as the value can't be incomplete.
+        if v.is_ok() && self.offsets.is_some() {
+            de.0.check_all_consumed("a dictionary value")?;
+        }
 
         if let Some(key_offset_size) = self.key_offset_size {
             self.de.0.pos += key_offset_size as usize;
@@ -688,11 +725,11 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> SeqAccess<'de
     where
         T: DeserializeSeed<'de>,
     {
-        let ctxt = Context::new(
-            self.de.0.ctxt.format(),
-            self.de.0.ctxt.endian(),
-            self.de.0.ctxt.position() + self.de.0.pos,
-        );
+        let ctxt = self
+            .de
+            .0
+            .ctxt
+            .with_position(self.de.0.ctxt.position() + self.de.0.pos);
         let element_signature = self.de.0.sig_parser.next_signature()?;
         let fixed_sized_element = crate::utils::is_fixed_sized_signature(&element_signature)?;
         let element_end = if !fixed_sized_element {
@@ -736,6 +773,9 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> SeqAccess<'de
         let v = seed.deserialize(&mut de).map(Some);
         self.de.0.pos += de.0.pos;
         // No need for retaking the container depths as the field can't be incomplete.
+        if v.is_ok() && !fixed_sized_element {
+            de.0.check_all_consumed("a structure field")?;
+        }
 
         if de.0.sig_parser.next_char()? == STRUCT_SIG_END_CHAR {
             // Last item in the struct
@@ -841,11 +881,11 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> SeqAccess<'de
                 let signature = Signature::try_from(slice)?;
                 let sig_parser = SignatureParser::new(signature);
 
-                let ctxt = Context::new(
-                    self.de.0.ctxt.format(),
-                    self.de.0.ctxt.endian(),
-                    self.de.0.ctxt.position() + self.value_start,
-                );
+                let ctxt = self
+                    .de
+                    .0
+                    .ctxt
+                    .with_position(self.de.0.ctxt.position() + self.value_start);
                 let mut de = Deserializer::<F>(DeserializerCommon {
                     ctxt,
                     sig_parser,
@@ -856,6 +896,9 @@ impl<'d, 'de, 'sig, 'f, #[cfg(unix)] F: AsFd, #[cfg(not(unix))] F> SeqAccess<'de
                 });
 
                 let v = seed.deserialize(&mut de).map(Some);
+                if v.is_ok() {
+                    de.0.check_all_consumed("a variant value")?;
+                }
 
                 self.de.0.pos = self.sig_end;
 
@@ -1,4 +1,46 @@
+//! The [GVariant] encoding format.
+//!
+//! GVariant is the serialization format of GLib. It uses the same type system as D-Bus but differs
+//! in its encoding: containers of variable-sized elements are followed by framing offsets, rather
+//! than preceded by their length, strings aren't prefixed by their length and there is a maybe type
+//! (`m`). This makes it a good fit for storage (e.g dconf databases or OSTree objects), as arrays
+//! and structures can be accessed randomly.
+//!
+//! Use a [`Context`] created through [`Context::new_gvariant`] with the [toplevel functions] and
+//! [`Data`] to encode and decode data in this format. This module additionally provides functions
+//! that only make sense for GVariant, such as [random access](array_element) to the elements of
+//! encoded containers and the [normal form](is_normal_form) checks.
+//!
+//! # Example
+//!
+//! Decoding data as produced by GLib's `g_variant_get_data`:
+//!
+//! ```
+//! use zvariant::{serialized::{Context, Data}, to_bytes, LE};
+//!
+//! let ctxt = Context::new_gvariant(LE, 0);
+//! // `("hello", 42)` of type `(su)`: the string, padding, the integer and the framing offset of
+//! // the end of the string.
+//! let bytes = b"hello\0\0\0\x2a\0\0\0\x06";
+//! let data = Data::new(&bytes[..], ctxt);
+//! let (value, _): ((&str, u32), _) = data.deserialize().unwrap();
+//! assert_eq!(value, ("hello", 42));
+//!
+//! let encoded = to_bytes(ctxt, &value).unwrap();
+//! assert_eq!(encoded.bytes(), bytes);
+//! ```
+//!
+//! [GVariant]: https://developer.gnome.org/documentation/specifications/gvariant-specification-1.0.html
+//! [`Context`]: crate::serialized::Context
+//! [`Context::new_gvariant`]: crate::serialized::Context::new_gvariant
+//! [toplevel functions]: crate#functions
+//! [`Data`]: crate::serialized::Data
+
 mod de;
 pub use de::*;
 mod ser;
 pub use ser::*;
+mod normal_form;
+pub use normal_form::*;
+mod random_access;
+pub use random_access::*;
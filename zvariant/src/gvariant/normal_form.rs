@@ -0,0 +1,339 @@
+use std::fmt;
+
+use serde::{
+    de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeMap, SerializeStruct, SerializeTupleStruct},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+#[cfg(unix)]
+use crate::Fd;
+use crate::{
+    serialized::{Context, Data, Format},
+    to_bytes_for_signature, DynamicType, Endian, Error, ObjectPath, Result, Signature,
+    SignatureTree, Value,
+};
+
+/// Check if `data` is the normal form of the GVariant encoding of a value of `signature`.
+///
+/// The GVariant format allows more than one encoding of the same value and the deserializer
+/// accepts the ones that aren't in normal form, just like GLib. This is the equivalent of GLib's
+/// `g_variant_is_normal_form`: the data is in normal form if it's valid and exactly what the
+/// serializer would produce for the value.
+///
+/// Returns `false` for data that isn't in the GVariant format.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{
+///     gvariant::is_normal_form,
+///     serialized::{Context, Data},
+///     to_bytes, Signature, LE,
+/// };
+///
+/// let ctxt = Context::new_gvariant(LE, 0);
+/// let encoded = to_bytes(ctxt, &(42u8, "hello")).unwrap();
+/// let signature = Signature::try_from("(ys)").unwrap();
+/// assert!(is_normal_form(&encoded, &signature));
+///
+/// // A variant of a `u8`, with an extra byte in the space for the value.
+/// let data = Data::new(&[42, 0, 0, b'y'][..], ctxt);
+/// let signature = Signature::try_from("v").unwrap();
+/// assert!(data.deserialize_for_signature::<_, zvariant::Value<'_>>(&signature).is_ok());
+/// assert!(!is_normal_form(&data, &signature));
+/// ```
+pub fn is_normal_form(data: &Data<'_, '_>, signature: &Signature<'_>) -> bool {
+    decode(data, signature)
+        .and_then(|node| to_bytes_for_signature(data.context(), signature, &node))
+        .map(|encoded| encoded.bytes() == data.bytes())
+        .unwrap_or(false)
+}
+
+/// Convert the GVariant encoding of a value of `signature` to the opposite endianness.
+///
+/// This is the equivalent of GLib's `g_variant_byteswap`, for e.g data from foreign-endian caches.
+/// The returned data is in normal form, even if `data` isn't.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{gvariant::byteswap, serialized::Context, to_bytes, Signature, BE, LE};
+///
+/// let value = (0x1234u16, "hello", vec![1u32, 2]);
+/// let signature = Signature::try_from("(qsau)").unwrap();
+/// let le = to_bytes(Context::new_gvariant(LE, 0), &value).unwrap();
+/// let be = byteswap(&le, &signature).unwrap();
+/// assert_eq!(be.context().endian(), BE);
+/// assert_eq!(be.bytes(), to_bytes(Context::new_gvariant(BE, 0), &value).unwrap().bytes());
+/// assert_eq!(be.deserialize::<(u16, &str, Vec<u32>)>().unwrap().0, value);
+/// ```
+pub fn byteswap(data: &Data<'_, '_>, signature: &Signature<'_>) -> Result<Data<'static, 'static>> {
+    let node = decode(data, signature)?;
+    let ctxt = data.context();
+    let endian = match ctxt.endian() {
+        Endian::Little => Endian::Big,
+        Endian::Big => Endian::Little,
+    };
+    let ctxt = Context::new(Format::GVariant, endian, ctxt.position())
+        .with_limits(ctxt.limits())
+        .with_strict(ctxt.strict());
+
+    to_bytes_for_signature(ctxt, signature, &node)
+}
+
+// Decode the value of `signature` in `data`.
+fn decode<'d>(data: &'d Data<'_, '_>, signature: &Signature<'_>) -> Result<Node<'d>> {
+    let format = data.context().format();
+    if format != Format::GVariant {
+        return Err(Error::IncompatibleFormat(signature.to_owned(), format));
+    }
+    let tree = SignatureTree::try_from(signature)?;
+
+    data.deserialize_with_seed(NodeSeed(&tree))
+        .map(|(node, _)| node)
+}
+
+// A decoded value that encodes back as it was decoded.
+//
+// Unlike `Value`, this keeps the order of dictionary entries and any duplicate keys, so that
+// nothing but the encoding itself changes on re-encoding.
+#[derive(Debug)]
+enum Node<'de> {
+    Basic(Value<'de>),
+    Variant(Signature<'de>, Box<Node<'de>>),
+    Array(Vec<Node<'de>>),
+    Dict(Vec<(Node<'de>, Node<'de>)>),
+    Struct(Vec<Node<'de>>),
+    Maybe(Option<Box<Node<'de>>>),
+}
+
+impl Serialize for Node<'_> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Node::Basic(value) => match value {
+                Value::U8(v) => v.serialize(serializer),
+                Value::Bool(v) => v.serialize(serializer),
+                Value::I16(v) => v.serialize(serializer),
+                Value::U16(v) => v.serialize(serializer),
+                Value::I32(v) => v.serialize(serializer),
+                Value::U32(v) => v.serialize(serializer),
+                Value::I64(v) => v.serialize(serializer),
+                Value::U64(v) => v.serialize(serializer),
+                Value::F64(v) => v.serialize(serializer),
+                Value::Str(v) => v.serialize(serializer),
+                Value::Signature(v) => v.serialize(serializer),
+                Value::ObjectPath(v) => v.serialize(serializer),
+                #[cfg(unix)]
+                Value::Fd(v) => v.serialize(serializer),
+                _ => unreachable!("non-basic value in `Node::Basic`"),
+            },
+            Node::Variant(signature, node) => {
+                // Same as the encoding of `Value`.
+                let mut structure = serializer.serialize_struct("zvariant::Value", 2)?;
+                structure.serialize_field("zvariant::Value::Signature", signature)?;
+                structure.serialize_field("zvariant::Value::Value", node)?;
+
+                structure.end()
+            }
+            Node::Array(elements) => serializer.collect_seq(elements),
+            Node::Dict(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+
+                map.end()
+            }
+            Node::Struct(fields) => {
+                let mut structure = serializer.serialize_tuple_struct("", fields.len())?;
+                for field in fields {
+                    structure.serialize_field(field)?;
+                }
+
+                structure.end()
+            }
+            Node::Maybe(Some(node)) => serializer.serialize_some(node),
+            Node::Maybe(None) => serializer.serialize_none(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct NodeSeed<'t>(&'t SignatureTree);
+
+impl DynamicType for NodeSeed<'_> {
+    fn dynamic_signature(&self) -> Signature<'_> {
+        Signature::from_string_unchecked(self.0.to_string())
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for NodeSeed<'_> {
+    type Value = Node<'de>;
+
+    fn deserialize<D>(self, deserializer: D) -> std::result::Result<Node<'de>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match self.0 {
+            SignatureTree::Basic(c) => match *c {
+                'y' => u8::deserialize(deserializer).map(Value::from),
+                'b' => bool::deserialize(deserializer).map(Value::from),
+                'n' => i16::deserialize(deserializer).map(Value::from),
+                'q' => u16::deserialize(deserializer).map(Value::from),
+                'i' => i32::deserialize(deserializer).map(Value::from),
+                'u' => u32::deserialize(deserializer).map(Value::from),
+                'x' => i64::deserialize(deserializer).map(Value::from),
+                't' => u64::deserialize(deserializer).map(Value::from),
+                'd' => f64::deserialize(deserializer).map(Value::from),
+                's' => <&str>::deserialize(deserializer).map(Value::from),
+                'o' => ObjectPath::deserialize(deserializer).map(Value::from),
+                'g' => Signature::deserialize(deserializer).map(Value::from),
+                #[cfg(unix)]
+                'h' => Fd::deserialize(deserializer).map(Value::from),
+                c => Err(de::Error::invalid_value(
+                    de::Unexpected::Char(c),
+                    &"a basic signature character",
+                )),
+            }
+            .map(Node::Basic),
+            SignatureTree::Variant => deserializer.deserialize_seq(VariantVisitor),
+            SignatureTree::Array(element) => match &**element {
+                SignatureTree::DictEntry(key, value) => {
+                    deserializer.deserialize_seq(DictVisitor(key, value))
+                }
+                element => deserializer.deserialize_seq(ArrayVisitor(element)),
+            },
+            SignatureTree::Struct(fields) => {
+                deserializer.deserialize_struct("", &[], StructVisitor(fields))
+            }
+            SignatureTree::Maybe(inner) => deserializer.deserialize_option(MaybeVisitor(inner)),
+            SignatureTree::DictEntry(..) => Err(de::Error::custom(
+                "dictionary entries are only valid as array elements",
+            )),
+        }
+    }
+}
+
+struct VariantVisitor;
+
+impl<'de> Visitor<'de> for VariantVisitor {
+    type Value = Node<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a variant")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Node<'de>, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let signature: Signature<'de> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let tree = SignatureTree::try_from(&signature).map_err(de::Error::custom)?;
+        let node = seq
+            .next_element_seed(NodeSeed(&tree))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok(Node::Variant(signature, Box::new(node)))
+    }
+}
+
+struct ArrayVisitor<'t>(&'t SignatureTree);
+
+impl<'de> Visitor<'de> for ArrayVisitor<'_> {
+    type Value = Node<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an array")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Node<'de>, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut elements = vec![];
+        while let Some(element) = seq.next_element_seed(NodeSeed(self.0))? {
+            elements.push(element);
+        }
+
+        Ok(Node::Array(elements))
+    }
+}
+
+struct DictVisitor<'t>(&'t SignatureTree, &'t SignatureTree);
+
+impl<'de> Visitor<'de> for DictVisitor<'_> {
+    type Value = Node<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a dictionary")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<Node<'de>, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = vec![];
+        while let Some(entry) = map.next_entry_seed(NodeSeed(self.0), NodeSeed(self.1))? {
+            entries.push(entry);
+        }
+
+        Ok(Node::Dict(entries))
+    }
+}
+
+struct StructVisitor<'t>(&'t [SignatureTree]);
+
+impl<'de> Visitor<'de> for StructVisitor<'_> {
+    type Value = Node<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a structure")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Node<'de>, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut fields = vec![];
+        for (i, field) in self.0.iter().enumerate() {
+            let field = seq
+                .next_element_seed(NodeSeed(field))?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+            fields.push(field);
+        }
+
+        Ok(Node::Struct(fields))
+    }
+}
+
+struct MaybeVisitor<'t>(&'t SignatureTree);
+
+impl<'de> Visitor<'de> for MaybeVisitor<'_> {
+    type Value = Node<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a maybe")
+    }
+
+    fn visit_none<E>(self) -> std::result::Result<Node<'de>, E>
+    where
+        E: de::Error,
+    {
+        Ok(Node::Maybe(None))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> std::result::Result<Node<'de>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        NodeSeed(self.0)
+            .deserialize(deserializer)
+            .map(|node| Node::Maybe(Some(Box::new(node))))
+    }
+}
@@ -0,0 +1,299 @@
+use crate::{
+    framing_offset_size::FramingOffsetSize,
+    serialized::{Data, Format},
+    utils::{alignment_for_signature, padding_for_n_bytes},
+    Error, Result, Signature, SignatureTree,
+};
+
+/// The number of elements of a GVariant-encoded array of `signature`.
+///
+/// Only the framing offsets are read for this, if any, so it's a constant-time operation.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{gvariant::array_len, serialized::Context, to_bytes, Signature, LE};
+///
+/// let ctxt = Context::new_gvariant(LE, 0);
+/// let encoded = to_bytes(ctxt, &vec!["hello", "world"]).unwrap();
+/// let signature = Signature::try_from("as").unwrap();
+/// assert_eq!(array_len(&encoded, &signature).unwrap(), 2);
+/// ```
+pub fn array_len(data: &Data<'_, '_>, signature: &Signature<'_>) -> Result<usize> {
+    let element = array_element_tree(data, signature)?;
+
+    ArrayLayout::new(data.bytes(), &element)?.len(data.bytes())
+}
+
+/// The GVariant-encoded element at `index`, of an array of `signature`.
+///
+/// The framing offsets are used to locate the element, in constant time, without deserializing
+/// any of the other elements. The returned data can then be deserialized as the element type.
+/// [`Error::OutOfBounds`] is returned if `index` is out of bounds.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{gvariant::array_element, serialized::Context, to_bytes, Signature, LE};
+///
+/// let ctxt = Context::new_gvariant(LE, 0);
+/// let strings: Vec<_> = (0..1000).map(|i| i.to_string()).collect();
+/// let encoded = to_bytes(ctxt, &strings).unwrap();
+/// let signature = Signature::try_from("as").unwrap();
+///
+/// let element = array_element(&encoded, &signature, 512).unwrap();
+/// assert_eq!(element.deserialize::<&str>().unwrap().0, "512");
+/// ```
+pub fn array_element<'b, 'f>(
+    data: &Data<'b, 'f>,
+    signature: &Signature<'_>,
+    index: usize,
+) -> Result<Data<'b, 'f>> {
+    let element = array_element_tree(data, signature)?;
+    let range = ArrayLayout::new(data.bytes(), &element)?.element(data.bytes(), index)?;
+
+    Ok(data.slice(range))
+}
+
+/// The GVariant-encoded field at `index`, of a structure of `signature`.
+///
+/// The framing offsets are used to locate the field, without deserializing any of the other
+/// fields. The fields of structures derived with [`Type`](derive.Type.html) are in the order of
+/// their declaration, so named fields are accessed by their position. [`Error::OutOfBounds`] is
+/// returned if `index` is out of bounds.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Serialize;
+/// use zvariant::{gvariant::struct_field, serialized::Context, to_bytes, Type, LE};
+///
+/// #[derive(Serialize, Type)]
+/// struct Entry<'s> {
+///     name: &'s str,
+///     tags: Vec<&'s str>,
+///     size: u64,
+/// }
+///
+/// // The position of the `size` field.
+/// const SIZE: usize = 2;
+///
+/// let ctxt = Context::new_gvariant(LE, 0);
+/// let entry = Entry {
+///     name: "zbus",
+///     tags: vec!["rust", "dbus"],
+///     size: 42,
+/// };
+/// let encoded = to_bytes(ctxt, &entry).unwrap();
+///
+/// let size = struct_field(&encoded, &Entry::signature(), SIZE).unwrap();
+/// assert_eq!(size.deserialize::<u64>().unwrap().0, 42);
+/// let tags = struct_field(&encoded, &Entry::signature(), 1).unwrap();
+/// assert_eq!(tags.deserialize::<Vec<&str>>().unwrap().0, ["rust", "dbus"]);
+/// ```
+pub fn struct_field<'b, 'f>(
+    data: &Data<'b, 'f>,
+    signature: &Signature<'_>,
+    index: usize,
+) -> Result<Data<'b, 'f>> {
+    check_format(data, signature)?;
+    let fields = match SignatureTree::try_from(signature)? {
+        SignatureTree::Struct(fields) => fields,
+        _ => {
+            return Err(Error::SignatureMismatch(
+                signature.to_owned(),
+                "a structure signature".to_string(),
+            ))
+        }
+    };
+    if index >= fields.len() {
+        return Err(Error::OutOfBounds);
+    }
+
+    let bytes = data.bytes();
+    let offset_size = FramingOffsetSize::for_encoded_container(bytes.len());
+    // The end of the fields, before the framing offsets read so far.
+    let mut end = bytes.len();
+    let mut pos = 0;
+    for (i, field) in fields.iter().enumerate() {
+        let start = pos + padding_for_n_bytes(pos, alignment(field)?);
+        let field_end = match fixed_size(field)? {
+            Some(size) => start + size,
+            // The last field has no framing offset.
+            None if i == fields.len() - 1 => end,
+            None => {
+                let offset = read_offset(&bytes[..end], offset_size)?;
+                end -= offset_size as usize;
+
+                offset
+            }
+        };
+        if start > field_end || field_end > end {
+            return Err(invalid_offset(field_end, end));
+        }
+        if i == index {
+            return Ok(data.slice(start..field_end));
+        }
+
+        pos = field_end;
+    }
+
+    unreachable!("field index checked already");
+}
+
+// The layout of the elements of an encoded array.
+enum ArrayLayout {
+    Fixed {
+        size: usize,
+    },
+    Framed {
+        alignment: usize,
+        offset_size: FramingOffsetSize,
+        // Where the framing offsets start, i-e the end of the last element.
+        offsets_start: usize,
+    },
+}
+
+impl ArrayLayout {
+    fn new(bytes: &[u8], element: &SignatureTree) -> Result<Self> {
+        if let Some(size) = fixed_size(element)? {
+            return Ok(Self::Fixed { size });
+        }
+
+        let offset_size = FramingOffsetSize::for_encoded_container(bytes.len());
+        // Empty arrays have no framing offsets.
+        let offsets_start = match bytes.len() {
+            0 => 0,
+            _ => read_offset(bytes, offset_size)?,
+        };
+        if offsets_start > bytes.len() {
+            return Err(invalid_offset(offsets_start, bytes.len()));
+        }
+
+        Ok(Self::Framed {
+            alignment: alignment(element)?,
+            offset_size,
+            offsets_start,
+        })
+    }
+
+    fn len(&self, bytes: &[u8]) -> Result<usize> {
+        let (len, element_size) = match self {
+            Self::Fixed { size } => (bytes.len(), *size),
+            Self::Framed {
+                offset_size,
+                offsets_start,
+                ..
+            } => (bytes.len() - offsets_start, *offset_size as usize),
+        };
+        if len % element_size != 0 {
+            return Err(serde::de::Error::invalid_length(
+                len,
+                &format!("a multiple of {element_size}").as_str(),
+            ));
+        }
+
+        Ok(len / element_size)
+    }
+
+    fn element(&self, bytes: &[u8], index: usize) -> Result<std::ops::Range<usize>> {
+        if index >= self.len(bytes)? {
+            return Err(Error::OutOfBounds);
+        }
+
+        match *self {
+            Self::Fixed { size } => Ok(index * size..(index + 1) * size),
+            Self::Framed {
+                alignment,
+                offset_size,
+                offsets_start,
+            } => {
+                let size = offset_size as usize;
+                let offset = |i: usize| {
+                    let start = offsets_start + i * size;
+
+                    read_offset(&bytes[start..start + size], offset_size)
+                };
+                let start = match index {
+                    0 => 0,
+                    _ => {
+                        let end = offset(index - 1)?;
+
+                        end + padding_for_n_bytes(end, alignment)
+                    }
+                };
+                let end = offset(index)?;
+                if start > end || end > offsets_start {
+                    return Err(invalid_offset(end, offsets_start));
+                }
+
+                Ok(start..end)
+            }
+        }
+    }
+}
+
+fn check_format(data: &Data<'_, '_>, signature: &Signature<'_>) -> Result<()> {
+    let format = data.context().format();
+    if format != Format::GVariant {
+        return Err(Error::IncompatibleFormat(signature.to_owned(), format));
+    }
+
+    Ok(())
+}
+
+fn array_element_tree(data: &Data<'_, '_>, signature: &Signature<'_>) -> Result<SignatureTree> {
+    check_format(data, signature)?;
+    match SignatureTree::try_from(signature)? {
+        SignatureTree::Array(element) => Ok(*element),
+        _ => Err(Error::SignatureMismatch(
+            signature.to_owned(),
+            "an array signature".to_string(),
+        )),
+    }
+}
+
+fn alignment(tree: &SignatureTree) -> Result<usize> {
+    match tree {
+        // Dictionary entry signatures are only valid within arrays.
+        SignatureTree::DictEntry(key, value) => Ok(alignment(key)?.max(alignment(value)?)),
+        _ => alignment_for_signature(&tree.signature()?, Format::GVariant),
+    }
+}
+
+// The size of values of `tree`, if it's a fixed-size type.
+fn fixed_size(tree: &SignatureTree) -> Result<Option<usize>> {
+    let fields = match tree {
+        SignatureTree::Basic('y' | 'b') => return Ok(Some(1)),
+        SignatureTree::Basic('n' | 'q') => return Ok(Some(2)),
+        SignatureTree::Basic('i' | 'u' | 'h') => return Ok(Some(4)),
+        SignatureTree::Basic('x' | 't' | 'd') => return Ok(Some(8)),
+        SignatureTree::Struct(fields) if fields.is_empty() => return Ok(Some(1)),
+        SignatureTree::Struct(fields) => fields.iter().collect(),
+        SignatureTree::DictEntry(key, value) => vec![&**key, &**value],
+        _ => return Ok(None),
+    };
+
+    let mut size = 0;
+    for field in fields {
+        match fixed_size(field)? {
+            Some(field_size) => size += padding_for_n_bytes(size, alignment(field)?) + field_size,
+            None => return Ok(None),
+        }
+    }
+
+    Ok(Some(size + padding_for_n_bytes(size, alignment(tree)?)))
+}
+
+// Read the framing offset at the end of `bytes`.
+fn read_offset(bytes: &[u8], offset_size: FramingOffsetSize) -> Result<usize> {
+    if bytes.len() < offset_size as usize {
+        return Err(Error::InsufficientData(offset_size as usize));
+    }
+
+    Ok(offset_size.read_last_offset_from_buffer(bytes))
+}
+
+fn invalid_offset(offset: usize, limit: usize) -> Error {
+    serde::de::Error::invalid_length(offset, &format!("<= {limit}").as_str())
+}
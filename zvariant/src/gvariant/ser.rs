@@ -76,9 +76,7 @@ where
                 self.0.container_depths = self.0.container_depths.dec_maybe();
 
                 if !fixed_sized_child {
-                    self.0
-                        .write_all(&b"\0"[..])
-                        .map_err(|e| Error::InputOutput(e.into()))?;
+                    self.0.write_all(&b"\0"[..]).map_err(Error::from)?;
                 }
             }
             None => {
@@ -169,6 +167,7 @@ where
         let c = self.0.sig_parser.next_char()?;
         if c == VARIANT_SIGNATURE_CHAR {
             self.0.value_sign = Some(signature_string!(v));
+            self.0.sig_parser.skip_char()?;
 
             // signature is serialized after the value in GVariant
             return Ok(());
@@ -177,22 +176,15 @@ where
         // Strings in GVariant format require no alignment.
 
         self.0.sig_parser.skip_char()?;
-        self.0
-            .write_all(v.as_bytes())
-            .map_err(|e| Error::InputOutput(e.into()))?;
-        self.0
-            .write_all(&b"\0"[..])
-            .map_err(|e| Error::InputOutput(e.into()))?;
+        self.0.write_all(v.as_bytes()).map_err(Error::from)?;
+        self.0.write_all(&b"\0"[..]).map_err(Error::from)?;
 
         Ok(())
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<()> {
         let seq = self.serialize_seq(Some(v.len()))?;
-        seq.ser
-            .0
-            .write(v)
-            .map_err(|e| Error::InputOutput(e.into()))?;
+        seq.ser.0.write_fixed_array(v)?;
         seq.end()
     }
 
@@ -223,9 +215,7 @@ where
     }
 
     fn serialize_unit(self) -> Result<()> {
-        self.0
-            .write_all(&b"\0"[..])
-            .map_err(|e| Error::InputOutput(e.into()))
+        self.0.write_all(&b"\0"[..]).map_err(Error::from)
     }
 
     fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
@@ -328,7 +318,7 @@ where
     }
 
     fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
-        if len == 0 {
+        if len == 0 && self.0.sig_parser.next_char().ok() != Some(ARRAY_SIGNATURE_CHAR) {
             return StructSerializer::unit(self).map(StructSeqSerializer::Struct);
         }
 
@@ -383,11 +373,12 @@ where
             .skip_chars(self.element_signature_len)?;
         self.ser.0.container_depths = self.ser.0.container_depths.dec_array();
 
+        let array_len = self.ser.0.bytes_written - self.start;
+        self.ser.0.ctxt.limits().check_array_len(array_len)?;
         let offsets = match self.offsets {
             Some(offsets) => offsets,
             None => return Ok(()),
         };
-        let array_len = self.ser.0.bytes_written - self.start;
         if array_len == 0 {
             // Empty sequence
             return Ok(());
@@ -555,14 +546,11 @@ where
                 value.serialize(&mut ser)?;
                 self.ser.0.bytes_written = ser.0.bytes_written;
 
-                self.ser
-                    .0
-                    .write_all(&b"\0"[..])
-                    .map_err(|e| Error::InputOutput(e.into()))?;
+                self.ser.0.write_all(&b"\0"[..]).map_err(Error::from)?;
                 self.ser
                     .0
                     .write_all(signature.as_bytes())
-                    .map_err(|e| Error::InputOutput(e.into()))?;
+                    .map_err(Error::from)?;
 
                 Ok(())
             }
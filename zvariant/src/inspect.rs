@@ -0,0 +1,432 @@
+use std::fmt::{self, Display, Write};
+
+use static_assertions::assert_impl_all;
+
+#[cfg(feature = "gvariant")]
+use crate::serialized::Format;
+use crate::{
+    serialized::Context, utils::padding_for_n_bytes, Error, MaxDepthExceeded, Result, Signature,
+    SignatureTree,
+};
+
+// The maximum nesting depth of containers, as per the D-Bus specification.
+const MAX_DEPTH: usize = 64;
+
+/// A part of an encoding, as identified by [`inspect`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    offset: usize,
+    len: usize,
+    depth: usize,
+    kind: SegmentKind,
+}
+
+assert_impl_all!(Segment: Send, Sync, Unpin);
+
+impl Segment {
+    /// The offset of the segment, from the start of the inspected bytes.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The length of the segment, in bytes.
+    ///
+    /// For containers, this is only the length of their header (e.g the array length), if any.
+    /// Their contents follow as separate segments.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the segment is empty, which is the case for structures and dictionary entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The nesting depth of the segment, 0 for the top-level values.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// What the segment is.
+    pub fn kind(&self) -> &SegmentKind {
+        &self.kind
+    }
+}
+
+/// The kind of a [`Segment`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SegmentKind {
+    /// Padding (zeros) for the alignment of the following value.
+    Padding,
+    /// A basic value of the given signature character, in the GVariant text format, except for
+    /// file descriptors, which are given as their index.
+    Basic(char, String),
+    /// The length of an array of the given signature, in bytes. The elements follow, one level
+    /// deeper.
+    Array(String, u32),
+    /// The start of a structure or dictionary entry of the given signature. The fields follow, one
+    /// level deeper.
+    Structure(String),
+    /// The signature of the value of a variant. The value follows, one level deeper.
+    Variant(String),
+    /// Bytes left after the last value.
+    Trailing,
+}
+
+impl Display for SegmentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Padding => f.write_str("padding"),
+            Self::Basic('h', index) => write!(f, "h fd index {index}"),
+            Self::Basic(c, value) => write!(f, "{c} {value}"),
+            Self::Array(signature, len) => write!(f, "{signature} array of {len} bytes"),
+            Self::Structure(signature) => write!(f, "{signature}"),
+            Self::Variant(signature) => write!(f, "v variant of `{signature}`"),
+            Self::Trailing => f.write_str("trailing bytes"),
+        }
+    }
+}
+
+/// The breakdown of an encoding, as returned by [`inspect`].
+///
+/// The `Display` implementation gives a table of the segments, one per line, with their offset,
+/// length and bytes. It ends with the error, if decoding failed.
+#[derive(Debug)]
+pub struct Inspection<'b> {
+    bytes: &'b [u8],
+    segments: Vec<Segment>,
+    error: Option<(usize, Error)>,
+}
+
+assert_impl_all!(Inspection<'_>: Send, Sync, Unpin);
+
+impl Inspection<'_> {
+    /// The segments identified, in order.
+    ///
+    /// If decoding failed, these are the segments up to the point of failure.
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// The error decoding failed with, if any, and the offset at which it happened.
+    pub fn error(&self) -> Option<(usize, &Error)> {
+        self.error.as_ref().map(|(offset, e)| (*offset, e))
+    }
+}
+
+impl Display for Inspection<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.segments {
+            write!(f, "{:#06x}  {:>4}  ", segment.offset, segment.len)?;
+            for _ in 0..segment.depth {
+                f.write_str("  ")?;
+            }
+            write!(f, "{}", segment.kind)?;
+
+            let bytes = &self.bytes[segment.offset..segment.offset + segment.len];
+            if !bytes.is_empty() {
+                f.write_str(" [")?;
+                for (i, byte) in bytes.iter().take(16).enumerate() {
+                    if i > 0 {
+                        f.write_char(' ')?;
+                    }
+                    write!(f, "{byte:02x}")?;
+                }
+                if bytes.len() > 16 {
+                    f.write_str(" ..")?;
+                }
+                f.write_char(']')?;
+            }
+            f.write_char('\n')?;
+        }
+        if let Some((offset, e)) = &self.error {
+            write!(f, "{offset:#06x}  error: {e}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Break down the encoding of values of the given signature, for debugging.
+///
+/// Unlike deserialization, which only gives a value or an error, this identifies every part of
+/// the encoding: the offsets of values and of the padding in between, array lengths, variant
+/// signatures etc. If decoding fails, the breakdown up to the point of failure is still available,
+/// along with the error and its offset. This helps in tracking down interoperability bugs and
+/// making sense of corrupt captures.
+///
+/// The [`Context::position`] is taken into account for the alignment of values. Only the D-Bus
+/// format is currently supported.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{inspect, serialized::Context, to_bytes, SegmentKind, Signature, LE};
+///
+/// let ctxt = Context::new_dbus(LE, 0);
+/// let encoded = to_bytes(ctxt, &(7u8, vec!["hi"])).unwrap();
+/// let signature = Signature::try_from("yas").unwrap();
+///
+/// let inspection = inspect(encoded.bytes(), &signature, ctxt);
+/// assert!(inspection.error().is_none());
+/// let kinds: Vec<_> = inspection.segments().iter().map(|s| s.kind().clone()).collect();
+/// assert_eq!(
+///     kinds,
+///     [
+///         SegmentKind::Basic('y', "0x07".into()),
+///         SegmentKind::Padding,
+///         SegmentKind::Array("as".into(), 7),
+///         SegmentKind::Basic('s', "\"hi\"".into()),
+///     ],
+/// );
+/// assert_eq!(
+///     inspection.to_string(),
+///     "0x0000     1  y 0x07 [07]\n\
+///      0x0001     3  padding [00 00 00]\n\
+///      0x0004     4  as array of 7 bytes [07 00 00 00]\n\
+///      0x0008     7    s \"hi\" [02 00 00 00 68 69 00]\n",
+/// );
+///
+/// // Truncated data.
+/// let inspection = inspect(&encoded.bytes()[..10], &signature, ctxt);
+/// assert_eq!(inspection.segments().len(), 3);
+/// assert_eq!(inspection.error().unwrap().0, 8);
+/// ```
+pub fn inspect<'b>(bytes: &'b [u8], signature: &Signature<'_>, ctxt: Context) -> Inspection<'b> {
+    let mut inspector = Inspector {
+        bytes,
+        ctxt,
+        pos: 0,
+        segments: vec![],
+    };
+    let error = inspector
+        .inspect(signature)
+        .err()
+        .map(|e| (inspector.pos, e));
+
+    Inspection {
+        bytes,
+        segments: inspector.segments,
+        error,
+    }
+}
+
+struct Inspector<'b> {
+    bytes: &'b [u8],
+    ctxt: Context,
+    pos: usize,
+    segments: Vec<Segment>,
+}
+
+impl<'b> Inspector<'b> {
+    fn inspect(&mut self, signature: &Signature<'_>) -> Result<()> {
+        #[cfg(feature = "gvariant")]
+        if self.ctxt.format() == Format::GVariant {
+            return Err(Error::IncompatibleFormat(
+                signature.to_owned(),
+                Format::GVariant,
+            ));
+        }
+
+        for tree in SignatureTree::parse(signature)? {
+            self.value(&tree, 0)?;
+        }
+        if self.pos < self.bytes.len() {
+            self.push(self.bytes.len() - self.pos, 0, SegmentKind::Trailing);
+        }
+
+        Ok(())
+    }
+
+    fn value(&mut self, tree: &SignatureTree, depth: usize) -> Result<()> {
+        if depth > MAX_DEPTH {
+            return Err(Error::MaxDepthExceeded(MaxDepthExceeded::Container));
+        }
+
+        match tree {
+            SignatureTree::Basic(c) => self.basic(*c, depth),
+            SignatureTree::Variant => {
+                let signature = self.signature()?;
+                self.push(
+                    signature.len() + 2,
+                    depth,
+                    SegmentKind::Variant(signature.to_string()),
+                );
+                let tree = SignatureTree::try_from(&signature)?;
+
+                self.value(&tree, depth + 1)
+            }
+            SignatureTree::Array(element) => {
+                self.align(4, depth)?;
+                let len = self.ctxt.endian().read_u32(self.peek(4)?);
+                self.push(4, depth, SegmentKind::Array(tree.to_string(), len));
+                self.align(alignment(element), depth + 1)?;
+                let end = self.pos + len as usize;
+                while self.pos < end {
+                    self.value(element, depth + 1)?;
+                }
+                if self.pos > end {
+                    return Err(Error::Message(format!(
+                        "array element overruns the array end at offset {end:#x}",
+                    )));
+                }
+
+                Ok(())
+            }
+            SignatureTree::DictEntry(key, value) => {
+                self.align(8, depth)?;
+                self.push(0, depth, SegmentKind::Structure(tree.to_string()));
+                self.value(key, depth + 1)?;
+
+                self.value(value, depth + 1)
+            }
+            SignatureTree::Struct(fields) => {
+                self.align(8, depth)?;
+                self.push(0, depth, SegmentKind::Structure(tree.to_string()));
+                for field in fields {
+                    self.value(field, depth + 1)?;
+                }
+
+                Ok(())
+            }
+            #[cfg(feature = "gvariant")]
+            SignatureTree::Maybe(_) => {
+                Err(Error::IncompatibleFormat(tree.signature()?, Format::DBus))
+            }
+        }
+    }
+
+    fn basic(&mut self, c: char, depth: usize) -> Result<()> {
+        let endian = self.ctxt.endian();
+        let (len, value) = match c {
+            'y' => {
+                let byte = self.peek(1)?[0];
+
+                (1, format!("0x{byte:02x}"))
+            }
+            'b' => {
+                self.align(4, depth)?;
+                let value = match endian.read_u32(self.peek(4)?) {
+                    0 => "false".to_string(),
+                    1 => "true".to_string(),
+                    other => {
+                        return Err(Error::Message(format!("invalid boolean value `{other}`")));
+                    }
+                };
+
+                (4, value)
+            }
+            'n' | 'q' => {
+                self.align(2, depth)?;
+                let bytes = self.peek(2)?;
+                let value = if c == 'n' {
+                    endian.read_i16(bytes).to_string()
+                } else {
+                    endian.read_u16(bytes).to_string()
+                };
+
+                (2, value)
+            }
+            'i' | 'u' | 'h' => {
+                self.align(4, depth)?;
+                let bytes = self.peek(4)?;
+                let value = if c == 'i' {
+                    endian.read_i32(bytes).to_string()
+                } else {
+                    endian.read_u32(bytes).to_string()
+                };
+
+                (4, value)
+            }
+            'x' | 't' | 'd' => {
+                self.align(8, depth)?;
+                let bytes = self.peek(8)?;
+                let value = match c {
+                    'x' => endian.read_i64(bytes).to_string(),
+                    't' => endian.read_u64(bytes).to_string(),
+                    _ => format!("{:?}", endian.read_f64(bytes)),
+                };
+
+                (8, value)
+            }
+            's' | 'o' => {
+                self.align(4, depth)?;
+                let len = endian.read_u32(self.peek(4)?) as usize;
+                let string = self.string(4, len)?;
+
+                (len + 5, format!("{string:?}"))
+            }
+            'g' => {
+                let signature = self.signature()?;
+
+                (signature.len() + 2, format!("{:?}", signature.as_str()))
+            }
+            _ => return Err(Error::Message(format!("unknown type `{c}`"))),
+        };
+        self.push(len, depth, SegmentKind::Basic(c, value));
+
+        Ok(())
+    }
+
+    // The signature of a variant or a `g` value at the current position.
+    fn signature(&self) -> Result<Signature<'static>> {
+        let len = self.peek(1)?[0] as usize;
+        let signature = self.string(1, len)?;
+
+        Signature::try_from(signature).map(|s| s.to_owned())
+    }
+
+    // The string of `len` bytes and its nul terminator, `skip` bytes after the current position.
+    fn string(&self, skip: usize, len: usize) -> Result<&'b str> {
+        let bytes = &self.peek(skip + len + 1)?[skip..];
+        if bytes[len] != b'\0' {
+            return Err(Error::Message("string is not nul-terminated".to_string()));
+        }
+
+        std::str::from_utf8(&bytes[..len]).map_err(Error::Utf8)
+    }
+
+    fn align(&mut self, alignment: usize, depth: usize) -> Result<()> {
+        let padding = padding_for_n_bytes(self.ctxt.position() + self.pos, alignment);
+        if padding == 0 || self.pos == self.bytes.len() {
+            return Ok(());
+        }
+        if let Some(byte) = self.peek(padding)?.iter().copied().find(|b| *b != 0) {
+            return Err(Error::PaddingNot0(byte));
+        }
+        self.push(padding, depth, SegmentKind::Padding);
+
+        Ok(())
+    }
+
+    // The `len` bytes at the current position.
+    fn peek(&self, len: usize) -> Result<&'b [u8]> {
+        let end = self.pos + len;
+
+        self.bytes
+            .get(self.pos..end)
+            .ok_or(Error::InsufficientData(end))
+    }
+
+    // Add a segment of `len` bytes at the current position and move past it.
+    fn push(&mut self, len: usize, depth: usize, kind: SegmentKind) {
+        self.segments.push(Segment {
+            offset: self.pos,
+            len,
+            depth,
+            kind,
+        });
+        self.pos += len;
+    }
+}
+
+fn alignment(tree: &SignatureTree) -> usize {
+    match tree {
+        SignatureTree::Basic('y' | 'g') | SignatureTree::Variant => 1,
+        SignatureTree::Basic('n' | 'q') => 2,
+        SignatureTree::Basic('x' | 't' | 'd') => 8,
+        SignatureTree::Struct(_) | SignatureTree::DictEntry(..) => 8,
+        _ => 4,
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, hash::BuildHasher};
+use std::{collections::HashMap, hash::BuildHasher, sync::Arc};
 
 #[cfg(feature = "gvariant")]
 use crate::Maybe;
@@ -85,6 +85,33 @@ impl From<String> for Value<'_> {
     }
 }
 
+impl From<Arc<str>> for Value<'_> {
+    fn from(v: Arc<str>) -> Self {
+        Value::Str(crate::Str::from(v))
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for Value<'_> {
+    fn from(v: uuid::Uuid) -> Self {
+        Value::from(v.as_bytes().to_vec())
+    }
+}
+
+#[cfg(feature = "url")]
+impl From<url::Url> for Value<'_> {
+    fn from(v: url::Url) -> Self {
+        Value::from(String::from(v))
+    }
+}
+
+#[cfg(feature = "url")]
+impl<'a> From<&'a url::Url> for Value<'a> {
+    fn from(v: &'a url::Url) -> Self {
+        Value::from(v.as_str())
+    }
+}
+
 impl<'v, 's: 'v, T> From<T> for Value<'v>
 where
     T: Into<Structure<'s>>,
@@ -112,6 +139,15 @@ where
     }
 }
 
+impl<'v, V, const N: usize> From<[V; N]> for Value<'v>
+where
+    [V; N]: Into<Array<'v>>,
+{
+    fn from(v: [V; N]) -> Value<'v> {
+        Value::Array(v.into())
+    }
+}
+
 impl<'v, V> From<&'v Vec<V>> for Value<'v>
 where
     &'v Vec<V>: Into<Array<'v>>,
@@ -134,6 +170,20 @@ where
     }
 }
 
+#[cfg(feature = "indexmap")]
+impl<'a, 'k, 'v, K, V, H> From<indexmap::IndexMap<K, V, H>> for Value<'a>
+where
+    'k: 'a,
+    'v: 'a,
+    K: Type + Into<Value<'k>> + std::hash::Hash + std::cmp::Eq,
+    V: Type + Into<Value<'v>>,
+    H: BuildHasher + Default,
+{
+    fn from(value: indexmap::IndexMap<K, V, H>) -> Self {
+        Self::Dict(value.into())
+    }
+}
+
 impl<'v> From<&'v String> for Value<'v> {
     fn from(v: &'v String) -> Value<'v> {
         Value::Str(v.into())
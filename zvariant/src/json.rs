@@ -0,0 +1,198 @@
+//! Conversions between [`Value`](enum@Value) and [`serde_json::Value`].
+//!
+//! Enabled by the `serde_json` feature, or its `json` alias.
+
+use serde_json::{Map, Number};
+#[cfg(unix)]
+use std::os::fd::AsRawFd;
+
+use crate::{Array, Dict, Error, OwnedValue, Result, Signature, Str, Value};
+
+/// Convert a JSON value to a [`Value`](enum@Value).
+///
+/// * `null` can't be represented in D-Bus and results in an error.
+/// * `true` & `false` are converted to `b`.
+/// * Numbers are converted to `x` if they fit in an `i64`, to `t` if they fit in an `u64` and to
+///   `d` otherwise.
+/// * Strings are converted to `s`.
+/// * Arrays are converted to arrays of the common type of their elements (e.g `as`). If the
+///   elements are of different types or the array is empty, the result is an `av`.
+/// * Objects are converted to `a{sv}` dictionaries.
+impl TryFrom<&serde_json::Value> for Value<'static> {
+    type Error = Error;
+
+    fn try_from(value: &serde_json::Value) -> Result<Self> {
+        match value {
+            serde_json::Value::Null => Err(Error::Message(
+                "JSON `null` can't be represented in D-Bus".into(),
+            )),
+            serde_json::Value::Bool(b) => Ok(Value::Bool(*b)),
+            serde_json::Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    Ok(Value::I64(i))
+                } else if let Some(u) = n.as_u64() {
+                    Ok(Value::U64(u))
+                } else {
+                    // With the default features of serde_json, every number is one of the above or
+                    // an f64.
+                    n.as_f64()
+                        .map(Value::F64)
+                        .ok_or_else(|| Error::Message(format!("unsupported JSON number `{n}`")))
+                }
+            }
+            serde_json::Value::String(s) => Ok(Value::Str(Str::from(s.clone()))),
+            serde_json::Value::Array(elements) => {
+                let elements = elements
+                    .iter()
+                    .map(Value::try_from)
+                    .collect::<Result<Vec<_>>>()?;
+                let common_signature = match elements.split_first() {
+                    Some((first, rest)) => {
+                        let signature = first.value_signature();
+                        rest.iter()
+                            .all(|e| e.value_signature() == signature)
+                            .then(|| signature.to_owned())
+                    }
+                    None => None,
+                };
+
+                let array = match common_signature {
+                    Some(signature) => {
+                        let mut array = Array::new(signature);
+                        for element in elements {
+                            array.append(element)?;
+                        }
+
+                        array
+                    }
+                    None => {
+                        let mut array = Array::new(VARIANT_SIGNATURE);
+                        for element in elements {
+                            array.append(Value::new(element))?;
+                        }
+
+                        array
+                    }
+                };
+
+                Ok(Value::Array(array))
+            }
+            serde_json::Value::Object(map) => {
+                let mut dict = Dict::new(STRING_SIGNATURE, VARIANT_SIGNATURE);
+                for (key, value) in map {
+                    let value = Value::try_from(value)?;
+                    dict.append(Value::from(key.clone()), Value::new(value))?;
+                }
+
+                Ok(Value::Dict(dict))
+            }
+        }
+    }
+}
+
+impl TryFrom<serde_json::Value> for Value<'static> {
+    type Error = Error;
+
+    fn try_from(value: serde_json::Value) -> Result<Self> {
+        Value::try_from(&value)
+    }
+}
+
+impl TryFrom<&serde_json::Value> for OwnedValue {
+    type Error = Error;
+
+    fn try_from(value: &serde_json::Value) -> Result<Self> {
+        Value::try_from(value)?.try_into()
+    }
+}
+
+impl TryFrom<serde_json::Value> for OwnedValue {
+    type Error = Error;
+
+    fn try_from(value: serde_json::Value) -> Result<Self> {
+        OwnedValue::try_from(&value)
+    }
+}
+
+/// Convert a [`Value`](enum@Value) to a JSON value.
+///
+/// * All integer types and `d` are converted to numbers. Since JSON has no representation for them,
+///   NaN and infinite `d` values are converted to `null`.
+/// * `b` is converted to a boolean.
+/// * `s`, `o` and `g` are converted to strings.
+/// * Variants (`v`) are replaced by the value they contain.
+/// * Arrays (including byte arrays, `ay`) and structures are converted to arrays.
+/// * Dictionaries are converted to objects. Keys that aren't strings are converted to their string
+///   representation (e.g `42` to `"42"`).
+/// * Maybe (`m`) values are converted to `null` if they're empty and to the value they contain
+///   otherwise.
+/// * File descriptors are converted to the number of the file descriptor.
+impl From<&Value<'_>> for serde_json::Value {
+    fn from(value: &Value<'_>) -> Self {
+        match value {
+            Value::U8(u) => (*u).into(),
+            Value::Bool(b) => (*b).into(),
+            Value::I16(i) => (*i).into(),
+            Value::U16(u) => (*u).into(),
+            Value::I32(i) => (*i).into(),
+            Value::U32(u) => (*u).into(),
+            Value::I64(i) => (*i).into(),
+            Value::U64(u) => (*u).into(),
+            Value::F64(f) => Number::from_f64(*f)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            Value::Str(s) => s.as_str().into(),
+            Value::Signature(s) => s.as_str().into(),
+            Value::ObjectPath(p) => p.as_str().into(),
+            Value::Value(v) => (&**v).into(),
+            Value::Array(a) => a.inner().iter().map(serde_json::Value::from).collect(),
+            Value::Dict(d) => {
+                let map = d
+                    .iter()
+                    .map(|(k, v)| {
+                        let key = match k {
+                            Value::Str(s) => s.to_string(),
+                            Value::Signature(s) => s.to_string(),
+                            Value::ObjectPath(p) => p.to_string(),
+                            k => serde_json::Value::from(k).to_string(),
+                        };
+
+                        (key, v.into())
+                    })
+                    .collect::<Map<_, _>>();
+
+                serde_json::Value::Object(map)
+            }
+            Value::Structure(s) => s.fields().iter().map(serde_json::Value::from).collect(),
+            #[cfg(feature = "gvariant")]
+            Value::Maybe(m) => m
+                .inner()
+                .as_ref()
+                .map(serde_json::Value::from)
+                .unwrap_or(serde_json::Value::Null),
+            #[cfg(unix)]
+            Value::Fd(fd) => fd.as_raw_fd().into(),
+        }
+    }
+}
+
+impl From<Value<'_>> for serde_json::Value {
+    fn from(value: Value<'_>) -> Self {
+        (&value).into()
+    }
+}
+
+impl From<&OwnedValue> for serde_json::Value {
+    fn from(value: &OwnedValue) -> Self {
+        (&**value).into()
+    }
+}
+
+impl From<OwnedValue> for serde_json::Value {
+    fn from(value: OwnedValue) -> Self {
+        (&value).into()
+    }
+}
+
+const STRING_SIGNATURE: Signature<'static> = Signature::from_static_str_unchecked("s");
+const VARIANT_SIGNATURE: Signature<'static> = Signature::from_static_str_unchecked("v");
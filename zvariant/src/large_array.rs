@@ -0,0 +1,97 @@
+//! Encoding of Rust arrays of any length.
+//!
+//! Arrays (`[T; N]`) are encoded as D-Bus arrays (`aT`), with the length checked on
+//! deserialization. However, serde only implements `Serialize` and `Deserialize` for arrays of up
+//! to 32 elements. For larger arrays, use the functions here with the [serde `with`
+//! attribute][with].
+//!
+//! # Examples
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use zvariant::{serialized::Context, to_bytes, Type, LE};
+//!
+//! #[derive(Deserialize, Serialize, Type, PartialEq, Debug)]
+//! struct Digest {
+//!     algorithm: String,
+//!     #[serde(with = "zvariant::large_array")]
+//!     bytes: [u8; 64],
+//! }
+//!
+//! assert_eq!(Digest::signature(), "(say)");
+//! let digest = Digest {
+//!     algorithm: "sha512".into(),
+//!     bytes: [0xAA; 64],
+//! };
+//! let ctxt = Context::new_dbus(LE, 0);
+//! let encoded = to_bytes(ctxt, &digest).unwrap();
+//! let decoded: Digest = encoded.deserialize().unwrap().0;
+//! assert_eq!(decoded, digest);
+//!
+//! // The length is checked.
+//! let encoded = to_bytes(ctxt, &("sha256", [0xAAu8; 32])).unwrap();
+//! assert!(encoded.deserialize::<Digest>().is_err());
+//! ```
+//!
+//! [with]: https://serde.rs/field-attrs.html#with
+
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Serialize `value` as an array.
+pub fn serialize<T, S, const N: usize>(value: &[T; N], serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    let mut tuple = serializer.serialize_tuple(N)?;
+    for element in value {
+        tuple.serialize_element(element)?;
+    }
+
+    tuple.end()
+}
+
+/// Deserialize a value from an array of exactly `N` elements.
+pub fn deserialize<'de, T, D, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_tuple(N, ArrayVisitor(PhantomData))
+}
+
+struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+impl<'de, T, const N: usize> Visitor<'de> for ArrayVisitor<T, N>
+where
+    T: Deserialize<'de>,
+{
+    type Value = [T; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "an array of {N} elements")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<[T; N], A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut elements = Vec::with_capacity(N);
+        while elements.len() < N {
+            match seq.next_element()? {
+                Some(element) => elements.push(element),
+                None => return Err(de::Error::invalid_length(elements.len(), &self)),
+            }
+        }
+
+        Ok(elements
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("array of incorrect length")))
+    }
+}
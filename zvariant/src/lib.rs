@@ -23,9 +23,16 @@ pub use array::*;
 mod basic;
 pub use basic::*;
 
+mod bytes;
+pub use bytes::*;
+
 mod dict;
 pub use dict::*;
 
+mod fixed_array;
+pub use fixed_array::FixedArray;
+pub use fixed_array::FixedSize;
+
 #[deprecated(since = "4.0.0", note = "Use `serialized::Context` instead")]
 #[doc(hidden)]
 pub type EncodingContext = serialized::Context;
@@ -55,6 +62,12 @@ pub mod gvariant;
 mod signature;
 pub use crate::signature::*;
 
+mod signature_tree;
+pub use crate::signature_tree::*;
+
+mod signature_match;
+pub use crate::signature_match::*;
+
 mod complete_type;
 pub use complete_type::*;
 
@@ -72,15 +85,27 @@ pub use crate::maybe::*;
 mod optional;
 pub use crate::optional::*;
 
+pub mod option;
+
+pub mod large_array;
+
+mod timestamp;
+pub use crate::timestamp::*;
+
 mod value;
 pub use value::*;
 
+mod object_path_macro;
+mod value_macro;
+
 mod serialize_value;
 pub use serialize_value::*;
 
 mod deserialize_value;
 pub use deserialize_value::*;
 
+mod value_deserializer;
+
 mod error;
 pub use error::*;
 
@@ -95,9 +120,19 @@ mod from_value;
 
 mod into_value;
 
+mod flatten;
+
+mod pretty;
+
+mod inspect;
+pub use inspect::*;
+
 mod owned_value;
 pub use owned_value::*;
 
+mod vardict;
+pub use vardict::*;
+
 #[cfg(feature = "gvariant")]
 mod framing_offset_size;
 #[cfg(feature = "gvariant")]
@@ -106,7 +141,17 @@ mod signature_parser;
 
 mod container_depths;
 
-pub use zvariant_derive::{DeserializeDict, OwnedValue, SerializeDict, Type, Value};
+#[cfg(feature = "serde_json")]
+mod json;
+
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
+pub use zvariant_derive::{
+    DeserializeDict, DeserializeEnum, OwnedValue, SerializeDict, SerializeEnum, Type, Value,
+};
 
 // Required for the macros to function within this crate.
 extern crate self as zvariant;
@@ -114,6 +159,10 @@ extern crate self as zvariant;
 // Macro support module, not part of the public API.
 #[doc(hidden)]
 pub mod export {
+    pub use crate::{
+        flatten::{deserialize_flattened, FlattenSerializer},
+        value_macro::array_value,
+    };
     pub use serde;
 }
 
@@ -133,6 +182,8 @@ mod tests {
     #[cfg(feature = "arrayvec")]
     use std::str::FromStr;
 
+    #[cfg(feature = "proptest")]
+    use ::proptest::strategy::{Just, Strategy};
     #[cfg(feature = "gvariant")]
     use glib::{variant::FromVariant, Bytes, Variant};
     use serde::{Deserialize, Serialize};
@@ -400,6 +451,35 @@ mod tests {
         fd_value_test!(LE, GVariant, Fd::from(fd), 4, 4, 6);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn owned_fd() {
+        use serde::de::{value::I32Deserializer, IntoDeserializer};
+        use std::os::fd::{AsFd, AsRawFd};
+
+        let stdout = std::io::stdout();
+        let fd = crate::OwnedFd::try_from(Fd::from(&stdout)).unwrap();
+        assert_ne!(fd.as_raw_fd(), stdout.as_raw_fd());
+        let clone = fd.try_clone().unwrap();
+        assert_ne!(clone.as_raw_fd(), fd.as_raw_fd());
+
+        let ctxt = Context::new_dbus(LE, 0);
+        let encoded = to_bytes(ctxt, &fd).unwrap();
+        assert_eq!(encoded.fds().len(), 1);
+        // Deserializing an `OwnedFd` duplicates the fd, unlike `Fd`.
+        let decoded: crate::OwnedFd = encoded.deserialize().unwrap().0;
+        assert_ne!(decoded.as_raw_fd(), encoded.fds()[0].as_raw_fd());
+        let decoded: Fd<'_> = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded.as_fd().as_raw_fd(), encoded.fds()[0].as_raw_fd());
+        let owned = std::os::fd::OwnedFd::from(clone);
+        let raw = owned.as_raw_fd();
+        assert_eq!(Fd::from(owned).as_raw_fd(), raw);
+
+        // The invalid fd can't be borrowed.
+        let deserializer: I32Deserializer<serde::de::value::Error> = (-1).into_deserializer();
+        Fd::deserialize(deserializer).unwrap_err();
+    }
+
     #[test]
     fn u16_value() {
         let encoded = basic_type_test!(BE, DBus, 0xABBA_u16, 2, u16, 2, U16, 6);
@@ -636,6 +716,36 @@ mod tests {
             .0;
     }
 
+    #[test]
+    fn array_iter() {
+        use crate::{Array, Structure};
+
+        let array = Array::from(vec![(1u32, "one"), (2, "two"), (3, "three")]);
+
+        let mut names = vec![];
+        for record in &array {
+            let record = record.downcast_ref::<&Structure<'_>>().unwrap();
+            let mut fields = record.iter();
+            assert_eq!(
+                fields.next().unwrap().downcast_ref::<u32>().unwrap(),
+                1 + names.len() as u32
+            );
+            names.push(fields.next().unwrap().downcast_ref::<&str>().unwrap());
+            assert!(fields.next().is_none());
+        }
+        assert_eq!(names, ["one", "two", "three"]);
+
+        let fields: Vec<Value<'_>> = array
+            .try_clone()
+            .unwrap()
+            .into_iter()
+            .flat_map(|v| Structure::try_from(v).unwrap())
+            .collect();
+        assert_eq!(fields.len(), 6);
+        let decoded: Vec<(u32, String)> = array.try_into_vec().unwrap();
+        assert_eq!(decoded[2], (3, "three".to_string()));
+    }
+
     #[test]
     fn array_value() {
         // Let's use D-Bus/GVariant terms
@@ -643,14 +753,38 @@ mod tests {
         //
         // Array of u8
         //
-        // First a normal Rust array that is actually serialized as a struct (thank you Serde!)
-        assert_eq!(<[u8; 2]>::signature(), "(yy)");
+        // First a normal Rust array, that Serde serializes as a tuple.
+        assert_eq!(<[u8; 2]>::signature(), "ay");
         let ay = [77u8, 88];
         let ctxt = Context::new_dbus(LE, 0);
         let encoded = to_bytes(ctxt, &ay).unwrap();
-        assert_eq!(encoded.len(), 2);
+        assert_eq!(encoded.len(), 6);
         let decoded: [u8; 2] = encoded.deserialize().unwrap().0;
         assert_eq!(&decoded, &[77u8, 88]);
+        let decoded: Vec<u8> = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded, [77u8, 88]);
+        // The length must match.
+        encoded.deserialize::<[u8; 3]>().unwrap_err();
+        encoded.deserialize::<[u8; 1]>().unwrap_err();
+        let encoded = to_bytes(ctxt, &([0u32; 0], [1i64; 3], 2u8)).unwrap();
+        assert_eq!(encoded.len(), 33);
+        let decoded: ([u32; 0], [i64; 3], u8) = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded, ([], [1; 3], 2));
+        let v = Value::from([1u16, 2]);
+        assert_eq!(v.value_signature(), "aq");
+        assert_eq!(
+            <[u16; 2]>::try_from(v.try_clone().unwrap()).unwrap(),
+            [1, 2]
+        );
+        <[u16; 3]>::try_from(v).unwrap_err();
+        #[cfg(feature = "gvariant")]
+        {
+            let ctxt = Context::new_gvariant(LE, 0);
+            let encoded = to_bytes(ctxt, &(["a", "bc"], 2u8)).unwrap();
+            let decoded: ([&str; 2], u8) = encoded.deserialize().unwrap().0;
+            assert_eq!(decoded, (["a", "bc"], 2));
+            encoded.deserialize::<([&str; 1], u8)>().unwrap_err();
+        }
 
         // Then rest of the tests just use ArrayVec or Vec
         #[cfg(feature = "arrayvec")]
@@ -1098,8 +1232,8 @@ mod tests {
     fn struct_ref() {
         let ctxt = Context::new_dbus(LE, 0);
         let encoded = to_bytes(ctxt, &(&1u32, &2u32)).unwrap();
-        let decoded: [u32; 2] = encoded.deserialize().unwrap().0;
-        assert_eq!(decoded, [1u32, 2u32]);
+        let decoded: (u32, u32) = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded, (1u32, 2u32));
     }
 
     #[test]
@@ -1158,7 +1292,7 @@ mod tests {
         assert_eq!(map[&2], "456");
         // Use iterator
         let mut dict = Dict::from(map);
-        let expect = vec![
+        let expect = [
             (Value::from(1i64), Value::from("123")),
             (Value::from(2i64), Value::from("456")),
         ];
@@ -1219,7 +1353,11 @@ mod tests {
         let v: Value<'_> = dict.into();
         assert_eq!(v.value_signature(), "a{sv}");
         let encoded = to_bytes(ctxt, &v).unwrap();
+        // The padding depends on the order of entries.
+        #[cfg(not(feature = "indexmap"))]
         assert_eq!(dbg!(encoded.len()), 66);
+        #[cfg(feature = "indexmap")]
+        assert_eq!(dbg!(encoded.len()), 68);
         let v: Value<'_> = encoded.deserialize().unwrap().0;
         if let Value::Dict(dict) = v {
             assert_eq!(
@@ -1303,6 +1441,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn builders() {
+        let ctxt = Context::new_dbus(LE, 0);
+
+        let s = Structure::builder()
+            .add(1u32)
+            .add("x")
+            .add(Value::new(7u8))
+            .build();
+        assert_eq!(s.signature(), "(usv)");
+        let v = Value::from(s);
+        let encoded = to_bytes(ctxt, &v).unwrap();
+        let decoded: Value<'_> = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded, v);
+
+        let mut builder = Dict::builder::<String, Value<'_>>()
+            .add("id", 42u32)
+            .add("name", "foo");
+        builder.push("nested", Value::new(Value::new(true)));
+        let dict = builder.build();
+        assert_eq!(dict.signature(), "a{sv}");
+        let v = Value::from(dict);
+        let encoded = to_bytes(ctxt, &v).unwrap();
+        let decoded: Value<'_> = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded, v);
+        let map = HashMap::<String, crate::OwnedValue>::try_from(decoded).unwrap();
+        assert_eq!(u32::try_from(&map["id"]).unwrap(), 42);
+        assert_eq!(<&str>::try_from(&map["name"]).unwrap(), "foo");
+
+        let dict = Dict::builder::<u8, Vec<u32>>()
+            .add(1u8, vec![1u32, 2])
+            .build();
+        assert_eq!(dict.signature(), "a{yau}");
+    }
+
+    #[test]
+    fn object_path_macro() {
+        use crate::{object_path, object_path_macro::is_valid_object_path, ObjectPath};
+
+        static PATH: ObjectPath<'static> = object_path!("/org/freedesktop/DBus");
+        assert_eq!(PATH, "/org/freedesktop/DBus");
+
+        // The `const` validation must agree with the runtime one.
+        for path in [
+            "", "/", "//", "/a", "/a/", "a/b", "/a//b", "/a_b/C0", "/a-b", "/ä",
+        ] {
+            assert_eq!(
+                is_valid_object_path(path),
+                ObjectPath::try_from(path).is_ok(),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn value_macro() {
+        let nested = Value::new(7i64);
+        let v = crate::value!({
+            "key": 1u32,
+            "list": [1, 2, 3],
+            "mixed": ["a", 1u8],
+            "empty": [],
+            "nested": { "x": true, "y": [{ "z": "deep" }] },
+            "expr": nested.try_clone().unwrap(),
+        });
+        assert_eq!(v.value_signature(), "a{sv}");
+        let map = HashMap::<String, crate::OwnedValue>::try_from(v.try_clone().unwrap()).unwrap();
+        assert_eq!(u32::try_from(&map["key"]).unwrap(), 1);
+        assert_eq!(map["list"].value_signature(), "ai");
+        assert_eq!(map["mixed"].value_signature(), "av");
+        assert_eq!(map["empty"].value_signature(), "av");
+        assert_eq!(map["nested"].value_signature(), "a{sv}");
+        assert_eq!(&*map["expr"], &nested);
+
+        let ctxt = Context::new_dbus(LE, 0);
+        let encoded = to_bytes(ctxt, &v).unwrap();
+        let decoded: Value<'_> = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded, v);
+
+        assert_eq!(crate::value!("hi"), Value::from("hi"));
+        assert_eq!(crate::value!([[1u8], [2u8]]).value_signature(), "aay");
+    }
+
     #[test]
     fn dict_compare() {
         // the order in which a dict has been constructed must not play a role
@@ -1318,6 +1539,73 @@ mod tests {
         assert_eq!(dict1, dict2);
     }
 
+    #[test]
+    fn vardict() {
+        use crate::{OwnedValue, VarDict};
+
+        let mut dict = VarDict::new()
+            .with("u", 7u32)
+            .unwrap()
+            .with("list", vec!["a", "b"])
+            .unwrap()
+            .with("nested", Value::new(1u8))
+            .unwrap();
+        assert_eq!(
+            dict.insert("u", 8u32).unwrap(),
+            Some(OwnedValue::from(7u32))
+        );
+        assert_eq!(dict.len(), 3);
+        assert_eq!(VarDict::signature(), "a{sv}");
+
+        let ctxt = Context::new_dbus(LE, 0);
+        let encoded = to_bytes(ctxt, &dict).unwrap();
+        let map: HashMap<String, OwnedValue> = encoded.deserialize().unwrap().0;
+        assert_eq!(VarDict::from(map.clone()), dict);
+
+        let dict: VarDict = encoded.deserialize().unwrap().0;
+        assert_eq!(dict.get::<u32>("u").unwrap(), Some(8));
+        let list = dict.get_value("list").unwrap().try_clone().unwrap();
+        assert_eq!(<Vec<String>>::try_from(list).unwrap(), ["a", "b"]);
+        assert_eq!(dict.get::<u8>("nested").unwrap(), Some(1));
+        assert_eq!(dict.get::<u8>("none").unwrap(), None);
+        assert!(dict.get::<u8>("u").is_err());
+
+        let value = Value::try_from(dict.clone()).unwrap();
+        assert_eq!(value.value_signature(), "a{sv}");
+        assert_eq!(VarDict::try_from(value).unwrap(), dict);
+        assert_eq!(HashMap::from(dict), map);
+    }
+
+    #[test]
+    #[cfg(feature = "indexmap")]
+    fn dict_order() {
+        use indexmap::IndexMap;
+
+        let map: IndexMap<&str, u32> = [("zero", 0), ("one", 1), ("two", 2)].into();
+        let ctxt = Context::new_dbus(LE, 0);
+        let encoded = to_bytes(ctxt, &map).unwrap();
+
+        // Wire order is preserved, both by `IndexMap` and `Dict`.
+        let decoded: IndexMap<String, u32> = encoded.deserialize().unwrap().0;
+        assert!(decoded.keys().eq(["zero", "one", "two"]));
+        let value = Value::from(map.clone());
+        let encoded = to_bytes(ctxt, &value).unwrap();
+        let decoded_value: Value<'_> = encoded.deserialize().unwrap().0;
+        let dict = <Dict<'_, '_>>::try_from(decoded_value).unwrap();
+        let keys: Vec<_> = dict
+            .iter()
+            .map(|(k, _)| k.downcast_ref::<&str>().unwrap())
+            .collect();
+        assert_eq!(keys, ["zero", "one", "two"]);
+        let reencoded = to_bytes(ctxt, &Value::from(dict)).unwrap();
+        assert_eq!(reencoded.bytes(), encoded.bytes());
+
+        // ..but still doesn't matter for comparison.
+        let reversed: IndexMap<&str, u32> = map.iter().rev().map(|(k, v)| (*k, *v)).collect();
+        assert_eq!(Dict::from(reversed), Dict::from(map.clone()));
+        assert_eq!(IndexMap::<String, u32>::try_from(value).unwrap(), decoded);
+    }
+
     #[test]
     fn value_value() {
         let ctxt = Context::new_dbus(BE, 0);
@@ -1625,6 +1913,128 @@ mod tests {
         let v = vec![1, 2];
         let l = crate::serialized_size(ctxt, &('a', "abc", &v)).unwrap();
         assert_eq!(*l, 28);
+
+        // The padding depends on the position.
+        let value = (7u8, 42u64);
+        let l = crate::serialized_size_for_signature(ctxt, "(yt)", &value).unwrap();
+        assert_eq!(*l, 16);
+        let ctxt = Context::new_dbus(LE, 4);
+        let l = crate::serialized_size_for_signature(ctxt, "(yt)", &value).unwrap();
+        assert_eq!(*l, 20);
+        assert_eq!(*l, to_bytes(ctxt, &value).unwrap().len());
+    }
+
+    #[test]
+    fn to_bytes_in() {
+        let ctxt = Context::new_dbus(LE, 0);
+        let mut buf = vec![];
+        let value = ("hello", vec![1u16, 2], 42u64);
+        let (range, written) = unsafe { crate::to_bytes_in(&mut buf, ctxt, &value) }.unwrap();
+        assert_eq!(range, 0..written.size());
+        assert_eq!(buf, to_bytes(ctxt, &value).unwrap().bytes());
+
+        // The alignment is relative to the position in the context.
+        let ctxt = Context::new_dbus(LE, range.end);
+        let (range, _) = unsafe { crate::to_bytes_in(&mut buf, ctxt, &value) }.unwrap();
+        let encoded = Data::new(&buf[range], ctxt);
+        let decoded: (&str, Vec<u16>, u64) = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded, value);
+
+        // Nothing is left behind on errors.
+        let len = buf.len();
+        unsafe { crate::to_bytes_in_for_signature(&mut buf, ctxt, "(su)", &value) }.unwrap_err();
+        assert_eq!(buf.len(), len);
+    }
+
+    #[test]
+    fn to_stream() {
+        // A writer that can't seek and only accepts a few bytes at a time.
+        struct Trickle(Vec<u8>);
+
+        impl std::io::Write for Trickle {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                let n = buf.len().min(3);
+                self.0.extend_from_slice(&buf[..n]);
+
+                Ok(n)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut map = HashMap::new();
+        map.insert("first", vec![(1u8, 2u64), (3, 4)]);
+        map.insert("second", vec![]);
+        let value = (
+            42u8,
+            vec![vec!["a", "bc"], vec![], vec!["def"]],
+            map,
+            Value::from(vec![1.5f64, 2.5]),
+        );
+
+        for ctxt in [
+            Context::new_dbus(LE, 0),
+            Context::new_dbus(BE, 3),
+            #[cfg(feature = "gvariant")]
+            Context::new_gvariant(LE, 0),
+        ] {
+            let expected = to_bytes(ctxt, &value).unwrap();
+            let mut writer = Trickle(vec![]);
+            let written = unsafe { crate::to_stream(&mut writer, ctxt, &value) }.unwrap();
+            assert_eq!(*written, expected.len());
+            assert_eq!(writer.0, expected.bytes());
+        }
+    }
+
+    #[test]
+    fn incremental_decoder() {
+        use crate::serialized::Decoder;
+
+        type Item = (u8, Vec<String>, HashMap<String, u64>);
+
+        let mut map = HashMap::new();
+        map.insert("a".to_string(), 1u64);
+        map.insert("bcd".to_string(), 2);
+        let items: Vec<Item> = vec![
+            (1, vec!["hello".into(), "world".into()], map),
+            (2, vec![], HashMap::new()),
+        ];
+
+        // Encode the items back to back, each aligned at its own position in the stream.
+        let mut stream = vec![];
+        for item in &items {
+            let ctxt = Context::new_dbus(BE, stream.len());
+            stream.extend_from_slice(&to_bytes(ctxt, item).unwrap());
+        }
+
+        // Feed one byte at a time.
+        let mut decoder = Decoder::new(Context::new_dbus(BE, 0));
+        let mut decoded = vec![];
+        for byte in &stream {
+            decoder.feed(std::slice::from_ref(byte));
+            if let Some(item) = decoder.decode::<Item>().unwrap() {
+                decoded.push(item);
+            }
+        }
+        assert_eq!(decoded, items);
+        assert_eq!(decoder.buffered(), 0);
+        assert_eq!(decoder.context().position(), stream.len());
+
+        // Truncated data is reported as such.
+        let ctxt = Context::new_dbus(LE, 0);
+        let encoded = to_bytes(ctxt, &("hello", 42u32)).unwrap();
+        let truncated = Data::new(&encoded[..encoded.len() - 1], ctxt);
+        assert_eq!(
+            truncated.deserialize::<(&str, u32)>().unwrap_err(),
+            Error::InsufficientData(encoded.len())
+        );
+
+        // Malformed data is still an error.
+        let mut decoder = Decoder::new(ctxt);
+        decoder.feed(&[1, 0, 0, 0, 0xff, 0]);
+        assert!(decoder.decode::<String>().is_err());
     }
 
     #[test]
@@ -1930,6 +2340,233 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(all(feature = "gvariant", not(feature = "option-as-array")))]
+    fn option_value_derive() {
+        #[derive(Debug, Clone, PartialEq, Type, crate::Value, crate::OwnedValue)]
+        struct Settings {
+            name: Option<String>,
+            size: u32,
+            parent: Option<u8>,
+        }
+        assert_eq!(Settings::signature(), "(msumy)");
+
+        let ctxt = Context::new_gvariant(NATIVE_ENDIAN, 0);
+        for settings in [
+            Settings {
+                name: Some("hello".into()),
+                size: 42,
+                parent: None,
+            },
+            Settings {
+                name: None,
+                size: 0,
+                parent: Some(7),
+            },
+        ] {
+            let v = Value::from(settings.clone());
+            let encoded = to_bytes(ctxt, &v).unwrap();
+            let decoded: Value<'_> = encoded.deserialize().unwrap().0;
+            let decoded = crate::OwnedValue::try_from(decoded).unwrap();
+            assert_eq!(Settings::try_from(decoded).unwrap(), settings);
+
+            let v = Value::from(settings.clone());
+            assert_eq!(Settings::try_from(v).unwrap(), settings);
+        }
+
+        // `Maybe` to `Option`, without going through references.
+        let maybe = crate::Maybe::from(Some(String::from("hello")));
+        assert_eq!(
+            maybe.try_into_option::<String>().unwrap().as_deref(),
+            Some("hello")
+        );
+        let maybe = crate::Maybe::from(None::<String>);
+        assert_eq!(maybe.into_inner(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde_json")]
+    fn json_value() {
+        let json = serde_json::json!({
+            "name": "zbus",
+            "version": 4,
+            "big": u64::MAX,
+            "ratio": 0.5,
+            "stable": true,
+            "tags": ["d-bus", "ipc"],
+            "mixed": [1, "two"],
+            "empty": [],
+        });
+
+        let value = Value::try_from(&json).unwrap();
+        assert_eq!(value.value_signature(), "a{sv}");
+        let dict = Dict::try_from(value.try_clone().unwrap()).unwrap();
+        assert_eq!(
+            dict.get::<_, Value<'_>>(&"name").unwrap().unwrap(),
+            Value::from("zbus")
+        );
+        assert_eq!(
+            dict.get::<_, Value<'_>>(&"version").unwrap().unwrap(),
+            Value::I64(4)
+        );
+        assert_eq!(
+            dict.get::<_, Value<'_>>(&"big").unwrap().unwrap(),
+            Value::U64(u64::MAX)
+        );
+        assert_eq!(
+            dict.get::<_, Value<'_>>(&"ratio").unwrap().unwrap(),
+            Value::F64(0.5)
+        );
+        let tags: Value<'_> = dict.get(&"tags").unwrap().unwrap();
+        assert_eq!(tags.value_signature(), "as");
+        let mixed: Value<'_> = dict.get(&"mixed").unwrap().unwrap();
+        assert_eq!(mixed.value_signature(), "av");
+        let empty: Value<'_> = dict.get(&"empty").unwrap().unwrap();
+        assert_eq!(empty.value_signature(), "av");
+
+        // Round trip.
+        assert_eq!(serde_json::Value::from(&value), json);
+        let owned = crate::OwnedValue::try_from(json.clone()).unwrap();
+        assert_eq!(serde_json::Value::from(owned), json);
+
+        assert!(Value::try_from(&serde_json::Value::Null).is_err());
+
+        // Non-string keys and byte arrays.
+        let mut map = HashMap::new();
+        map.insert(1u32, vec![1u8, 2, 3]);
+        let value = Value::from(map);
+        assert_eq!(
+            serde_json::Value::from(value),
+            serde_json::json!({ "1": [1, 2, 3] })
+        );
+        let value = Value::from((1u8, "s", f64::NAN));
+        assert_eq!(
+            serde_json::Value::from(value),
+            serde_json::json!([1, "s", null])
+        );
+    }
+
+    #[test]
+    fn value_deserializer() {
+        use serde::de::IntoDeserializer;
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        enum State {
+            Idle,
+            Busy,
+        }
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Device<'a> {
+            name: &'a str,
+            id: u32,
+            state: State,
+            parent: Option<String>,
+            tags: Vec<String>,
+        }
+
+        let mut dict = HashMap::new();
+        dict.insert("name", Value::from("eth0"));
+        dict.insert("id", Value::from(2u32));
+        dict.insert("state", Value::from("Busy"));
+        dict.insert("tags", Value::from(vec!["wired", "up"]));
+        let value = Value::from(dict);
+        assert_eq!(value.value_signature(), "a{sv}");
+
+        let device = Device::deserialize(value.try_clone().unwrap()).unwrap();
+        assert_eq!(
+            device,
+            Device {
+                name: "eth0",
+                id: 2,
+                state: State::Busy,
+                parent: None,
+                tags: vec!["wired".into(), "up".into()],
+            }
+        );
+
+        // Structures deserialize as sequences and enums also from their index.
+        let value = Value::from(("eth1", 3u32, 0u32, "lo", vec!["virtual"]));
+        let device = Device::deserialize(value.into_deserializer()).unwrap();
+        assert_eq!(
+            device,
+            Device {
+                name: "eth1",
+                id: 3,
+                state: State::Idle,
+                parent: Some("lo".into()),
+                tags: vec!["virtual".into()],
+            }
+        );
+
+        // Through `OwnedValue`.
+        let mut dict = HashMap::new();
+        dict.insert("a", 1u32);
+        dict.insert("b", 2u32);
+        let owned = crate::OwnedValue::try_from(Value::from(dict.clone())).unwrap();
+        let decoded = HashMap::<String, u32>::deserialize(owned).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded["a"], 1);
+
+        // Mismatched type.
+        assert!(u32::deserialize(Value::from("nope")).is_err());
+    }
+
+    #[test]
+    fn time_wrappers() {
+        use crate::{SecsDuration, UsecDuration, UsecTimestamp};
+        use std::time::{Duration, SystemTime};
+
+        let ctxt = Context::new_dbus(LE, 0);
+
+        // The default encoding is seconds & nanoseconds.
+        assert_eq!(Duration::signature(), "(tu)");
+        assert_eq!(SystemTime::signature(), "(tu)");
+
+        let time =
+            UsecTimestamp(SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789));
+        assert_eq!(UsecTimestamp::signature(), "t");
+        let encoded = to_bytes(ctxt, &time).unwrap();
+        assert_eq!(encoded.len(), 8);
+        let usec: u64 = encoded.deserialize().unwrap().0;
+        assert_eq!(usec, 1_700_000_000_123_456);
+        let decoded: UsecTimestamp = encoded.deserialize().unwrap().0;
+        // Precision beyond microseconds is lost.
+        assert_eq!(
+            decoded,
+            UsecTimestamp(SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_000))
+        );
+
+        // Times before the epoch can't be encoded.
+        let before_epoch = UsecTimestamp(SystemTime::UNIX_EPOCH - Duration::from_secs(1));
+        assert!(to_bytes(ctxt, &before_epoch).is_err());
+        assert!(Value::try_from(before_epoch).is_err());
+
+        let duration = UsecDuration(Duration::from_millis(1500));
+        let encoded = to_bytes(ctxt, &duration).unwrap();
+        let usec: u64 = encoded.deserialize().unwrap().0;
+        assert_eq!(usec, 1_500_000);
+        let decoded: UsecDuration = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded, duration);
+
+        let duration = SecsDuration(Duration::from_millis(1500));
+        let encoded = to_bytes(ctxt, &duration).unwrap();
+        let secs: u64 = encoded.deserialize().unwrap().0;
+        assert_eq!(secs, 1);
+        let decoded: SecsDuration = encoded.deserialize().unwrap().0;
+        assert_eq!(*decoded, Duration::from_secs(1));
+
+        // Value conversions.
+        let value = Value::try_from(UsecDuration(Duration::from_micros(42))).unwrap();
+        assert_eq!(value, Value::U64(42));
+        let decoded = UsecDuration::try_from(&value).unwrap();
+        assert_eq!(*decoded, Duration::from_micros(42));
+        let owned = crate::OwnedValue::try_from(SecsDuration(Duration::from_secs(7))).unwrap();
+        let decoded = SecsDuration::try_from(owned).unwrap();
+        assert_eq!(*decoded, Duration::from_secs(7));
+        assert!(UsecTimestamp::try_from(Value::from("nope")).is_err());
+    }
+
     #[test]
     fn struct_with_hashmap() {
         use serde::{Deserialize, Serialize};
@@ -2063,6 +2700,70 @@ mod tests {
         let encoded = to_bytes(ctxt, &date).unwrap();
         let decoded: time::PrimitiveDateTime = encoded.deserialize().unwrap().0;
         assert_eq!(date, decoded);
+
+        // As microseconds since the epoch.
+        let offset =
+            time::OffsetDateTime::from_unix_timestamp_nanos(1_308_663_462_123_456_000).unwrap();
+        let encoded = to_bytes(ctxt, &crate::UsecTimestamp::from(offset)).unwrap();
+        assert_eq!(encoded.len(), 8);
+        let decoded: crate::UsecTimestamp = encoded.deserialize().unwrap().0;
+        assert_eq!(time::OffsetDateTime::from(decoded), offset);
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn chrono() {
+        let date =
+            chrono::DateTime::parse_from_rfc3339("2011-06-21T13:37:42.123456+02:00").unwrap();
+        let ctxt = Context::new_dbus(LE, 0);
+        let encoded = to_bytes(ctxt, &date).unwrap();
+        let decoded: chrono::DateTime<chrono::FixedOffset> = encoded.deserialize().unwrap().0;
+        assert_eq!(date, decoded);
+
+        // As microseconds since the epoch.
+        let encoded = to_bytes(ctxt, &crate::UsecTimestamp::from(date)).unwrap();
+        let usec: u64 = encoded.deserialize().unwrap().0;
+        assert_eq!(usec, 1_308_656_262_123_456);
+        let decoded: crate::UsecTimestamp = encoded.deserialize().unwrap().0;
+        let decoded = chrono::DateTime::<chrono::Utc>::try_from(decoded).unwrap();
+        assert_eq!(decoded, date);
+    }
+
+    #[test]
+    #[cfg(feature = "uuid")]
+    fn uuid() {
+        let uuid = uuid::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+        let ctxt = Context::new_dbus(LE, 0);
+        let encoded = to_bytes(ctxt, &uuid).unwrap();
+        assert_eq!(encoded.len(), 20);
+        let decoded: uuid::Uuid = encoded.deserialize().unwrap().0;
+        assert_eq!(uuid, decoded);
+
+        let value = Value::from(uuid);
+        assert_eq!(value.value_signature(), uuid::Uuid::signature());
+        assert_eq!(uuid::Uuid::try_from(&value).unwrap(), uuid);
+        let owned = crate::OwnedValue::from(uuid);
+        assert_eq!(uuid::Uuid::try_from(owned).unwrap(), uuid);
+        assert!(uuid::Uuid::try_from(Value::from(vec![1u8, 2, 3])).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "url")]
+    fn url() {
+        let url = url::Url::parse("https://example.com/path?query=1").unwrap();
+        let ctxt = Context::new_dbus(LE, 0);
+        let encoded = to_bytes(ctxt, &url).unwrap();
+        let decoded: &str = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded, url.as_str());
+        let decoded: url::Url = encoded.deserialize().unwrap().0;
+        assert_eq!(url, decoded);
+
+        let value = Value::from(&url);
+        assert_eq!(value.value_signature(), url::Url::signature());
+        assert_eq!(url::Url::try_from(&value).unwrap(), url);
+        let owned = crate::OwnedValue::from(url.clone());
+        assert_eq!(url::Url::try_from(owned).unwrap(), url);
+        assert!(url::Url::try_from(Value::from("not a url")).is_err());
     }
 
     #[test]
@@ -2121,4 +2822,396 @@ mod tests {
         // * Test deserializers.
         // * Test gvariant format.
     }
+
+    #[test]
+    fn size_limits() {
+        use crate::{serialized::Limits, MaxSizeExceeded};
+
+        let ctxt = Context::new_dbus(LE, 0);
+        assert_eq!(ctxt.limits(), Limits::dbus());
+        let array_limited = ctxt.with_limits(Limits::default().with_max_array_len(8));
+        let total_limited = ctxt.with_limits(Limits::default().with_max_total_len(16));
+
+        // Serialization.
+        let vec = vec![0u32; 3];
+        assert!(to_bytes(array_limited, &(vec![0u32; 2], "hello world")).is_ok());
+        assert_eq!(
+            to_bytes(array_limited, &vec).unwrap_err(),
+            Error::MaxSizeExceeded(MaxSizeExceeded::Array)
+        );
+        assert_eq!(
+            to_bytes(total_limited, &("hello", "world")).unwrap_err(),
+            Error::MaxSizeExceeded(MaxSizeExceeded::Total)
+        );
+        assert_eq!(
+            crate::serialized_size(total_limited, &("hello", "world")).unwrap_err(),
+            Error::MaxSizeExceeded(MaxSizeExceeded::Total)
+        );
+        // Serialization stops as soon as the limit is exceeded.
+        struct Counted(std::cell::Cell<usize>);
+        impl Serialize for Counted {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.collect_seq((0..1_000_000u32).inspect(|_| self.0.set(self.0.get() + 1)))
+            }
+        }
+        impl Type for Counted {
+            fn signature() -> Signature<'static> {
+                <Vec<u32>>::signature()
+            }
+        }
+        let counted = Counted(Default::default());
+        assert_eq!(
+            to_bytes(total_limited, &counted).unwrap_err(),
+            Error::MaxSizeExceeded(MaxSizeExceeded::Total)
+        );
+        assert_eq!(counted.0.get(), 4);
+
+        // Deserialization.
+        let encoded = to_bytes(ctxt, &vec).unwrap();
+        let limited = Data::new(encoded.bytes(), array_limited);
+        assert_eq!(
+            limited.deserialize::<Vec<u32>>().unwrap_err(),
+            Error::MaxSizeExceeded(MaxSizeExceeded::Array)
+        );
+        let encoded = to_bytes(ctxt, &("hello", "world")).unwrap();
+        let limited = Data::new(encoded.bytes(), total_limited);
+        assert_eq!(
+            limited.deserialize::<(&str, &str)>().unwrap_err(),
+            Error::MaxSizeExceeded(MaxSizeExceeded::Total)
+        );
+
+        // An array claiming to be bigger than the spec allows is rejected right away.
+        let malicious = Data::new(&[0x08, 0x00, 0x00, 0x00][..], Context::new_dbus(BE, 0));
+        assert_eq!(
+            malicious.deserialize::<Vec<u8>>().unwrap_err(),
+            Error::MaxSizeExceeded(MaxSizeExceeded::Array)
+        );
+        let unlimited = Data::new(
+            &[0x08, 0x00, 0x00, 0x00][..],
+            Context::new_dbus(BE, 0).with_limits(Limits::unlimited()),
+        );
+        assert!(matches!(
+            unlimited.deserialize::<Vec<u8>>().unwrap_err(),
+            Error::InsufficientData(_)
+        ));
+
+        #[cfg(feature = "gvariant")]
+        {
+            let ctxt = Context::new_gvariant(LE, 0);
+            assert_eq!(ctxt.limits(), Limits::unlimited());
+            let limited = ctxt.with_limits(Limits::unlimited().with_max_array_len(8));
+            assert_eq!(
+                to_bytes(limited, &vec![0u32; 3]).unwrap_err(),
+                Error::MaxSizeExceeded(MaxSizeExceeded::Array)
+            );
+            let encoded = to_bytes(ctxt, &vec!["hello", "world"]).unwrap();
+            let limited = Data::new(encoded.bytes(), limited);
+            assert_eq!(
+                limited.deserialize::<Vec<&str>>().unwrap_err(),
+                Error::MaxSizeExceeded(MaxSizeExceeded::Array)
+            );
+        }
+    }
+
+    #[test]
+    fn fixed_array() {
+        use crate::FixedArray;
+
+        fn check<T>(elements: Vec<T>)
+        where
+            T: crate::FixedSize
+                + serde::Serialize
+                + for<'de> Deserialize<'de>
+                + PartialEq
+                + std::fmt::Debug,
+        {
+            for ctxt in [
+                Context::new_dbus(LE, 0),
+                Context::new_dbus(BE, 3),
+                #[cfg(feature = "gvariant")]
+                Context::new_gvariant(LE, 0),
+                #[cfg(feature = "gvariant")]
+                Context::new_gvariant(BE, 3),
+            ] {
+                let encoded = to_bytes(ctxt, &FixedArray::from(&elements)).unwrap();
+                assert_eq!(encoded.bytes(), to_bytes(ctxt, &elements).unwrap().bytes());
+
+                let decoded: FixedArray<'_, T> = encoded.deserialize().unwrap().0;
+                assert_eq!(*decoded, *elements);
+                let decoded: Vec<T> = encoded.deserialize().unwrap().0;
+                assert_eq!(decoded, elements);
+
+                // Also as part of a structure, for the padding.
+                let s = (1u8, FixedArray::from(&elements), "end");
+                let encoded = to_bytes(ctxt, &s).unwrap();
+                let decoded: (u8, FixedArray<'_, T>, &str) = encoded.deserialize().unwrap().0;
+                assert_eq!(decoded, s);
+            }
+        }
+
+        check(vec![1u8, 2, 0xff]);
+        check(vec![1i16, -2, i16::MAX]);
+        check(vec![1u16, 2, u16::MAX]);
+        check(vec![1i32, -2, i32::MIN]);
+        check(vec![1u32, 2, u32::MAX]);
+        check(vec![1i64, -2, i64::MAX]);
+        check(vec![1u64, 2, u64::MAX]);
+        check(vec![1.5f64, -2.25, f64::MAX]);
+        check(Vec::<u32>::new());
+
+        // The array length must be a multiple of the element size.
+        let data = Data::new(
+            &[0x03, 0x00, 0x00, 0x00, 1, 2, 3][..],
+            Context::new_dbus(LE, 0),
+        );
+        assert!(data.deserialize::<FixedArray<'_, u32>>().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "gvariant")]
+    fn value_in_nested_struct() {
+        // The signature of values inside structures must be consumed, for the fields after them.
+        let ctxt = Context::new_gvariant(LE, 0);
+        let s = ((4u32, Value::from("hello")), 5u8);
+        let encoded = to_bytes(ctxt, &s).unwrap();
+        let decoded: ((u32, Value<'_>), u8) = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded, s);
+    }
+
+    #[test]
+    fn inspect() {
+        use crate::{inspect, SegmentKind};
+
+        let dict = HashMap::from([("k", Value::from(1u64))]);
+        let ctxt = Context::new_dbus(LE, 0);
+        let encoded = to_bytes(ctxt, &(1i16, dict)).unwrap();
+        let signature = Signature::try_from("na{sv}").unwrap();
+        let inspection = inspect(encoded.bytes(), &signature, ctxt);
+        assert!(inspection.error().is_none());
+        let segments: Vec<_> = inspection
+            .segments()
+            .iter()
+            .map(|s| (s.offset(), s.len(), s.depth(), s.kind().clone()))
+            .collect();
+        assert_eq!(
+            segments,
+            [
+                (0, 2, 0, SegmentKind::Basic('n', "1".into())),
+                (2, 2, 0, SegmentKind::Padding),
+                (4, 4, 0, SegmentKind::Array("a{sv}".into(), 24)),
+                (8, 0, 1, SegmentKind::Structure("{sv}".into())),
+                (8, 6, 2, SegmentKind::Basic('s', "\"k\"".into())),
+                (14, 3, 2, SegmentKind::Variant("t".into())),
+                (17, 7, 3, SegmentKind::Padding),
+                (24, 8, 3, SegmentKind::Basic('t', "1".into())),
+            ]
+        );
+
+        // The position in the context is taken into account.
+        let ctxt = Context::new_dbus(LE, 1);
+        let encoded = to_bytes(ctxt, &("a", 2u32)).unwrap();
+        let signature = Signature::try_from("(su)").unwrap();
+        let inspection = inspect(encoded.bytes(), &signature, ctxt);
+        let offsets: Vec<_> = inspection.segments().iter().map(|s| s.offset()).collect();
+        assert_eq!(offsets, [0, 7, 7, 13, 15]);
+
+        // Non-zero padding.
+        let bytes = [1, 1, 0, 0, 1, 0, 0, 0];
+        let ctxt = Context::new_dbus(LE, 0);
+        let inspection = inspect(&bytes, &Signature::try_from("yu").unwrap(), ctxt);
+        assert_eq!(inspection.segments().len(), 1);
+        assert_eq!(inspection.error(), Some((1, &Error::PaddingNot0(1))));
+
+        // Data left over.
+        let inspection = inspect(&bytes, &Signature::try_from("y").unwrap(), ctxt);
+        assert_eq!(inspection.segments()[1].kind(), &SegmentKind::Trailing,);
+        assert_eq!(inspection.segments()[1].len(), 7);
+    }
+
+    #[test]
+    fn strict() {
+        use crate::{serialized::Data, OwnedValue};
+
+        let ctxt = Context::new_dbus(LE, 0).with_strict(true);
+        let value = (
+            vec!["a", "bc"],
+            HashMap::from([("k", Value::from(7u8))]),
+            3u16,
+        );
+        let encoded = to_bytes(ctxt, &value).unwrap();
+        let decoded: (Vec<String>, HashMap<String, OwnedValue>, u16) =
+            encoded.deserialize().unwrap().0;
+        assert_eq!(decoded.0, value.0);
+        assert_eq!(decoded.2, 3);
+
+        // Trailing bytes.
+        let mut bytes = encoded.bytes().to_vec();
+        bytes.push(0);
+        let data = Data::new(&bytes, ctxt);
+        assert_eq!(
+            data.deserialize::<(Vec<&str>, HashMap<&str, Value<'_>>, u16)>()
+                .unwrap_err(),
+            Error::NonCanonical("1 unused bytes after the value".into()),
+        );
+        let data = Data::new(&bytes, ctxt.with_strict(false));
+        let (_, size) = data
+            .deserialize::<(Vec<&str>, HashMap<&str, Value<'_>>, u16)>()
+            .unwrap();
+        assert_eq!(size, encoded.len());
+
+        #[cfg(feature = "gvariant")]
+        {
+            let ctxt = Context::new_gvariant(LE, 0).with_strict(true);
+            let encoded = to_bytes(ctxt, &value).unwrap();
+            let decoded: (Vec<String>, HashMap<String, OwnedValue>, u16) =
+                encoded.deserialize().unwrap().0;
+            assert_eq!(decoded.0, value.0);
+
+            // A variant of a `u8`, with an extra byte in the space for the value.
+            let bytes = [0x2a, 0, 0, b'y'];
+            let data = Data::new(&bytes[..], ctxt.with_strict(false));
+            assert_eq!(data.deserialize::<Value<'_>>().unwrap().0, Value::U8(42));
+            let data = Data::new(&bytes[..], ctxt);
+            assert_eq!(
+                data.deserialize::<Value<'_>>().unwrap_err(),
+                Error::NonCanonical("1 unused bytes after a variant value".into()),
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "gvariant")]
+    fn gvariant_normal_form() {
+        use crate::{
+            gvariant::{byteswap, is_normal_form},
+            serialized::Data,
+            SerializeValue,
+        };
+
+        // The order of dictionary entries, even within variants, is kept as is.
+        let map: HashMap<String, u32> = (0..20).map(|i| (format!("key{i}"), i)).collect();
+        let value = (SerializeValue(&map), Some(vec![(1u8, -2i64)]), None::<&str>);
+        let signature = Signature::try_from("(vma(yx)ms)").unwrap();
+        let le = to_bytes(Context::new_gvariant(LE, 0), &value).unwrap();
+        assert!(is_normal_form(&le, &signature));
+        let be = byteswap(&le, &signature).unwrap();
+        assert_eq!(be.context().endian(), BE);
+        assert!(is_normal_form(&be, &signature));
+        let decoded: (crate::OwnedValue, Option<Vec<(u8, i64)>>, Option<&str>) =
+            be.deserialize().unwrap().0;
+        assert_eq!(HashMap::<String, u32>::try_from(decoded.0).unwrap(), map);
+        assert_eq!((decoded.1, decoded.2), (value.1, value.2));
+        assert_eq!(byteswap(&be, &signature).unwrap().bytes(), le.bytes());
+
+        let data = Data::new(&[42, 0, 0, b'y'][..], Context::new_gvariant(LE, 0));
+        assert!(!is_normal_form(&data, &Signature::try_from("v").unwrap()));
+
+        let dbus = to_bytes(Context::new_dbus(LE, 0), &(1u8, "s")).unwrap();
+        let signature = Signature::try_from("(ys)").unwrap();
+        assert!(!is_normal_form(&dbus, &signature));
+        byteswap(&dbus, &signature).unwrap_err();
+    }
+
+    #[test]
+    #[cfg(feature = "gvariant")]
+    fn gvariant_random_access() {
+        use crate::gvariant::{array_element, array_len, struct_field};
+
+        let ctxt = Context::new_gvariant(LE, 0);
+
+        // Elements of non-fixed size, with padding between them.
+        let elements = vec![("a", 1u64), ("bcd", 2), ("", 3)];
+        let signature = Signature::try_from("a(st)").unwrap();
+        let encoded = to_bytes(ctxt, &elements).unwrap();
+        assert_eq!(array_len(&encoded, &signature).unwrap(), 3);
+        for (i, element) in elements.iter().enumerate() {
+            let data = array_element(&encoded, &signature, i).unwrap();
+            assert_eq!(&data.deserialize::<(&str, u64)>().unwrap().0, element);
+        }
+        assert!(matches!(
+            array_element(&encoded, &signature, 3),
+            Err(Error::OutOfBounds)
+        ));
+
+        // Fixed-size elements.
+        let signature = Signature::try_from("a(yq)").unwrap();
+        let encoded = to_bytes(ctxt, &vec![(1u8, 2u16), (3, 4)]).unwrap();
+        assert_eq!(array_len(&encoded, &signature).unwrap(), 2);
+        let data = array_element(&encoded, &signature, 1).unwrap();
+        assert_eq!(data.deserialize::<(u8, u16)>().unwrap().0, (3, 4));
+
+        let signature = Signature::try_from("as").unwrap();
+        let encoded = to_bytes(ctxt, &Vec::<&str>::new()).unwrap();
+        assert_eq!(array_len(&encoded, &signature).unwrap(), 0);
+
+        // Fields, of both fixed and non-fixed size.
+        let value = ("hello", 7u32, vec!["a", "b"], 8u8, "world");
+        let signature = Signature::try_from("(suasys)").unwrap();
+        let encoded = to_bytes(ctxt, &value).unwrap();
+        let field = |i| struct_field(&encoded, &signature, i).unwrap();
+        assert_eq!(field(0).deserialize::<&str>().unwrap().0, value.0);
+        assert_eq!(field(1).deserialize::<u32>().unwrap().0, value.1);
+        assert_eq!(field(2).deserialize::<Vec<&str>>().unwrap().0, value.2);
+        assert_eq!(field(3).deserialize::<u8>().unwrap().0, value.3);
+        assert_eq!(field(4).deserialize::<&str>().unwrap().0, value.4);
+        assert!(matches!(
+            struct_field(&encoded, &signature, 5),
+            Err(Error::OutOfBounds)
+        ));
+
+        // Dictionaries are arrays of entries.
+        let map: HashMap<&str, &str> = [("key", "value")].into_iter().collect();
+        let encoded = to_bytes(ctxt, &map).unwrap();
+        let signature = Signature::try_from("a{ss}").unwrap();
+        let entry = array_element(&encoded, &signature, 0).unwrap();
+        let entry = entry.deserialize::<(&str, &str)>().unwrap().0;
+        assert_eq!(entry, ("key", "value"));
+
+        let dbus = to_bytes(Context::new_dbus(LE, 0), &vec!["a"]).unwrap();
+        array_len(&dbus, &signature).unwrap_err();
+        let signature = Signature::try_from("(ss)").unwrap();
+        array_len(&encoded, &signature).unwrap_err();
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn arbitrary_value_round_trip() {
+        use ::arbitrary::{Arbitrary, Unstructured};
+        use rand::RngCore;
+
+        let mut bytes = vec![0u8; 4096];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let mut u = Unstructured::new(&bytes);
+        for _ in 0..32 {
+            let value = Value::arbitrary(&mut u).unwrap();
+            let ctxt = Context::new_dbus(LE, 0);
+            let encoded = to_bytes(ctxt, &value).unwrap();
+            let decoded: Value<'_> = encoded.deserialize().unwrap().0;
+            assert_eq!(decoded, value);
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    ::proptest::proptest! {
+        #[test]
+        fn proptest_value_round_trip(value in crate::proptest::value()) {
+            let ctxt = Context::new_dbus(LE, 0);
+            let encoded = to_bytes(ctxt, &value).unwrap();
+            let decoded: Value<'_> = encoded.deserialize().unwrap().0;
+            ::proptest::prop_assert_eq!(decoded, value);
+        }
+
+        #[test]
+        fn proptest_value_of_signature(
+            (signature, value) in crate::proptest::signature().prop_flat_map(|signature| {
+                let value = crate::proptest::value_of(&signature);
+                (Just(signature), value)
+            })
+        ) {
+            ::proptest::prop_assert_eq!(value.value_signature(), signature);
+        }
+    }
 }
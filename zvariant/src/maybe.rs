@@ -67,7 +67,7 @@ impl<'a> Maybe<'a> {
     /// Get the inner value as a concrete type
     pub fn get<T>(&'a self) -> core::result::Result<Option<T>, Error>
     where
-        T: ?Sized + TryFrom<&'a Value<'a>>,
+        T: TryFrom<&'a Value<'a>>,
         <T as TryFrom<&'a Value<'a>>>::Error: Into<crate::Error>,
     {
         self.value
@@ -77,6 +77,22 @@ impl<'a> Maybe<'a> {
             .transpose()
     }
 
+    /// Convert into an `Option` of the concrete type, consuming `self`.
+    ///
+    /// Unlike [`Maybe::get`], this allows extracting owned values (e.g `String`) without copying.
+    pub fn try_into_option<T>(self) -> core::result::Result<Option<T>, Error>
+    where
+        T: TryFrom<Value<'a>>,
+        T::Error: Into<crate::Error>,
+    {
+        self.into_inner().map(|v| v.downcast()).transpose()
+    }
+
+    /// Get the underlying value, consuming `self`.
+    pub fn into_inner(self) -> Option<Value<'a>> {
+        *self.value
+    }
+
     /// Get the signature of `Maybe`.
     ///
     /// NB: This method potentially allocates and copies. Use [`full_signature`] if you'd like to
@@ -119,7 +135,7 @@ impl<'a> Maybe<'a> {
                 self.value
                     .as_ref()
                     .as_ref()
-                    .map(|v| v.try_clone().map(Into::into))
+                    .map(|v| v.try_clone())
                     .transpose()?,
             ),
             signature: self.signature.clone(),
@@ -129,6 +145,10 @@ impl<'a> Maybe<'a> {
 
 impl Display for Maybe<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return crate::pretty::maybe_pretty_fmt(self, f, 0);
+        }
+
         maybe_display_fmt(self, f, true)
     }
 }
@@ -6,7 +6,10 @@ use serde::{
 use static_assertions::assert_impl_all;
 use std::borrow::Cow;
 
-use crate::{serialized::Format, Basic, Error, Result, Signature, Str, Type};
+use crate::{
+    object_path_macro::is_valid_object_path, serialized::Format, Basic, Error, Result, Signature,
+    Str, Type,
+};
 
 /// String that identifies objects at a given destination on the D-Bus bus.
 ///
@@ -85,6 +88,26 @@ impl<'a> ObjectPath<'a> {
         Self(Str::from_static(name))
     }
 
+    /// Same as `from_static_str`, except it's a `const fn` that panics if `name` is invalid.
+    ///
+    /// When used in `const` and `static` items, an invalid path is therefore a compile error:
+    ///
+    /// ```
+    /// use zvariant::ObjectPath;
+    ///
+    /// static PATH: ObjectPath<'static> = ObjectPath::from_static_str_checked("/org/gnome/Shell");
+    /// assert_eq!(PATH, "/org/gnome/Shell");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `name` is not a valid object path.
+    pub const fn from_static_str_checked(name: &'static str) -> Self {
+        assert!(is_valid_object_path(name), "invalid object path");
+
+        Self::from_static_str_unchecked(name)
+    }
+
     /// Same as `from_str_unchecked`, except it takes an owned `String`.
     ///
     /// Since the passed string is not checked for correctness, prefer using the
@@ -112,6 +135,30 @@ impl<'a> ObjectPath<'a> {
     pub fn into_owned(self) -> ObjectPath<'static> {
         ObjectPath(self.0.into_owned())
     }
+
+    /// Whether this path is `path` itself or one of its descendants.
+    ///
+    /// Paths are matched element-wise, as for the `path_namespace` key of match rules, so
+    /// `/org/zbus/Object` starts with `/org/zbus` but not with `/org/zb`. Every path starts with
+    /// `/`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::ObjectPath;
+    ///
+    /// let path = ObjectPath::try_from("/org/zbus/Object").unwrap();
+    /// assert!(path.starts_with_path(&ObjectPath::try_from("/org/zbus").unwrap()));
+    /// assert!(path.starts_with_path(&path));
+    /// assert!(path.starts_with_path(&ObjectPath::try_from("/").unwrap()));
+    /// assert!(!path.starts_with_path(&ObjectPath::try_from("/org/zb").unwrap()));
+    /// ```
+    pub fn starts_with_path(&self, path: &ObjectPath<'_>) -> bool {
+        match self.as_str().strip_prefix(path.as_str()) {
+            Some(rest) => rest.is_empty() || path.as_str() == "/" || rest.starts_with('/'),
+            None => false,
+        }
+    }
 }
 
 impl std::default::Default for ObjectPath<'_> {
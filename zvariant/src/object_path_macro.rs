@@ -0,0 +1,49 @@
+/// Create an [`ObjectPath`](crate::ObjectPath) from a string literal, validated at compile time.
+///
+/// Unlike `ObjectPath::try_from`, an invalid path is a compile error instead of a runtime error,
+/// so there is no `Result` to unwrap. The macro can be used in `const` and `static` items. It's a
+/// shorthand for [`ObjectPath::from_static_str_checked`] in a `const` context.
+///
+/// [`ObjectPath::from_static_str_checked`]: crate::ObjectPath::from_static_str_checked
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{object_path, ObjectPath};
+///
+/// const PATH: ObjectPath<'static> = object_path!("/org/freedesktop/DBus");
+/// let path = object_path!("/");
+/// assert_eq!(path, "/");
+/// ```
+///
+/// ```compile_fail
+/// let path = zvariant::object_path!("/end/with/slash/");
+/// ```
+#[macro_export]
+macro_rules! object_path {
+    ($path:expr) => {{
+        const PATH: $crate::ObjectPath<'static> =
+            $crate::ObjectPath::from_static_str_checked($path);
+        PATH
+    }};
+}
+
+// The `const` version of the `ObjectPath` validation.
+pub(crate) const fn is_valid_object_path(path: &str) -> bool {
+    let path = path.as_bytes();
+    if path.is_empty() || path[0] != b'/' || (path.len() > 1 && path[path.len() - 1] == b'/') {
+        return false;
+    }
+
+    let mut i = 1;
+    while i < path.len() {
+        let c = path[i];
+        let double_slash = c == b'/' && path[i - 1] == b'/';
+        if double_slash || (!c.is_ascii_alphanumeric() && c != b'/' && c != b'_') {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
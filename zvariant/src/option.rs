@@ -0,0 +1,200 @@
+//! Encodings of [`Option`] for the D-Bus format.
+//!
+//! D-Bus has no maybe type so `Option<T>` can only be encoded as is in the GVariant format (or as
+//! an array, with the `option-as-array` feature). The modules here provide the common conventions
+//! used by D-Bus APIs instead, to be used with the [serde `with` attribute][with] on `Option`
+//! fields. Pair that with the `option` attribute of the [`Type`] derive, for the matching
+//! signature:
+//!
+//! * [`array`]: An array (`aT`) of no element for `None` and one element for `Some`.
+//! * [`variant`]: A variant (`v`) of the value for `Some` and of a `0u8` for `None`, just like the
+//!   encoding of an empty structure.
+//! * [`pair`]: A structure (`(bT)`) of whether there is a value and the value, where the default
+//!   value of `T` is used for `None`.
+//!
+//! # Examples
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use zvariant::{serialized::Context, to_bytes, Type, LE};
+//!
+//! #[derive(Deserialize, Serialize, Type, PartialEq, Debug)]
+//! struct Struct {
+//!     #[serde(with = "zvariant::option::array")]
+//!     #[zvariant(option = "array")]
+//!     array: Option<u32>,
+//!     #[serde(with = "zvariant::option::variant")]
+//!     #[zvariant(option = "variant")]
+//!     variant: Option<String>,
+//!     #[serde(with = "zvariant::option::pair")]
+//!     #[zvariant(option = "pair")]
+//!     pair: Option<i64>,
+//! }
+//!
+//! assert_eq!(Struct::signature(), "(auv(bx))");
+//! let s = Struct {
+//!     array: Some(42),
+//!     variant: None,
+//!     pair: Some(-1),
+//! };
+//! let ctxt = Context::new_dbus(LE, 0);
+//! let encoded = to_bytes(ctxt, &s).unwrap();
+//! let decoded: Struct = encoded.deserialize().unwrap().0;
+//! assert_eq!(decoded, s);
+//! ```
+//!
+//! [with]: https://serde.rs/field-attrs.html#with
+//! [`Type`]: derive.Type.html
+
+/// Encoding of `Option<T>` as an array (`aT`) of at most one element.
+pub mod array {
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{Signature, Type};
+
+    /// The signature of the encoding of `Option<T>`.
+    pub fn signature<T: Type>() -> Signature<'static> {
+        <[T]>::signature()
+    }
+
+    /// Serialize `value` as an array.
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(value)
+    }
+
+    /// Deserialize a value from an array.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let mut elements = Vec::<T>::deserialize(deserializer)?;
+        if elements.len() > 1 {
+            return Err(de::Error::invalid_length(
+                elements.len(),
+                &"an array of at most one element",
+            ));
+        }
+
+        Ok(elements.pop())
+    }
+}
+
+/// Encoding of `Option<T>` as a variant (`v`), of a `0u8` for `None`.
+///
+/// On deserialization, a variant of the signature of `T` is taken as `Some`, even when `T` is
+/// `u8`.
+pub mod variant {
+    use std::marker::PhantomData;
+
+    use serde::{
+        de::{self, SeqAccess, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use crate::{SerializeValue, Signature, Type};
+
+    /// The signature of the encoding of `Option<T>`.
+    pub fn signature<T: Type>() -> Signature<'static> {
+        Signature::from_static_str_unchecked("v")
+    }
+
+    /// Serialize `value` as a variant.
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Type + Serialize,
+        S: Serializer,
+    {
+        match value {
+            Some(value) => SerializeValue(value).serialize(serializer),
+            None => SerializeValue(&0u8).serialize(serializer),
+        }
+    }
+
+    /// Deserialize a value from a variant.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: Type + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["zvariant::Value::Signature", "zvariant::Value::Value"];
+        deserializer.deserialize_struct("zvariant::Value", FIELDS, OptionVisitor(PhantomData))
+    }
+
+    struct OptionVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Type + Deserialize<'de>> Visitor<'de> for OptionVisitor<T> {
+        type Value = Option<T>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            formatter.write_str("zvariant::Value")
+        }
+
+        fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+        where
+            V: SeqAccess<'de>,
+        {
+            let sig: Signature<'_> = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            if sig == T::signature() {
+                return seq
+                    .next_element()?
+                    .map(Some)
+                    .ok_or_else(|| de::Error::invalid_length(1, &self));
+            }
+            if sig == u8::signature() {
+                seq.next_element::<u8>()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+                return Ok(None);
+            }
+
+            Err(de::Error::invalid_value(
+                de::Unexpected::Str(&sig),
+                &"the value signature or `y`",
+            ))
+        }
+    }
+}
+
+/// Encoding of `Option<T>` as a structure (`(bT)`) of whether there is a value and the value.
+///
+/// The default value of `T` is encoded for `None`.
+pub mod pair {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{Signature, Type};
+
+    /// The signature of the encoding of `Option<T>`.
+    pub fn signature<T: Type>() -> Signature<'static> {
+        <(bool, T)>::signature()
+    }
+
+    /// Serialize `value` as a pair.
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize + Default,
+        S: Serializer,
+    {
+        match value {
+            Some(value) => (true, value).serialize(serializer),
+            None => (false, T::default()).serialize(serializer),
+        }
+    }
+
+    /// Deserialize a value from a pair.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let (is_some, value) = <(bool, T)>::deserialize(deserializer)?;
+
+        Ok(is_some.then_some(value))
+    }
+}
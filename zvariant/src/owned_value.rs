@@ -1,6 +1,6 @@
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use static_assertions::assert_impl_all;
-use std::{collections::HashMap, hash::BuildHasher};
+use std::{collections::HashMap, hash::BuildHasher, sync::Arc};
 
 use crate::{
     Array, Dict, NoneValue, ObjectPath, Optional, OwnedObjectPath, OwnedSignature, Signature, Str,
@@ -17,19 +17,44 @@ use crate::Maybe;
 // https://github.com/dbus2/zbus/issues/138
 
 /// Owned [`Value`](enum.Value.html)
-#[derive(Debug, PartialEq, Serialize, Type)]
-pub struct OwnedValue(pub(crate) Value<'static>);
+///
+/// The value is kept in shared storage, so cloning an `OwnedValue` is cheap, regardless of the
+/// size of the value.
+///
+/// Conversions from an `OwnedValue` into owned types (e.g `TryFrom<OwnedValue> for Array`) move the
+/// value out of the storage if this is the only reference to it. Otherwise, they create a deep copy
+/// of the value (same as [`OwnedValue::try_clone`]), which is as costly as the size of the value
+/// and fails if duplicating a contained file descriptor fails. Convert from a reference (e.g
+/// `TryFrom<&OwnedValue> for &Array`) to avoid the copy. The same applies to the conversion into
+/// [`Value`], except that it panics if a file descriptor can't be duplicated.
+#[derive(Debug, Clone, PartialEq, Type)]
+pub struct OwnedValue(pub(crate) Arc<Value<'static>>);
 
 assert_impl_all!(OwnedValue: Send, Sync, Unpin);
 
 impl OwnedValue {
-    /// Attempt to clone the value.
+    pub(crate) fn new(value: Value<'static>) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// Attempt to create a deep copy of the value.
+    ///
+    /// Unlike [`Clone::clone`], which shares the value, this duplicates the file descriptors
+    /// contained in the value, if any.
     pub fn try_clone(&self) -> Result<Self, crate::Error> {
-        self.0.try_clone().map(Self)
+        self.0.try_clone().map(Self::new)
+    }
+
+    /// Extract the inner value, creating a deep copy of it if it's shared.
+    ///
+    /// Copying can only fail on Unix platforms for values containing file descriptors.
+    pub(crate) fn try_into_inner(self) -> crate::Result<Value<'static>> {
+        Arc::try_unwrap(self.0).or_else(|v| v.try_clone())
     }
 
     pub(crate) fn into_inner(self) -> Value<'static> {
-        self.0
+        self.try_into_inner()
+            .expect("failed to duplicate file descriptor of a shared value")
     }
 
     pub(crate) fn inner(&self) -> &Value<'_> {
@@ -43,7 +68,7 @@ macro_rules! ov_try_from {
             type Error = crate::Error;
 
             fn try_from(v: OwnedValue) -> Result<Self, Self::Error> {
-                <$to>::try_from(v.0)
+                <$to>::try_from(v.try_into_inner()?)
             }
         }
     };
@@ -55,7 +80,7 @@ macro_rules! ov_try_from_ref {
             type Error = crate::Error;
 
             fn try_from(v: &'a OwnedValue) -> Result<Self, Self::Error> {
-                <$to>::try_from(&v.0)
+                <$to>::try_from(&*v.0)
             }
         }
     };
@@ -71,6 +96,7 @@ ov_try_from!(i64);
 ov_try_from!(u64);
 ov_try_from!(f64);
 ov_try_from!(String);
+ov_try_from!(Arc<str>);
 ov_try_from!(Signature<'static>);
 ov_try_from!(OwnedSignature);
 ov_try_from!(ObjectPath<'static>);
@@ -83,6 +109,10 @@ ov_try_from!(Str<'static>);
 ov_try_from!(Structure<'static>);
 #[cfg(unix)]
 ov_try_from!(Fd<'static>);
+#[cfg(feature = "uuid")]
+ov_try_from!(uuid::Uuid);
+#[cfg(feature = "url")]
+ov_try_from!(url::Url);
 
 ov_try_from_ref!(u8);
 ov_try_from_ref!(bool);
@@ -94,6 +124,7 @@ ov_try_from_ref!(i64);
 ov_try_from_ref!(u64);
 ov_try_from_ref!(f64);
 ov_try_from_ref!(&'a str);
+ov_try_from_ref!(Arc<str>);
 ov_try_from_ref!(&'a Signature<'a>);
 ov_try_from_ref!(&'a ObjectPath<'a>);
 ov_try_from_ref!(&'a Array<'a>);
@@ -104,6 +135,10 @@ ov_try_from_ref!(&'a Structure<'a>);
 ov_try_from_ref!(&'a Maybe<'a>);
 #[cfg(unix)]
 ov_try_from_ref!(&'a Fd<'a>);
+#[cfg(feature = "uuid")]
+ov_try_from_ref!(uuid::Uuid);
+#[cfg(feature = "url")]
+ov_try_from_ref!(url::Url);
 
 impl<'a, T> TryFrom<OwnedValue> for Vec<T>
 where
@@ -113,7 +148,7 @@ where
     type Error = crate::Error;
 
     fn try_from(value: OwnedValue) -> Result<Self, Self::Error> {
-        if let Value::Array(v) = value.0 {
+        if let Value::Array(v) = value.try_into_inner()? {
             Self::try_from(v)
         } else {
             Err(crate::Error::IncorrectType)
@@ -130,7 +165,7 @@ where
     type Error = crate::Error;
 
     fn try_from(value: OwnedValue) -> Result<Self, Self::Error> {
-        Self::try_from(value.0)
+        Self::try_from(value.try_into_inner()?)
     }
 }
 
@@ -145,7 +180,7 @@ where
     type Error = crate::Error;
 
     fn try_from(value: OwnedValue) -> Result<Self, Self::Error> {
-        if let Value::Dict(v) = value.0 {
+        if let Value::Dict(v) = value.try_into_inner()? {
             Self::try_from(v)
         } else {
             Err(crate::Error::IncorrectType)
@@ -160,7 +195,7 @@ where
     H: BuildHasher + Default,
 {
     fn from(value: HashMap<K, V, H>) -> Self {
-        Self(value.into())
+        Self::new(value.into())
     }
 }
 
@@ -172,7 +207,7 @@ where
     type Error = crate::Error;
 
     fn try_from(value: OwnedValue) -> Result<Self, Self::Error> {
-        Self::try_from(value.0)
+        Self::try_from(value.try_into_inner()?)
     }
 }
 
@@ -181,7 +216,7 @@ where
     V: Into<Value<'static>> + NoneValue<NoneType = V>,
 {
     fn from(v: Optional<V>) -> OwnedValue {
-        Self(Value::from(v))
+        Self::new(Value::from(v))
     }
 }
 
@@ -208,7 +243,7 @@ macro_rules! to_value {
     ($from:ty, $variant:ident) => {
         impl<'a> From<$from> for OwnedValue {
             fn from(v: $from) -> Self {
-                OwnedValue(<Value<'static>>::$variant(v.to_owned()))
+                OwnedValue::new(<Value<'static>>::$variant(v.to_owned()))
             }
         }
     };
@@ -247,6 +282,35 @@ try_to_value!(Structure<'a>);
 #[cfg(unix)]
 try_to_value!(Fd<'a>);
 
+impl From<Arc<str>> for OwnedValue {
+    fn from(v: Arc<str>) -> Self {
+        OwnedValue::new(Value::from(v))
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for OwnedValue {
+    fn from(v: uuid::Uuid) -> Self {
+        OwnedValue::new(Value::from(v))
+    }
+}
+
+#[cfg(feature = "url")]
+impl From<url::Url> for OwnedValue {
+    fn from(v: url::Url) -> Self {
+        OwnedValue::new(Value::from(v))
+    }
+}
+
+/// Extract the value, creating a deep copy of it if it's shared.
+///
+/// See the [`OwnedValue`] documentation for the cost of this conversion.
+///
+/// # Panics
+///
+/// If `v` is shared with other `OwnedValue` instances and contains file descriptors that can't be
+/// duplicated. This can only happen on Unix platforms, when the process runs out of file
+/// descriptors.
 impl From<OwnedValue> for Value<'_> {
     fn from(v: OwnedValue) -> Self {
         v.into_inner()
@@ -269,6 +333,15 @@ impl std::ops::Deref for OwnedValue {
     }
 }
 
+impl Serialize for OwnedValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
 impl<'de> Deserialize<'de> for OwnedValue {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -281,7 +354,7 @@ impl<'de> Deserialize<'de> for OwnedValue {
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, error::Error};
+    use std::{collections::HashMap, error::Error, sync::Arc};
 
     use crate::{serialized::Context, to_bytes, OwnedValue, Value, LE};
 
@@ -333,4 +406,35 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn shared_clone() -> Result<(), Box<dyn Error>> {
+        let list: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        let ov = OwnedValue::try_from(Value::from(list.clone()))?;
+        let clone = ov.clone();
+        // Both share the same storage.
+        assert!(std::ptr::eq(&*ov, &*clone));
+        assert_eq!(ov, clone);
+
+        // Conversions work on shared values, as well as unshared ones.
+        assert_eq!(<Vec<String>>::try_from(clone)?, list);
+        let deep = ov.try_clone()?;
+        assert!(!std::ptr::eq(&*ov, &*deep));
+        assert_eq!(<Vec<String>>::try_from(ov)?, list);
+        assert_eq!(Value::from(deep), Value::from(list));
+
+        Ok(())
+    }
+
+    #[test]
+    fn shared_str() -> Result<(), Box<dyn Error>> {
+        let name: Arc<str> = Arc::from("org.freedesktop.zbus");
+        let ov = OwnedValue::from(name.clone());
+        // No copies of the string are made in either direction.
+        assert!(Arc::ptr_eq(&name, &Arc::<str>::try_from(&ov)?));
+        assert!(Arc::ptr_eq(&name, &Arc::<str>::try_from(ov.clone())?));
+        assert!(Arc::ptr_eq(&name, &Arc::<str>::try_from(ov)?));
+
+        Ok(())
+    }
 }
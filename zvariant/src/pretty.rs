@@ -0,0 +1,119 @@
+use std::fmt::{Formatter, Result, Write};
+
+#[cfg(unix)]
+use std::os::fd::AsRawFd;
+
+#[cfg(feature = "gvariant")]
+use crate::Maybe;
+use crate::{Array, Dict, Structure, Value};
+
+const INDENT: &str = "    ";
+
+// The multi-line, typed format used for the alternate (`{:#}`) `Display` of values.
+//
+// Every value is prefixed with its type, elements of arrays and fields of structures with their
+// index and entries of dictionaries with their key. Containers span multiple lines, with their
+// contents indented one level deeper than `level`.
+pub(crate) fn value_pretty_fmt(value: &Value<'_>, f: &mut Formatter<'_>, level: usize) -> Result {
+    match value {
+        Value::U8(num) => write!(f, "byte 0x{num:02x}"),
+        Value::Bool(boolean) => write!(f, "boolean {boolean}"),
+        Value::I16(num) => write!(f, "int16 {num}"),
+        Value::U16(num) => write!(f, "uint16 {num}"),
+        Value::I32(num) => write!(f, "int32 {num}"),
+        Value::U32(num) => write!(f, "uint32 {num}"),
+        Value::I64(num) => write!(f, "int64 {num}"),
+        Value::U64(num) => write!(f, "uint64 {num}"),
+        Value::F64(num) => write!(f, "double {num:?}"),
+        Value::Str(string) => write!(f, "string {:?}", string.as_str()),
+        Value::Signature(val) => write!(f, "signature {:?}", val.as_str()),
+        Value::ObjectPath(val) => write!(f, "object_path {:?}", val.as_str()),
+        Value::Value(child) => {
+            f.write_str("variant ")?;
+            value_pretty_fmt(child, f, level)
+        }
+        Value::Array(array) => array_pretty_fmt(array, f, level),
+        Value::Dict(dict) => dict_pretty_fmt(dict, f, level),
+        Value::Structure(structure) => structure_pretty_fmt(structure, f, level),
+        #[cfg(feature = "gvariant")]
+        Value::Maybe(maybe) => maybe_pretty_fmt(maybe, f, level),
+        #[cfg(unix)]
+        Value::Fd(fd) => write!(f, "unix_fd {}", fd.as_raw_fd()),
+    }
+}
+
+pub(crate) fn array_pretty_fmt(array: &Array<'_>, f: &mut Formatter<'_>, level: usize) -> Result {
+    write!(f, "array {} ", array.full_signature())?;
+
+    indexed_pretty_fmt(array.inner(), ('[', ']'), f, level)
+}
+
+pub(crate) fn structure_pretty_fmt(
+    structure: &Structure<'_>,
+    f: &mut Formatter<'_>,
+    level: usize,
+) -> Result {
+    write!(f, "struct {} ", structure.full_signature())?;
+
+    indexed_pretty_fmt(structure.fields(), ('{', '}'), f, level)
+}
+
+pub(crate) fn dict_pretty_fmt(dict: &Dict<'_, '_>, f: &mut Formatter<'_>, level: usize) -> Result {
+    write!(f, "dict {} {{", dict.full_signature())?;
+
+    let mut empty = true;
+    for (key, value) in dict.iter() {
+        new_line(f, level + 1)?;
+        value_pretty_fmt(key, f, level + 1)?;
+        f.write_str(" => ")?;
+        value_pretty_fmt(value, f, level + 1)?;
+        empty = false;
+    }
+    if !empty {
+        new_line(f, level)?;
+    }
+
+    f.write_char('}')
+}
+
+#[cfg(feature = "gvariant")]
+pub(crate) fn maybe_pretty_fmt(maybe: &Maybe<'_>, f: &mut Formatter<'_>, level: usize) -> Result {
+    write!(f, "maybe {} ", maybe.full_signature())?;
+
+    match maybe.inner() {
+        Some(inner) => {
+            f.write_str("just ")?;
+            value_pretty_fmt(inner, f, level)
+        }
+        None => f.write_str("nothing"),
+    }
+}
+
+// Write the `values`, each on its own line and prefixed by its index.
+fn indexed_pretty_fmt(
+    values: &[Value<'_>],
+    (open, close): (char, char),
+    f: &mut Formatter<'_>,
+    level: usize,
+) -> Result {
+    f.write_char(open)?;
+    for (i, value) in values.iter().enumerate() {
+        new_line(f, level + 1)?;
+        write!(f, "[{i}] ")?;
+        value_pretty_fmt(value, f, level + 1)?;
+    }
+    if !values.is_empty() {
+        new_line(f, level)?;
+    }
+
+    f.write_char(close)
+}
+
+fn new_line(f: &mut Formatter<'_>, level: usize) -> Result {
+    f.write_char('\n')?;
+    for _ in 0..level {
+        f.write_str(INDENT)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+//! [`proptest`](::proptest) strategies, for property-based testing.
+//!
+//! Generated values are always valid D-Bus values: strings don't contain any nul bytes, floating
+//! point numbers are never NaN (so values can be compared after a round trip) and file descriptors
+//! are never generated.
+//!
+//! # Example
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use zvariant::{serialized::Context, to_bytes, LE};
+//!
+//! proptest! {
+//!     // Add `#[test]` to have this run as part of your tests.
+//!     fn value_round_trip(value in zvariant::proptest::value()) {
+//!         let ctxt = Context::new_dbus(LE, 0);
+//!         let encoded = to_bytes(ctxt, &value).unwrap();
+//!         let (decoded, _) = encoded.deserialize::<zvariant::Value<'_>>().unwrap();
+//!         prop_assert_eq!(decoded, value);
+//!     }
+//! }
+//!
+//! value_round_trip();
+//! ```
+
+use ::proptest::{collection::vec, prelude::*, sample::select, string::string_regex};
+
+use crate::{
+    Array, Dict, ObjectPath, Signature, SignatureTree, StructureBuilder, Value,
+    GENERATED_BASIC_TYPES, GENERATED_MAX_DEPTH, GENERATED_MAX_LEN,
+};
+
+/// A strategy for random signatures of a single complete type.
+pub fn signature() -> impl Strategy<Value = Signature<'static>> {
+    signature_tree(GENERATED_MAX_DEPTH).prop_map(|tree| signature_of(&tree))
+}
+
+/// A strategy for random values, of random signatures.
+pub fn value() -> impl Strategy<Value = Value<'static>> {
+    signature_tree(GENERATED_MAX_DEPTH)
+        .prop_flat_map(|tree| value_of_tree(&tree, GENERATED_MAX_DEPTH))
+}
+
+/// A strategy for random signatures, along with a random value of each.
+pub fn signature_and_value() -> impl Strategy<Value = (Signature<'static>, Value<'static>)> {
+    value().prop_map(|value| (value.value_signature().to_owned(), value))
+}
+
+/// A strategy for random values of the given `signature`.
+///
+/// This is useful to generate D-Bus encoded forms of your own types, as their signature is known.
+///
+/// # Panics
+///
+/// If `signature` is not a single complete type, or if it contains file descriptors.
+pub fn value_of(signature: &Signature<'_>) -> BoxedStrategy<Value<'static>> {
+    let tree = SignatureTree::try_from(signature).expect("not a single complete type signature");
+
+    value_of_tree(&tree, GENERATED_MAX_DEPTH)
+}
+
+/// A strategy for random object paths.
+pub fn object_path() -> impl Strategy<Value = ObjectPath<'static>> {
+    string_regex("(/[A-Za-z0-9_]{1,8}){0,3}")
+        .expect("invalid regex")
+        .prop_map(|path| {
+            let path = if path.is_empty() { "/".into() } else { path };
+
+            ObjectPath::try_from(path).expect("generated an invalid object path")
+        })
+}
+
+fn basic_type() -> impl Strategy<Value = char> {
+    select(GENERATED_BASIC_TYPES)
+}
+
+fn signature_tree(levels: u32) -> BoxedStrategy<SignatureTree> {
+    let leaf = basic_type().prop_map(SignatureTree::Basic);
+    if levels == 0 {
+        return leaf.boxed();
+    }
+
+    leaf.prop_recursive(levels, 32, GENERATED_MAX_LEN as u32, |inner| {
+        prop_oneof![
+            Just(SignatureTree::Variant),
+            inner.clone().prop_map(SignatureTree::array),
+            (basic_type(), inner.clone())
+                .prop_map(|(key, value)| SignatureTree::dict(SignatureTree::Basic(key), value)),
+            vec(inner, 1..=GENERATED_MAX_LEN).prop_map(SignatureTree::structure),
+        ]
+    })
+    .boxed()
+}
+
+fn value_of_tree(tree: &SignatureTree, levels: u32) -> BoxedStrategy<Value<'static>> {
+    let levels = levels.saturating_sub(1);
+    match tree {
+        SignatureTree::Basic(c) => basic_value(*c, levels),
+        SignatureTree::Variant => signature_tree(levels)
+            .prop_flat_map(move |tree| value_of_tree(&tree, levels))
+            .prop_map(|value| Value::Value(Box::new(value)))
+            .boxed(),
+        SignatureTree::Array(element) => match &**element {
+            SignatureTree::DictEntry(key, value) => {
+                let key_signature = signature_of(key);
+                let value_signature = signature_of(value);
+                let entry = (value_of_tree(key, levels), value_of_tree(value, levels));
+
+                vec(entry, 0..=GENERATED_MAX_LEN)
+                    .prop_map(move |entries| {
+                        let mut dict = Dict::new(key_signature.clone(), value_signature.clone());
+                        for (key, value) in entries {
+                            dict.append(key, value)
+                                .expect("generated a dict entry of the wrong signature");
+                        }
+
+                        Value::Dict(dict)
+                    })
+                    .boxed()
+            }
+            element => {
+                let signature = signature_of(element);
+
+                vec(value_of_tree(element, levels), 0..=GENERATED_MAX_LEN)
+                    .prop_map(move |elements| {
+                        let mut array = Array::new(signature.clone());
+                        for element in elements {
+                            array
+                                .append(element)
+                                .expect("generated an element of the wrong signature");
+                        }
+
+                        Value::Array(array)
+                    })
+                    .boxed()
+            }
+        },
+        SignatureTree::Struct(fields) => fields
+            .iter()
+            .map(|field| value_of_tree(field, levels))
+            .collect::<Vec<_>>()
+            .prop_map(|fields| {
+                let mut builder = StructureBuilder::new();
+                for field in fields {
+                    builder.push_value(field);
+                }
+
+                Value::Structure(builder.build())
+            })
+            .boxed(),
+        SignatureTree::DictEntry(..) => panic!("dict entries are only valid as array elements"),
+        #[cfg(feature = "gvariant")]
+        SignatureTree::Maybe(_) => panic!("maybe types are not supported"),
+    }
+}
+
+fn basic_value(c: char, levels: u32) -> BoxedStrategy<Value<'static>> {
+    match c {
+        'y' => any::<u8>().prop_map(Value::U8).boxed(),
+        'b' => any::<bool>().prop_map(Value::Bool).boxed(),
+        'n' => any::<i16>().prop_map(Value::I16).boxed(),
+        'q' => any::<u16>().prop_map(Value::U16).boxed(),
+        'i' => any::<i32>().prop_map(Value::I32).boxed(),
+        'u' => any::<u32>().prop_map(Value::U32).boxed(),
+        'x' => any::<i64>().prop_map(Value::I64).boxed(),
+        't' => any::<u64>().prop_map(Value::U64).boxed(),
+        'd' => any::<f64>()
+            .prop_filter("NaN is not equal to itself", |f| !f.is_nan())
+            .prop_map(Value::F64)
+            .boxed(),
+        's' => any::<String>()
+            .prop_map(|s| Value::from(s.replace('\0', "")))
+            .boxed(),
+        'o' => object_path().prop_map(Value::ObjectPath).boxed(),
+        'g' => signature_tree(levels)
+            .prop_map(|tree| Value::Signature(signature_of(&tree)))
+            .boxed(),
+        _ => panic!("values of type `{c}` can't be generated"),
+    }
+}
+
+fn signature_of(tree: &SignatureTree) -> Signature<'static> {
+    tree.signature().expect("generated an invalid signature")
+}
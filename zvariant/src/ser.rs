@@ -1,5 +1,10 @@
 use serde::Serialize;
-use std::io::{Seek, Write};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    io::{Seek, Write},
+    ops::Range,
+};
 
 #[cfg(unix)]
 use std::os::fd::OwnedFd;
@@ -9,6 +14,7 @@ use crate::gvariant::Serializer as GVSerializer;
 use crate::{
     container_depths::ContainerDepths,
     dbus::Serializer as DBusSerializer,
+    fixed_array::{convert_endian, fixed_element_size},
     serialized::{Context, Data, Format, Size, Written},
     signature_parser::SignatureParser,
     utils::*,
@@ -35,6 +41,11 @@ impl Seek for NullWriteSeek {
 
 /// Calculate the serialized size of `T`.
 ///
+/// The value is walked through without writing any of the bytes, so this is much cheaper than
+/// serializing it. The length is exact, including any padding, and hence depends on the position
+/// in `ctxt`. It can be used to preallocate buffers, check the size of a value against the limits
+/// before serializing it or to write length-prefixed framing.
+///
 /// # Examples
 ///
 /// ```
@@ -50,9 +61,35 @@ impl Seek for NullWriteSeek {
 pub fn serialized_size<T>(ctxt: Context, value: &T) -> Result<Size>
 where
     T: ?Sized + Serialize + DynamicType,
+{
+    serialized_size_for_signature(ctxt, value.dynamic_signature(), value)
+}
+
+/// Calculate the serialized size of `T` that has the given signature.
+///
+/// Use this function instead of [`serialized_size`] if the value being serialized does not
+/// implement [`DynamicType`].
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{serialized::Context, serialized_size_for_signature, LE};
+///
+/// let ctxt = Context::new_dbus(LE, 0);
+/// let len = serialized_size_for_signature(ctxt, "(st)", &("hello world!", 42_u64)).unwrap();
+/// assert_eq!(*len, 32);
+/// ```
+pub fn serialized_size_for_signature<'s, S, T>(
+    ctxt: Context,
+    signature: S,
+    value: &T,
+) -> Result<Size>
+where
+    S: TryInto<Signature<'s>>,
+    S::Error: Into<Error>,
+    T: ?Sized + Serialize,
 {
     let mut null = NullWriteSeek;
-    let signature = value.dynamic_signature();
     #[cfg(unix)]
     let mut fds = FdList::Number(0);
 
@@ -239,6 +276,277 @@ where
     Ok(encoded)
 }
 
+/// Serialize `T`, appending the bytes to `buf`.
+///
+/// Unlike [`to_bytes`], this doesn't allocate a new byte vector for each value, so a buffer can be
+/// reused, e.g after clearing it, to serialize many values. The range of `buf` the value was
+/// written to is returned, along with the [`Written`] information. Note that the alignment of the
+/// data is relative to the position in `ctxt` and not to the existing length of `buf`.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{serialized::Context, to_bytes_in, LE};
+///
+/// let ctxt = Context::new_dbus(LE, 0);
+/// let mut buf = vec![];
+/// for value in ["hello", "world"] {
+///     buf.clear();
+///     // SAFETY: No FDs are being serialized here so its completely safe.
+///     let (range, written) = unsafe { to_bytes_in(&mut buf, ctxt, value) }.unwrap();
+///     assert_eq!((range, written.size()), (0..10, 10));
+/// }
+///
+/// // Appending to existing bytes.
+/// let (range, _) = unsafe { to_bytes_in(&mut buf, ctxt, &7u8) }.unwrap();
+/// assert_eq!(range, 10..11);
+/// assert_eq!(&buf[range], [7]);
+/// ```
+///
+/// # Safety
+///
+/// The same as for [`to_writer`].
+pub unsafe fn to_bytes_in<T>(
+    buf: &mut Vec<u8>,
+    ctxt: Context,
+    value: &T,
+) -> Result<(Range<usize>, Written)>
+where
+    T: ?Sized + Serialize + DynamicType,
+{
+    let signature = value.dynamic_signature();
+
+    to_bytes_in_for_signature(buf, ctxt, &signature, value)
+}
+
+/// Serialize `T` that has the given signature, appending the bytes to `buf`.
+///
+/// Use this function instead of [`to_bytes_in`] if the value being serialized does not implement
+/// [`DynamicType`].
+///
+/// # Safety
+///
+/// The same as for [`to_writer`].
+pub unsafe fn to_bytes_in_for_signature<'s, S, T>(
+    buf: &mut Vec<u8>,
+    ctxt: Context,
+    signature: S,
+    value: &T,
+) -> Result<(Range<usize>, Written)>
+where
+    S: TryInto<Signature<'s>>,
+    S::Error: Into<Error>,
+    T: ?Sized + Serialize,
+{
+    let start = buf.len();
+    let mut cursor = std::io::Cursor::new(buf);
+    cursor.set_position(start as u64);
+    let written = match to_writer_for_signature(&mut cursor, ctxt, signature, value) {
+        Ok(written) => written,
+        Err(e) => {
+            // Don't leave any partially written value behind.
+            cursor.into_inner().truncate(start);
+
+            return Err(e);
+        }
+    };
+
+    Ok((start..start + written.size(), written))
+}
+
+/// Serialize `T` to the given `writer`, which doesn't need to be seekable.
+///
+/// Unlike [`to_writer`], the output is written strictly sequentially, so this can be used to
+/// marshal large values directly into a pipe, socket or any other [`Write`] implementation without
+/// first materializing the entire encoding in memory.
+///
+/// This is achieved by serializing `value` twice. The first pass doesn't write anything, but only
+/// records the length of each array, which the D-Bus format requires before the array elements.
+/// The second pass then writes the actual data to `writer`. Therefore, `value` must serialize
+/// identically each time.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{serialized::{Context, Data}, to_stream, LE};
+///
+/// let ctxt = Context::new_dbus(LE, 0);
+/// let mut encoded = vec![];
+/// // SAFETY: No FDs are being serialized here so its completely safe.
+/// unsafe { to_stream(&mut encoded, ctxt, &vec!["hello", "world"]) }.unwrap();
+/// let encoded = Data::new(encoded, ctxt);
+/// let value: Vec<&str> = encoded.deserialize().unwrap().0;
+/// assert_eq!(value, ["hello", "world"]);
+/// ```
+///
+/// # Safety
+///
+/// The same as for [`to_writer`].
+pub unsafe fn to_stream<W, T>(writer: &mut W, ctxt: Context, value: &T) -> Result<Written>
+where
+    W: Write,
+    T: ?Sized + Serialize + DynamicType,
+{
+    let signature = value.dynamic_signature();
+
+    to_stream_for_signature(writer, ctxt, &signature, value)
+}
+
+/// Serialize `T` that has the given signature, to the given `writer`, which doesn't need to be
+/// seekable.
+///
+/// Use this function instead of [`to_stream`] if the value being serialized does not implement
+/// [`DynamicType`].
+///
+/// # Safety
+///
+/// The same as for [`to_writer`].
+pub unsafe fn to_stream_for_signature<'s, W, S, T>(
+    writer: &mut W,
+    ctxt: Context,
+    signature: S,
+    value: &T,
+) -> Result<Written>
+where
+    W: Write,
+    S: TryInto<Signature<'s>>,
+    S::Error: Into<Error>,
+    T: ?Sized + Serialize,
+{
+    let signature = signature.try_into().map_err(Into::into)?;
+    let patches = match ctxt.format() {
+        Format::DBus => {
+            let mut recorder = PatchRecorder::default();
+            #[cfg(unix)]
+            let mut fds = FdList::Number(0);
+            let mut ser = DBusSerializer::<PatchRecorder>::new(
+                &signature,
+                &mut recorder,
+                #[cfg(unix)]
+                &mut fds,
+                ctxt,
+            )?;
+            value.serialize(&mut ser)?;
+
+            recorder.patches
+        }
+        // The GVariant serializer never seeks.
+        #[cfg(feature = "gvariant")]
+        Format::GVariant => BTreeMap::new(),
+    };
+
+    let mut writer = PatchingWriter {
+        inner: writer,
+        pos: 0,
+        emitted: 0,
+        patches,
+    };
+
+    to_writer_for_signature(&mut writer, ctxt, &signature, value)
+}
+
+/// Records all data written over previously written data, without keeping any of the latter.
+#[derive(Default)]
+struct PatchRecorder {
+    pos: u64,
+    end: u64,
+    patches: BTreeMap<u64, Vec<u8>>,
+}
+
+impl Write for PatchRecorder {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.pos < self.end {
+            self.patches.insert(self.pos, buf.to_vec());
+        }
+        self.pos += buf.len() as u64;
+        self.end = self.end.max(self.pos);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for PatchRecorder {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.pos = seek_pos(self.pos, pos)?;
+
+        Ok(self.pos)
+    }
+}
+
+/// Writes data sequentially to `inner`, applying the recorded patches as it goes and skipping any
+/// writes over already emitted data.
+struct PatchingWriter<'w, W> {
+    inner: &'w mut W,
+    pos: u64,
+    emitted: u64,
+    patches: BTreeMap<u64, Vec<u8>>,
+}
+
+impl<'w, W> Write for PatchingWriter<'w, W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let start = self.pos;
+        let end = start + buf.len() as u64;
+        self.pos = end;
+        if end <= self.emitted {
+            return Ok(buf.len());
+        }
+        if start > self.emitted {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "unexpected gap in the serialized data",
+            ));
+        }
+
+        let mut data = buf[(self.emitted - start) as usize..].to_vec();
+        let data_start = self.emitted;
+        for (patch_start, patch) in self.patches.range(..end).rev() {
+            let patch_end = patch_start + patch.len() as u64;
+            if patch_end <= data_start {
+                break;
+            }
+
+            let from = data_start.max(*patch_start);
+            let to = end.min(patch_end);
+            data[(from - data_start) as usize..(to - data_start) as usize].copy_from_slice(
+                &patch[(from - patch_start) as usize..(to - patch_start) as usize],
+            );
+        }
+        self.inner.write_all(&data)?;
+        self.emitted = end;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<'w, W> Seek for PatchingWriter<'w, W> {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.pos = seek_pos(self.pos, pos)?;
+
+        Ok(self.pos)
+    }
+}
+
+fn seek_pos(current: u64, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+    let new = match pos {
+        std::io::SeekFrom::Start(n) => Some(n),
+        std::io::SeekFrom::Current(n) => current.checked_add_signed(n),
+        std::io::SeekFrom::End(_) => None,
+    };
+
+    new.ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid seek"))
+}
+
 /// Context for all our serializers and provides shared functionality.
 pub(crate) struct SerializerCommon<'ser, 'sig, W> {
     pub(crate) ctxt: Context,
@@ -295,8 +603,7 @@ where
         if padding > 0 {
             let byte = [0_u8; 1];
             for _ in 0..padding {
-                self.write_all(&byte)
-                    .map_err(|e| Error::InputOutput(e.into()))?;
+                self.write_all(&byte).map_err(Error::from)?;
             }
         }
 
@@ -331,7 +638,7 @@ where
 
         // Now serialize the veriant index.
         self.write_u32(self.ctxt.endian(), variant_index)
-            .map_err(|e| Error::InputOutput(e.into()))?;
+            .map_err(Error::from)?;
 
         // Skip the `(`, `u`.
         self.sig_parser.skip_chars(2)?;
@@ -339,6 +646,21 @@ where
         Ok(())
     }
 
+    /// Write the elements of an array being serialized, given as bytes in native endianness.
+    ///
+    /// The bytes of each element are swapped if needed, if the elements are [`FixedSize`].
+    ///
+    /// [`FixedSize`]: crate::FixedSize
+    pub(crate) fn write_fixed_array(&mut self, bytes: &[u8]) -> Result<()> {
+        let bytes = match fixed_element_size(self.sig_parser.next_char()?) {
+            Some(size) => convert_endian(bytes, size, self.ctxt.endian())?,
+            // Not an array of fixed-size elements so write the bytes as is.
+            None => Cow::Borrowed(bytes),
+        };
+
+        self.write_all(&bytes).map_err(Error::from)
+    }
+
     fn abs_pos(&self) -> usize {
         self.ctxt.position() + self.bytes_written
     }
@@ -349,7 +671,14 @@ where
     W: Write + Seek,
 {
     /// Write `buf` and increment internal bytes written counter.
+    ///
+    /// Fails without writing anything if that would exceed the total size limit.
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.ctxt
+            .limits()
+            .check_total_len(self.abs_pos() + buf.len())
+            .map_err(std::io::Error::other)?;
+
         self.writer.write(buf).map(|n| {
             self.bytes_written += n;
 
@@ -1,6 +1,9 @@
 use static_assertions::assert_impl_all;
 
-use crate::{serialized::Format, Endian};
+use crate::{
+    serialized::{Format, Limits},
+    Endian,
+};
 
 /// The encoding context to use with the [serialization and deserialization] API.
 ///
@@ -33,17 +36,24 @@ pub struct Context {
     format: Format,
     position: usize,
     endian: Endian,
+    limits: Limits,
+    strict: bool,
 }
 
 assert_impl_all!(Context: Send, Sync, Unpin);
 
 impl Context {
     /// Create a new encoding context.
+    ///
+    /// The default [`Limits`] for `format` are enforced. Use [`Context::with_limits`] to change
+    /// them.
     pub fn new(format: Format, endian: Endian, position: usize) -> Self {
         Self {
             format,
             position,
             endian,
+            limits: Limits::for_format(format),
+            strict: false,
         }
     }
 
@@ -76,4 +86,67 @@ impl Context {
     pub fn position(self) -> usize {
         self.position
     }
+
+    /// The size [`Limits`] enforced with this context.
+    pub fn limits(self) -> Limits {
+        self.limits
+    }
+
+    /// Set the size [`Limits`] to enforce.
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+
+        self
+    }
+
+    /// Whether only the canonical encoding of values is accepted on deserialization.
+    pub fn strict(self) -> bool {
+        self.strict
+    }
+
+    /// Set whether to only accept the canonical encoding of values on deserialization.
+    ///
+    /// Deserialization is lenient by default, as the D-Bus and GVariant specifications allow
+    /// different encodings of the same value in some cases. In strict mode,
+    /// [`Error::NonCanonical`] is returned for any encoding that isn't the one serialization would
+    /// produce:
+    ///
+    /// * Bytes left over after the deserialized value.
+    /// * In the GVariant format, values that don't take up all the space that the framing offsets
+    ///   of their container assign to them.
+    ///
+    /// Non-0 padding bytes and booleans other than `0` and `1` are rejected regardless, as is
+    /// invalid data. Strict mode ensures that the encoding of each value is unique, e.g for hashing
+    /// or signing encoded data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::{serialized::{Context, Data}, Error, LE};
+    ///
+    /// let ctxt = Context::new_dbus(LE, 0);
+    /// let data = Data::new(&[42, 0, 0, 0, 1][..], ctxt);
+    /// assert_eq!(data.deserialize::<u32>().unwrap(), (42, 4));
+    ///
+    /// let data = Data::new(&[42, 0, 0, 0, 1][..], ctxt.with_strict(true));
+    /// assert!(matches!(
+    ///     data.deserialize::<u32>(),
+    ///     Err(Error::NonCanonical(_)),
+    /// ));
+    /// ```
+    ///
+    /// [`Error::NonCanonical`]: crate::Error::NonCanonical
+    #[must_use]
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+
+        self
+    }
+
+    pub(crate) fn with_position(mut self, position: usize) -> Self {
+        self.position = position;
+
+        self
+    }
 }
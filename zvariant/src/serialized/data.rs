@@ -81,6 +81,18 @@ impl<'bytes, 'fds> Data<'bytes, 'fds> {
         &self.inner.fds
     }
 
+    /// Take the file descriptors out of `self`, if it's the only reference to them.
+    ///
+    /// `None` is returned if the file descriptors are shared with other `Data` instances, i-e
+    /// clones or slices of `self`. Since the serialized bytes still refer to the file descriptors
+    /// by index, deserializing any file descriptors from `self` will fail afterwards.
+    ///
+    /// This method is only available on Unix platforms.
+    #[cfg(unix)]
+    pub fn take_fds(&mut self) -> Option<Vec<Fd<'fds>>> {
+        Arc::get_mut(&mut self.inner).map(|inner| std::mem::take(&mut inner.fds))
+    }
+
     /// Returns a slice of `self` for the provided range.
     ///
     /// # Panics
@@ -104,11 +116,7 @@ impl<'bytes, 'fds> Data<'bytes, 'fds> {
         );
         assert!(end <= len, "range end out of bounds: {end:?} > {len:?}");
 
-        let context = Context::new(
-            self.context.format(),
-            self.context.endian(),
-            self.context.position() + start,
-        );
+        let context = self.context.with_position(self.context.position() + start);
         let range = Range {
             start: self.range.start + start,
             end: self.range.start + end,
@@ -141,7 +149,7 @@ impl<'bytes, 'fds> Data<'bytes, 'fds> {
     /// A tuple containing the deserialized value and the number of bytes parsed from `bytes`.
     pub fn deserialize<'d, T>(&'d self) -> Result<(T, usize)>
     where
-        T: ?Sized + Deserialize<'d> + Type,
+        T: Deserialize<'d> + Type,
     {
         let signature = T::signature();
         self.deserialize_for_signature(&signature)
@@ -215,7 +223,7 @@ impl<'bytes, 'fds> Data<'bytes, 'fds> {
     /// A tuple containing the deserialized value and the number of bytes parsed from `bytes`.
     pub fn deserialize_for_signature<'d, S, T>(&'d self, signature: S) -> Result<(T, usize)>
     where
-        T: ?Sized + Deserialize<'d>,
+        T: Deserialize<'d>,
         S: TryInto<Signature<'d>>,
         S::Error: Into<Error>,
     {
@@ -254,11 +262,14 @@ impl<'bytes, 'fds> Data<'bytes, 'fds> {
             .map(Deserializer::DBus)?,
         };
 
-        T::deserialize(&mut de).map(|t| match de {
+        let t = T::deserialize(&mut de)?;
+        match de {
             #[cfg(feature = "gvariant")]
-            Deserializer::GVariant(de) => (t, de.0.pos),
-            Deserializer::DBus(de) => (t, de.0.pos),
-        })
+            Deserializer::GVariant(de) => {
+                de.0.check_all_consumed("the value").map(|_| (t, de.0.pos))
+            }
+            Deserializer::DBus(de) => de.0.check_all_consumed("the value").map(|_| (t, de.0.pos)),
+        }
     }
 
     /// Deserialize `T` from `self`, with the given dynamic signature.
@@ -321,11 +332,14 @@ impl<'bytes, 'fds> Data<'bytes, 'fds> {
             .map(Deserializer::DBus)?,
         };
 
-        seed.deserialize(&mut de).map(|t| match de {
+        let t = seed.deserialize(&mut de)?;
+        match de {
             #[cfg(feature = "gvariant")]
-            Deserializer::GVariant(de) => (t, de.0.pos),
-            Deserializer::DBus(de) => (t, de.0.pos),
-        })
+            Deserializer::GVariant(de) => {
+                de.0.check_all_consumed("the value").map(|_| (t, de.0.pos))
+            }
+            Deserializer::DBus(de) => de.0.check_all_consumed("the value").map(|_| (t, de.0.pos)),
+        }
     }
 }
 
@@ -0,0 +1,131 @@
+use serde::de::DeserializeOwned;
+
+use crate::{
+    serialized::{Context, Data, Format},
+    Error, Result, Signature, Type,
+};
+
+/// Incrementally deserialize values from a stream of bytes.
+///
+/// Bytes are fed to the decoder as they come in, through [`Decoder::feed`], and values are pulled
+/// out through [`Decoder::decode`], which returns `None` while the buffered bytes don't yet contain
+/// a complete value. Consumed bytes are dropped from the internal buffer.
+///
+/// Only the D-Bus format is supported, since the extent of a GVariant-encoded value can't be known
+/// without knowing its total size up front. Values containing file descriptors are not supported
+/// either.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{serialized::{Context, Decoder}, to_bytes, LE};
+///
+/// let ctxt = Context::new_dbus(LE, 0);
+/// let encoded = to_bytes(ctxt, &("hello", 42u32)).unwrap();
+///
+/// let mut decoder = Decoder::new(ctxt);
+/// let (first, second) = encoded.split_at(7);
+/// decoder.feed(first);
+/// assert_eq!(decoder.decode::<(String, u32)>().unwrap(), None);
+/// assert!(decoder.needed() > 0);
+///
+/// decoder.feed(second);
+/// let decoded: (String, u32) = decoder.decode().unwrap().unwrap();
+/// assert_eq!(decoded, ("hello".to_string(), 42));
+/// assert_eq!(decoder.buffered(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Decoder {
+    context: Context,
+    buffer: Vec<u8>,
+    // The minimum length of `buffer` needed for the next value, as far as we know.
+    needed: usize,
+}
+
+impl Decoder {
+    /// Create a new decoder for data starting at the position given in `context`.
+    pub fn new(context: Context) -> Self {
+        Self {
+            context,
+            buffer: vec![],
+            needed: 0,
+        }
+    }
+
+    /// The context for the next value to be decoded.
+    ///
+    /// The position of the returned context accounts for all the bytes consumed so far.
+    pub fn context(&self) -> Context {
+        self.context
+    }
+
+    /// Append `bytes` to the internal buffer.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// The number of bytes buffered but not yet consumed.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// The minimum number of additional bytes known to be needed to decode the next value.
+    ///
+    /// This is `0` until a decoding attempt reports more bytes are needed. Since the exact size of
+    /// a value is often not known until it's entirely decoded, more bytes than this can be needed.
+    pub fn needed(&self) -> usize {
+        self.needed.saturating_sub(self.buffer.len())
+    }
+
+    /// Try to decode the next `T` from the buffered bytes.
+    ///
+    /// Returns `Ok(None)` if more bytes are needed to decode the value.
+    pub fn decode<T>(&mut self) -> Result<Option<T>>
+    where
+        T: DeserializeOwned + Type,
+    {
+        self.decode_for_signature(T::signature())
+    }
+
+    /// Try to decode the next `T` with the given signature, from the buffered bytes.
+    ///
+    /// Use this method instead of [`Decoder::decode`] if the value being decoded does not
+    /// implement [`Type`].
+    ///
+    /// Returns `Ok(None)` if more bytes are needed to decode the value.
+    pub fn decode_for_signature<'s, S, T>(&mut self, signature: S) -> Result<Option<T>>
+    where
+        T: DeserializeOwned,
+        S: TryInto<Signature<'s>>,
+        S::Error: Into<Error>,
+    {
+        if self.context.format() != Format::DBus {
+            return Err(Error::Message(
+                "incremental decoding only supports the D-Bus format".into(),
+            ));
+        }
+        if self.buffer.len() < self.needed {
+            return Ok(None);
+        }
+
+        let signature = signature.try_into().map_err(Into::into)?;
+        let data = Data::new(&self.buffer[..], self.context);
+        match data.deserialize_for_signature(&signature) {
+            Ok((value, consumed)) => {
+                self.buffer.drain(..consumed);
+                self.context = self
+                    .context
+                    .with_position(self.context.position() + consumed);
+                self.needed = 0;
+
+                Ok(Some(value))
+            }
+            Err(Error::InsufficientData(needed)) => {
+                self.needed = needed;
+
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use static_assertions::assert_impl_all;
+
+use crate::{serialized::Format, Error, MaxSizeExceeded, Result};
+
+/// The maximum length of an array in D-Bus format, as specified by the D-Bus specification.
+const DBUS_MAX_ARRAY_LEN: usize = 1 << 26;
+/// The maximum length of a message in D-Bus format, as specified by the D-Bus specification.
+const DBUS_MAX_TOTAL_LEN: usize = 1 << 27;
+
+/// The size limits enforced during serialization and deserialization.
+///
+/// By default, the limits from the D-Bus specification are enforced for the D-Bus format: 64 MiB
+/// for each array and 128 MiB for the entire message. The GVariant specification doesn't define any
+/// limits, so none are enforced by default for the GVariant format. Set the limits on the
+/// [`Context`] through [`Context::with_limits`].
+///
+/// The lengths are in bytes. The total length includes the [position] the (de)serialization
+/// starts at, since that is the number of bytes of the message preceding the data.
+///
+/// Exceeding any of the limits results in an [`Error::MaxSizeExceeded`] error.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{
+///     serialized::{Context, Limits},
+///     to_bytes, Error, MaxSizeExceeded, LE,
+/// };
+///
+/// let ctxt = Context::new_dbus(LE, 0).with_limits(Limits::default().with_max_array_len(4));
+/// assert!(to_bytes(ctxt, &vec![1u8, 2, 3, 4]).is_ok());
+/// assert_eq!(
+///     to_bytes(ctxt, &vec![1u8, 2, 3, 4, 5]).unwrap_err(),
+///     Error::MaxSizeExceeded(MaxSizeExceeded::Array),
+/// );
+/// ```
+///
+/// [`Context`]: crate::serialized::Context
+/// [`Context::with_limits`]: crate::serialized::Context::with_limits
+/// [position]: crate::serialized::Context::position
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Limits {
+    max_array_len: usize,
+    max_total_len: usize,
+}
+
+assert_impl_all!(Limits: Send, Sync, Unpin);
+
+impl Limits {
+    /// The limits from the D-Bus specification.
+    pub const fn dbus() -> Self {
+        Self {
+            max_array_len: DBUS_MAX_ARRAY_LEN,
+            max_total_len: DBUS_MAX_TOTAL_LEN,
+        }
+    }
+
+    /// No limits at all.
+    pub const fn unlimited() -> Self {
+        Self {
+            max_array_len: usize::MAX,
+            max_total_len: usize::MAX,
+        }
+    }
+
+    /// The default limits for `format`.
+    pub const fn for_format(format: Format) -> Self {
+        match format {
+            Format::DBus => Self::dbus(),
+            #[cfg(feature = "gvariant")]
+            Format::GVariant => Self::unlimited(),
+        }
+    }
+
+    /// Set the maximum length of each array.
+    pub const fn with_max_array_len(mut self, len: usize) -> Self {
+        self.max_array_len = len;
+
+        self
+    }
+
+    /// Set the maximum total length.
+    pub const fn with_max_total_len(mut self, len: usize) -> Self {
+        self.max_total_len = len;
+
+        self
+    }
+
+    /// The maximum length of each array.
+    pub const fn max_array_len(self) -> usize {
+        self.max_array_len
+    }
+
+    /// The maximum total length.
+    pub const fn max_total_len(self) -> usize {
+        self.max_total_len
+    }
+
+    pub(crate) fn check_array_len(self, len: usize) -> Result<()> {
+        if len > self.max_array_len {
+            return Err(Error::MaxSizeExceeded(MaxSizeExceeded::Array));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn check_total_len(self, len: usize) -> Result<()> {
+        if len > self.max_total_len {
+            return Err(Error::MaxSizeExceeded(MaxSizeExceeded::Total));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Limits {
+    /// The limits from the D-Bus specification.
+    fn default() -> Self {
+        Self::dbus()
+    }
+}
@@ -8,3 +8,7 @@ mod format;
 pub use format::Format;
 mod context;
 pub use context::Context;
+mod limits;
+pub use limits::Limits;
+mod decoder;
+pub use decoder::Decoder;
@@ -15,7 +15,10 @@ use std::{
     sync::Arc,
 };
 
-use crate::{serialized::Format, signature_parser::SignatureParser, Basic, Error, Result, Type};
+use crate::{
+    serialized::Format, signature_parser::SignatureParser, Basic, Error, Result, Type,
+    STRUCT_SIG_END_CHAR, STRUCT_SIG_START_CHAR,
+};
 
 // A data type similar to Cow and [`bytes::Bytes`] but unlike the former won't allow us to only keep
 // the owned bytes in Arc and latter doesn't have a notion of borrowed data and would require API
@@ -124,7 +127,7 @@ impl Hash for Bytes<'_> {
 ///
 /// [identifies]: https://dbus.freedesktop.org/doc/dbus-specification.html#type-system
 /// [`slice`]: #method.slice
-#[derive(Hash, Clone, PartialOrd, Ord)]
+#[derive(Clone, PartialOrd, Ord)]
 pub struct Signature<'a> {
     bytes: Bytes<'a>,
     pos: usize,
@@ -336,6 +339,64 @@ impl<'a> Signature<'a> {
         }
         Ok(count)
     }
+
+    /// The signature in the form used for message bodies on the wire.
+    ///
+    /// While zbus (de)serializes message bodies of multiple arguments from/to structures (i-e
+    /// tuples), D-Bus has no such concept and the body signature in the message header lists the
+    /// arguments without any enclosing parentheses. This method removes the parentheses of the
+    /// structure if the signature is of exactly one structure, so both `(ss)` and `ss` give `ss`.
+    ///
+    /// Only one level of parentheses is removed since the body of a single structure argument is
+    /// `((ss))` in the zbus form. A signature of multiple complete types, such as `(s)(s)`, is
+    /// returned as is.
+    ///
+    /// Equality of signatures is based on their canonical forms, so `(ss)` is equal to `ss`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Signature;
+    ///
+    /// let sig = Signature::try_from("(sa{sv})").unwrap();
+    /// assert_eq!(sig.canonicalize(), "sa{sv}");
+    /// let sig = Signature::try_from("((ss))").unwrap();
+    /// assert_eq!(sig.canonicalize(), "(ss)");
+    /// let sig = Signature::try_from("(s)(s)").unwrap();
+    /// assert_eq!(sig.canonicalize(), "(s)(s)");
+    ///
+    /// assert_eq!(Signature::try_from("(ss)").unwrap(), Signature::try_from("ss").unwrap());
+    /// ```
+    #[must_use]
+    pub fn canonicalize(&self) -> Signature<'a> {
+        if self.is_single_structure() {
+            self.slice(1..self.len() - 1)
+        } else {
+            self.clone()
+        }
+    }
+
+    // If the signature is of exactly one structure.
+    fn is_single_structure(&self) -> bool {
+        let bytes = self.as_bytes();
+        if bytes.first() != Some(&(STRUCT_SIG_START_CHAR as u8)) {
+            return false;
+        }
+
+        let mut depth = 0;
+        for (i, b) in bytes.iter().enumerate() {
+            if *b == STRUCT_SIG_START_CHAR as u8 {
+                depth += 1;
+            } else if *b == STRUCT_SIG_END_CHAR as u8 {
+                depth -= 1;
+                if depth == 0 {
+                    return i == bytes.len() - 1;
+                }
+            }
+        }
+
+        false
+    }
 }
 
 impl<'a> Debug for Signature<'a> {
@@ -432,27 +493,16 @@ impl<'a> std::ops::Deref for Signature<'a> {
 }
 
 /// Checks whether the string slice has balanced parentheses.
-fn has_balanced_parentheses(signature_str: &str) -> bool {
-    signature_str.chars().fold(0, |count, ch| match ch {
-        '(' => count + 1,
-        ')' if count != 0 => count - 1,
-        _ => count,
-    }) == 0
-}
-
-/// Determines whether the signature has outer parentheses and if so, return the
-/// string slice without those parentheses.
+/// The string slice of the canonical form of the signature.
 fn without_outer_parentheses<'a, 'b>(sig: &'a Signature<'b>) -> &'a str
 where
     'b: 'a,
 {
     let sig_str = sig.as_str();
-
-    if let Some(subslice) = sig_str.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
-        if has_balanced_parentheses(subslice) {
-            return subslice;
-        }
+    if sig.is_single_structure() {
+        return &sig_str[1..sig_str.len() - 1];
     }
+
     sig_str
 }
 
@@ -463,6 +513,13 @@ impl<'a, 'b> PartialEq<Signature<'a>> for Signature<'b> {
     }
 }
 
+impl Hash for Signature<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Must be kept consistent with the `PartialEq` implementation.
+        without_outer_parentheses(self).hash(state)
+    }
+}
+
 impl<'a> PartialEq<str> for Signature<'a> {
     fn eq(&self, other: &str) -> bool {
         self.as_bytes() == other.as_bytes()
@@ -605,6 +662,31 @@ mod tests {
         assert_eq!(owned1, borrowed1);
     }
 
+    #[test]
+    fn canonicalize() {
+        for (sig, canonical) in [
+            ("", ""),
+            ("s", "s"),
+            ("ss", "ss"),
+            ("(ss)", "ss"),
+            ("((ss))", "(ss)"),
+            ("(s)(s)", "(s)(s)"),
+            ("(s)s", "(s)s"),
+            ("(a(ss)a{s(ii)})", "a(ss)a{s(ii)}"),
+            ("a(ss)", "a(ss)"),
+        ] {
+            let sig = Signature::try_from(sig).unwrap();
+            assert_eq!(sig.canonicalize(), canonical);
+        }
+
+        // Equality is based on the canonical forms.
+        let sig = Signature::try_from("(su)").unwrap();
+        assert_eq!(sig, Signature::try_from("su").unwrap());
+        assert_ne!(sig, Signature::try_from("((su))").unwrap());
+        let sig = Signature::try_from("(s)(s)").unwrap();
+        assert_ne!(sig, Signature::from_str_unchecked("s)(s"));
+    }
+
     #[test]
     fn signature_slicing() {
         let sig = Signature::from_str_unchecked("(asta{sv})");
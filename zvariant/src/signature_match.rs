@@ -0,0 +1,222 @@
+use std::fmt;
+
+use static_assertions::assert_impl_all;
+
+use crate::{Signature, SignatureTree, Type};
+
+/// A mismatch between two signatures, as reported by [`signature_matches`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mismatch {
+    path: String,
+    expected: String,
+    found: String,
+}
+
+assert_impl_all!(Mismatch: Send, Sync, Unpin);
+
+impl Mismatch {
+    /// The path to the mismatching type, from the top level.
+    ///
+    /// The path is empty if the types mismatch at the top level. Otherwise, it's made up of the
+    /// following components:
+    ///
+    /// * `.N` for the field `N` (starting from 0) of a structure, or of multiple top-level types.
+    /// * `[]` for the element type of an array.
+    /// * `.key` and `.value` for the key and value types of a dictionary entry.
+    /// * `?` for the inner type of a GVariant maybe.
+    ///
+    /// For example, `.1[].value` is the value type of the dictionary that is the second field.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The expected (sub)signature at [`Mismatch::path`].
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    /// The (sub)signature found at [`Mismatch::path`].
+    pub fn found(&self) -> &str {
+        &self.found
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected `{}`, found `{}`", self.expected, self.found)?;
+        if !self.path.is_empty() {
+            write!(f, " at `{}`", self.path)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+/// Check if values of `signature` can be deserialized as `T`.
+///
+/// Unlike the errors of deserialization that fails midway, the returned [`Mismatch`] tells where
+/// exactly the signatures differ. As with deserialization of message bodies, the parentheses of
+/// a top-level structure are insignificant (e.g `(ss)` matches `ss`).
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use zvariant::{signature_matches, OwnedValue, Signature};
+///
+/// type Body = (u32, HashMap<String, OwnedValue>);
+///
+/// let signature = Signature::try_from("ua{sv}").unwrap();
+/// assert!(signature_matches::<Body>(&signature).is_ok());
+///
+/// let signature = Signature::try_from("ua{ss}").unwrap();
+/// let mismatch = signature_matches::<Body>(&signature).unwrap_err();
+/// assert_eq!(mismatch.path(), ".1[].value");
+/// assert_eq!(mismatch.expected(), "v");
+/// assert_eq!(mismatch.found(), "s");
+/// ```
+pub fn signature_matches<T>(signature: &Signature<'_>) -> Result<(), Mismatch>
+where
+    T: Type + ?Sized,
+{
+    signatures_match(&T::signature(), signature)
+}
+
+// Check if the `found` signature matches the `expected` one, ignoring parentheses of a top-level
+// structure.
+pub(crate) fn signatures_match(
+    expected: &Signature<'_>,
+    found: &Signature<'_>,
+) -> Result<(), Mismatch> {
+    if expected == found {
+        return Ok(());
+    }
+
+    let parse = |signature: &Signature<'_>| -> Result<Vec<SignatureTree>, Mismatch> {
+        let mut trees = SignatureTree::parse(signature).map_err(|_| Mismatch {
+            path: String::new(),
+            expected: expected.to_string(),
+            found: found.to_string(),
+        })?;
+        while let [SignatureTree::Struct(fields)] = trees.as_mut_slice() {
+            trees = std::mem::take(fields);
+        }
+
+        Ok(trees)
+    };
+    let expected_trees = parse(expected)?;
+    let found_trees = parse(found)?;
+    if expected_trees.len() != found_trees.len() {
+        return Err(Mismatch {
+            path: String::new(),
+            expected: expected.to_string(),
+            found: found.to_string(),
+        });
+    }
+
+    let mut path = String::new();
+    let multiple = expected_trees.len() > 1;
+    for (i, (expected, found)) in expected_trees.iter().zip(&found_trees).enumerate() {
+        path.clear();
+        if multiple {
+            path.push_str(&format!(".{i}"));
+        }
+        trees_match(expected, found, &mut path)?;
+    }
+
+    Ok(())
+}
+
+fn sub_trees_match(
+    component: &str,
+    expected: &SignatureTree,
+    found: &SignatureTree,
+    path: &mut String,
+) -> Result<(), Mismatch> {
+    let len = path.len();
+    path.push_str(component);
+    trees_match(expected, found, path)?;
+    path.truncate(len);
+
+    Ok(())
+}
+
+fn trees_match(
+    expected: &SignatureTree,
+    found: &SignatureTree,
+    path: &mut String,
+) -> Result<(), Mismatch> {
+    match (expected, found) {
+        (SignatureTree::Basic(e), SignatureTree::Basic(f)) if e == f => Ok(()),
+        (SignatureTree::Variant, SignatureTree::Variant) => Ok(()),
+        (SignatureTree::Array(e), SignatureTree::Array(f)) => sub_trees_match("[]", e, f, path),
+        (SignatureTree::DictEntry(ek, ev), SignatureTree::DictEntry(fk, fv)) => {
+            sub_trees_match(".key", ek, fk, path)?;
+            sub_trees_match(".value", ev, fv, path)
+        }
+        (SignatureTree::Struct(e), SignatureTree::Struct(f)) if e.len() == f.len() => {
+            for (i, (e, f)) in e.iter().zip(f).enumerate() {
+                sub_trees_match(&format!(".{i}"), e, f, path)?;
+            }
+
+            Ok(())
+        }
+        #[cfg(feature = "gvariant")]
+        (SignatureTree::Maybe(e), SignatureTree::Maybe(f)) => sub_trees_match("?", e, f, path),
+        _ => Err(Mismatch {
+            path: path.clone(),
+            expected: expected.to_string(),
+            found: found.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{signature_matches, Mismatch};
+    use crate::{OwnedValue, Signature};
+
+    fn check<T: crate::Type>(signature: &str) -> Result<(), Mismatch> {
+        signature_matches::<T>(&Signature::try_from(signature).unwrap())
+    }
+
+    #[test]
+    fn matching() {
+        assert!(check::<u32>("u").is_ok());
+        assert!(check::<(String, String)>("ss").is_ok());
+        assert!(check::<(String, String)>("((ss))").is_ok());
+        assert!(check::<((String, String),)>("ss").is_ok());
+        assert!(check::<(u8, Vec<(i32, OwnedValue)>)>("ya(iv)").is_ok());
+        assert!(check::<()>("").is_ok());
+    }
+
+    #[test]
+    fn mismatching() {
+        let mismatch = check::<u32>("s").unwrap_err();
+        assert_eq!(mismatch.path(), "");
+        assert_eq!(mismatch.to_string(), "expected `u`, found `s`");
+
+        let mismatch = check::<(String, String)>("s").unwrap_err();
+        assert_eq!(mismatch.path(), "");
+        assert_eq!(mismatch.to_string(), "expected `(ss)`, found `s`");
+
+        let mismatch = check::<HashMap<String, (u8, Vec<u32>)>>("a{s(yaq)}").unwrap_err();
+        assert_eq!(mismatch.path(), "[].value.1[]");
+        assert_eq!((mismatch.expected(), mismatch.found()), ("u", "q"));
+
+        let mismatch = check::<(u8, (u8, u8))>("y(yyy)").unwrap_err();
+        assert_eq!(mismatch.path(), ".1");
+        assert_eq!((mismatch.expected(), mismatch.found()), ("(yy)", "(yyy)"));
+
+        let mismatch = check::<Vec<(String, OwnedValue)>>("a{sv}").unwrap_err();
+        assert_eq!(mismatch.path(), "[]");
+        assert_eq!((mismatch.expected(), mismatch.found()), ("(sv)", "{sv}"));
+
+        let mismatch = check::<(String, Vec<u8>)>("sas").unwrap_err();
+        assert_eq!(mismatch.to_string(), "expected `y`, found `s` at `.1[]`");
+    }
+}
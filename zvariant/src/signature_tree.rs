@@ -0,0 +1,310 @@
+use std::fmt::{self, Display, Write};
+
+#[cfg(feature = "gvariant")]
+use crate::MAYBE_SIGNATURE_CHAR;
+use crate::{
+    Basic, Error, Result, Signature, ARRAY_SIGNATURE_CHAR, DICT_ENTRY_SIG_END_CHAR,
+    DICT_ENTRY_SIG_START_CHAR, STRUCT_SIG_END_CHAR, STRUCT_SIG_START_CHAR, VARIANT_SIGNATURE_CHAR,
+};
+
+/// A parsed representation of a single complete type signature.
+///
+/// This is useful for inspecting signatures, e.g for mapping them to types in code generators,
+/// and for composing them programmatically.
+///
+/// # Examples
+///
+/// Parsing a signature:
+///
+/// ```
+/// use zvariant::{Signature, SignatureTree};
+///
+/// let signature = Signature::try_from("a{sv}").unwrap();
+/// let tree = SignatureTree::try_from(&signature).unwrap();
+/// match tree {
+///     SignatureTree::Array(element) => match *element {
+///         SignatureTree::DictEntry(key, value) => {
+///             assert_eq!(*key, SignatureTree::Basic('s'));
+///             assert_eq!(*value, SignatureTree::Variant);
+///         }
+///         _ => panic!("expected a dictionary"),
+///     },
+///     _ => panic!("expected an array"),
+/// }
+/// ```
+///
+/// Building one:
+///
+/// ```
+/// use zvariant::{Signature, SignatureTree};
+///
+/// let tree = SignatureTree::structure([
+///     SignatureTree::basic::<u32>(),
+///     SignatureTree::dict(SignatureTree::basic::<&str>(), SignatureTree::Variant),
+///     SignatureTree::array(SignatureTree::basic::<u8>()),
+/// ]);
+/// assert_eq!(tree.to_string(), "(ua{sv}ay)");
+/// let signature = Signature::try_from(&tree).unwrap();
+/// assert_eq!(signature, "(ua{sv}ay)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SignatureTree {
+    /// A basic type, identified by its signature character (e.g `u` for `u32`).
+    Basic(char),
+    /// A variant (`v`).
+    Variant,
+    /// An array of the contained element type.
+    Array(Box<SignatureTree>),
+    /// A dictionary entry of the contained key and value types. Only valid as an array element.
+    DictEntry(Box<SignatureTree>, Box<SignatureTree>),
+    /// A structure of the contained field types.
+    Struct(Vec<SignatureTree>),
+    /// A GVariant maybe type of the contained type.
+    #[cfg(feature = "gvariant")]
+    Maybe(Box<SignatureTree>),
+}
+
+impl SignatureTree {
+    /// Parse all the complete types in `signature`.
+    ///
+    /// Use [`SignatureTree::try_from`] instead, if the signature is of a single complete type.
+    pub fn parse(signature: &Signature<'_>) -> Result<Vec<Self>> {
+        let bytes = signature.as_bytes();
+        let mut pos = 0;
+        let mut trees = vec![];
+        while pos < bytes.len() {
+            trees.push(Self::parse_next(bytes, &mut pos)?);
+        }
+
+        Ok(trees)
+    }
+
+    /// A basic type.
+    pub fn basic<T: Basic>() -> Self {
+        Self::Basic(T::SIGNATURE_CHAR)
+    }
+
+    /// An array of `element`.
+    pub fn array(element: Self) -> Self {
+        Self::Array(Box::new(element))
+    }
+
+    /// A dictionary, i.e an array of dictionary entries.
+    pub fn dict(key: Self, value: Self) -> Self {
+        Self::array(Self::DictEntry(Box::new(key), Box::new(value)))
+    }
+
+    /// A structure of `fields`.
+    pub fn structure<I>(fields: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        Self::Struct(fields.into_iter().collect())
+    }
+
+    /// A maybe of `inner`.
+    #[cfg(feature = "gvariant")]
+    pub fn maybe(inner: Self) -> Self {
+        Self::Maybe(Box::new(inner))
+    }
+
+    /// Render the tree into a signature.
+    ///
+    /// Unlike the [`Display`] implementation, this validates the resulting signature. Trees built
+    /// through the constructors can still be invalid, e.g with a non-basic dictionary key type.
+    pub fn signature(&self) -> Result<Signature<'static>> {
+        self.validate(false)?;
+
+        Signature::try_from(self.to_string())
+    }
+
+    fn validate(&self, array_element: bool) -> Result<()> {
+        match self {
+            Self::Basic(_) | Self::Variant => Ok(()),
+            Self::Array(element) => element.validate(true),
+            Self::DictEntry(key, value) => {
+                if !array_element {
+                    return Err(Error::Message(
+                        "dictionary entry is only allowed as an array element".into(),
+                    ));
+                }
+                if !matches!(**key, Self::Basic(_)) {
+                    return Err(Error::Message(format!(
+                        "dictionary key type must be basic, not `{key}`"
+                    )));
+                }
+
+                value.validate(false)
+            }
+            Self::Struct(fields) => fields.iter().try_for_each(|f| f.validate(false)),
+            #[cfg(feature = "gvariant")]
+            Self::Maybe(inner) => inner.validate(false),
+        }
+    }
+
+    fn parse_next(bytes: &[u8], pos: &mut usize) -> Result<Self> {
+        let c = *bytes
+            .get(*pos)
+            .ok_or_else(|| Error::Message("incomplete signature".into()))? as char;
+        *pos += 1;
+
+        let tree = match c {
+            VARIANT_SIGNATURE_CHAR => Self::Variant,
+            ARRAY_SIGNATURE_CHAR => Self::array(Self::parse_next(bytes, pos)?),
+            #[cfg(feature = "gvariant")]
+            MAYBE_SIGNATURE_CHAR => Self::maybe(Self::parse_next(bytes, pos)?),
+            STRUCT_SIG_START_CHAR => {
+                let mut fields = vec![];
+                while bytes.get(*pos).copied() != Some(STRUCT_SIG_END_CHAR as u8) {
+                    fields.push(Self::parse_next(bytes, pos)?);
+                }
+                *pos += 1;
+
+                Self::Struct(fields)
+            }
+            DICT_ENTRY_SIG_START_CHAR => {
+                let key = Self::parse_next(bytes, pos)?;
+                let value = Self::parse_next(bytes, pos)?;
+                if bytes.get(*pos).copied() != Some(DICT_ENTRY_SIG_END_CHAR as u8) {
+                    return Err(Error::Message(
+                        "dictionary entry must have exactly two types".into(),
+                    ));
+                }
+                *pos += 1;
+
+                Self::DictEntry(Box::new(key), Box::new(value))
+            }
+            STRUCT_SIG_END_CHAR | DICT_ENTRY_SIG_END_CHAR => {
+                return Err(Error::Message(format!("unexpected `{c}` in signature")))
+            }
+            c => Self::Basic(c),
+        };
+
+        Ok(tree)
+    }
+}
+
+impl Display for SignatureTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Basic(c) => f.write_char(*c),
+            Self::Variant => f.write_char(VARIANT_SIGNATURE_CHAR),
+            Self::Array(element) => {
+                f.write_char(ARRAY_SIGNATURE_CHAR)?;
+                element.fmt(f)
+            }
+            Self::DictEntry(key, value) => {
+                f.write_char(DICT_ENTRY_SIG_START_CHAR)?;
+                key.fmt(f)?;
+                value.fmt(f)?;
+                f.write_char(DICT_ENTRY_SIG_END_CHAR)
+            }
+            Self::Struct(fields) => {
+                f.write_char(STRUCT_SIG_START_CHAR)?;
+                for field in fields {
+                    field.fmt(f)?;
+                }
+                f.write_char(STRUCT_SIG_END_CHAR)
+            }
+            #[cfg(feature = "gvariant")]
+            Self::Maybe(inner) => {
+                f.write_char(MAYBE_SIGNATURE_CHAR)?;
+                inner.fmt(f)
+            }
+        }
+    }
+}
+
+impl TryFrom<&Signature<'_>> for SignatureTree {
+    type Error = Error;
+
+    fn try_from(signature: &Signature<'_>) -> Result<Self> {
+        let mut trees = Self::parse(signature)?;
+        if trees.len() != 1 {
+            return Err(Error::IncorrectType);
+        }
+
+        Ok(trees.remove(0))
+    }
+}
+
+impl TryFrom<Signature<'_>> for SignatureTree {
+    type Error = Error;
+
+    fn try_from(signature: Signature<'_>) -> Result<Self> {
+        Self::try_from(&signature)
+    }
+}
+
+impl TryFrom<&str> for SignatureTree {
+    type Error = Error;
+
+    fn try_from(signature: &str) -> Result<Self> {
+        Self::try_from(Signature::try_from(signature)?)
+    }
+}
+
+impl TryFrom<&SignatureTree> for Signature<'static> {
+    type Error = Error;
+
+    fn try_from(tree: &SignatureTree) -> Result<Self> {
+        tree.signature()
+    }
+}
+
+impl TryFrom<SignatureTree> for Signature<'static> {
+    type Error = Error;
+
+    fn try_from(tree: SignatureTree) -> Result<Self> {
+        tree.signature()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SignatureTree;
+    use crate::Signature;
+
+    #[test]
+    fn roundtrip() {
+        for s in [
+            "y",
+            "as",
+            "a{sv}",
+            "(ua(sv)a{oa{sa{sv}}})",
+            "aaay",
+            "a{s(ii)}",
+        ] {
+            let tree = SignatureTree::try_from(s).unwrap();
+            assert_eq!(tree.to_string(), s);
+            assert_eq!(tree.signature().unwrap(), s);
+        }
+
+        let trees = SignatureTree::parse(&Signature::try_from("sa{sv}as").unwrap()).unwrap();
+        assert_eq!(
+            trees,
+            [
+                SignatureTree::basic::<&str>(),
+                SignatureTree::dict(SignatureTree::Basic('s'), SignatureTree::Variant),
+                SignatureTree::array(SignatureTree::Basic('s')),
+            ]
+        );
+        assert!(SignatureTree::try_from("su").is_err());
+    }
+
+    #[test]
+    fn invalid_trees() {
+        // Non-basic key.
+        let tree = SignatureTree::dict(SignatureTree::Variant, SignatureTree::Variant);
+        assert_eq!(tree.to_string(), "a{vv}");
+        assert!(tree.signature().is_err());
+
+        // Dict entry outside of an array.
+        let tree = SignatureTree::DictEntry(
+            Box::new(SignatureTree::basic::<u32>()),
+            Box::new(SignatureTree::Variant),
+        );
+        assert!(Signature::try_from(tree).is_err());
+    }
+}
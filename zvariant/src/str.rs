@@ -14,6 +14,10 @@ use crate::{serialized::Format, Basic, Signature, Type};
 /// This is used for keeping strings in a [`Value`]. API is provided to convert from, and to a
 /// [`&str`] and [`String`].
 ///
+/// Owned strings are kept in an [`Arc<str>`], so cloning an owned `Str` doesn't copy the string.
+/// Converting from, and to an `Arc<str>` doesn't copy owned strings either, allowing them to be
+/// shared cheaply with other code, e.g caches.
+///
 /// [`Value`]: enum.Value.html#variant.Str
 /// [`&str`]: https://doc.rust-lang.org/std/str/index.html
 /// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
@@ -87,6 +91,15 @@ impl<'de: 'a, 'a> Deserialize<'de> for Inner<'a> {
 assert_impl_all!(Str<'_>: Send, Sync, Unpin);
 
 impl<'a> Str<'a> {
+    /// The underlying string, if it's not owned by `self`.
+    pub(crate) fn as_borrowed_str(&self) -> Option<&'a str> {
+        match &self.0 {
+            Inner::Static(s) => Some(s),
+            Inner::Borrowed(s) => Some(s),
+            Inner::Owned(_) => None,
+        }
+    }
+
     /// An owned string without allocations
     pub const fn from_static(s: &'static str) -> Self {
         Str(Inner::Static(s))
@@ -179,6 +192,22 @@ impl<'a> From<Str<'a>> for String {
     }
 }
 
+impl<'a> From<Str<'a>> for Arc<str> {
+    fn from(value: Str<'a>) -> Arc<str> {
+        match value.0 {
+            Inner::Static(s) => s.into(),
+            Inner::Borrowed(s) => s.into(),
+            Inner::Owned(s) => s,
+        }
+    }
+}
+
+impl<'a> From<&Str<'a>> for Arc<str> {
+    fn from(value: &Str<'a>) -> Arc<str> {
+        value.clone().into()
+    }
+}
+
 impl<'a> From<&'a Str<'_>> for &'a str {
     fn from(value: &'a Str<'_>) -> &'a str {
         value.as_str()
@@ -219,6 +248,8 @@ impl<'a> std::fmt::Display for Str<'a> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::Str;
 
     #[test]
@@ -228,6 +259,18 @@ mod tests {
         assert_eq!(v.as_str(), "value");
     }
 
+    #[test]
+    fn arc_str() {
+        let arc: Arc<str> = Arc::from("value");
+        let v = Str::from(arc.clone());
+        let shared = Arc::<str>::from(&v);
+        assert!(Arc::ptr_eq(&arc, &shared));
+        assert!(Arc::ptr_eq(&arc, &Arc::from(v)));
+
+        let v = Str::from("value");
+        assert_eq!(&*Arc::<str>::from(v), "value");
+    }
+
     #[test]
     fn test_ordering() {
         let first = Str::from("a".to_string());
@@ -39,6 +39,18 @@ impl<'a> StructureBuilder<'a> {
         self.append_field(Value::new(field))
     }
 
+    /// Append `field` to `self`.
+    ///
+    /// Same as `add_field`.
+    #[allow(clippy::should_implement_trait)]
+    #[must_use]
+    pub fn add<T>(self, field: T) -> Self
+    where
+        T: DynamicType + Into<Value<'a>>,
+    {
+        self.add_field(field)
+    }
+
     /// Append `field` to `self`.
     ///
     /// Identical to `add_field`, except the field must be in the form of a `Value`.
@@ -164,11 +176,32 @@ pub struct Structure<'a> {
 assert_impl_all!(Structure<'_>: Send, Sync, Unpin);
 
 impl<'a> Structure<'a> {
+    /// Create a [`StructureBuilder`].
+    ///
+    /// Same as `StructureBuilder::new()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Structure;
+    ///
+    /// let s = Structure::builder().add(1u32).add("x").build();
+    /// assert_eq!(s.signature(), "(us)");
+    /// ```
+    pub fn builder() -> StructureBuilder<'a> {
+        StructureBuilder::new()
+    }
+
     /// Get a reference to all the fields of `self`.
     pub fn fields(&self) -> &[Value<'a>] {
         &self.fields
     }
 
+    /// An iterator over references to the fields.
+    pub fn iter(&self) -> std::slice::Iter<'_, Value<'a>> {
+        self.fields.iter()
+    }
+
     /// Converts `self` to a `Vec` containing all its fields.
     pub fn into_fields(self) -> Vec<Value<'a>> {
         self.fields
@@ -217,6 +250,10 @@ impl<'a> Structure<'a> {
 
 impl Display for Structure<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return crate::pretty::structure_pretty_fmt(self, f, 0);
+        }
+
         structure_display_fmt(self, f, true)
     }
 }
@@ -250,6 +287,24 @@ pub(crate) fn structure_display_fmt(
     f.write_char(')')
 }
 
+impl<'a> IntoIterator for Structure<'a> {
+    type Item = Value<'a>;
+    type IntoIter = std::vec::IntoIter<Value<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fields.into_iter()
+    }
+}
+
+impl<'i, 'a> IntoIterator for &'i Structure<'a> {
+    type Item = &'i Value<'a>;
+    type IntoIter = std::slice::Iter<'i, Value<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<'a> Default for Structure<'a> {
     fn default() -> Self {
         let signature = Signature::from_static_str_unchecked("()");
@@ -369,7 +424,7 @@ macro_rules! tuple_impls {
                 type Error = crate::Error;
 
                 fn try_from(v: OwnedValue) -> core::result::Result<Self, Self::Error> {
-                    Self::try_from(Value::from(v))
+                    Self::try_from(v.try_into_inner()?)
                 }
             }
         )+
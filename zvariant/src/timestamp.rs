@@ -0,0 +1,236 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    ops::{Deref, DerefMut},
+    time::{Duration, SystemTime},
+};
+
+use crate::{Error, OwnedValue, Signature, Type, Value};
+
+macro_rules! u64_time_wrapper {
+    (
+        $(#[$attr:meta])*
+        $name:ident($inner:ty),
+        $to_u64:expr,
+        $from_u64:expr,
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(pub $inner);
+
+        impl $name {
+            fn as_u64(&self) -> Result<u64, Error> {
+                #[allow(clippy::redundant_closure_call)]
+                ($to_u64)(&self.0)
+            }
+
+            fn from_u64(value: u64) -> Result<Self, Error> {
+                #[allow(clippy::redundant_closure_call)]
+                ($from_u64)(value).map(Self)
+            }
+        }
+
+        impl Type for $name {
+            #[inline]
+            fn signature() -> Signature<'static> {
+                u64::signature()
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.as_u64()
+                    .map_err(serde::ser::Error::custom)?
+                    .serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let value = u64::deserialize(deserializer)?;
+
+                Self::from_u64(value).map_err(de::Error::custom)
+            }
+        }
+
+        impl Deref for $name {
+            type Target = $inner;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl DerefMut for $name {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl From<$inner> for $name {
+            fn from(value: $inner) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for $inner {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl TryFrom<$name> for Value<'_> {
+            type Error = Error;
+
+            fn try_from(value: $name) -> Result<Self, Error> {
+                value.as_u64().map(Value::U64)
+            }
+        }
+
+        impl TryFrom<$name> for OwnedValue {
+            type Error = Error;
+
+            fn try_from(value: $name) -> Result<Self, Error> {
+                Value::try_from(value)?.try_into()
+            }
+        }
+
+        impl TryFrom<Value<'_>> for $name {
+            type Error = Error;
+
+            fn try_from(value: Value<'_>) -> Result<Self, Error> {
+                Self::from_u64(value.downcast()?)
+            }
+        }
+
+        impl TryFrom<&Value<'_>> for $name {
+            type Error = Error;
+
+            fn try_from(value: &Value<'_>) -> Result<Self, Error> {
+                Self::from_u64(*value.downcast_ref::<&u64>()?)
+            }
+        }
+
+        impl TryFrom<OwnedValue> for $name {
+            type Error = Error;
+
+            fn try_from(value: OwnedValue) -> Result<Self, Error> {
+                Self::try_from(&*value)
+            }
+        }
+    };
+}
+
+u64_time_wrapper! {
+    /// A [`SystemTime`] encoded as the number of microseconds since the UNIX epoch (`t`).
+    ///
+    /// This is the representation used by systemd and many other D-Bus services for timestamps
+    /// (typically in properties with a `USec` or `Timestamp` suffix). By default, [`SystemTime`] is
+    /// encoded as a `(tu)` structure of seconds and nanoseconds instead.
+    ///
+    /// Serialization fails for times before the UNIX epoch or too far in the future. Precision
+    /// beyond microseconds is lost.
+    ///
+    /// With the `chrono` and `time` features enabled, `chrono::DateTime` and
+    /// `time::OffsetDateTime` can be converted to and from this type, for encoding them as `t`
+    /// instead of their default (string or tuple) representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use zvariant::{serialized::Context, to_bytes, UsecTimestamp, LE};
+    ///
+    /// let ctxt = Context::new_dbus(LE, 0);
+    /// let time = UsecTimestamp(SystemTime::UNIX_EPOCH + Duration::from_micros(42));
+    /// let encoded = to_bytes(ctxt, &time).unwrap();
+    /// assert_eq!(encoded.bytes(), &[42, 0, 0, 0, 0, 0, 0, 0]);
+    /// let decoded: UsecTimestamp = encoded.deserialize().unwrap().0;
+    /// assert_eq!(decoded, time);
+    /// ```
+    UsecTimestamp(SystemTime),
+    |time: &SystemTime| {
+        time.duration_since(SystemTime::UNIX_EPOCH)
+            .map_err(|_| Error::Message("time is before the UNIX epoch".into()))
+            .and_then(|d| duration_as_micros(&d))
+    },
+    |usec| {
+        SystemTime::UNIX_EPOCH
+            .checked_add(Duration::from_micros(usec))
+            .ok_or_else(|| Error::Message("timestamp out of range".into()))
+    },
+}
+
+u64_time_wrapper! {
+    /// A [`Duration`] encoded as a number of microseconds (`t`).
+    ///
+    /// By default, [`Duration`] is encoded as a `(tu)` structure of seconds and nanoseconds.
+    /// Serialization fails for durations that don't fit in an `u64` of microseconds. Precision
+    /// beyond microseconds is lost.
+    UsecDuration(Duration),
+    duration_as_micros,
+    |usec| Ok::<_, Error>(Duration::from_micros(usec)),
+}
+
+u64_time_wrapper! {
+    /// A [`Duration`] encoded as a number of whole seconds (`t`).
+    ///
+    /// By default, [`Duration`] is encoded as a `(tu)` structure of seconds and nanoseconds.
+    /// Precision beyond seconds is lost.
+    SecsDuration(Duration),
+    |duration: &Duration| Ok::<_, Error>(duration.as_secs()),
+    |secs| Ok::<_, Error>(Duration::from_secs(secs)),
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for UsecTimestamp {
+    fn from(value: chrono::DateTime<Tz>) -> Self {
+        let secs = value.timestamp();
+        let nanos = Duration::from_nanos(value.timestamp_subsec_nanos().into());
+        let time = if secs >= 0 {
+            SystemTime::UNIX_EPOCH + Duration::from_secs(secs.unsigned_abs()) + nanos
+        } else {
+            SystemTime::UNIX_EPOCH - Duration::from_secs(secs.unsigned_abs()) + nanos
+        };
+
+        Self(time)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<UsecTimestamp> for chrono::DateTime<chrono::Utc> {
+    type Error = Error;
+
+    fn try_from(value: UsecTimestamp) -> Result<Self, Error> {
+        i64::try_from(value.as_u64()?)
+            .ok()
+            .and_then(chrono::DateTime::from_timestamp_micros)
+            .ok_or_else(|| Error::Message("timestamp out of range".into()))
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for UsecTimestamp {
+    fn from(value: time::OffsetDateTime) -> Self {
+        Self(value.into())
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<UsecTimestamp> for time::OffsetDateTime {
+    fn from(value: UsecTimestamp) -> Self {
+        value.0.into()
+    }
+}
+
+fn duration_as_micros(duration: &Duration) -> Result<u64, Error> {
+    duration
+        .as_micros()
+        .try_into()
+        .map_err(|_| Error::Message("duration out of range".into()))
+}
@@ -97,7 +97,7 @@ where
 
 impl<'de, T> DynamicDeserialize<'de> for T
 where
-    T: Type + ?Sized + Deserialize<'de>,
+    T: Type + Deserialize<'de>,
 {
     type Deserializer = PhantomData<T>;
 
@@ -106,37 +106,17 @@ where
         S: TryInto<Signature<'de>>,
         S::Error: Into<zvariant::Error>,
     {
-        let mut expected = <T as Type>::signature();
+        let expected = <T as Type>::signature();
         let original = signature.try_into().map_err(Into::into)?;
 
-        if original == expected {
-            return Ok(PhantomData);
-        }
-
-        let mut signature = original.as_ref();
-        while expected.len() < signature.len()
-            && signature.starts_with(STRUCT_SIG_START_CHAR)
-            && signature.ends_with(STRUCT_SIG_END_CHAR)
-        {
-            signature = signature.slice(1..signature.len() - 1);
-        }
-
-        while signature.len() < expected.len()
-            && expected.starts_with(STRUCT_SIG_START_CHAR)
-            && expected.ends_with(STRUCT_SIG_END_CHAR)
-        {
-            expected = expected.slice(1..expected.len() - 1);
-        }
-
-        if signature == expected {
-            Ok(PhantomData)
-        } else {
-            let expected = <T as Type>::signature();
-            Err(zvariant::Error::SignatureMismatch(
-                original.to_owned(),
-                format!("`{expected}`"),
-            ))
-        }
+        crate::signatures_match(&expected, &original)
+            .map(|_| PhantomData)
+            .map_err(|mismatch| {
+                zvariant::Error::SignatureMismatch(
+                    original.to_owned(),
+                    format!("`{expected}` ({mismatch})"),
+                )
+            })
     }
 }
 
@@ -285,23 +265,16 @@ tuple_impls! {
 
 ////////////////////////////////////////////////////////////////////////////////
 
-// Arrays are serialized as tuples/structs by Serde so we treat them as such too even though
-// it's very strange. Slices and arrayvec::ArrayVec can be used anyway so I guess it's no big
-// deal.
+// Arrays are serialized as tuples by Serde but they're arrays in the D-Bus sense, so the
+// serializers encode tuples as arrays if the signature says so. The length is checked on
+// deserialization.
 impl<T, const N: usize> Type for [T; N]
 where
     T: Type,
 {
-    #[allow(clippy::reversed_empty_ranges)]
+    #[inline]
     fn signature() -> Signature<'static> {
-        let mut sig = String::with_capacity(255);
-        sig.push(STRUCT_SIG_START_CHAR);
-        for _ in 0..N {
-            sig.push_str(T::signature().as_str());
-        }
-        sig.push(STRUCT_SIG_END_CHAR);
-
-        Signature::from_string_unchecked(sig)
+        <[T]>::signature()
     }
 }
 
@@ -314,6 +287,9 @@ use std::{
     time::SystemTime,
 };
 
+#[cfg(feature = "indexmap")]
+use indexmap::IndexMap;
+
 macro_rules! map_impl {
     ($ty:ident < K $(: $kbound1:ident $(+ $kbound2:ident)*)*, V $(, $typaram:ident : $bound:ident)* >) => {
         impl<K, V $(, $typaram)*> Type for $ty<K, V $(, $typaram)*>
@@ -332,13 +308,21 @@ macro_rules! map_impl {
 
 map_impl!(BTreeMap<K: Ord, V>);
 map_impl!(HashMap<K: Eq + Hash, V, H: BuildHasher>);
+#[cfg(feature = "indexmap")]
+map_impl!(IndexMap<K: Eq + Hash, V, H: BuildHasher>);
 
+/// Encoded as seconds and nanoseconds, the same as serde's default representation. Use
+/// [`UsecDuration`](crate::UsecDuration) or [`SecsDuration`](crate::SecsDuration) for the `t`
+/// representations commonly used in D-Bus APIs.
 impl Type for Duration {
     fn signature() -> Signature<'static> {
         <(u64, u32)>::signature()
     }
 }
 
+/// Encoded as seconds and nanoseconds since the UNIX epoch, the same as serde's default
+/// representation. Use [`UsecTimestamp`](crate::UsecTimestamp) for the `t` representation commonly
+/// used in D-Bus APIs.
 impl Type for SystemTime {
     #[inline]
     fn signature() -> Signature<'static> {
@@ -34,6 +34,18 @@ pub const DICT_ENTRY_SIG_START_STR: &str = "{";
 /// creation.
 pub const DICT_ENTRY_SIG_END_STR: &str = "}";
 pub(crate) const DICT_ENTRY_ALIGNMENT_DBUS: usize = 8;
+
+/// The basic types generated for the `arbitrary` & `proptest` support. File descriptors are left
+/// out since they can't be made up.
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+pub(crate) const GENERATED_BASIC_TYPES: &[char] =
+    &['y', 'b', 'n', 'q', 'i', 'u', 'x', 't', 'd', 's', 'o', 'g'];
+/// The maximum nesting depth of generated container types.
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+pub(crate) const GENERATED_MAX_DEPTH: u32 = 3;
+/// The maximum number of elements or fields of generated container values.
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+pub(crate) const GENERATED_MAX_LEN: usize = 3;
 /// The VARIANT type signature. Provided for manual signature creation.
 pub const VARIANT_SIGNATURE_CHAR: char = 'v';
 /// The VARIANT type signature, as a string. Provided for manual signature creation.
@@ -74,7 +74,41 @@ use crate::Fd;
 /// );
 /// ```
 ///
+/// # Display
+///
+/// Values are displayed in the [GVariant text format]. For logs of D-Bus payloads, the alternate
+/// form (`{:#}`) is often more readable: it spans multiple lines, annotates every value with its
+/// type and shows the indices of array elements and structure fields.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use zvariant::Value;
+///
+/// let v = Value::new((42u32, HashMap::from([("Muted", Value::new(true))]), vec!["a", "b"]));
+/// assert_eq!(
+///     format!("{v:#}"),
+///     r#"struct (ua{sv}as) {
+///     [0] uint32 42
+///     [1] dict a{sv} {
+///         string "Muted" => variant boolean true
+///     }
+///     [2] array as [
+///         [0] string "a"
+///         [1] string "b"
+///     ]
+/// }"#,
+/// );
+/// ```
+///
+/// # Cloning
+///
+/// `Value` doesn't implement [`Clone`] since it can contain file descriptors, whose duplication
+/// can fail. Use [`Value::try_clone`] instead, which duplicates owned file descriptors and reports
+/// any failure to do so. If cheap clones are needed, convert the value into an [`OwnedValue`],
+/// whose `clone` shares the value (including the file descriptors) instead of duplicating it.
+///
 /// [D-Bus specification]: https://dbus.freedesktop.org/doc/dbus-specification.html#container-types
+/// [GVariant text format]: https://docs.gtk.org/glib/gvariant-text-format.html
 #[derive(Debug, PartialEq, PartialOrd)]
 pub enum Value<'a> {
     // Simple types
@@ -217,7 +251,7 @@ impl<'a> Value<'a> {
     /// This method can currently only fail on Unix platforms for [`Value::Fd`] variant. This
     /// happens when the current process exceeds the maximum number of open file descriptors.
     pub fn try_to_owned(&self) -> crate::Result<OwnedValue> {
-        Ok(OwnedValue(match self {
+        Ok(OwnedValue::new(match self {
             Value::U8(v) => Value::U8(*v),
             Value::Bool(v) => Value::Bool(*v),
             Value::I16(v) => Value::I16(*v),
@@ -232,7 +266,7 @@ impl<'a> Value<'a> {
             Value::ObjectPath(v) => Value::ObjectPath(v.to_owned()),
             Value::Value(v) => {
                 let o = OwnedValue::try_from(&**v)?;
-                Value::Value(Box::new(o.into_inner()))
+                Value::Value(Box::new(o.try_into_inner()?))
             }
 
             Value::Array(v) => Value::Array(v.try_to_owned()?),
@@ -280,7 +314,7 @@ impl<'a> Value<'a> {
     ///
     /// This method can currently only fail on Unix platforms for [`Value::Fd`] variant containing
     /// an [`Fd::Owned`] variant. This happens when the current process exceeds the maximum number
-    /// of open file descriptors.
+    /// of open file descriptors (`EMFILE`). Borrowed file descriptors are copied as is.
     pub fn try_clone(&self) -> crate::Result<Self> {
         Ok(match self {
             Value::U8(v) => Value::U8(*v),
@@ -389,7 +423,7 @@ impl<'a> Value<'a> {
     /// [`From<Value>`]: https://doc.rust-lang.org/std/convert/trait.From.html
     pub fn downcast<T>(self) -> Result<T, crate::Error>
     where
-        T: ?Sized + TryFrom<Value<'a>>,
+        T: TryFrom<Value<'a>>,
         <T as TryFrom<Value<'a>>>::Error: Into<crate::Error>,
     {
         if let Value::Value(v) = self {
@@ -439,7 +473,7 @@ impl<'a> Value<'a> {
     /// [`downcast`]: enum.Value.html#method.downcast
     pub fn downcast_ref<T>(&'a self) -> Result<T, crate::Error>
     where
-        T: ?Sized + TryFrom<&'a Value<'a>>,
+        T: TryFrom<&'a Value<'a>>,
         <T as TryFrom<&'a Value<'a>>>::Error: Into<crate::Error>,
     {
         if let Value::Value(v) = self {
@@ -453,6 +487,10 @@ impl<'a> Value<'a> {
 
 impl Display for Value<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return crate::pretty::value_pretty_fmt(self, f, 0);
+        }
+
         value_display_fmt(self, f, true)
     }
 }
@@ -579,7 +617,13 @@ impl<'de: 'a, 'a> Deserialize<'de> for Value<'a> {
     {
         let visitor = ValueVisitor;
 
-        deserializer.deserialize_any(visitor)
+        // Deserializing as the structure `Value` serializes to, rather than through
+        // `deserialize_any`, allows deserializing a `Value` from a (non-variant) `Value` as well.
+        deserializer.deserialize_struct(
+            "zvariant::Value",
+            &["zvariant::Value::Signature", "zvariant::Value::Value"],
+            visitor,
+        )
     }
 }
 
@@ -798,8 +842,7 @@ where
         })? {
             #[cfg(unix)]
             b'h' => {
-                // SAFETY: The `'de` lifetimes will ensure the borrow won't outlive the raw FD.
-                let fd = unsafe { std::os::fd::BorrowedFd::borrow_raw(value) };
+                let fd = crate::fd::borrow_raw(value)?;
                 Fd::Borrowed(fd).into()
             }
             _ => value.into(),
@@ -1145,4 +1188,51 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn value_pretty_display() {
+        let v = Value::new((
+            255_u8,
+            -1_i16,
+            1.0_f64,
+            Value::new(Value::new("hi")),
+            Vec::<u32>::new(),
+            HashMap::<&str, u32>::new(),
+            ((0_u8,), ObjectPath::from_static_str("/").unwrap()),
+            vec![HashMap::from([(
+                1_i64,
+                vec![Signature::from_static_str("s").unwrap()],
+            )])],
+        ));
+        assert_eq!(
+            format!("{v:#}"),
+            r#"struct (yndvaua{su}((y)o)aa{xag}) {
+    [0] byte 0xff
+    [1] int16 -1
+    [2] double 1.0
+    [3] variant variant string "hi"
+    [4] array au []
+    [5] dict a{su} {}
+    [6] struct ((y)o) {
+        [0] struct (y) {
+            [0] byte 0x00
+        }
+        [1] object_path "/"
+    }
+    [7] array aa{xag} [
+        [0] dict a{xag} {
+            int64 1 => array ag [
+                [0] signature "s"
+            ]
+        }
+    ]
+}"#,
+        );
+
+        #[cfg(feature = "gvariant")]
+        assert_eq!(
+            format!("{:#}", Value::new((Some(7_u16), None::<&str>))),
+            "struct (mqms) {\n    [0] maybe mq just uint16 7\n    [1] maybe ms nothing\n}",
+        );
+    }
 }
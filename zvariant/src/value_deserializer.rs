@@ -0,0 +1,368 @@
+use serde::de::{
+    value::{MapDeserializer, SeqDeserializer},
+    DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, VariantAccess, Visitor,
+};
+
+#[cfg(unix)]
+use std::os::fd::AsRawFd;
+
+use crate::{Error, OwnedValue, Result, Str, Value};
+
+/// Deserialize a `T: Deserialize` directly from a [`Value`](enum@Value), without encoding it first.
+///
+/// This mostly follows how the D-Bus encoding of the value would be deserialized:
+///
+/// * Variants (`v`) are transparent and deserialize as the value they contain.
+///   [`DeserializeValue`](crate::DeserializeValue) can also deserialize any value as a variant.
+/// * Arrays and structures deserialize as sequences, and dictionaries as maps. Hence structs can be
+///   deserialized from either a structure or a dictionary (e.g a `a{sv}`).
+/// * `Option<T>` deserializes as `None` from an empty GVariant maybe value and as `Some` from
+///   everything else.
+/// * Enums deserialize from a string (unit variants by name), an integer (unit variants by index)
+///   or a structure of the variant index and its content.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use serde::Deserialize;
+/// use zvariant::{Value, Type};
+///
+/// #[derive(Deserialize, Type, Debug, PartialEq)]
+/// struct Device {
+///     name: String,
+///     id: u32,
+/// }
+///
+/// let mut dict = HashMap::new();
+/// dict.insert("name", Value::from("eth0"));
+/// dict.insert("id", Value::from(2u32));
+/// let value = Value::from(dict);
+///
+/// let device = Device::deserialize(value).unwrap();
+/// assert_eq!(device, Device { name: "eth0".into(), id: 2 });
+/// ```
+impl<'de, 'a: 'de> Deserializer<'de> for Value<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::U8(v) => visitor.visit_u8(v),
+            Value::Bool(v) => visitor.visit_bool(v),
+            Value::I16(v) => visitor.visit_i16(v),
+            Value::U16(v) => visitor.visit_u16(v),
+            Value::I32(v) => visitor.visit_i32(v),
+            Value::U32(v) => visitor.visit_u32(v),
+            Value::I64(v) => visitor.visit_i64(v),
+            Value::U64(v) => visitor.visit_u64(v),
+            Value::F64(v) => visitor.visit_f64(v),
+            Value::Str(s) => visit_str(s, visitor),
+            Value::Signature(s) => visitor.visit_str(s.as_str()),
+            Value::ObjectPath(p) => visitor.visit_str(p.as_str()),
+            Value::Value(v) => v.deserialize_any(visitor),
+            Value::Array(a) => {
+                let mut seq = SeqDeserializer::new(a.into_elements().into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+
+                Ok(value)
+            }
+            Value::Dict(d) => {
+                let mut map = MapDeserializer::new(d.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+
+                Ok(value)
+            }
+            Value::Structure(s) => {
+                let mut seq = SeqDeserializer::new(s.into_fields().into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+
+                Ok(value)
+            }
+            #[cfg(feature = "gvariant")]
+            Value::Maybe(m) => match m.into_inner() {
+                Some(v) => visitor.visit_some(v),
+                None => visitor.visit_none(),
+            },
+            #[cfg(unix)]
+            Value::Fd(fd) => visitor.visit_i32(fd.as_raw_fd()),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            #[cfg(feature = "gvariant")]
+            Value::Maybe(_) => self.deserialize_any(visitor),
+            Value::Value(v) => v.deserialize_option(visitor),
+            v => visitor.visit_some(v),
+        }
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Array(a) if a.element_signature() == "y" => {
+                let bytes = a
+                    .into_elements()
+                    .into_iter()
+                    .map(|v| match v {
+                        Value::U8(b) => Ok(b),
+                        _ => Err(Error::IncorrectType),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                visitor.visit_byte_buf(bytes)
+            }
+            Value::Value(v) => v.deserialize_bytes(visitor),
+            v => v.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if name != "zvariant::Value" {
+            return self.deserialize_any(visitor);
+        }
+        if let Value::Value(v) = self {
+            return v.deserialize_struct(name, fields, visitor);
+        }
+
+        // Deserializing a variant, e.g with `DeserializeValue`, so provide the signature of the
+        // value before the value, just like its encoding does.
+        let signature = Value::Signature(self.value_signature().to_owned());
+        let mut seq = SeqDeserializer::new([signature, self].into_iter());
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+
+        Ok(value)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Value(v) => v.deserialize_enum(_name, _variants, visitor),
+            Value::Str(s) => visitor.visit_enum(Enum::Name(s)),
+            Value::U8(i) => visitor.visit_enum(Enum::Index(i.into(), None)),
+            Value::U16(i) => visitor.visit_enum(Enum::Index(i.into(), None)),
+            Value::U32(i) => visitor.visit_enum(Enum::Index(i, None)),
+            Value::Structure(s) => {
+                let mut fields = s.into_fields().into_iter();
+                let index = match fields.next() {
+                    Some(Value::U32(i)) => i,
+                    _ => return Err(Error::IncorrectType),
+                };
+                let content = fields.next().ok_or(Error::IncorrectType)?;
+                if fields.next().is_some() {
+                    return Err(Error::IncorrectType);
+                }
+
+                visitor.visit_enum(Enum::Index(index, Some(content)))
+            }
+            _ => Err(Error::IncorrectType),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string unit unit_struct seq tuple
+        tuple_struct map identifier ignored_any
+    }
+}
+
+impl<'de, 'a: 'de> IntoDeserializer<'de, Error> for Value<'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// Same as the [`Deserializer`] implementation of [`Value`](enum@Value).
+impl<'de> Deserializer<'de> for OwnedValue {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.try_into_inner()?.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.try_into_inner()?.deserialize_option(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.try_into_inner()?.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.try_into_inner()?.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.try_into_inner()?
+            .deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.try_into_inner()?
+            .deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.try_into_inner()?
+            .deserialize_enum(name, variants, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string unit unit_struct seq tuple
+        tuple_struct map identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for OwnedValue {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+fn visit_str<'de, 'a: 'de, V>(s: Str<'a>, visitor: V) -> Result<V::Value>
+where
+    V: Visitor<'de>,
+{
+    match s.as_borrowed_str() {
+        Some(s) => visitor.visit_borrowed_str(s),
+        None => visitor.visit_str(s.as_str()),
+    }
+}
+
+enum Enum<'a> {
+    Name(Str<'a>),
+    Index(u32, Option<Value<'a>>),
+}
+
+impl<'de, 'a: 'de> EnumAccess<'de> for Enum<'a> {
+    type Error = Error;
+    type Variant = Variant<'a>;
+
+    fn variant_seed<S>(self, seed: S) -> Result<(S::Value, Self::Variant)>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        match self {
+            Enum::Name(name) => seed
+                .deserialize(Value::Str(name))
+                .map(|variant| (variant, Variant(None))),
+            Enum::Index(index, content) => seed
+                .deserialize(Value::U32(index))
+                .map(|variant| (variant, Variant(content))),
+        }
+    }
+}
+
+struct Variant<'a>(Option<Value<'a>>);
+
+impl<'de, 'a: 'de> VariantAccess<'de> for Variant<'a> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.0.ok_or(Error::IncorrectType)?)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.ok_or(Error::IncorrectType)?.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.ok_or(Error::IncorrectType)?.deserialize_any(visitor)
+    }
+}
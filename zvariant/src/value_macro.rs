@@ -0,0 +1,116 @@
+use crate::{Array, Signature, Value};
+
+/// Construct a [`Value`](enum@crate::Value) from a JSON-like literal.
+///
+/// This is analogous to `serde_json::json!` and is particularly handy for tests and for building
+/// the `a{sv}` option dictionaries ubiquitous in D-Bus APIs:
+///
+/// * `{ "key": value, ... }` creates a dictionary of signature `a{sv}`. Keys must be string
+///   literals.
+/// * `[value, ...]` creates an array. If all the elements are of the same type, the array is of
+///   that type (e.g `[1u32, 2u32]` is an `au`). Otherwise, or if there are no elements, the
+///   elements are wrapped into variants, giving an `av`. Note that each element is typed on its
+///   own, so `[1u32, 2]` is an `av`.
+/// * Anything else is taken as an expression and converted into a `Value` through [`From`].
+///
+/// Dictionaries and arrays can be nested arbitrarily. The resulting value may borrow from the
+/// expressions used. Convert it into an [`OwnedValue`](crate::OwnedValue) if you need an owned
+/// value.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{value, Value};
+///
+/// let v = value!({
+///     "key": 1u32,
+///     "list": [1, 2, 3],
+///     "nested": { "x": true },
+/// });
+/// assert_eq!(v.value_signature(), "a{sv}");
+///
+/// let v = value!([1u32, 2u32, 3u32]);
+/// assert_eq!(v.value_signature(), "au");
+/// let v = value!(["hello", 42u8]);
+/// assert_eq!(v.value_signature(), "av");
+///
+/// let name = String::from("foo");
+/// let v = value!(name.as_str());
+/// assert_eq!(v, Value::from("foo"));
+/// ```
+#[macro_export]
+macro_rules! value {
+    // Dictionary entries.
+    (@dict $dict:ident) => {};
+    (@dict $dict:ident $key:literal : { $($v:tt)* } $(, $($rest:tt)*)?) => {
+        $dict.push($key, $crate::value!({ $($v)* }));
+        $crate::value!(@dict $dict $($($rest)*)?);
+    };
+    (@dict $dict:ident $key:literal : [ $($v:tt)* ] $(, $($rest:tt)*)?) => {
+        $dict.push($key, $crate::value!([ $($v)* ]));
+        $crate::value!(@dict $dict $($($rest)*)?);
+    };
+    (@dict $dict:ident $key:literal : $v:expr $(, $($rest:tt)*)?) => {
+        $dict.push($key, $crate::value!($v));
+        $crate::value!(@dict $dict $($($rest)*)?);
+    };
+
+    // Array elements.
+    (@array [$($elements:expr,)*]) => {
+        $crate::export::array_value(::std::vec![$($elements,)*])
+    };
+    (@array [$($elements:expr,)*] { $($v:tt)* } $(, $($rest:tt)*)?) => {
+        $crate::value!(@array [$($elements,)* $crate::value!({ $($v)* }),] $($($rest)*)?)
+    };
+    (@array [$($elements:expr,)*] [ $($v:tt)* ] $(, $($rest:tt)*)?) => {
+        $crate::value!(@array [$($elements,)* $crate::value!([ $($v)* ]),] $($($rest)*)?)
+    };
+    (@array [$($elements:expr,)*] $v:expr $(, $($rest:tt)*)?) => {
+        $crate::value!(@array [$($elements,)* $crate::value!($v),] $($($rest)*)?)
+    };
+
+    ({ $($tt:tt)* }) => {{
+        #[allow(unused_mut)]
+        let mut dict = $crate::Dict::builder::<&str, $crate::Value<'_>>();
+        $crate::value!(@dict dict $($tt)*);
+        $crate::Value::from(dict.build())
+    }};
+    ([ $($tt:tt)* ]) => {
+        $crate::value!(@array [] $($tt)*)
+    };
+    ($e:expr) => {
+        $crate::Value::from($e)
+    };
+}
+
+// Create an array value from `elements`, for the `value!` macro.
+#[doc(hidden)]
+pub fn array_value(elements: Vec<Value<'_>>) -> Value<'_> {
+    let homogeneous = elements
+        .first()
+        .map(|first| {
+            let signature = first.value_signature();
+            elements.iter().all(|e| e.value_signature() == signature)
+        })
+        .unwrap_or(false);
+    let (element_signature, elements): (_, Vec<_>) = if homogeneous {
+        (elements[0].value_signature().to_owned(), elements)
+    } else {
+        let elements = elements
+            .into_iter()
+            .map(|e| Value::Value(Box::new(e)))
+            .collect();
+
+        (Signature::from_static_str_unchecked("v"), elements)
+    };
+
+    let mut array = Array::new(element_signature);
+    for element in elements {
+        // The signatures are guaranteed to match.
+        array
+            .append(element)
+            .expect("array element signature mismatch");
+    }
+
+    Value::Array(array)
+}
@@ -0,0 +1,203 @@
+use serde::{Deserialize, Serialize};
+use static_assertions::assert_impl_all;
+use std::collections::{hash_map, HashMap};
+
+use crate::{Dict, Error, OwnedValue, Signature, Type, Value};
+
+/// A dictionary of string keys and variant values (`a{sv}`).
+///
+/// These "vardicts" are ubiquitous in D-Bus APIs, be it for properties, options or hints. While a
+/// `HashMap<String, OwnedValue>` does the job, `VarDict` makes the common operations on it less
+/// verbose: values are looked up as their concrete type and inserted from anything that converts
+/// into a [`Value`](enum@Value).
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{serialized::Context, to_bytes, VarDict, LE};
+///
+/// let hints = VarDict::new()
+///     .with("Volume", 42u32)?
+///     .with("Muted", false)?
+///     .with("Sink", "speakers")?;
+///
+/// let ctxt = Context::new_dbus(LE, 0);
+/// let encoded = to_bytes(ctxt, &hints)?;
+/// let hints: VarDict = encoded.deserialize()?.0;
+///
+/// assert_eq!(hints.get::<u32>("Volume")?, Some(42));
+/// assert_eq!(hints.get::<&str>("Sink")?, Some("speakers"));
+/// assert_eq!(hints.get::<bool>("Brightness")?, None);
+/// // Type mismatch.
+/// assert!(hints.get::<String>("Muted").is_err());
+/// # Ok::<(), zvariant::Error>(())
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VarDict(HashMap<String, OwnedValue>);
+
+assert_impl_all!(VarDict: Send, Sync, Unpin);
+
+impl VarDict {
+    /// Create a new empty `VarDict`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the value for `key`, as a `T`.
+    ///
+    /// Returns `Ok(None)` if there is no entry for `key` and an error if the value isn't a `T`.
+    pub fn get<'a, T>(&'a self, key: &str) -> Result<Option<T>, Error>
+    where
+        T: TryFrom<&'a Value<'a>>,
+        <T as TryFrom<&'a Value<'a>>>::Error: Into<Error>,
+    {
+        self.0.get(key).map(|v| v.downcast_ref()).transpose()
+    }
+
+    /// Get the value for `key`.
+    pub fn get_value(&self, key: &str) -> Option<&OwnedValue> {
+        self.0.get(key)
+    }
+
+    /// Insert the entry of `key` and `value`, returning the previous value for `key`, if any.
+    ///
+    /// # Errors
+    ///
+    /// Converting `value` into an [`OwnedValue`] can only fail on Unix platforms, for values
+    /// containing file descriptors that fail to be duplicated.
+    pub fn insert<'v, V>(
+        &mut self,
+        key: impl Into<String>,
+        value: V,
+    ) -> Result<Option<OwnedValue>, Error>
+    where
+        V: Into<Value<'v>>,
+    {
+        let value = value.into().try_to_owned()?;
+
+        Ok(self.0.insert(key.into(), value))
+    }
+
+    /// Insert the entry of `key` and `value`.
+    ///
+    /// Same as [`VarDict::insert`], except that this method returns `Self` so that you can use
+    /// the builder pattern to create a vardict.
+    pub fn with<'v, V>(mut self, key: impl Into<String>, value: V) -> Result<Self, Error>
+    where
+        V: Into<Value<'v>>,
+    {
+        self.insert(key, value)?;
+
+        Ok(self)
+    }
+
+    /// Remove the entry for `key`, returning its value, if any.
+    pub fn remove(&mut self, key: &str) -> Option<OwnedValue> {
+        self.0.remove(key)
+    }
+
+    /// Whether there is an entry for `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    /// The number of entries.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the entries, in arbitrary order.
+    pub fn iter(&self) -> hash_map::Iter<'_, String, OwnedValue> {
+        self.0.iter()
+    }
+
+    /// Convert into the underlying `HashMap`.
+    pub fn into_inner(self) -> HashMap<String, OwnedValue> {
+        self.0
+    }
+}
+
+impl Type for VarDict {
+    fn signature() -> Signature<'static> {
+        <HashMap<String, OwnedValue>>::signature()
+    }
+}
+
+impl From<HashMap<String, OwnedValue>> for VarDict {
+    fn from(map: HashMap<String, OwnedValue>) -> Self {
+        Self(map)
+    }
+}
+
+impl From<VarDict> for HashMap<String, OwnedValue> {
+    fn from(dict: VarDict) -> Self {
+        dict.0
+    }
+}
+
+impl<'a> TryFrom<Value<'a>> for VarDict {
+    type Error = Error;
+
+    fn try_from(value: Value<'a>) -> Result<Self, Self::Error> {
+        HashMap::try_from(value).map(Self)
+    }
+}
+
+impl TryFrom<OwnedValue> for VarDict {
+    type Error = Error;
+
+    fn try_from(value: OwnedValue) -> Result<Self, Self::Error> {
+        HashMap::try_from(value).map(Self)
+    }
+}
+
+/// Values shared with other [`OwnedValue`]s are copied, which can only fail on Unix platforms, for
+/// values containing file descriptors.
+impl TryFrom<VarDict> for Value<'_> {
+    type Error = Error;
+
+    fn try_from(dict: VarDict) -> Result<Self, Self::Error> {
+        let mut value = Dict::new(String::signature(), OwnedValue::signature());
+        for (k, v) in dict.0 {
+            value.add(k, v.try_into_inner()?)?;
+        }
+
+        Ok(Value::Dict(value))
+    }
+}
+
+impl FromIterator<(String, OwnedValue)> for VarDict {
+    fn from_iter<I: IntoIterator<Item = (String, OwnedValue)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Extend<(String, OwnedValue)> for VarDict {
+    fn extend<I: IntoIterator<Item = (String, OwnedValue)>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl IntoIterator for VarDict {
+    type Item = (String, OwnedValue);
+    type IntoIter = hash_map::IntoIter<String, OwnedValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a VarDict {
+    type Item = (&'a String, &'a OwnedValue);
+    type IntoIter = hash_map::Iter<'a, String, OwnedValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
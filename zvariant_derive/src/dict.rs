@@ -1,7 +1,7 @@
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote, ToTokens};
 use syn::{punctuated::Punctuated, spanned::Spanned, Data, DeriveInput, Error, Field};
-use zvariant_utils::{case, macros};
+use zvariant_utils::macros;
 
 use crate::utils::*;
 
@@ -15,18 +15,7 @@ fn dict_name_for_field(
     } else {
         let ident = f.ident.as_ref().unwrap().to_string();
 
-        match rename_all_attr {
-            Some("lowercase") => Ok(ident.to_ascii_lowercase()),
-            Some("UPPERCASE") => Ok(ident.to_ascii_uppercase()),
-            Some("PascalCase") => Ok(case::pascal_or_camel_case(&ident, true)),
-            Some("camelCase") => Ok(case::pascal_or_camel_case(&ident, false)),
-            Some("snake_case") => Ok(case::snake_case(&ident)),
-            None => Ok(ident),
-            Some(other) => Err(Error::new(
-                f.span(),
-                format!("invalid `rename_all` attribute value {other}"),
-            )),
-        }
+        rename_identifier(ident, f.span(), rename_all_attr)
     }
 }
 
@@ -41,11 +30,25 @@ pub fn expand_serialize_derive(input: DeriveInput) -> Result<TokenStream, Error>
     let zv = zvariant_path();
     let mut entries = quote! {};
     let mut num_entries: usize = 0;
+    let mut has_flattened = false;
 
     for f in &data.fields {
-        let FieldAttributes { rename } = FieldAttributes::parse(&f.attrs)?;
+        let FieldAttributes {
+            rename, flatten, ..
+        } = FieldAttributes::parse(&f.attrs)?;
 
         let name = &f.ident;
+        if flatten {
+            entries.extend(quote! {
+                #zv::export::serde::ser::Serialize::serialize(
+                    &self.#name,
+                    #zv::export::FlattenSerializer(&mut map),
+                )?;
+            });
+            has_flattened = true;
+
+            continue;
+        }
         let dict_name = dict_name_for_field(f, rename, rename_all.as_deref())?;
 
         let is_option = macros::ty_is_option(&f.ty);
@@ -69,7 +72,13 @@ pub fn expand_serialize_derive(input: DeriveInput) -> Result<TokenStream, Error>
     let generics = input.generics;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let num_entries = num_entries.to_token_stream();
+    // The number of entries of flattened fields is unknown.
+    let num_entries = if has_flattened {
+        quote! { ::std::option::Option::None }
+    } else {
+        let num_entries = num_entries.to_token_stream();
+        quote! { ::std::option::Option::Some(#num_entries) }
+    };
     Ok(quote! {
         #[allow(deprecated)]
         impl #impl_generics #zv::export::serde::ser::Serialize for #name #ty_generics
@@ -82,7 +91,7 @@ pub fn expand_serialize_derive(input: DeriveInput) -> Result<TokenStream, Error>
                 use #zv::export::serde::ser::SerializeMap;
 
                 // zbus doesn't care about number of entries (it would need bytes instead)
-                let mut map = serializer.serialize_map(::std::option::Option::Some(#num_entries))?;
+                let mut map = serializer.serialize_map(#num_entries)?;
                 #entries
                 map.end()
             }
@@ -108,11 +117,27 @@ pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Erro
     let mut req_fields = Vec::new();
     let mut dict_names = Vec::new();
     let mut entries = Vec::new();
+    let mut flattened_fields = Vec::new();
+    let mut all_fields = Vec::new();
 
     for f in &data.fields {
-        let FieldAttributes { rename } = FieldAttributes::parse(&f.attrs)?;
+        let FieldAttributes {
+            rename, flatten, ..
+        } = FieldAttributes::parse(&f.attrs)?;
 
         let name = &f.ident;
+        all_fields.push(name);
+        if flatten {
+            if deny_unknown_fields {
+                return Err(Error::new(
+                    f.span(),
+                    "`flatten` can't be combined with `deny_unknown_fields`",
+                ));
+            }
+            flattened_fields.push(name);
+
+            continue;
+        }
         let dict_name = dict_name_for_field(f, rename, rename_all.as_deref())?;
 
         let is_option = macros::ty_is_option(&f.ty);
@@ -132,7 +157,15 @@ pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Erro
         }
     }
 
-    let fallback = if deny_unknown_fields {
+    let fallback = if !flattened_fields.is_empty() {
+        // Keep the entries of unknown fields for the flattened fields.
+        quote! {
+            unknown => {
+                let value = access.next_value::<#zv::Value<'de>>()?;
+                __flattened.push((unknown, value));
+            }
+        }
+    } else if deny_unknown_fields {
         quote! {
             field => {
                 return ::std::result::Result::Err(
@@ -151,6 +184,11 @@ pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Erro
         }
     };
     entries.push(fallback);
+    let flattened_init = (!flattened_fields.is_empty()).then(|| {
+        quote! {
+            let mut __flattened = ::std::vec::Vec::new();
+        }
+    });
 
     let (_, ty_generics, _) = input.generics.split_for_impl();
     let mut generics = input.generics.clone();
@@ -193,6 +231,7 @@ pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Erro
                         M: #zv::export::serde::de::MapAccess<'de>,
                     {
                         #( let mut #fields = ::std::default::Default::default(); )*
+                        #flattened_init
 
                         // does not check duplicated fields, since those shouldn't exist in stream
                         while let ::std::option::Option::Some(key) = access.next_key::<&str>()? {
@@ -211,7 +250,10 @@ pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Erro
                             );
                         };)*
 
-                        ::std::result::Result::Ok(#name { #(#fields),* })
+                        #(let #flattened_fields =
+                            #zv::export::deserialize_flattened::<_, M::Error>(&__flattened)?;)*
+
+                        ::std::result::Result::Ok(#name { #(#all_fields),* })
                     }
                 }
 
@@ -0,0 +1,351 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    punctuated::Punctuated, spanned::Spanned, Data, DataEnum, DeriveInput, Error, Fields,
+    GenericParam, Ident, Lifetime, LifetimeParam, Variant,
+};
+
+use crate::utils::*;
+
+/// The encoding of a data-carrying enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// A `(uv)` structure of the variant index and the variant's fields.
+    Tagged,
+    /// A `v` of the variant's fields.
+    Untagged,
+    /// A `(sv)` structure of the variant name and the variant's fields.
+    Named,
+}
+
+impl Encoding {
+    /// The encoding selected through the `tagged`, `untagged` or `named` attribute, if any.
+    pub fn from_attrs(attrs: &StructAttributes, span: Span) -> Result<Option<Self>, Error> {
+        match (attrs.tagged, attrs.untagged, attrs.named) {
+            (true, false, false) => Ok(Some(Self::Tagged)),
+            (false, true, false) => Ok(Some(Self::Untagged)),
+            (false, false, true) => Ok(Some(Self::Named)),
+            (false, false, false) => Ok(None),
+            _ => Err(Error::new(
+                span,
+                "`tagged`, `untagged` and `named` attributes are mutually exclusive",
+            )),
+        }
+    }
+
+    pub fn signature(self) -> &'static str {
+        match self {
+            Self::Tagged => "(uv)",
+            Self::Untagged => "v",
+            Self::Named => "(sv)",
+        }
+    }
+}
+
+fn parse_input(input: &DeriveInput) -> Result<(&DataEnum, Encoding, Vec<String>), Error> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => return Err(Error::new(input.span(), "only enums supported")),
+    };
+    let attrs = StructAttributes::parse(&input.attrs)?;
+    let encoding = Encoding::from_attrs(&attrs, input.span())?.ok_or_else(|| {
+        Error::new(
+            input.span(),
+            "one of `#[zvariant(tagged)]`, `#[zvariant(untagged)]` or `#[zvariant(named)]` \
+             attributes is required",
+        )
+    })?;
+    if encoding == Encoding::Untagged {
+        if let Some(variant) = data
+            .variants
+            .iter()
+            .find(|v| matches!(v.fields, Fields::Unit))
+        {
+            return Err(Error::new(
+                variant.span(),
+                "unit variants are not supported by the `untagged` encoding",
+            ));
+        }
+    }
+
+    let names = data
+        .variants
+        .iter()
+        .map(|v| rename_identifier(v.ident.to_string(), v.span(), attrs.rename_all.as_deref()))
+        .collect::<Result<_, _>>()?;
+
+    Ok((data, encoding, names))
+}
+
+// The bindings for the fields of `variant`, for use in patterns.
+fn field_bindings(variant: &Variant) -> Vec<Ident> {
+    variant
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| match &f.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("__field{}", i),
+        })
+        .collect()
+}
+
+// The pattern matching `variant` of enum `name` and binding its fields.
+fn variant_pattern(name: &Ident, variant: &Variant, bindings: &[Ident]) -> TokenStream {
+    let ident = &variant.ident;
+    match &variant.fields {
+        Fields::Unit => quote! { #name::#ident },
+        Fields::Unnamed(_) => quote! { #name::#ident(#(#bindings),*) },
+        Fields::Named(_) => quote! { #name::#ident { #(#bindings),* } },
+    }
+}
+
+// Whether the fields of `variant` are carried as is, rather than in a structure.
+fn is_newtype(variant: &Variant) -> bool {
+    matches!(&variant.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1)
+}
+
+pub fn expand_serialize_derive(input: DeriveInput) -> Result<TokenStream, Error> {
+    let (data, encoding, names) = parse_input(&input)?;
+    let name = &input.ident;
+    let zv = zvariant_path();
+
+    let variants = data.variants.iter().zip(&names).enumerate();
+    let arms = variants.map(|(index, (variant, variant_name))| {
+        let bindings = field_bindings(variant);
+        let pattern = variant_pattern(name, variant, &bindings);
+        // Unit variants carry a byte, just like empty structures.
+        let fields = match &variant.fields {
+            Fields::Unit => quote! { &0u8 },
+            _ if is_newtype(variant) => quote! { #(#bindings)* },
+            _ => quote! { &(#(#bindings,)*) },
+        };
+        let index = index as u32;
+
+        match encoding {
+            Encoding::Tagged => quote! {
+                #pattern => {
+                    let mut structure = serializer.serialize_struct(::std::stringify!(#name), 2)?;
+                    structure.serialize_field("tag", &#index)?;
+                    structure.serialize_field("value", &#zv::SerializeValue(#fields))?;
+                    structure.end()
+                }
+            },
+            Encoding::Untagged => quote! {
+                #pattern => #zv::export::serde::ser::Serialize::serialize(
+                    &#zv::SerializeValue(#fields),
+                    serializer,
+                ),
+            },
+            Encoding::Named => quote! {
+                #pattern => {
+                    let mut structure = serializer.serialize_struct(::std::stringify!(#name), 2)?;
+                    structure.serialize_field("name", #variant_name)?;
+                    structure.serialize_field("value", &#zv::SerializeValue(#fields))?;
+                    structure.end()
+                }
+            },
+        }
+    });
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[allow(deprecated)]
+        impl #impl_generics #zv::export::serde::ser::Serialize for #name #ty_generics
+        #where_clause
+        {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: #zv::export::serde::ser::Serializer,
+            {
+                #[allow(unused_imports)]
+                use #zv::export::serde::ser::SerializeStruct;
+
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Error> {
+    let (data, encoding, names) = parse_input(&input)?;
+    let name = &input.ident;
+    let visitor = format_ident!("{}Visitor", name);
+    let zv = zvariant_path();
+
+    let mut arms = Vec::with_capacity(data.variants.len());
+    for (index, (variant, variant_name)) in data.variants.iter().zip(&names).enumerate() {
+        let bindings = field_bindings(variant);
+        let pattern = variant_pattern(name, variant, &bindings);
+        let field_types: Vec<_> = variant.fields.iter().map(|f| &f.ty).collect();
+        let (fields_type, fields_pattern) = match &variant.fields {
+            Fields::Unit => (quote! { u8 }, quote! { _ }),
+            _ if is_newtype(variant) => (quote! { #(#field_types)* }, quote! { #(#bindings)* }),
+            _ => (quote! { (#(#field_types,)*) }, quote! { (#(#bindings,)*) }),
+        };
+        let index = index as u32;
+
+        arms.push(match encoding {
+            Encoding::Tagged => quote! {
+                #index => {
+                    let #fields_pattern = seq
+                        .next_element::<#zv::DeserializeValue<'de, #fields_type>>()?
+                        .ok_or_else(|| {
+                            <A::Error as #zv::export::serde::de::Error>::invalid_length(1, &self)
+                        })?
+                        .0;
+
+                    ::std::result::Result::Ok(#pattern)
+                }
+            },
+            Encoding::Named => quote! {
+                #variant_name => {
+                    let #fields_pattern = seq
+                        .next_element::<#zv::DeserializeValue<'de, #fields_type>>()?
+                        .ok_or_else(|| {
+                            <A::Error as #zv::export::serde::de::Error>::invalid_length(1, &self)
+                        })?
+                        .0;
+
+                    ::std::result::Result::Ok(#pattern)
+                }
+            },
+            Encoding::Untagged => quote! {
+                if signature == <#fields_type as #zv::Type>::signature() {
+                    let #fields_pattern = seq.next_element::<#fields_type>()?.ok_or_else(|| {
+                        <A::Error as #zv::export::serde::de::Error>::invalid_length(1, &self)
+                    })?;
+
+                    return ::std::result::Result::Ok(#pattern);
+                }
+            },
+        });
+    }
+
+    let (visit_seq, deserialize) = match encoding {
+        Encoding::Tagged => (
+            quote! {
+                let tag: u32 = seq.next_element()?.ok_or_else(|| {
+                    <A::Error as #zv::export::serde::de::Error>::invalid_length(0, &self)
+                })?;
+                match tag {
+                    #(#arms)*
+                    tag => ::std::result::Result::Err(
+                        <A::Error as #zv::export::serde::de::Error>::invalid_value(
+                            #zv::export::serde::de::Unexpected::Unsigned(tag.into()),
+                            &"a variant index",
+                        ),
+                    ),
+                }
+            },
+            quote! {
+                deserializer.deserialize_struct(
+                    ::std::stringify!(#name),
+                    &["tag", "value"],
+                    #visitor(::std::marker::PhantomData),
+                )
+            },
+        ),
+        Encoding::Named => (
+            quote! {
+                const VARIANTS: &[&str] = &[#(#names),*];
+
+                let variant_name: #zv::Str<'_> = seq.next_element()?.ok_or_else(|| {
+                    <A::Error as #zv::export::serde::de::Error>::invalid_length(0, &self)
+                })?;
+                match variant_name.as_str() {
+                    #(#arms)*
+                    variant_name => ::std::result::Result::Err(
+                        <A::Error as #zv::export::serde::de::Error>::unknown_variant(
+                            variant_name,
+                            VARIANTS,
+                        ),
+                    ),
+                }
+            },
+            quote! {
+                deserializer.deserialize_struct(
+                    ::std::stringify!(#name),
+                    &["name", "value"],
+                    #visitor(::std::marker::PhantomData),
+                )
+            },
+        ),
+        Encoding::Untagged => (
+            quote! {
+                let signature: #zv::Signature<'_> = seq.next_element()?.ok_or_else(|| {
+                    <A::Error as #zv::export::serde::de::Error>::invalid_length(0, &self)
+                })?;
+                #(#arms)*
+
+                ::std::result::Result::Err(
+                    <A::Error as #zv::export::serde::de::Error>::invalid_value(
+                        #zv::export::serde::de::Unexpected::Str(&signature),
+                        &"the signature of one of the variants",
+                    ),
+                )
+            },
+            quote! {
+                deserializer.deserialize_struct(
+                    "zvariant::Value",
+                    &["zvariant::Value::Signature", "zvariant::Value::Value"],
+                    #visitor(::std::marker::PhantomData),
+                )
+            },
+        ),
+    };
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut generics = input.generics.clone();
+    // The deserialized data must outlive all the lifetimes of the type.
+    let bounds: Punctuated<Lifetime, _> =
+        generics.lifetimes().map(|l| l.lifetime.clone()).collect();
+    let def = LifetimeParam {
+        attrs: Vec::new(),
+        lifetime: Lifetime::new("'de", Span::call_site()),
+        colon_token: (!bounds.is_empty()).then(Default::default),
+        bounds,
+    };
+    generics.params = Some(GenericParam::Lifetime(def))
+        .into_iter()
+        .chain(generics.params)
+        .collect();
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[allow(deprecated)]
+        impl #impl_generics #zv::export::serde::de::Deserialize<'de> for #name #ty_generics
+        #where_clause
+        {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: #zv::export::serde::de::Deserializer<'de>,
+            {
+                struct #visitor #ty_generics(::std::marker::PhantomData<#name #ty_generics>);
+
+                impl #impl_generics #zv::export::serde::de::Visitor<'de> for #visitor #ty_generics
+                #where_clause
+                {
+                    type Value = #name #ty_generics;
+
+                    fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                        formatter.write_str(::std::concat!("enum ", ::std::stringify!(#name)))
+                    }
+
+                    fn visit_seq<A>(self, mut seq: A) -> ::std::result::Result<Self::Value, A::Error>
+                    where
+                        A: #zv::export::serde::de::SeqAccess<'de>,
+                    {
+                        #visit_seq
+                    }
+                }
+
+                #deserialize
+            }
+        }
+    })
+}
@@ -15,6 +15,7 @@ use proc_macro::TokenStream;
 use syn::DeriveInput;
 
 mod dict;
+mod r#enum;
 mod r#type;
 mod utils;
 mod value;
@@ -102,6 +103,8 @@ mod value;
 /// assert_eq!(StructFields::signature(), "(u(qxs))");
 /// ```
 ///
+/// For enums whose variants have differing fields, see [`SerializeEnum`] and [`DeserializeEnum`].
+///
 /// # Custom signatures
 ///
 /// There are times when you'd find yourself wanting to specify a hardcoded signature yourself for
@@ -155,10 +158,65 @@ mod value;
 /// assert_eq!(decoded, StrEnum::Variant2);
 /// ```
 ///
+/// # `Option` fields
+///
+/// D-Bus has no maybe type, so `Option` fields can't be encoded as is in the D-Bus format. The
+/// `option` attribute on such fields selects one of the conventions from [`zvariant::option`]
+/// module instead: `"array"`, `"variant"` or `"pair"`. The attribute only dictates the signature of
+/// the field so you'll need to pair it with the corresponding serde `with` attribute:
+///
+/// ```
+/// use zvariant::Type;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Deserialize, Serialize, Type)]
+/// struct Struct {
+///     id: u32,
+///     #[serde(with = "zvariant::option::array")]
+///     #[zvariant(option = "array")]
+///     name: Option<String>,
+/// }
+///
+/// assert_eq!(Struct::signature(), "(uas)");
+/// ```
+///
+/// # Generic types
+///
+/// By default, the implementation requires `Type` from each type parameter used in the fields.
+/// When that's not what you need (e.g a parameter is only used through a marker type that
+/// implements `Type` regardless of it), use the `bound` attribute to provide the predicates of the
+/// `where` clause yourself or `no_bound` to not add any:
+///
+/// ```
+/// use zvariant::Type;
+///
+/// trait Kind {
+///     type Id;
+/// }
+///
+/// #[derive(Type)]
+/// #[zvariant(bound = "K::Id: Type")]
+/// struct Handle<K: Kind> {
+///     id: K::Id,
+///     name: String,
+/// }
+///
+/// struct Device;
+///
+/// impl Kind for Device {
+///     type Id = u64;
+/// }
+///
+/// assert_eq!(Handle::<Device>::signature(), "(ts)");
+/// ```
+///
 /// [`Type`]: https://docs.rs/zvariant/latest/zvariant/trait.Type.html
 /// [`Serialize`]: https://docs.serde.rs/serde/trait.Serialize.html
 /// [`Deserialize`]: https://docs.serde.rs/serde/de/trait.Deserialize.html
 /// [serde_repr]: https://crates.io/crates/serde_repr
+/// [`zvariant::option`]: https://docs.rs/zvariant/latest/zvariant/option/index.html
+/// [`SerializeEnum`]: derive.SerializeEnum.html
+/// [`DeserializeEnum`]: derive.DeserializeEnum.html
 #[proc_macro_derive(Type, attributes(zvariant))]
 pub fn type_macro_derive(input: TokenStream) -> TokenStream {
     let ast: DeriveInput = syn::parse(input).unwrap();
@@ -225,6 +283,35 @@ pub fn type_macro_derive(input: TokenStream) -> TokenStream {
 /// * `"camelCase"`
 /// * `"snake_case"`
 ///
+/// # Flattening
+///
+/// Just like serde's `flatten` attribute, `#[zvariant(flatten)]` on a field merges its entries into
+/// the dictionary, instead of having them in a dictionary of their own. This allows sharing common
+/// blocks of options between dictionaries:
+///
+/// ```
+/// use zvariant::{SerializeDict, Type};
+///
+/// #[derive(SerializeDict, Type)]
+/// #[zvariant(signature = "a{sv}")]
+/// struct Hints {
+///     urgency: u8,
+///     category: Option<String>,
+/// }
+///
+/// #[derive(SerializeDict, Type)]
+/// #[zvariant(signature = "a{sv}")]
+/// struct Notification {
+///     summary: String,
+///     #[zvariant(flatten)]
+///     hints: Hints,
+/// }
+/// ```
+///
+/// The serialized D-Bus version of `Notification {"hi", Hints {1, None}}` will be
+/// `{"summary": Value::Str("hi"), "urgency": Value::U8(1)}`. Only fields of dictionary structs and
+/// maps can be flattened.
+///
 /// [`Serialize`]: https://docs.serde.rs/serde/trait.Serialize.html
 #[proc_macro_derive(SerializeDict, attributes(zvariant))]
 pub fn serialize_dict_macro_derive(input: TokenStream) -> TokenStream {
@@ -293,6 +380,53 @@ pub fn serialize_dict_macro_derive(input: TokenStream) -> TokenStream {
 /// * `"camelCase"`
 /// * `"snake_case"`
 ///
+/// # Flattening
+///
+/// Just like serde's `flatten` attribute, `#[zvariant(flatten)]` on a field deserializes it from
+/// the entries that don't belong to any of the other fields:
+///
+/// ```
+/// use zvariant::{DeserializeDict, Type};
+///
+/// #[derive(DeserializeDict, Type)]
+/// #[zvariant(signature = "a{sv}")]
+/// ##[allow(unused)]
+/// struct Hints {
+///     urgency: u8,
+///     category: Option<String>,
+/// }
+///
+/// #[derive(DeserializeDict, Type)]
+/// #[zvariant(signature = "a{sv}")]
+/// ##[allow(unused)]
+/// struct Notification {
+///     summary: String,
+///     #[zvariant(flatten)]
+///     hints: Hints,
+/// }
+/// ```
+///
+/// Only fields of dictionary structs and maps can be flattened and the attribute can't be combined
+/// with `deny_unknown_fields`.
+///
+/// A flattened map, e.g `HashMap<String, OwnedValue>`, receives all the entries that aren't
+/// matched by any of the other fields. Since [`SerializeDict`] merges the map back, this allows
+/// forward-compatible code to preserve and round-trip options it doesn't know about:
+///
+/// ```
+/// use std::collections::HashMap;
+/// use zvariant::{DeserializeDict, OwnedValue, SerializeDict, Type};
+///
+/// #[derive(DeserializeDict, SerializeDict, Type)]
+/// #[zvariant(signature = "a{sv}")]
+/// ##[allow(unused)]
+/// struct Options {
+///     modal: Option<bool>,
+///     #[zvariant(flatten)]
+///     unknown: HashMap<String, OwnedValue>,
+/// }
+/// ```
+///
 /// [`Deserialize`]: https://docs.serde.rs/serde/de/trait.Deserialize.html
 #[proc_macro_derive(DeserializeDict, attributes(zvariant))]
 pub fn deserialize_dict_macro_derive(input: TokenStream) -> TokenStream {
@@ -302,6 +436,101 @@ pub fn deserialize_dict_macro_derive(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Adds [`Serialize`] implementation to data-carrying enums.
+///
+/// Serde's [`Serialize`] macro can only be used with enums where all variants have the same number
+/// and types of fields, since the encoding of a type must be dictated by its signature. This macro
+/// lifts that restriction by (de)serializing the fields of each variant as a variant value, in one
+/// of the following encodings that you select through an attribute:
+///
+/// * `#[zvariant(tagged)]`: A structure of signature `(uv)`, with the index of the variant (in
+///   declaration order) as the first field and the fields of the variant as the second.
+/// * `#[zvariant(untagged)]`: A variant value (signature `v`) of the fields of the variant. During
+///   deserialization, the first variant with fields of matching signature is chosen so each variant
+///   must have fields of a unique signature. Unit variants are not supported in this encoding.
+/// * `#[zvariant(named)]`: A structure of signature `(sv)`, with the name of the variant as the
+///   first field and the fields of the variant as the second. The names can be adjusted through a
+///   Serde-like `#[zvariant(rename_all = "case")]` attribute, with the same cases as
+///   [`SerializeDict`] supports. Unlike the index, the name remains stable when variants are added
+///   or reordered.
+///
+/// Variants with a single unnamed field carry that field as is. Otherwise, the fields are carried
+/// as a structure, and unit variants carry a byte of value `0`.
+///
+/// [`Type`] macro supports the same attributes, for providing the corresponding signature.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{serialized::Context, to_bytes, DeserializeEnum, SerializeEnum, Type, LE};
+///
+/// #[derive(SerializeEnum, DeserializeEnum, Type, PartialEq, Debug)]
+/// #[zvariant(tagged)]
+/// enum Shape {
+///     Empty,
+///     Circle(f64),
+///     Rectangle { width: u32, height: u32 },
+/// }
+///
+/// assert_eq!(Shape::signature(), "(uv)");
+/// let ctxt = Context::new_dbus(LE, 0);
+/// let shape = Shape::Rectangle { width: 4, height: 2 };
+/// let encoded = to_bytes(ctxt, &shape).unwrap();
+/// let decoded: Shape = encoded.deserialize().unwrap().0;
+/// assert_eq!(decoded, shape);
+///
+/// #[derive(SerializeEnum, DeserializeEnum, Type, PartialEq, Debug)]
+/// #[zvariant(untagged)]
+/// enum Setting {
+///     Flag(bool),
+///     Name(String),
+/// }
+///
+/// assert_eq!(Setting::signature(), "v");
+/// let encoded = to_bytes(ctxt, &Setting::Name("foo".into())).unwrap();
+/// let decoded: Setting = encoded.deserialize().unwrap().0;
+/// assert_eq!(decoded, Setting::Name("foo".into()));
+///
+/// #[derive(SerializeEnum, DeserializeEnum, Type, PartialEq, Debug)]
+/// #[zvariant(named, rename_all = "snake_case")]
+/// enum Event {
+///     KeyPressed(u32),
+///     Moved { x: i32, y: i32 },
+/// }
+///
+/// assert_eq!(Event::signature(), "(sv)");
+/// let encoded = to_bytes(ctxt, &Event::KeyPressed(42)).unwrap();
+/// let (name, _): (&str, zvariant::Value<'_>) = encoded.deserialize().unwrap().0;
+/// assert_eq!(name, "key_pressed");
+/// let decoded: Event = encoded.deserialize().unwrap().0;
+/// assert_eq!(decoded, Event::KeyPressed(42));
+/// ```
+///
+/// [`Serialize`]: https://docs.serde.rs/serde/trait.Serialize.html
+/// [`Type`]: derive.Type.html
+/// [`SerializeDict`]: derive.SerializeDict.html
+#[proc_macro_derive(SerializeEnum, attributes(zvariant))]
+pub fn serialize_enum_macro_derive(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = syn::parse(input).unwrap();
+    r#enum::expand_serialize_derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Adds [`Deserialize`] implementation to data-carrying enums.
+///
+/// See [`SerializeEnum`] documentation for details and examples.
+///
+/// [`Deserialize`]: https://docs.serde.rs/serde/de/trait.Deserialize.html
+/// [`SerializeEnum`]: derive.SerializeEnum.html
+#[proc_macro_derive(DeserializeEnum, attributes(zvariant))]
+pub fn deserialize_enum_macro_derive(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = syn::parse(input).unwrap();
+    r#enum::expand_deserialize_derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 /// Implements conversions for your type to/from [`Value`].
 ///
 /// Implements `TryFrom<Value>` and `Into<Value>` for your type.
@@ -1,15 +1,59 @@
-use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
 use syn::{
-    spanned::Spanned, Attribute, Data, DataEnum, DeriveInput, Error, Fields, Generics, Ident,
+    punctuated::Punctuated, spanned::Spanned, Attribute, Data, DataEnum, DeriveInput, Error, Field,
+    Fields, GenericArgument, GenericParam, Generics, Ident, PathArguments, Token, Type, TypePath,
+    WherePredicate,
 };
 
-use crate::utils::*;
+use crate::{r#enum::Encoding, utils::*};
 
-pub fn expand_derive(ast: DeriveInput) -> Result<TokenStream, Error> {
-    let StructAttributes { signature, .. } = StructAttributes::parse(&ast.attrs)?;
+pub fn expand_derive(mut ast: DeriveInput) -> Result<TokenStream, Error> {
+    let attrs = StructAttributes::parse(&ast.attrs)?;
+    let encoding = Encoding::from_attrs(&attrs, ast.span())?;
+    let StructAttributes {
+        signature,
+        bound,
+        no_bound,
+        ..
+    } = attrs;
 
     let zv = zvariant_path();
+    let signature = match (signature, encoding) {
+        (Some(_), Some(_)) => {
+            return Err(Error::new(
+                ast.span(),
+                "`signature` attribute can't be combined with `tagged`, `untagged` or `named`",
+            ))
+        }
+        (Some(signature), None) => Some(signature),
+        (None, Some(encoding)) if matches!(ast.data, Data::Enum(_)) => {
+            Some(encoding.signature().to_string())
+        }
+        (None, Some(_)) => {
+            return Err(Error::new(
+                ast.span(),
+                "`tagged`, `untagged` and `named` attributes are only supported on enums",
+            ))
+        }
+        (None, None) => None,
+    };
+    let zv_type = quote! { #zv::Type };
+    let bounds = match (bound, no_bound) {
+        (Some(_), true) => {
+            return Err(Error::new(
+                ast.span(),
+                "`bound` and `no_bound` attributes can't be combined",
+            ))
+        }
+        (Some(bound), false) => Bounds::Custom(parse_bound(&bound, ast.span())?),
+        (None, true) => Bounds::None,
+        // The fields are irrelevant when the signature is provided.
+        (None, false) if signature.is_some() => Bounds::None,
+        (None, false) => Bounds::Inferred,
+    };
+    add_bounds(&mut ast.generics, &ast.data, bounds, &zv_type);
+
     if let Some(signature) = signature {
         let signature = match signature.as_str() {
             "dict" => "a{sv}".to_string(),
@@ -58,6 +102,66 @@ pub fn expand_derive(ast: DeriveInput) -> Result<TokenStream, Error> {
     })
 }
 
+/// How the where clause of the `Type` implementation is extended.
+enum Bounds {
+    /// Require `Type` from the type parameters used in the fields.
+    Inferred,
+    /// Use the predicates given through the `bound` attribute.
+    Custom(Punctuated<WherePredicate, Token![,]>),
+    /// Don't add any predicates.
+    None,
+}
+
+fn parse_bound(
+    bound: &str,
+    span: proc_macro2::Span,
+) -> Result<Punctuated<WherePredicate, Token![,]>, Error> {
+    syn::parse::Parser::parse_str(
+        Punctuated::<WherePredicate, Token![,]>::parse_terminated,
+        bound,
+    )
+    .map_err(|e| Error::new(span, format!("invalid `bound` attribute: {e}")))
+}
+
+fn add_bounds(generics: &mut Generics, data: &Data, bounds: Bounds, zv_type: &TokenStream) {
+    let predicates: Vec<WherePredicate> = match bounds {
+        Bounds::None => return,
+        Bounds::Custom(predicates) => predicates.into_iter().collect(),
+        Bounds::Inferred => {
+            let fields: Vec<&Field> = match data {
+                Data::Struct(data) => data.fields.iter().collect(),
+                Data::Enum(data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+                Data::Union(_) => return,
+            };
+            generics
+                .params
+                .iter()
+                .filter_map(|param| match param {
+                    GenericParam::Type(param) => Some(&param.ident),
+                    _ => None,
+                })
+                .filter(|ident| {
+                    fields
+                        .iter()
+                        .any(|field| contains_ident(field.ty.to_token_stream(), ident))
+                })
+                .map(|ident| syn::parse_quote!(#ident: #zv_type))
+                .collect()
+        }
+    };
+
+    generics.make_where_clause().predicates.extend(predicates);
+}
+
+// Whether `ident` is used anywhere in `tokens`.
+fn contains_ident(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(i) => i == *ident,
+        TokenTree::Group(group) => contains_ident(group.stream(), ident),
+        _ => false,
+    })
+}
+
 fn impl_struct(
     name: Ident,
     generics: Generics,
@@ -65,7 +169,7 @@ fn impl_struct(
     zv: &TokenStream,
 ) -> Result<TokenStream, Error> {
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-    let signature = signature_for_struct(&fields, zv, false);
+    let signature = signature_for_struct(&fields, zv, false)?;
 
     Ok(quote! {
         impl #impl_generics #zv::Type for #name #ty_generics #where_clause {
@@ -81,25 +185,28 @@ fn signature_for_struct(
     fields: &Fields,
     zv: &TokenStream,
     insert_enum_variant: bool,
-) -> TokenStream {
-    let field_types = fields.iter().map(|field| field.ty.to_token_stream());
+) -> Result<TokenStream, Error> {
+    let field_signatures = fields
+        .iter()
+        .map(|field| signature_for_field(field, zv))
+        .collect::<Result<Vec<_>, _>>()?;
     let new_type = match fields {
         Fields::Named(_) => false,
-        Fields::Unnamed(_) if field_types.len() == 1 => true,
+        Fields::Unnamed(_) if field_signatures.len() == 1 => true,
         Fields::Unnamed(_) => false,
         Fields::Unit => panic!("signature_for_struct must not be called for unit fields"),
     };
     let inner_impl = if new_type {
         quote! {
             #(
-                <#field_types as #zv::Type>::signature()
+                #field_signatures
              )*
         }
     } else {
         quote! {
             let mut s = <::std::string::String as ::std::convert::From<_>>::from("(");
             #(
-                s.push_str(<#field_types as #zv::Type>::signature().as_str());
+                s.push_str(#field_signatures.as_str());
             )*
             s.push_str(")");
 
@@ -107,7 +214,7 @@ fn signature_for_struct(
         }
     };
 
-    if insert_enum_variant {
+    let signature = if insert_enum_variant {
         quote! {
             let inner_signature = {
                 #inner_impl
@@ -121,6 +228,54 @@ fn signature_for_struct(
         }
     } else {
         inner_impl
+    };
+
+    Ok(signature)
+}
+
+// The signature of `field`, taking its `option` attribute into account.
+fn signature_for_field(field: &Field, zv: &TokenStream) -> Result<TokenStream, Error> {
+    let FieldAttributes { option, .. } = FieldAttributes::parse(&field.attrs)?;
+    let ty = &field.ty;
+    let option = match option {
+        Some(option) => option,
+        None => return Ok(quote! { <#ty as #zv::Type>::signature() }),
+    };
+    let encoding = match option.as_str() {
+        "array" | "variant" | "pair" => format_ident!("{}", option),
+        _ => {
+            return Err(Error::new(
+                field.span(),
+                format!("invalid `option` attribute value {option}"),
+            ))
+        }
+    };
+    let inner = option_inner_type(ty).ok_or_else(|| {
+        Error::new(
+            ty.span(),
+            "`option` attribute is only supported on `Option` fields",
+        )
+    })?;
+
+    Ok(quote! { #zv::option::#encoding::signature::<#inner>() })
+}
+
+// The `T` of an `Option<T>` type.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(TypePath { path, .. }) => path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
     }
 }
 
@@ -208,7 +363,7 @@ fn signature_for_variant(
 
             Ok(quote! { <#repr as #zv::Type>::signature() })
         }
-        Fields::Named(_) => Ok(signature_for_struct(&variant.fields, zv, true)),
-        Fields::Unnamed(_) => Ok(signature_for_struct(&variant.fields, zv, true)),
+        Fields::Named(_) => signature_for_struct(&variant.fields, zv, true),
+        Fields::Unnamed(_) => signature_for_struct(&variant.fields, zv, true),
     }
 }
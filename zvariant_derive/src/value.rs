@@ -2,7 +2,7 @@ use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::{
     spanned::Spanned, Attribute, Data, DataEnum, DeriveInput, Error, Expr, Fields, Generics, Ident,
-    Lifetime, LifetimeParam,
+    Lifetime, LifetimeParam, Type,
 };
 
 use crate::utils::*;
@@ -43,6 +43,19 @@ pub fn expand_derive(ast: DeriveInput, value_type: ValueType) -> Result<TokenStr
     }
 }
 
+/// Whether `ty` is (syntactically) an `Option`.
+fn is_option_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
 fn impl_struct(
     value_type: ValueType,
     name: Ident,
@@ -149,25 +162,39 @@ fn impl_struct(
                             #into_value_error_transform
                     },
                 ),
-                Some(_) | None => (
-                    quote! {
-                        let mut fields = #zv::Structure::try_from(value)?.into_fields();
+                Some(_) | None => {
+                    let field_values = fields.iter().map(|field| {
+                        if is_option_type(&field.ty) {
+                            // `Option<T>` can't implement `TryFrom<Value>` so we go through
+                            // `Maybe` (GVariant only).
+                            quote! {
+                                #zv::Maybe::try_from(fields.remove(0))?.try_into_option()?
+                            }
+                        } else {
+                            quote! { fields.remove(0).downcast()? }
+                        }
+                    });
 
-                        ::std::result::Result::Ok(Self {
+                    (
+                        quote! {
+                            let mut fields = #zv::Structure::try_from(value)?.into_fields();
+
+                            ::std::result::Result::Ok(Self {
+                                #(
+                                    #field_names: #field_values
+                                ),*
+                            })
+                        },
+                        quote! {
+                            <#value_type>::#into_value_method(#zv::StructureBuilder::new()
                             #(
-                                #field_names: fields.remove(0).downcast()?
-                            ),*
-                        })
-                    },
-                    quote! {
-                        <#value_type>::#into_value_method(#zv::StructureBuilder::new()
-                        #(
-                            .add_field(s.#field_names)
-                        )*
-                        .build())
-                        #into_value_error_transform
-                    },
-                ),
+                                .add_field(s.#field_names)
+                            )*
+                            .build())
+                            #into_value_error_transform
+                        },
+                    )
+                }
             };
             Ok(quote! {
                 impl #impl_generics ::std::convert::TryFrom<#value_type> for #name #ty_generics
@@ -1,9 +1,10 @@
 #![allow(dead_code)]
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use zvariant::{
     serialized::{Context, Format},
-    DeserializeDict, OwnedValue, SerializeDict, Type, Value, LE,
+    DeserializeDict, DeserializeEnum, OwnedValue, SerializeDict, SerializeEnum, Type, Value, LE,
 };
 
 #[test]
@@ -39,6 +40,140 @@ fn derive_enum() {
     assert_eq!(RequestNameFlags::signature(), "u")
 }
 
+#[test]
+fn derive_data_enum() {
+    #[derive(SerializeEnum, DeserializeEnum, Type, PartialEq, Debug)]
+    #[zvariant(tagged)]
+    enum Tagged<'a> {
+        Unit,
+        NewType(&'a str),
+        Tuple(u8, Vec<u32>),
+        Struct { name: String, age: u8 },
+    }
+
+    assert_eq!(Tagged::signature(), "(uv)");
+    let ctxt = Context::new(Format::DBus, LE, 0);
+    for tagged in [
+        Tagged::Unit,
+        Tagged::NewType("hello"),
+        Tagged::Tuple(1, vec![2, 3]),
+        Tagged::Struct {
+            name: "foo".to_string(),
+            age: 42,
+        },
+    ] {
+        let encoded = zvariant::to_bytes(ctxt, &tagged).unwrap();
+        let decoded: Tagged<'_> = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded, tagged);
+    }
+
+    // The encoding is a structure of the variant index and the fields as a variant.
+    let encoded = zvariant::to_bytes(ctxt, &Tagged::Tuple(1, vec![2, 3])).unwrap();
+    let (index, value): (u32, Value<'_>) = encoded.deserialize().unwrap().0;
+    assert_eq!(index, 2);
+    assert_eq!(value.value_signature(), "(yau)");
+    let encoded = zvariant::to_bytes(ctxt, &(4u32, Value::from("hello"))).unwrap();
+    assert!(encoded.deserialize::<Tagged<'_>>().is_err());
+
+    #[derive(SerializeEnum, DeserializeEnum, Type, PartialEq, Debug)]
+    #[zvariant(untagged)]
+    enum Untagged {
+        Number(u32),
+        Pair(u32, String),
+    }
+
+    assert_eq!(Untagged::signature(), "v");
+    for untagged in [Untagged::Number(7), Untagged::Pair(7, "seven".to_string())] {
+        let encoded = zvariant::to_bytes(ctxt, &untagged).unwrap();
+        let decoded: Untagged = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded, untagged);
+    }
+    let encoded = zvariant::to_bytes(ctxt, &Value::from(7u32)).unwrap();
+    let decoded: Untagged = encoded.deserialize().unwrap().0;
+    assert_eq!(decoded, Untagged::Number(7));
+    let encoded = zvariant::to_bytes(ctxt, &Value::from("seven")).unwrap();
+    assert!(encoded.deserialize::<Untagged>().is_err());
+
+    #[derive(SerializeEnum, DeserializeEnum, Type, PartialEq, Debug)]
+    #[zvariant(named, rename_all = "lowercase")]
+    enum Named<'a> {
+        Unit,
+        NewType(&'a str),
+        Struct { name: String, age: u8 },
+    }
+
+    assert_eq!(Named::signature(), "(sv)");
+    for named in [
+        Named::Unit,
+        Named::NewType("hello"),
+        Named::Struct {
+            name: "foo".to_string(),
+            age: 42,
+        },
+    ] {
+        let encoded = zvariant::to_bytes(ctxt, &named).unwrap();
+        let decoded: Named<'_> = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded, named);
+    }
+
+    // The encoding is a structure of the variant name and the fields as a variant.
+    let encoded = zvariant::to_bytes(ctxt, &Named::NewType("hello")).unwrap();
+    let (name, value): (&str, Value<'_>) = encoded.deserialize().unwrap().0;
+    assert_eq!(name, "newtype");
+    assert_eq!(value, Value::from("hello"));
+    let encoded = zvariant::to_bytes(ctxt, &("NewType", Value::from("hello"))).unwrap();
+    assert!(encoded.deserialize::<Named<'_>>().is_err());
+}
+
+#[test]
+fn derive_option_fields() {
+    #[derive(Deserialize, Serialize, Type, PartialEq, Debug)]
+    struct Test<'a> {
+        #[serde(with = "zvariant::option::array")]
+        #[zvariant(option = "array")]
+        array: Option<&'a str>,
+        #[serde(with = "zvariant::option::variant")]
+        #[zvariant(option = "variant")]
+        variant: Option<(u8, String)>,
+        #[serde(with = "zvariant::option::pair")]
+        #[zvariant(option = "pair")]
+        pair: Option<u16>,
+    }
+
+    assert_eq!(Test::signature(), "(asv(bq))");
+    let ctxt = Context::new(Format::DBus, LE, 0);
+    for test in [
+        Test {
+            array: Some("hello"),
+            variant: Some((1, "world".to_string())),
+            pair: Some(0),
+        },
+        Test {
+            array: None,
+            variant: None,
+            pair: None,
+        },
+    ] {
+        let encoded = zvariant::to_bytes(ctxt, &test).unwrap();
+        let decoded: Test<'_> = encoded.deserialize().unwrap().0;
+        assert_eq!(decoded, test);
+    }
+
+    // The `None` encodings.
+    let none = Test {
+        array: None,
+        variant: None,
+        pair: None,
+    };
+    let encoded = zvariant::to_bytes(ctxt, &none).unwrap();
+    let decoded: (Vec<&str>, Value<'_>, (bool, u16)) = encoded.deserialize().unwrap().0;
+    assert_eq!(decoded, (vec![], Value::U8(0), (false, 0)));
+
+    // More than one element is not an option.
+    let encoded = zvariant::to_bytes(ctxt, &(vec!["a", "b"], Value::U8(0), (false, 0u16))).unwrap();
+    assert!(encoded.deserialize::<Test<'_>>().is_err());
+}
+
 #[test]
 fn derive_dict() {
     #[derive(SerializeDict, DeserializeDict, Type)]
@@ -82,3 +217,127 @@ fn derive_dict() {
 
     assert_eq!(Test::signature(), "a{sv}")
 }
+
+#[test]
+fn derive_dict_flatten() {
+    #[derive(SerializeDict, DeserializeDict, Type, Debug, PartialEq)]
+    #[zvariant(signature = "a{sv}")]
+    struct Hints {
+        urgency: u8,
+        category: Option<String>,
+    }
+
+    #[derive(SerializeDict, DeserializeDict, Type, Debug, PartialEq)]
+    #[zvariant(signature = "a{sv}")]
+    struct Notification {
+        summary: String,
+        #[zvariant(flatten)]
+        hints: Hints,
+        timeout: Option<i32>,
+    }
+
+    let notification = Notification {
+        summary: "Hello".to_string(),
+        hints: Hints {
+            urgency: 2,
+            category: Some("im".to_string()),
+        },
+        timeout: None,
+    };
+    assert_eq!(Notification::signature(), "a{sv}");
+
+    let ctxt = Context::new(Format::DBus, LE, 0);
+    let serialized = zvariant::to_bytes(ctxt, &notification).unwrap();
+    let map: HashMap<String, OwnedValue> = serialized.deserialize().unwrap().0;
+    assert_eq!(map.len(), 3);
+    assert_eq!(map["summary"], Value::from("Hello").try_into().unwrap());
+    assert_eq!(map["urgency"], Value::from(2u8).try_into().unwrap());
+    assert_eq!(map["category"], Value::from("im").try_into().unwrap());
+
+    let deserialized: Notification = serialized.deserialize().unwrap().0;
+    assert_eq!(deserialized, notification);
+
+    // Missing fields of the flattened struct are still required.
+    let mut map = map;
+    map.remove("urgency");
+    let serialized = zvariant::to_bytes(ctxt, &map).unwrap();
+    serialized.deserialize::<Notification>().unwrap_err();
+}
+
+#[test]
+fn derive_dict_flatten_unknown() {
+    #[derive(SerializeDict, DeserializeDict, Type, Debug, PartialEq)]
+    #[zvariant(signature = "a{sv}")]
+    struct Options {
+        modal: Option<bool>,
+        #[zvariant(flatten)]
+        unknown: HashMap<String, OwnedValue>,
+    }
+
+    let mut map = HashMap::<&str, Value<'_>>::new();
+    map.insert("modal", Value::from(true));
+    map.insert("handle_token", Value::from("t1"));
+    map.insert("choices", Value::from(vec![1u32, 2]));
+    let ctxt = Context::new(Format::DBus, LE, 0);
+    let serialized = zvariant::to_bytes(ctxt, &map).unwrap();
+
+    // All the entries not matched by the named fields end up in the flattened map.
+    let options: Options = serialized.deserialize().unwrap().0;
+    assert_eq!(options.modal, Some(true));
+    assert_eq!(options.unknown.len(), 2);
+    assert_eq!(
+        options.unknown["handle_token"],
+        Value::from("t1").try_into().unwrap()
+    );
+
+    // .. and round-trip.
+    let serialized = zvariant::to_bytes(ctxt, &options).unwrap();
+    let map2: HashMap<&str, Value<'_>> = serialized.deserialize().unwrap().0;
+    assert_eq!(map2, map);
+}
+
+#[test]
+fn derive_generic_bounds() {
+    use std::marker::PhantomData;
+
+    // `T: Type` is inferred from the fields.
+    #[derive(Type)]
+    struct Inferred<T> {
+        id: u32,
+        items: Vec<T>,
+    }
+    assert_eq!(Inferred::<String>::signature(), "(uas)");
+
+    // A marker type that is `Type` no matter its parameter.
+    struct Tag<M>(PhantomData<M>);
+    impl<M> Type for Tag<M> {
+        fn signature() -> zvariant::Signature<'static> {
+            zvariant::Signature::from_static_str_unchecked("s")
+        }
+    }
+    struct NotType;
+
+    #[derive(Type)]
+    #[zvariant(no_bound)]
+    struct NoBound<M> {
+        id: u32,
+        tag: Tag<M>,
+    }
+    assert_eq!(NoBound::<NotType>::signature(), "(us)");
+
+    trait Kind {
+        type Id;
+    }
+    struct Device;
+    impl Kind for Device {
+        type Id = u64;
+    }
+
+    #[derive(Type)]
+    #[zvariant(bound = "K::Id: Type")]
+    struct Custom<K: Kind> {
+        id: K::Id,
+        tag: Tag<K>,
+    }
+    assert_eq!(Custom::<Device>::signature(), "(ts)");
+}